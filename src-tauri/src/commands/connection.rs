@@ -1,5 +1,5 @@
 ﻿use crate::models::{ConnectionConfig, ConnectionStatus, ConnectionTestResult};
-use crate::services::ConnectionService;
+use crate::services::{ConnectionService, SchemaCacheService};
 use crate::database::s3_client::S3ClientManager;
 use tauri::State;
 use log::{debug, error, info};
@@ -123,24 +123,31 @@ pub async fn get_connection(
 #[tauri::command]
 pub async fn update_connection(
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
     s3_manager: State<'_, Arc<Mutex<S3ClientManager>>>,
     config: ConnectionConfig,
 ) -> Result<(), String> {
     debug!("处理更新连接命令: {}", config.name);
 
+    let connection_id = config.id.clone();
     connection_service
         .update_connection(config, Some(s3_manager.inner().clone()))
         .await
         .map_err(|e| {
             error!("更新连接失败: {}", e);
             format!("更新连接失败: {}", e)
-        })
+        })?;
+
+    // 连接地址或凭据可能已变化，旧的结构信息不再可信
+    schema_cache.invalidate(&connection_id, None).await;
+    Ok(())
 }
 
 /// 删除连接
 #[tauri::command]
 pub async fn delete_connection(
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
     connection_id: String,
 ) -> Result<(), String> {
     debug!("处理删除连接命令: {}", connection_id);
@@ -151,7 +158,11 @@ pub async fn delete_connection(
         .map_err(|e| {
             error!("删除连接失败: {}", e);
             format!("删除连接失败: {}", e)
-        })
+        })?;
+
+    crate::commands::influxdb1::stop_query_watch(&connection_id);
+    schema_cache.invalidate(&connection_id, None).await;
+    Ok(())
 }
 
 /// 获取连接状态
@@ -243,6 +254,7 @@ pub async fn disconnect_from_database(
 ) -> Result<(), String> {
     debug!("处理断开数据库连接命令: {}", connection_id);
 
+    crate::commands::influxdb1::stop_query_watch(&connection_id);
     connection_service
         .disconnect_from_database(&connection_id)
         .await
@@ -2,6 +2,12 @@ use serde::{Deserialize, Serialize};
 use tauri::State;
 use log::debug;
 use crate::services::ConnectionService;
+use crate::models::QueryParams;
+use crate::utils::query_params::{
+    aggregate_field, iotdb_device_path, parse_duration_nanos, quote_identifier, quote_iotdb_node, time_bound,
+    unquote_identifier, ParamDialect,
+};
+use std::collections::HashMap;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SqlGenerationRequest {
@@ -33,6 +39,8 @@ pub struct OrderBy {
 pub struct SqlGenerationResult {
     pub sql: String,
     pub description: String,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub params: QueryParams, // 执行时随查询一起提交
 }
 
 /// 生成智能 SQL 查询
@@ -42,17 +50,18 @@ pub async fn generate_smart_sql(
 ) -> Result<SqlGenerationResult, String> {
     debug!("生成智能 SQL: {:?}", request.sql_type);
     
+    let mut params = QueryParams::new();
     let sql = match request.sql_type.as_str() {
-        "select_all" => generate_select_all_sql(&request),
-        "select_fields" => generate_select_fields_sql(&request),
-        "count_records" => generate_count_sql(&request),
+        "select_all" => generate_select_all_sql(&request)?,
+        "select_fields" => generate_select_fields_sql(&request)?,
+        "count_records" => generate_count_sql(&request)?,
         "show_measurements" => generate_show_measurements_sql(&request),
-        "show_tag_keys" => generate_show_tag_keys_sql(&request),
+        "show_tag_keys" => generate_show_tag_keys_sql(&request)?,
         "show_tag_values" => generate_show_tag_values_sql(&request),
-        "show_field_keys" => generate_show_field_keys_sql(&request),
+        "show_field_keys" => generate_show_field_keys_sql(&request)?,
         "describe_measurement" => generate_describe_sql(&request),
-        "time_series" => generate_time_series_sql(&request),
-        "aggregation" => generate_aggregation_sql(&request),
+        "time_series" => generate_time_series_sql(&request, &mut params)?,
+        "aggregation" => generate_aggregation_sql(&request)?,
         _ => return Err("不支持的 SQL 类型".to_string()),
     };
 
@@ -61,6 +70,7 @@ pub async fn generate_smart_sql(
     Ok(SqlGenerationResult {
         sql,
         description,
+        params,
     })
 }
 
@@ -401,138 +411,51 @@ pub async fn get_field_context_menu(
 }
 
 // SQL 生成辅助函数
-fn generate_select_all_sql(request: &SqlGenerationRequest) -> String {
+fn generate_select_all_sql(request: &SqlGenerationRequest) -> Result<String, String> {
     let default_database = String::new();
     let database = request.database.as_ref().unwrap_or(&default_database);
     let default_measurement = String::new();
     let measurement = request.measurement.as_ref().unwrap_or(&default_measurement);
     let limit = request.limit.unwrap_or(100);
 
-    // 根据数据库类型生成不同的SQL
-    if let Some(db_type) = &request.database_type {
-        match db_type.as_str() {
-            "iotdb" => {
-                // IoTDB使用不带引号的路径格式
-                let device_path = if database.is_empty() {
-                    measurement.to_string()
-                } else if measurement.starts_with(database) {
-                    measurement.to_string()
-                } else {
-                    format!("{}.{}", database, measurement)
-                };
-                format!("SELECT * FROM {} LIMIT {}", device_path, limit)
-            }
-            _ => {
-                // InfluxDB使用带引号的格式
-                format!("SELECT * FROM \"{}\" LIMIT {}", measurement, limit)
-            }
-        }
+    if is_iotdb_request(request, database, measurement) {
+        // IoTDB使用路径格式，特殊节点用反引号
+        let device_path = iotdb_device_path(database, measurement).map_err(|e| e.to_string())?;
+        Ok(format!("SELECT * FROM {} LIMIT {}", device_path, limit))
     } else {
-        // 检查是否是IoTDB路径格式
-        if database.starts_with("root.") || measurement.starts_with("root.") {
-            // IoTDB使用不带引号的路径格式
-            let device_path = if database.is_empty() {
-                measurement.to_string()
-            } else if measurement.starts_with(database) {
-                measurement.to_string()
-            } else {
-                format!("{}.{}", database, measurement)
-            };
-            format!("SELECT * FROM {} LIMIT {}", device_path, limit)
-        } else {
-            // InfluxDB使用带引号的格式
-            format!("SELECT * FROM \"{}\" LIMIT {}", measurement, limit)
-        }
+        // InfluxDB使用带引号的格式
+        Ok(format!("SELECT * FROM {} LIMIT {}", quoted(measurement), limit))
     }
 }
 
-fn generate_select_fields_sql(request: &SqlGenerationRequest) -> String {
+fn generate_select_fields_sql(request: &SqlGenerationRequest) -> Result<String, String> {
     let default_database = String::new();
     let database = request.database.as_ref().unwrap_or(&default_database);
     let default_measurement = String::new();
     let measurement = request.measurement.as_ref().unwrap_or(&default_measurement);
-    let fields = request.fields.as_ref().map(|f| f.join(", ")).unwrap_or("*".to_string());
     let limit = request.limit.unwrap_or(100);
 
-    // 根据数据库类型生成不同的SQL
-    if let Some(db_type) = &request.database_type {
-        match db_type.as_str() {
-            "iotdb" => {
-                // IoTDB使用不带引号的路径格式
-                let device_path = if database.is_empty() {
-                    measurement.to_string()
-                } else if measurement.starts_with(database) {
-                    measurement.to_string()
-                } else {
-                    format!("{}.{}", database, measurement)
-                };
-                format!("SELECT {} FROM {} LIMIT {}", fields, device_path, limit)
-            }
-            _ => {
-                // InfluxDB使用带引号的格式
-                format!("SELECT {} FROM \"{}\" LIMIT {}", fields, measurement, limit)
-            }
-        }
+    if is_iotdb_request(request, database, measurement) {
+        let fields = field_list(request.fields.as_deref(), quote_iotdb_node);
+        let device_path = iotdb_device_path(database, measurement).map_err(|e| e.to_string())?;
+        Ok(format!("SELECT {} FROM {} LIMIT {}", fields, device_path, limit))
     } else {
-        // 检查是否是IoTDB路径格式
-        if database.starts_with("root.") || measurement.starts_with("root.") {
-            // IoTDB使用不带引号的路径格式
-            let device_path = if database.is_empty() {
-                measurement.to_string()
-            } else if measurement.starts_with(database) {
-                measurement.to_string()
-            } else {
-                format!("{}.{}", database, measurement)
-            };
-            format!("SELECT {} FROM {} LIMIT {}", fields, device_path, limit)
-        } else {
-            // InfluxDB使用带引号的格式
-            format!("SELECT {} FROM \"{}\" LIMIT {}", fields, measurement, limit)
-        }
+        let fields = field_list(request.fields.as_deref(), quoted);
+        Ok(format!("SELECT {} FROM {} LIMIT {}", fields, quoted(measurement), limit))
     }
 }
 
-fn generate_count_sql(request: &SqlGenerationRequest) -> String {
+fn generate_count_sql(request: &SqlGenerationRequest) -> Result<String, String> {
     let default_database = String::new();
     let database = request.database.as_ref().unwrap_or(&default_database);
     let default_measurement = String::new();
     let measurement = request.measurement.as_ref().unwrap_or(&default_measurement);
 
-    // 根据数据库类型生成不同的SQL
-    if let Some(db_type) = &request.database_type {
-        match db_type.as_str() {
-            "iotdb" => {
-                // IoTDB使用不带引号的路径格式
-                let device_path = if database.is_empty() {
-                    measurement.to_string()
-                } else if measurement.starts_with(database) {
-                    measurement.to_string()
-                } else {
-                    format!("{}.{}", database, measurement)
-                };
-                format!("SELECT COUNT(*) FROM {}", device_path)
-            }
-            _ => {
-                // InfluxDB使用带引号的格式
-                format!("SELECT COUNT(*) FROM \"{}\"", measurement)
-            }
-        }
+    if is_iotdb_request(request, database, measurement) {
+        let device_path = iotdb_device_path(database, measurement).map_err(|e| e.to_string())?;
+        Ok(format!("SELECT COUNT(*) FROM {}", device_path))
     } else {
-        // 检查是否是IoTDB路径格式
-        if database.starts_with("root.") || measurement.starts_with("root.") {
-            // IoTDB使用不带引号的路径格式
-            let device_path = if database.is_empty() {
-                measurement.to_string()
-            } else if measurement.starts_with(database) {
-                measurement.to_string()
-            } else {
-                format!("{}.{}", database, measurement)
-            };
-            format!("SELECT COUNT(*) FROM {}", device_path)
-        } else {
-            // InfluxDB使用带引号的格式
-            format!("SELECT COUNT(*) FROM \"{}\"", measurement)
-        }
+        Ok(format!("SELECT COUNT(*) FROM {}", quoted(measurement)))
     }
 }
 
@@ -543,53 +466,28 @@ fn generate_show_measurements_sql(request: &SqlGenerationRequest) -> String {
     if database.is_empty() {
         "SHOW MEASUREMENTS".to_string()
     } else {
-        format!("SHOW MEASUREMENTS ON \"{}\"", database)
+        format!("SHOW MEASUREMENTS ON {}", quoted(database))
     }
 }
 
-fn generate_show_tag_keys_sql(request: &SqlGenerationRequest) -> String {
+fn generate_show_tag_keys_sql(request: &SqlGenerationRequest) -> Result<String, String> {
     let default_database = String::new();
     let database = request.database.as_ref().unwrap_or(&default_database);
     let default_measurement = String::new();
     let measurement = request.measurement.as_ref().unwrap_or(&default_measurement);
 
-    // 根据数据库类型生成不同的SQL
-    if let Some(db_type) = &request.database_type {
-        match db_type.as_str() {
-            "iotdb" => {
-                // IoTDB不支持TAG概念，使用SHOW DEVICES
-                if measurement.is_empty() {
-                    format!("SHOW DEVICES {}.**", database)
-                } else {
-                    format!("SHOW DEVICES {}.{}", database, measurement)
-                }
-            }
-            _ => {
-                // InfluxDB使用SHOW TAG KEYS语法
-                if measurement.is_empty() {
-                    format!("SHOW TAG KEYS ON \"{}\"", database)
-                } else {
-                    format!("SHOW TAG KEYS ON \"{}\" FROM \"{}\"", database, measurement)
-                }
-            }
-        }
-    } else {
-        // 检查是否是IoTDB路径格式
-        if database.starts_with("root.") {
-            // IoTDB不支持TAG概念，使用SHOW DEVICES
-            if measurement.is_empty() {
-                format!("SHOW DEVICES {}.**", database)
-            } else {
-                format!("SHOW DEVICES {}.{}", database, measurement)
-            }
+    if is_iotdb_request(request, database, "") {
+        // IoTDB不支持TAG概念，使用SHOW DEVICES
+        if measurement.is_empty() {
+            Ok(format!("SHOW DEVICES {}.**", iotdb_device_path(database, "").map_err(|e| e.to_string())?))
         } else {
-            // InfluxDB使用SHOW TAG KEYS语法
-            if measurement.is_empty() {
-                format!("SHOW TAG KEYS ON \"{}\"", database)
-            } else {
-                format!("SHOW TAG KEYS ON \"{}\" FROM \"{}\"", database, measurement)
-            }
+            Ok(format!("SHOW DEVICES {}", iotdb_device_path(database, measurement).map_err(|e| e.to_string())?))
         }
+    } else if measurement.is_empty() {
+        // InfluxDB使用SHOW TAG KEYS语法
+        Ok(format!("SHOW TAG KEYS ON {}", quoted(database)))
+    } else {
+        Ok(format!("SHOW TAG KEYS ON {} FROM {}", quoted(database), quoted(measurement)))
     }
 }
 
@@ -602,101 +500,127 @@ fn generate_show_tag_values_sql(request: &SqlGenerationRequest) -> String {
     if let Some(tags) = &request.tags {
         if !tags.is_empty() {
             if measurement.is_empty() {
-                return format!("SHOW TAG VALUES ON \"{}\" WITH KEY = \"{}\"", database, tags[0]);
+                return format!("SHOW TAG VALUES ON {} WITH KEY = {}", quoted(database), quoted(&tags[0]));
             } else {
-                return format!("SHOW TAG VALUES ON \"{}\" FROM \"{}\" WITH KEY = \"{}\"", database, measurement, tags[0]);
+                return format!("SHOW TAG VALUES ON {} FROM {} WITH KEY = {}", quoted(database), quoted(measurement), quoted(&tags[0]));
             }
         }
     }
 
     if measurement.is_empty() {
-        format!("SHOW TAG VALUES ON \"{}\"", database)
+        format!("SHOW TAG VALUES ON {}", quoted(database))
     } else {
-        format!("SHOW TAG VALUES ON \"{}\" FROM \"{}\"", database, measurement)
+        format!("SHOW TAG VALUES ON {} FROM {}", quoted(database), quoted(measurement))
     }
 }
 
-fn generate_show_field_keys_sql(request: &SqlGenerationRequest) -> String {
+fn generate_show_field_keys_sql(request: &SqlGenerationRequest) -> Result<String, String> {
     let default_database = String::new();
     let database = request.database.as_ref().unwrap_or(&default_database);
     let default_measurement = String::new();
     let measurement = request.measurement.as_ref().unwrap_or(&default_measurement);
 
-    // 根据数据库类型生成不同的SQL
-    if let Some(db_type) = &request.database_type {
-        match db_type.as_str() {
-            "iotdb" => {
-                // IoTDB使用SHOW TIMESERIES语法
-                if measurement.is_empty() {
-                    format!("SHOW TIMESERIES {}.**", database)
-                } else {
-                    format!("SHOW TIMESERIES {}.{}.*", database, measurement)
-                }
-            }
-            _ => {
-                // InfluxDB使用SHOW FIELD KEYS语法
-                if measurement.is_empty() {
-                    format!("SHOW FIELD KEYS ON \"{}\"", database)
-                } else {
-                    format!("SHOW FIELD KEYS ON \"{}\" FROM \"{}\"", database, measurement)
-                }
-            }
-        }
-    } else {
-        // 检查是否是IoTDB路径格式
-        if database.starts_with("root.") {
-            // IoTDB使用SHOW TIMESERIES语法
-            if measurement.is_empty() {
-                format!("SHOW TIMESERIES {}.**", database)
-            } else {
-                format!("SHOW TIMESERIES {}.{}.*", database, measurement)
-            }
+    if is_iotdb_request(request, database, "") {
+        // IoTDB使用SHOW TIMESERIES语法
+        if measurement.is_empty() {
+            Ok(format!("SHOW TIMESERIES {}.**", iotdb_device_path(database, "").map_err(|e| e.to_string())?))
         } else {
-            // 默认使用InfluxDB语法
-            if measurement.is_empty() {
-                format!("SHOW FIELD KEYS ON \"{}\"", database)
-            } else {
-                format!("SHOW FIELD KEYS ON \"{}\" FROM \"{}\"", database, measurement)
-            }
+            Ok(format!("SHOW TIMESERIES {}.*", iotdb_device_path(database, measurement).map_err(|e| e.to_string())?))
         }
+    } else if measurement.is_empty() {
+        // InfluxDB使用SHOW FIELD KEYS语法
+        Ok(format!("SHOW FIELD KEYS ON {}", quoted(database)))
+    } else {
+        Ok(format!("SHOW FIELD KEYS ON {} FROM {}", quoted(database), quoted(measurement)))
     }
 }
 
 fn generate_describe_sql(request: &SqlGenerationRequest) -> String {
     let default_measurement = String::new();
     let measurement = request.measurement.as_ref().unwrap_or(&default_measurement);
-    format!("SHOW SERIES FROM \"{}\" LIMIT 1", measurement)
+    format!("SHOW SERIES FROM {} LIMIT 1", quoted(measurement))
 }
 
-fn generate_time_series_sql(request: &SqlGenerationRequest) -> String {
+fn generate_time_series_sql(request: &SqlGenerationRequest, params: &mut QueryParams) -> Result<String, String> {
     let default_measurement = String::new();
     let measurement = request.measurement.as_ref().unwrap_or(&default_measurement);
     let limit = request.limit.unwrap_or(100);
 
     if let Some(time_range) = &request.time_range {
-        format!(
-            "SELECT * FROM \"{}\" WHERE time >= '{}' AND time <= '{}' LIMIT {}",
-            measurement, time_range.start, time_range.end, limit
-        )
+        // 绝对时间作为参数传递，避免拼接到 SQL 中
+        let start = time_bound("start", &time_range.start, params).map_err(|e| e.to_string())?;
+        let end = time_bound("end", &time_range.end, params).map_err(|e| e.to_string())?;
+        Ok(format!("SELECT * FROM {} WHERE time >= {} AND time <= {} LIMIT {}", quoted(measurement), start, end, limit))
     } else {
-        format!("SELECT * FROM \"{}\" WHERE time >= now() - 1h LIMIT {}", measurement, limit)
+        Ok(format!("SELECT * FROM {} WHERE time >= now() - 1h LIMIT {}", quoted(measurement), limit))
     }
 }
 
-fn generate_aggregation_sql(request: &SqlGenerationRequest) -> String {
+fn generate_aggregation_sql(request: &SqlGenerationRequest) -> Result<String, String> {
     let default_measurement = String::new();
     let measurement = request.measurement.as_ref().unwrap_or(&default_measurement);
-    let fields = request.fields.as_ref().map(|f| f.join(", ")).unwrap_or("COUNT(*)".to_string());
+    let fields = match request.fields.as_deref() {
+        Some(fields) if !fields.is_empty() => fields
+            .iter()
+            .map(|field| aggregate_field(field))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(|e| e.to_string())?
+            .join(", "),
+        _ => "COUNT(*)".to_string(),
+    };
 
-    let mut sql = format!("SELECT {} FROM \"{}\"", fields, measurement);
+    let mut sql = format!("SELECT {} FROM {}", fields, quoted(measurement));
 
     if let Some(group_by) = &request.group_by {
         if !group_by.is_empty() {
+            let group_by = group_by
+                .iter()
+                .map(|key| group_by_key(key))
+                .collect::<Result<Vec<_>, _>>()?;
             sql.push_str(&format!(" GROUP BY {}", group_by.join(", ")));
         }
     }
 
-    sql
+    Ok(sql)
+}
+
+/// 是否按 IoTDB 语法生成：指定了数据库类型时以其为准，否则按 root. 路径判断
+fn is_iotdb_request(request: &SqlGenerationRequest, database: &str, measurement: &str) -> bool {
+    match &request.database_type {
+        Some(db_type) => db_type == "iotdb",
+        None => database.starts_with("root.") || measurement.starts_with("root."),
+    }
+}
+
+/// 字段列表，未指定字段时查询全部
+fn field_list(fields: Option<&[String]>, quote: impl Fn(&str) -> String) -> String {
+    match fields {
+        Some(fields) if !fields.is_empty() => fields
+            .iter()
+            .map(|field| if field == "*" { "*".to_string() } else { quote(field) })
+            .collect::<Vec<_>>()
+            .join(", "),
+        _ => "*".to_string(),
+    }
+}
+
+/// GROUP BY 项：time(时长) 或标签键
+fn group_by_key(key: &str) -> Result<String, String> {
+    let key = key.trim();
+    if let Some(interval) = key
+        .get(..5)
+        .filter(|head| head.eq_ignore_ascii_case("time("))
+        .and_then(|_| key[5..].strip_suffix(')'))
+    {
+        parse_duration_nanos(interval).map_err(|e| e.to_string())?;
+        return Ok(format!("time({})", interval.trim()));
+    }
+    Ok(quoted(&unquote_identifier(key)))
+}
+
+/// InfluxQL 标识符加引号并转义
+fn quoted(name: &str) -> String {
+    quote_identifier(name, ParamDialect::InfluxQL)
 }
 
 fn get_sql_description(sql_type: &str) -> String {
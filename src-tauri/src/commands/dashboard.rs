@@ -4,6 +4,8 @@ use log::{debug, error, info};
 use std::collections::HashMap;
 use std::sync::Mutex;
 use uuid::Uuid;
+use crate::models::{QueryParamValue, QueryParams};
+use crate::utils::query_params::{param_from_json, parse_time, relative_duration};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DashboardConfig {
@@ -512,3 +514,50 @@ pub async fn duplicate_dashboard(
         Err("原仪表板不存在".to_string())
     }
 }
+
+/// 解析仪表板查询参数：变量按名称绑定（values 中的当前值优先于默认值），
+/// 时间范围绑定为 $timeStart / $timeEnd，相对时间额外绑定为 $timeRange
+#[tauri::command]
+pub async fn resolve_dashboard_query_params(
+    dashboard_storage: State<'_, DashboardStorage>,
+    dashboard_id: String,
+    values: Option<HashMap<String, serde_json::Value>>,
+) -> Result<QueryParams, String> {
+    debug!("解析仪表板查询参数: {}", dashboard_id);
+
+    let dashboard = {
+        let storage = dashboard_storage.lock().map_err(|e| {
+            error!("获取仪表板存储锁失败: {}", e);
+            "存储访问失败".to_string()
+        })?;
+        storage.get(&dashboard_id).cloned().ok_or_else(|| "仪表板不存在".to_string())?
+    };
+
+    let values = values.unwrap_or_default();
+    let mut params = QueryParams::new();
+    for variable in &dashboard.variables {
+        let value = values.get(&variable.name).unwrap_or(&variable.default_value);
+        if value.is_null() {
+            continue;
+        }
+        let param = param_from_json(value, variable.var_type == "interval")
+            .map_err(|e| format!("变量 {} 无效: {}", variable.name, e))?;
+        params.insert(variable.name.clone(), param);
+    }
+
+    for (name, time) in [("timeStart", &dashboard.time_range.start), ("timeEnd", &dashboard.time_range.end)] {
+        if parse_time(time).is_ok() {
+            params.insert(name.to_string(), QueryParamValue::Time(time.clone()));
+        }
+    }
+    if let Some(duration) = dashboard
+        .time_range
+        .relative
+        .as_ref()
+        .and_then(|relative| relative_duration(relative.amount, &relative.unit))
+    {
+        params.insert("timeRange".to_string(), QueryParamValue::Duration(duration));
+    }
+
+    Ok(params)
+}
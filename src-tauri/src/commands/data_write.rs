@@ -1,5 +1,5 @@
 use crate::models::{DataWriteRequest, DataWriteResult, DataFormat, BatchWriteRequest, WriteResult, WriteError, DataPoint};
-use crate::services::ConnectionService;
+use crate::services::{ConnectionService, SchemaCacheService};
 use tauri::State;
 use log::{debug, error, info};
 use std::time::Instant;
@@ -8,6 +8,7 @@ use std::time::Instant;
 #[tauri::command]
 pub async fn write_data(
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
     request: DataWriteRequest,
 ) -> Result<DataWriteResult, String> {
     debug!("处理数据写入命令: {} -> {}", request.connection_id, request.database);
@@ -70,6 +71,7 @@ pub async fn write_data(
     };
 
     info!("数据写入完成: {} 个数据点，耗时 {}ms", points_written, duration);
+    schema_cache.invalidate(&request.connection_id, Some(&request.database)).await;
     Ok(result)
 }
 
@@ -77,6 +79,7 @@ pub async fn write_data(
 #[tauri::command]
 pub async fn write_data_points(
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
     request: BatchWriteRequest,
 ) -> Result<WriteResult, String> {
     debug!("处理批量数据写入命令: {} -> {}, {} 个数据点",
@@ -161,6 +164,7 @@ pub async fn write_data_points(
 
     info!("批量数据写入完成: {} 个数据点，{} 个错误，耗时 {}ms",
           points_written, result.errors.len(), duration);
+    schema_cache.invalidate(&request.connection_id, Some(&request.database)).await;
     Ok(result)
 }
 
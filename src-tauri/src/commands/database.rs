@@ -1,5 +1,7 @@
 ﻿use crate::models::{RetentionPolicy, RetentionPolicyConfig, QueryResult, DatabaseInfo, DatabaseStats, Measurement};
-use crate::services::ConnectionService;
+use crate::services::{ConnectionService, SchemaCacheService};
+use crate::services::schema_cache::SchemaItem;
+use crate::utils::schema_search::{self, Matcher, SchemaSearchHit, SearchMode};
 use crate::models::TableSchema;
 use crate::commands::settings::SettingsStorage;
 use tauri::State;
@@ -110,6 +112,7 @@ pub async fn get_databases(
 #[tauri::command(rename_all = "camelCase")]
 pub async fn create_database(
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
     connection_id: String,
     database_name: String,
 ) -> Result<(), String> {
@@ -126,13 +129,17 @@ pub async fn create_database(
         .map_err(|e| {
             error!("创建数据库失败: {}", e);
             format!("创建数据库失败: {}", e)
-        })
+        })?;
+
+    schema_cache.invalidate(&connection_id, Some(&database_name)).await;
+    Ok(())
 }
 
 /// 删除数据库
 #[tauri::command(rename_all = "camelCase")]
 pub async fn drop_database(
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
     connection_id: String,
     database_name: String,
 ) -> Result<(), String> {
@@ -149,7 +156,10 @@ pub async fn drop_database(
         .map_err(|e| {
             error!("删除数据库失败: {}", e);
             format!("删除数据库失败: {}", e)
-        })
+        })?;
+
+    schema_cache.invalidate(&connection_id, Some(&database_name)).await;
+    Ok(())
 }
 
 /// 获取保留策略
@@ -1072,6 +1082,7 @@ fn export_to_json(result: &QueryResult, file_path: &str) -> Result<String, Strin
 #[tauri::command]
 pub async fn refresh_database_structure(
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
     connection_id: String,
     database: String,
 ) -> Result<(), String> {
@@ -1084,11 +1095,27 @@ pub async fn refresh_database_structure(
             format!("获取连接失败: {}", e)
         })?;
 
-    // 这里可以添加缓存清理逻辑
+    // 清除该数据库的结构信息缓存，下次访问时重新从服务器加载
+    schema_cache.invalidate(&connection_id, Some(&database)).await;
     info!("数据库 '{}' 结构已刷新", database);
     Ok(())
 }
 
+/// 使连接的结构缓存失效
+///
+/// 数据源树的刷新操作调用此命令，`database` 为空时清除整个连接的缓存。
+#[tauri::command]
+pub async fn invalidate_schema_cache(
+    schema_cache: State<'_, SchemaCacheService>,
+    connection_id: String,
+    database: Option<String>,
+) -> Result<(), String> {
+    debug!("处理清除结构缓存命令: {} - {:?}", connection_id, database);
+
+    schema_cache.invalidate(&connection_id, database.as_deref()).await;
+    Ok(())
+}
+
 /// 创建测量模板
 #[tauri::command]
 pub async fn create_measurement_template(
@@ -1203,6 +1230,7 @@ pub async fn alter_retention_policy(
 #[tauri::command]
 pub async fn drop_measurement(
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
     connection_id: String,
     database: String,
     measurement: String,
@@ -1224,6 +1252,7 @@ pub async fn drop_measurement(
             format!("删除测量失败: {}", e)
         })?;
 
+    schema_cache.invalidate(&connection_id, Some(&database)).await;
     info!("测量 '{}' 删除成功", measurement);
     Ok(())
 }
@@ -1261,6 +1290,7 @@ pub async fn get_measurements(
 #[tauri::command]
 pub async fn get_field_keys(
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
     connection_id: String,
     database: String,
     measurement: Option<String>,
@@ -1286,61 +1316,13 @@ pub async fn get_field_keys(
             format!("获取连接失败: {}", e)
         })?;
 
-    // 根据连接类型构建不同的查询语句
-    let query = {
-        // 检查连接类型，如果是IoTDB则使用SHOW TIMESERIES语法
-        let db_type = client.get_database_type();
-        debug!("🔍 commands/database.rs字段查询 - 数据库类型: {:?}, database: {}, measurement: {:?}", db_type, database, measurement);
-        if matches!(db_type, crate::models::DatabaseType::IoTDB) {
-            // IoTDB使用SHOW TIMESERIES语法，不使用引号
-            if let Some(measurement) = measurement {
-                // 智能处理路径重复问题
-                let full_path = if measurement.starts_with(&database) {
-                    // measurement已经包含database前缀，直接使用
-                    measurement.clone()
-                } else {
-                    // measurement不包含database前缀，需要拼接
-                    format!("{}.{}", database, measurement)
-                };
-                let query = format!("SHOW TIMESERIES {}.*", full_path);
-                debug!("🔍 commands/database.rs生成IoTDB字段查询: {}", query);
-                query
-            } else {
-                let query = format!("SHOW TIMESERIES {}.**", database);
-                debug!("🔍 commands/database.rs生成IoTDB字段查询: {}", query);
-                query
-            }
-        } else {
-            // InfluxDB使用SHOW FIELD KEYS语法
-            if let Some(measurement) = measurement {
-                let query = format!("SHOW FIELD KEYS ON \"{}\" FROM \"{}\"", database, measurement);
-                debug!("🔍 commands/database.rs生成InfluxDB字段查询: {}", query);
-                query
-            } else {
-                let query = format!("SHOW FIELD KEYS ON \"{}\"", database);
-                debug!("🔍 commands/database.rs生成InfluxDB字段查询: {}", query);
-                query
-            }
-        }
-    };
-
-    let result = client.execute_query(&query, Some(&database)).await
+    schema_cache
+        .get(&connection_id, &client, SchemaItem::FieldKeys { database, measurement })
+        .await
         .map_err(|e| {
             error!("获取字段键失败: {}", e);
             format!("获取字段键失败: {}", e)
-        })?;
-
-    // 解析字段键
-    let mut field_keys = Vec::new();
-    for row in result.rows() {
-        if let Some(field_key) = row.get(0) {
-            if let Some(key_str) = field_key.as_str() {
-                field_keys.push(key_str.to_string());
-            }
-        }
-    }
-
-    Ok(field_keys)
+        })
 }
 
 /// 获取标签键
@@ -1360,42 +1342,11 @@ pub async fn get_tag_keys(
             format!("获取连接失败: {}", e)
         })?;
 
-    // 根据连接类型处理标签查询
-    let db_type = client.get_database_type();
-    debug!("🔍 commands/database.rs标签查询 - 数据库类型: {:?}, database: {}, measurement: {:?}", db_type, database, measurement);
-
-    if matches!(db_type, crate::models::DatabaseType::IoTDB) {
-        // IoTDB不支持TAG概念，直接返回空结果
-        debug!("🔍 commands/database.rs IoTDB不支持标签，返回空结果");
-        return Ok(vec![]);
-    }
-
-    // InfluxDB使用SHOW TAG KEYS语法
-    let query = if let Some(measurement) = measurement {
-        format!("SHOW TAG KEYS ON \"{}\" FROM \"{}\"", database, measurement)
-    } else {
-        format!("SHOW TAG KEYS ON \"{}\"", database)
-    };
-
-    debug!("🔍 commands/database.rs生成InfluxDB标签查询: {}", query);
-
-    let result = client.execute_query(&query, Some(&database)).await
+    client.get_tag_keys(&database, measurement.as_deref()).await
         .map_err(|e| {
             error!("获取标签键失败: {}", e);
             format!("获取标签键失败: {}", e)
-        })?;
-
-    // 解析标签键
-    let mut tag_keys = Vec::new();
-    for row in result.rows() {
-        if let Some(tag_key) = row.get(0) {
-            if let Some(key_str) = tag_key.as_str() {
-                tag_keys.push(key_str.to_string());
-            }
-        }
-    }
-
-    Ok(tag_keys)
+        })
 }
 
 /// 获取标签值
@@ -1416,49 +1367,18 @@ pub async fn get_tag_values(
             format!("获取连接失败: {}", e)
         })?;
     
-    // 根据连接类型构建不同的查询语句
-    let query = {
-        let db_type = client.get_database_type();
-        if matches!(db_type, crate::models::DatabaseType::IoTDB) {
-            // IoTDB不支持TAG VALUES概念，返回设备信息
-            if let Some(measurement) = measurement {
-                format!("SHOW DEVICES {}.{}", database, measurement)
-            } else {
-                format!("SHOW DEVICES {}.**", database)
-            }
-        } else {
-            // InfluxDB使用SHOW TAG VALUES语法
-            if let Some(measurement) = measurement {
-                format!("SHOW TAG VALUES ON \"{}\" FROM \"{}\" WITH KEY = \"{}\"", database, measurement, tag_key)
-            } else {
-                format!("SHOW TAG VALUES ON \"{}\" WITH KEY = \"{}\"", database, tag_key)
-            }
-        }
-    };
-    
-    let result = client.execute_query(&query, Some(&database)).await
+    client.get_tag_values(&database, &tag_key, measurement.as_deref()).await
         .map_err(|e| {
             error!("获取标签值失败: {}", e);
             format!("获取标签值失败: {}", e)
-        })?;
-    
-    // 解析标签值
-    let mut tag_values = Vec::new();
-    for row in result.rows() {
-        if let Some(tag_value) = row.get(1) { // 标签值通常在第二列
-            if let Some(value_str) = tag_value.as_str() {
-                tag_values.push(value_str.to_string());
-            }
-        }
-    }
-    
-    Ok(tag_values)
+        })
 }
 
 /// 获取表结构信息（包含字段和标签）
 #[tauri::command]
 pub async fn get_table_schema(
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
     connection_id: String,
     database: String,
     measurement: String,
@@ -1472,13 +1392,40 @@ pub async fn get_table_schema(
             format!("获取连接失败: {}", e)
         })?;
     
-    client.get_table_schema(&database, &measurement).await
+    schema_cache.get_table_schema(&connection_id, &client, &database, &measurement).await
         .map_err(|e| {
             error!("获取表结构失败: {}", e);
             format!("获取表结构失败: {}", e)
         })
 }
 
+/// 跨连接搜索结构信息，只搜索已缓存的结构
+#[tauri::command]
+pub async fn search_schema(
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+    query: String,
+    mode: Option<SearchMode>,
+    limit: Option<usize>,
+) -> Result<Vec<SchemaSearchHit>, String> {
+    debug!("处理搜索结构信息命令: {} - {:?}", query, mode);
+
+    let matcher = Matcher::new(&query, mode.unwrap_or_default()).map_err(|e| {
+        error!("搜索结构信息失败: {}", e);
+        e
+    })?;
+
+    let mut hits = Vec::new();
+    for config in connection_service.get_connections().await {
+        hits.extend(schema_cache.search(&config.id, &config.name, &matcher).await);
+    }
+
+    let total = hits.len();
+    schema_search::rank(&mut hits, limit);
+    debug!("找到 {} 个结果，返回 {} 个", total, hits.len());
+    Ok(hits)
+}
+
 /// 字段映射配置
 #[derive(Debug, Deserialize, Serialize)]
 pub struct FieldMapping {
@@ -1753,6 +1700,7 @@ pub async fn get_tree_nodes(
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_tree_children(
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
     connection_id: String,
     parent_node_id: String,
     node_type: String,
@@ -1774,7 +1722,9 @@ pub async fn get_tree_children(
         })?;
 
     // 根据节点类型获取子节点
-    let children = client.get_tree_children(&parent_node_id, &node_type, metadata.as_ref()).await
+    let children = schema_cache
+        .get_tree_children(&connection_id, &client, &parent_node_id, &node_type, metadata.as_ref())
+        .await
         .map_err(|e| {
             error!("获取树节点子节点失败: {}", e);
             format!("获取树节点子节点失败: {}", e)
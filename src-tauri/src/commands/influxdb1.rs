@@ -0,0 +1,802 @@
+/**
+ * InfluxDB 1.x 特定命令
+ *
+ * 处理 InfluxDB 1.x 的连续查询(Continuous Query)、用户和权限、分片(Shard)以及运行中查询相关操作
+ */
+
+use tauri::{AppHandle, Emitter, State};
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use crate::services::connection_service::ConnectionService;
+use crate::services::SchemaCacheService;
+
+/// 连续查询回填进度事件
+pub const CQ_BACKFILL_PROGRESS_EVENT: &str = "influxdb1-cq-backfill-progress";
+
+/// 运行中查询轮询事件
+pub const SERVER_QUERIES_EVENT: &str = "influxdb1-server-queries";
+
+// 运行中查询轮询任务：连接 ID → 任务编号，编号变化或移除时对应的后台任务退出
+lazy_static::lazy_static! {
+    static ref QUERY_WATCHERS: Mutex<HashMap<String, u64>> = Mutex::new(HashMap::new());
+}
+static NEXT_WATCHER_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 轮询连续失败达到该次数后停止
+const MAX_WATCH_ERRORS: u32 = 5;
+
+/// 连续查询信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InfluxContinuousQueryInfo {
+    pub name: String,
+    pub database: String,
+    pub query: String,                     // 完整的 CREATE CONTINUOUS QUERY 语句
+    pub select: String,                    // BEGIN ... END 之间的 SELECT ... INTO 语句
+    pub resample_every: Option<String>,
+    pub resample_for: Option<String>,
+    pub group_by_interval: Option<String>, // GROUP BY time(...) 间隔
+    pub target: Option<String>,            // INTO 目标
+}
+
+/// 创建连续查询请求
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateInfluxContinuousQueryRequest {
+    pub name: String,
+    pub database: String,
+    pub select: String, // SELECT ... INTO ... FROM ... GROUP BY time(...) 查询体
+    pub resample_every: Option<String>,
+    pub resample_for: Option<String>,
+}
+
+/// 连续查询回填请求
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackfillContinuousQueryRequest {
+    pub name: String,
+    pub database: String,
+    pub start: String,         // RFC3339 时间
+    pub end: Option<String>,   // RFC3339 时间，为空时回填到当前时间
+    pub chunk: Option<String>, // 每次执行的时间窗口，默认 1d
+}
+
+/// 连续查询回填进度
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContinuousQueryBackfillProgress {
+    pub connection_id: String,
+    pub database: String,
+    pub name: String,
+    pub completed: usize,
+    pub total: usize,
+    pub window_start: String,
+    pub window_end: String,
+    pub points_written: i64,
+}
+
+/// 连续查询回填结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContinuousQueryBackfillResult {
+    pub windows: usize,
+    pub points_written: i64,
+    pub execution_time: u64, // 毫秒
+}
+
+/// 用户信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InfluxUser1xInfo {
+    pub name: String,
+    pub admin: bool,
+}
+
+/// 创建用户请求
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateInfluxUser1xRequest {
+    pub name: String,
+    pub password: String,
+    #[serde(default)]
+    pub admin: bool, // 为 true 时使用 WITH ALL PRIVILEGES 创建管理员
+}
+
+/// 用户在单个数据库上的权限
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseGrant1x {
+    pub database: String,
+    pub privilege: String, // READ / WRITE / ALL / NONE
+    pub read: bool,
+    pub write: bool,
+}
+
+/// 权限矩阵中的一行（一个用户）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivilegeMatrixRow {
+    pub user: String,
+    pub admin: bool,
+    pub privileges: Vec<DatabaseGrant1x>, // 与 PrivilegeMatrix::databases 顺序一致
+}
+
+/// 用户 × 数据库 权限矩阵
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivilegeMatrix {
+    pub databases: Vec<String>,
+    pub rows: Vec<PrivilegeMatrixRow>,
+}
+
+/// 时间范围
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeRange1x {
+    pub start: String,
+    pub end: String,
+}
+
+/// 分片信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShardInfo {
+    pub id: u64,
+    pub database: String,
+    pub retention_policy: String,
+    pub shard_group: u64,
+    pub start_time: String,
+    pub end_time: String,
+    pub expiry_time: String,
+    pub expired: bool,
+    pub owners: Option<String>,
+    pub size_bytes: Option<i64>, // 来自 _internal 数据库，未启用监控时为空
+}
+
+/// 分片组信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShardGroupInfo {
+    pub id: u64,
+    pub database: String,
+    pub retention_policy: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub expiry_time: String,
+    pub shard_ids: Vec<u64>,
+    pub size_bytes: Option<i64>,
+}
+
+/// 单个保留策略的分片覆盖时间线
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionPolicyTimeline {
+    pub database: String,
+    pub retention_policy: String,
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+    pub total_size_bytes: Option<i64>,
+    pub groups: Vec<ShardGroupInfo>, // 按开始时间排序
+    pub gaps: Vec<TimeRange1x>,      // 相邻分片组之间未覆盖的时间段
+}
+
+/// 服务器上正在执行的查询
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunningQueryInfo {
+    pub qid: u64,
+    pub query: String,
+    pub database: Option<String>,
+    pub duration: String,          // 服务器返回的原始时长，如 1m2.5s
+    pub duration_ms: Option<f64>,
+    pub status: String,            // running / killed
+    pub host: Option<String>,      // 仅集群版返回
+}
+
+/// 运行中查询轮询快照
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerQueriesSnapshot {
+    pub connection_id: String,
+    pub queries: Vec<RunningQueryInfo>,
+    pub error: Option<String>,
+    pub timestamp: i64, // 毫秒
+}
+
+/// 获取连续查询列表，指定数据库时只返回该数据库的连续查询
+#[tauri::command]
+pub async fn get_influxdb1_continuous_queries(
+    connection_id: String,
+    database: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<InfluxContinuousQueryInfo>, String> {
+    debug!("获取连续查询列表: {} - {:?}", connection_id, database);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let queries = client
+        .get_influxdb1_continuous_queries()
+        .await
+        .map_err(|e| format!("获取连续查询列表失败: {}", e))?;
+
+    Ok(queries
+        .into_iter()
+        .filter(|cq| database.as_ref().is_none_or(|db| &cq.database == db))
+        .collect())
+}
+
+/// 创建连续查询
+#[tauri::command]
+pub async fn create_influxdb1_continuous_query(
+    connection_id: String,
+    request: CreateInfluxContinuousQueryRequest,
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+) -> Result<InfluxContinuousQueryInfo, String> {
+    debug!("创建连续查询: {} - {}.{}", connection_id, request.database, request.name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .create_influxdb1_continuous_query(&request)
+        .await
+        .map_err(|e| format!("创建连续查询失败: {}", e))?;
+
+    info!("连续查询 '{}' 创建成功", result.name);
+    schema_cache.invalidate(&connection_id, Some(&request.database)).await;
+    Ok(result)
+}
+
+/// 删除连续查询
+#[tauri::command]
+pub async fn drop_influxdb1_continuous_query(
+    connection_id: String,
+    database: String,
+    name: String,
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+) -> Result<(), String> {
+    debug!("删除连续查询: {} - {}.{}", connection_id, database, name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .drop_influxdb1_continuous_query(&database, &name)
+        .await
+        .map_err(|e| format!("删除连续查询失败: {}", e))?;
+
+    info!("连续查询 '{}' 删除成功", name);
+    schema_cache.invalidate(&connection_id, Some(&database)).await;
+    Ok(())
+}
+
+/// 在指定时间范围内回填连续查询，按窗口分段执行并通过事件上报进度
+#[tauri::command]
+pub async fn backfill_influxdb1_continuous_query(
+    app: AppHandle,
+    connection_id: String,
+    request: BackfillContinuousQueryRequest,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<ContinuousQueryBackfillResult, String> {
+    debug!("回填连续查询: {} - {}.{}", connection_id, request.database, request.name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .backfill_influxdb1_continuous_query(&request, |progress| {
+            if let Err(e) = app.emit(CQ_BACKFILL_PROGRESS_EVENT, progress) {
+                warn!("发送连续查询回填进度失败: {}", e);
+            }
+        })
+        .await
+        .map_err(|e| format!("回填连续查询失败: {}", e))?;
+
+    info!(
+        "连续查询 '{}' 回填完成: {} 个窗口，写入 {} 个点",
+        request.name, result.windows, result.points_written
+    );
+    Ok(result)
+}
+
+/// 获取用户列表
+#[tauri::command]
+pub async fn get_influxdb1_users(
+    connection_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<InfluxUser1xInfo>, String> {
+    debug!("获取用户列表: {}", connection_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb1_users()
+        .await
+        .map_err(|e| format!("获取用户列表失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 创建用户
+#[tauri::command]
+pub async fn create_influxdb1_user(
+    connection_id: String,
+    request: CreateInfluxUser1xRequest,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("创建用户: {} - {}", connection_id, request.name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .create_influxdb1_user(&request)
+        .await
+        .map_err(|e| format!("创建用户失败: {}", e))?;
+
+    info!("用户 '{}' 创建成功", request.name);
+    Ok(())
+}
+
+/// 修改用户密码
+#[tauri::command]
+pub async fn set_influxdb1_user_password(
+    connection_id: String,
+    username: String,
+    password: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("修改用户密码: {} - {}", connection_id, username);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .set_influxdb1_user_password(&username, &password)
+        .await
+        .map_err(|e| format!("修改用户密码失败: {}", e))?;
+
+    info!("用户 '{}' 密码修改成功", username);
+    Ok(())
+}
+
+/// 授予或收回管理员权限
+#[tauri::command]
+pub async fn set_influxdb1_user_admin(
+    connection_id: String,
+    username: String,
+    admin: bool,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("设置管理员权限: {} - {} - {}", connection_id, username, admin);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .set_influxdb1_user_admin(&username, admin)
+        .await
+        .map_err(|e| format!("设置管理员权限失败: {}", e))?;
+
+    info!("用户 '{}' 管理员权限已更新", username);
+    Ok(())
+}
+
+/// 删除用户
+#[tauri::command]
+pub async fn drop_influxdb1_user(
+    connection_id: String,
+    username: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("删除用户: {} - {}", connection_id, username);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .drop_influxdb1_user(&username)
+        .await
+        .map_err(|e| format!("删除用户失败: {}", e))?;
+
+    info!("用户 '{}' 删除成功", username);
+    Ok(())
+}
+
+/// 获取用户在各数据库上的权限
+#[tauri::command]
+pub async fn get_influxdb1_user_grants(
+    connection_id: String,
+    username: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<DatabaseGrant1x>, String> {
+    debug!("获取用户权限: {} - {}", connection_id, username);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb1_user_grants(&username)
+        .await
+        .map_err(|e| format!("获取用户权限失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 授予数据库权限（READ / WRITE / ALL）
+#[tauri::command]
+pub async fn grant_influxdb1_privilege(
+    connection_id: String,
+    username: String,
+    database: String,
+    privilege: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("授予权限: {} - {} {} ON {}", connection_id, username, privilege, database);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .grant_influxdb1_privilege(&username, &database, &privilege)
+        .await
+        .map_err(|e| format!("授予权限失败: {}", e))?;
+
+    info!("已授予用户 '{}' 在 '{}' 上的 {} 权限", username, database, privilege);
+    Ok(())
+}
+
+/// 收回数据库权限（READ / WRITE / ALL）
+#[tauri::command]
+pub async fn revoke_influxdb1_privilege(
+    connection_id: String,
+    username: String,
+    database: String,
+    privilege: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("收回权限: {} - {} {} ON {}", connection_id, username, privilege, database);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .revoke_influxdb1_privilege(&username, &database, &privilege)
+        .await
+        .map_err(|e| format!("收回权限失败: {}", e))?;
+
+    info!("已收回用户 '{}' 在 '{}' 上的 {} 权限", username, database, privilege);
+    Ok(())
+}
+
+/// 将用户在某数据库上的权限设置为指定的读写组合
+#[tauri::command]
+pub async fn set_influxdb1_privileges(
+    connection_id: String,
+    username: String,
+    database: String,
+    read: bool,
+    write: bool,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<DatabaseGrant1x, String> {
+    debug!("设置权限: {} - {} ON {} (read: {}, write: {})", connection_id, username, database, read, write);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .set_influxdb1_privileges(&username, &database, read, write)
+        .await
+        .map_err(|e| format!("设置权限失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 获取用户 × 数据库权限矩阵
+#[tauri::command]
+pub async fn get_influxdb1_privilege_matrix(
+    connection_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<PrivilegeMatrix, String> {
+    debug!("获取权限矩阵: {}", connection_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb1_privilege_matrix()
+        .await
+        .map_err(|e| format!("获取权限矩阵失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 获取分片列表，指定数据库时只返回该数据库的分片
+#[tauri::command]
+pub async fn get_influxdb1_shards(
+    connection_id: String,
+    database: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<ShardInfo>, String> {
+    debug!("获取分片列表: {} - {:?}", connection_id, database);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb1_shards(database.as_deref())
+        .await
+        .map_err(|e| format!("获取分片列表失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 获取分片组列表，指定数据库时只返回该数据库的分片组
+#[tauri::command]
+pub async fn get_influxdb1_shard_groups(
+    connection_id: String,
+    database: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<ShardGroupInfo>, String> {
+    debug!("获取分片组列表: {} - {:?}", connection_id, database);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb1_shard_groups(database.as_deref())
+        .await
+        .map_err(|e| format!("获取分片组列表失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 获取按保留策略汇总的分片时间线
+#[tauri::command]
+pub async fn get_influxdb1_shard_timeline(
+    connection_id: String,
+    database: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<RetentionPolicyTimeline>, String> {
+    debug!("获取分片时间线: {} - {:?}", connection_id, database);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb1_shard_timeline(database.as_deref())
+        .await
+        .map_err(|e| format!("获取分片时间线失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 删除分片，`confirm_shard_id` 必须与 `shard_id` 一致，避免误删
+#[tauri::command]
+pub async fn drop_influxdb1_shard(
+    connection_id: String,
+    shard_id: u64,
+    confirm_shard_id: u64,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<ShardInfo, String> {
+    debug!("删除分片: {} - {}", connection_id, shard_id);
+
+    if shard_id != confirm_shard_id {
+        return Err(format!("确认的分片 ID {} 与要删除的分片 {} 不一致", confirm_shard_id, shard_id));
+    }
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .drop_influxdb1_shard(shard_id)
+        .await
+        .map_err(|e| format!("删除分片失败: {}", e))?;
+
+    info!("分片 {} ({}.{}) 删除成功", shard_id, result.database, result.retention_policy);
+    Ok(result)
+}
+
+/// 获取服务器上正在执行的查询（SHOW QUERIES）
+#[tauri::command]
+pub async fn get_server_queries(
+    connection_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<RunningQueryInfo>, String> {
+    debug!("获取运行中查询: {}", connection_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb1_running_queries()
+        .await
+        .map_err(|e| format!("获取运行中查询失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 终止服务器上正在执行的查询（KILL QUERY）
+#[tauri::command]
+pub async fn kill_server_query(
+    connection_id: String,
+    qid: u64,
+    host: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("终止查询: {} - {}", connection_id, qid);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .kill_influxdb1_query(qid, host.as_deref())
+        .await
+        .map_err(|e| format!("终止查询失败: {}", e))?;
+
+    info!("查询 {} 已终止", qid);
+    Ok(())
+}
+
+/// 开始定时轮询运行中的查询，结果通过事件推送；重复调用会替换原有轮询任务
+#[tauri::command]
+pub async fn start_server_query_watch(
+    app: AppHandle,
+    connection_id: String,
+    interval_seconds: Option<u64>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    let interval = interval_seconds.unwrap_or(2).max(1);
+    debug!("开始轮询运行中查询: {} (间隔 {}秒)", connection_id, interval);
+
+    let watcher_id = NEXT_WATCHER_ID.fetch_add(1, Ordering::Relaxed);
+    QUERY_WATCHERS
+        .lock()
+        .map_err(|e| format!("获取轮询状态锁失败: {}", e))?
+        .insert(connection_id.clone(), watcher_id);
+
+    let manager = connection_service.get_manager();
+    tokio::spawn(async move {
+        let mut consecutive_errors = 0;
+        loop {
+            let active = match QUERY_WATCHERS.lock() {
+                Ok(watchers) => watchers.get(&connection_id) == Some(&watcher_id),
+                Err(e) => {
+                    error!("获取轮询状态锁失败: {}", e);
+                    false
+                }
+            };
+            if !active {
+                break;
+            }
+
+            // 连接已不存在时停止轮询，不再重复报错
+            let (result, stop) = match manager.get_connection(&connection_id).await {
+                Ok(client) => (client.get_influxdb1_running_queries().await, false),
+                Err(e) => (Err(e), true),
+            };
+            consecutive_errors = if result.is_ok() { 0 } else { consecutive_errors + 1 };
+            let stop = stop || consecutive_errors >= MAX_WATCH_ERRORS;
+
+            let snapshot = match result {
+                Ok(queries) => ServerQueriesSnapshot {
+                    connection_id: connection_id.clone(),
+                    queries: crate::database::influxdb::running_queries::exclude_own_show_queries(queries),
+                    error: None,
+                    timestamp: chrono::Utc::now().timestamp_millis(),
+                },
+                Err(e) => ServerQueriesSnapshot {
+                    connection_id: connection_id.clone(),
+                    queries: Vec::new(),
+                    error: Some(if stop { format!("{}，已停止轮询", e) } else { e.to_string() }),
+                    timestamp: chrono::Utc::now().timestamp_millis(),
+                },
+            };
+            if let Err(e) = app.emit(SERVER_QUERIES_EVENT, &snapshot) {
+                warn!("发送运行中查询事件失败: {}", e);
+            }
+
+            if stop {
+                warn!("运行中查询轮询连续失败，停止轮询: {}", connection_id);
+                if let Ok(mut watchers) = QUERY_WATCHERS.lock() {
+                    if watchers.get(&connection_id) == Some(&watcher_id) {
+                        watchers.remove(&connection_id);
+                    }
+                }
+                break;
+            }
+
+            tokio::time::sleep(tokio::time::Duration::from_secs(interval)).await;
+        }
+
+        debug!("运行中查询轮询已停止: {}", connection_id);
+    });
+
+    Ok(())
+}
+
+/// 停止轮询运行中的查询
+#[tauri::command]
+pub async fn stop_server_query_watch(connection_id: String) -> Result<(), String> {
+    debug!("停止轮询运行中查询: {}", connection_id);
+
+    stop_query_watch(&connection_id);
+    Ok(())
+}
+
+/// 停止连接的运行中查询轮询，断开或删除连接时调用
+pub fn stop_query_watch(connection_id: &str) {
+    match QUERY_WATCHERS.lock() {
+        Ok(mut watchers) => {
+            watchers.remove(connection_id);
+        }
+        Err(e) => error!("获取轮询状态锁失败: {}", e),
+    }
+}
@@ -1,13 +1,16 @@
 /**
  * InfluxDB 2.x 特定命令
  * 
- * 处理 InfluxDB 2.x 的组织(Organization)和存储桶(Bucket)相关操作
+ * 处理 InfluxDB 2.x 的组织(Organization)、存储桶(Bucket)、任务(Task)、监控告警、API 令牌、
+ * 用户、标签(Label)、Telegraf 配置和抓取器(Scraper)相关操作
  */
 
 use tauri::State;
 use log::{debug, info};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use crate::services::connection_service::ConnectionService;
+use crate::services::SchemaCacheService;
 
 /// 存储桶信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +47,403 @@ pub struct CreateBucketRequest {
     pub description: Option<String>,
 }
 
+/// 任务信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskInfo {
+    pub id: String,
+    pub name: String,
+    pub org_id: String,
+    pub org_name: String,
+    pub status: String, // active / inactive
+    pub every: Option<String>,
+    pub cron: Option<String>,
+    pub offset: Option<String>,
+    pub flux: String,
+    pub description: Option<String>,
+    pub latest_completed: Option<String>,
+    pub last_run_status: Option<String>,
+    pub last_run_error: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// 创建任务请求
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTaskRequest {
+    pub name: String,
+    pub org_id: Option<String>,   // 为空时使用连接配置中的组织
+    pub flux: String,             // 任务脚本，已有的 option task 声明会被替换
+    pub every: Option<String>,
+    pub cron: Option<String>,
+    pub offset: Option<String>,
+    pub description: Option<String>,
+    pub active: Option<bool>,     // 默认启用
+}
+
+/// 更新任务请求，未设置的字段保持不变
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTaskRequest {
+    pub name: Option<String>,
+    pub flux: Option<String>,
+    pub every: Option<String>,
+    pub cron: Option<String>,
+    pub offset: Option<String>,
+    pub description: Option<String>,
+    pub active: Option<bool>,
+}
+
+/// 任务运行记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskRunInfo {
+    pub id: String,
+    pub task_id: String,
+    pub status: String, // scheduled / started / success / failed / canceled
+    pub scheduled_for: Option<String>,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub requested_at: Option<String>,
+}
+
+/// 任务运行日志
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskLogEntry {
+    pub run_id: Option<String>,
+    pub time: String,
+    pub message: String,
+}
+
+/// 检查阈值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckThreshold {
+    pub threshold_type: String, // greater / lesser / range
+    pub level: String,          // CRIT / WARN / INFO / OK
+    pub value: Option<f64>,     // greater / lesser 使用
+    pub min: Option<f64>,       // range 使用
+    pub max: Option<f64>,
+    pub within: Option<bool>,
+    #[serde(default)]
+    pub all_values: bool,
+}
+
+/// Deadman 检查参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadmanConfig {
+    pub time_since: String, // 多久没有数据视为异常，例如 90s
+    pub stale_time: String, // 多久后不再重复报告
+    pub level: String,
+    #[serde(default)]
+    pub report_zero: bool,
+}
+
+/// 检查信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckInfo {
+    pub id: String,
+    pub name: String,
+    pub org_id: String,
+    pub check_type: String, // threshold / deadman
+    pub status: String,
+    pub every: Option<String>,
+    pub offset: Option<String>,
+    pub query: String,
+    pub status_message_template: Option<String>,
+    pub description: Option<String>,
+    pub thresholds: Vec<CheckThreshold>,
+    pub deadman: Option<DeadmanConfig>,
+    pub last_run_status: Option<String>,
+    pub last_run_error: Option<String>,
+    pub latest_completed: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// 创建或更新检查请求
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveCheckRequest {
+    pub name: String,
+    pub org_id: Option<String>, // 为空时使用连接配置中的组织
+    pub check_type: String,
+    pub query: String,
+    pub every: String,
+    pub offset: Option<String>,
+    pub status_message_template: Option<String>,
+    pub description: Option<String>,
+    pub active: Option<bool>,
+    #[serde(default)]
+    pub thresholds: Vec<CheckThreshold>,
+    pub deadman: Option<DeadmanConfig>,
+}
+
+/// 通知端点信息（不返回密码、令牌等敏感字段）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationEndpointInfo {
+    pub id: String,
+    pub name: String,
+    pub org_id: String,
+    pub endpoint_type: String, // http / slack / pagerduty
+    pub status: String,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    pub method: Option<String>,
+    pub auth_method: Option<String>,
+    pub username: Option<String>,
+    pub headers: HashMap<String, String>,
+    pub client_url: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// 创建或更新通知端点请求
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveNotificationEndpointRequest {
+    pub name: String,
+    pub org_id: Option<String>,
+    pub endpoint_type: String,
+    pub description: Option<String>,
+    pub active: Option<bool>,
+    pub url: Option<String>,         // http / slack
+    pub method: Option<String>,      // http: POST / GET / PUT
+    pub auth_method: Option<String>, // http: none / basic / bearer
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub token: Option<String>,       // http bearer / slack
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    pub client_url: Option<String>,  // pagerduty
+    pub routing_key: Option<String>, // pagerduty
+}
+
+/// 通知规则的状态匹配条件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusRule {
+    pub current_level: String,
+    pub previous_level: Option<String>,
+}
+
+/// 通知规则的标签匹配条件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagRule {
+    pub key: String,
+    pub value: String,
+    pub operator: String, // equal / notequal / equalregex / notequalregex
+}
+
+/// 通知规则信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationRuleInfo {
+    pub id: String,
+    pub name: String,
+    pub org_id: String,
+    pub rule_type: String, // 与端点类型一致
+    pub endpoint_id: String,
+    pub status: String,
+    pub every: Option<String>,
+    pub offset: Option<String>,
+    pub description: Option<String>,
+    pub status_rules: Vec<StatusRule>,
+    pub tag_rules: Vec<TagRule>,
+    pub message_template: Option<String>,
+    pub channel: Option<String>,
+    pub last_run_status: Option<String>,
+    pub last_run_error: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// 创建或更新通知规则请求，规则类型由端点决定
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveNotificationRuleRequest {
+    pub name: String,
+    pub org_id: Option<String>,
+    pub endpoint_id: String,
+    pub every: String,
+    pub offset: Option<String>,
+    pub description: Option<String>,
+    pub active: Option<bool>,
+    pub status_rules: Vec<StatusRule>,
+    #[serde(default)]
+    pub tag_rules: Vec<TagRule>,
+    pub message_template: Option<String>, // slack / pagerduty 必填
+    pub channel: Option<String>,          // slack
+}
+
+/// 检查状态记录（来自 _monitoring 存储桶）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckStatusEntry {
+    pub time: String,
+    pub check_id: String,
+    pub check_name: String,
+    pub level: String,
+    pub message: String,
+}
+
+/// 令牌权限
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionInfo {
+    pub action: String,        // read / write
+    pub resource_type: String, // buckets / orgs / tasks ...
+    pub resource_id: Option<String>, // 为空表示该类型的全部资源
+    pub resource_name: Option<String>,
+    pub org_id: Option<String>,
+    pub org_name: Option<String>,
+}
+
+/// API 令牌（授权）信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorizationInfo {
+    pub id: String,
+    pub description: Option<String>,
+    pub status: String, // active / inactive
+    pub org_id: String,
+    pub org_name: Option<String>,
+    pub user_id: Option<String>,
+    pub user_name: Option<String>,
+    pub token: Option<String>, // 列表中为脱敏值，仅创建时返回完整令牌
+    pub is_current: bool,      // 是否为当前连接使用的令牌
+    pub permissions: Vec<PermissionInfo>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// 创建存储桶范围令牌请求
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAuthorizationRequest {
+    pub description: Option<String>,
+    pub bucket_names: Vec<String>,
+    pub read: bool,
+    pub write: bool,
+}
+
+/// InfluxDB 2.x 用户信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct User2xInfo {
+    pub id: String,
+    pub name: String,
+    pub status: String, // active / inactive
+}
+
+/// 组织成员信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrgMemberInfo {
+    pub id: String,
+    pub name: String,
+    pub status: String,
+    pub role: String, // owner / member
+}
+
+/// 标签信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelInfo {
+    pub id: String,
+    pub org_id: String,
+    pub name: String,
+    pub color: Option<String>,
+    pub description: Option<String>,
+}
+
+/// 创建或更新标签请求
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveLabelRequest {
+    pub org_name: Option<String>, // 仅创建时使用，为空时使用连接配置中的组织
+    pub name: String,
+    pub color: Option<String>,    // #RRGGBB
+    pub description: Option<String>,
+}
+
+/// Telegraf 配置信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelegrafConfigInfo {
+    pub id: String,
+    pub org_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub config: String, // TOML 文本
+    pub buckets: Vec<String>,
+}
+
+/// 创建或更新 Telegraf 配置请求
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveTelegrafRequest {
+    pub org_id: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub config: String,
+    #[serde(default)]
+    pub buckets: Vec<String>,
+}
+
+/// Telegraf 配置校验结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelegrafValidation {
+    pub valid: bool,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+}
+
+/// 生成的 Telegraf 入门配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelegrafStarterConfig {
+    pub config: String,
+    pub bucket_name: String,
+    pub org_name: String,
+    pub token_id: Option<String>, // 新建令牌的 ID，未创建令牌时为空
+}
+
+/// 抓取器信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScraperInfo {
+    pub id: String,
+    pub name: String,
+    pub scraper_type: String, // 目前仅支持 prometheus
+    pub url: String,
+    pub org_id: String,
+    pub org_name: Option<String>,
+    pub bucket_id: String,
+    pub bucket_name: Option<String>,
+    pub allow_insecure: bool,
+}
+
+/// 创建或更新抓取器请求
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveScraperRequest {
+    pub name: String,
+    pub url: String,
+    pub bucket_name: String,
+    #[serde(default)]
+    pub allow_insecure: bool,
+}
+
 /// 获取组织列表
 #[tauri::command]
 pub async fn get_influxdb2_organizations(
@@ -147,6 +547,7 @@ pub async fn create_influxdb2_bucket(
     connection_id: String,
     request: CreateBucketRequest,
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
 ) -> Result<(), String> {
     debug!("创建 InfluxDB 2.x 存储桶: {} - {}", connection_id, request.name);
 
@@ -163,6 +564,7 @@ pub async fn create_influxdb2_bucket(
         .map_err(|e| format!("创建存储桶失败: {}", e))?;
 
     info!("存储桶 '{}' 创建成功", request.name);
+    schema_cache.invalidate(&connection_id, Some(&request.name)).await;
     Ok(())
 }
 
@@ -172,6 +574,7 @@ pub async fn delete_influxdb2_bucket(
     connection_id: String,
     bucket_name: String,
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
 ) -> Result<(), String> {
     debug!("删除 InfluxDB 2.x 存储桶: {} - {}", connection_id, bucket_name);
 
@@ -188,6 +591,7 @@ pub async fn delete_influxdb2_bucket(
         .map_err(|e| format!("删除存储桶失败: {}", e))?;
 
     info!("存储桶 '{}' 删除成功", bucket_name);
+    schema_cache.invalidate(&connection_id, Some(&bucket_name)).await;
     Ok(())
 }
 
@@ -198,6 +602,7 @@ pub async fn update_bucket_retention(
     bucket_name: String,
     retention_period: Option<i64>,
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
 ) -> Result<(), String> {
     debug!("更新存储桶保留策略: {} - {} - {:?}", connection_id, bucket_name, retention_period);
 
@@ -214,6 +619,1372 @@ pub async fn update_bucket_retention(
         .map_err(|e| format!("更新保留策略失败: {}", e))?;
 
     info!("存储桶 '{}' 保留策略更新成功", bucket_name);
+    schema_cache.invalidate(&connection_id, Some(&bucket_name)).await;
     Ok(())
 }
 
+
+/// 获取任务列表
+#[tauri::command]
+pub async fn get_influxdb2_tasks(
+    connection_id: String,
+    org_name: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<TaskInfo>, String> {
+    debug!("获取 InfluxDB 2.x 任务列表: {} - {:?}", connection_id, org_name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let tasks = client
+        .get_influxdb2_tasks(org_name.as_deref())
+        .await
+        .map_err(|e| format!("获取任务列表失败: {}", e))?;
+
+    info!("获取到 {} 个任务", tasks.len());
+    Ok(tasks)
+}
+
+/// 获取任务详细信息
+#[tauri::command]
+pub async fn get_influxdb2_task(
+    connection_id: String,
+    task_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<TaskInfo, String> {
+    debug!("获取任务信息: {} - {}", connection_id, task_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let task = client
+        .get_influxdb2_task(&task_id)
+        .await
+        .map_err(|e| format!("获取任务信息失败: {}", e))?;
+
+    Ok(task)
+}
+
+/// 创建任务
+#[tauri::command]
+pub async fn create_influxdb2_task(
+    connection_id: String,
+    request: CreateTaskRequest,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<TaskInfo, String> {
+    debug!("创建 InfluxDB 2.x 任务: {} - {}", connection_id, request.name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let task = client
+        .create_influxdb2_task(&request)
+        .await
+        .map_err(|e| format!("创建任务失败: {}", e))?;
+
+    info!("任务 '{}' 创建成功", task.name);
+    Ok(task)
+}
+
+/// 更新任务
+#[tauri::command]
+pub async fn update_influxdb2_task(
+    connection_id: String,
+    task_id: String,
+    request: UpdateTaskRequest,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<TaskInfo, String> {
+    debug!("更新 InfluxDB 2.x 任务: {} - {}", connection_id, task_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let task = client
+        .update_influxdb2_task(&task_id, &request)
+        .await
+        .map_err(|e| format!("更新任务失败: {}", e))?;
+
+    info!("任务 '{}' 更新成功", task.name);
+    Ok(task)
+}
+
+/// 启用或停用任务
+#[tauri::command]
+pub async fn set_influxdb2_task_active(
+    connection_id: String,
+    task_id: String,
+    active: bool,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<TaskInfo, String> {
+    debug!("设置任务状态: {} - {} - {}", connection_id, task_id, active);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let request = UpdateTaskRequest {
+        active: Some(active),
+        ..Default::default()
+    };
+
+    let task = client
+        .update_influxdb2_task(&task_id, &request)
+        .await
+        .map_err(|e| format!("设置任务状态失败: {}", e))?;
+
+    info!("任务 '{}' 已{}", task.name, if active { "启用" } else { "停用" });
+    Ok(task)
+}
+
+/// 删除任务
+#[tauri::command]
+pub async fn delete_influxdb2_task(
+    connection_id: String,
+    task_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("删除 InfluxDB 2.x 任务: {} - {}", connection_id, task_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .delete_influxdb2_task(&task_id)
+        .await
+        .map_err(|e| format!("删除任务失败: {}", e))?;
+
+    info!("任务 '{}' 删除成功", task_id);
+    Ok(())
+}
+
+/// 手动触发任务运行
+#[tauri::command]
+pub async fn run_influxdb2_task(
+    connection_id: String,
+    task_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<TaskRunInfo, String> {
+    debug!("手动运行任务: {} - {}", connection_id, task_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let run = client
+        .run_influxdb2_task(&task_id)
+        .await
+        .map_err(|e| format!("运行任务失败: {}", e))?;
+
+    info!("任务 '{}' 已触发运行: {}", task_id, run.id);
+    Ok(run)
+}
+
+/// 获取任务运行历史
+#[tauri::command]
+pub async fn get_influxdb2_task_runs(
+    connection_id: String,
+    task_id: String,
+    limit: Option<u32>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<TaskRunInfo>, String> {
+    debug!("获取任务运行历史: {} - {}", connection_id, task_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let runs = client
+        .get_influxdb2_task_runs(&task_id, limit.unwrap_or(20))
+        .await
+        .map_err(|e| format!("获取任务运行历史失败: {}", e))?;
+
+    Ok(runs)
+}
+
+/// 获取任务日志，指定 run_id 时只返回该次运行的日志
+#[tauri::command]
+pub async fn get_influxdb2_task_logs(
+    connection_id: String,
+    task_id: String,
+    run_id: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<TaskLogEntry>, String> {
+    debug!("获取任务日志: {} - {} - {:?}", connection_id, task_id, run_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let logs = client
+        .get_influxdb2_task_logs(&task_id, run_id.as_deref())
+        .await
+        .map_err(|e| format!("获取任务日志失败: {}", e))?;
+
+    Ok(logs)
+}
+
+/// 获取检查列表
+#[tauri::command]
+pub async fn get_influxdb2_checks(
+    connection_id: String,
+    org_name: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<CheckInfo>, String> {
+    debug!("获取 InfluxDB 2.x 检查列表: {} - {:?}", connection_id, org_name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb2_checks(org_name.as_deref())
+        .await
+        .map_err(|e| format!("获取检查列表失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 创建检查（threshold / deadman）
+#[tauri::command]
+pub async fn create_influxdb2_check(
+    connection_id: String,
+    request: SaveCheckRequest,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<CheckInfo, String> {
+    debug!("创建 InfluxDB 2.x 检查: {} - {}", connection_id, request.name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .create_influxdb2_check(&request)
+        .await
+        .map_err(|e| format!("创建检查失败: {}", e))?;
+
+    info!("检查 '{}' 创建成功", result.name);
+    Ok(result)
+}
+
+/// 更新检查
+#[tauri::command]
+pub async fn update_influxdb2_check(
+    connection_id: String,
+    check_id: String,
+    request: SaveCheckRequest,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<CheckInfo, String> {
+    debug!("更新 InfluxDB 2.x 检查: {} - {}", connection_id, check_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .update_influxdb2_check(&check_id, &request)
+        .await
+        .map_err(|e| format!("更新检查失败: {}", e))?;
+
+    info!("检查 '{}' 更新成功", result.name);
+    Ok(result)
+}
+
+/// 删除检查
+#[tauri::command]
+pub async fn delete_influxdb2_check(
+    connection_id: String,
+    check_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("删除 InfluxDB 2.x 检查: {} - {}", connection_id, check_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .delete_influxdb2_check(&check_id)
+        .await
+        .map_err(|e| format!("删除检查失败: {}", e))?;
+
+    info!("检查 '{}' 删除成功", check_id);
+    Ok(())
+}
+
+/// 获取通知端点列表
+#[tauri::command]
+pub async fn get_influxdb2_notification_endpoints(
+    connection_id: String,
+    org_name: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<NotificationEndpointInfo>, String> {
+    debug!("获取 InfluxDB 2.x 通知端点列表: {} - {:?}", connection_id, org_name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb2_notification_endpoints(org_name.as_deref())
+        .await
+        .map_err(|e| format!("获取通知端点列表失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 创建通知端点（HTTP / Slack / PagerDuty）
+#[tauri::command]
+pub async fn create_influxdb2_notification_endpoint(
+    connection_id: String,
+    request: SaveNotificationEndpointRequest,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<NotificationEndpointInfo, String> {
+    debug!("创建 InfluxDB 2.x 通知端点: {} - {}", connection_id, request.name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .create_influxdb2_notification_endpoint(&request)
+        .await
+        .map_err(|e| format!("创建通知端点失败: {}", e))?;
+
+    info!("通知端点 '{}' 创建成功", result.name);
+    Ok(result)
+}
+
+/// 更新通知端点
+#[tauri::command]
+pub async fn update_influxdb2_notification_endpoint(
+    connection_id: String,
+    endpoint_id: String,
+    request: SaveNotificationEndpointRequest,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<NotificationEndpointInfo, String> {
+    debug!("更新 InfluxDB 2.x 通知端点: {} - {}", connection_id, endpoint_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .update_influxdb2_notification_endpoint(&endpoint_id, &request)
+        .await
+        .map_err(|e| format!("更新通知端点失败: {}", e))?;
+
+    info!("通知端点 '{}' 更新成功", result.name);
+    Ok(result)
+}
+
+/// 删除通知端点
+#[tauri::command]
+pub async fn delete_influxdb2_notification_endpoint(
+    connection_id: String,
+    endpoint_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("删除 InfluxDB 2.x 通知端点: {} - {}", connection_id, endpoint_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .delete_influxdb2_notification_endpoint(&endpoint_id)
+        .await
+        .map_err(|e| format!("删除通知端点失败: {}", e))?;
+
+    info!("通知端点 '{}' 删除成功", endpoint_id);
+    Ok(())
+}
+
+/// 获取通知规则列表
+#[tauri::command]
+pub async fn get_influxdb2_notification_rules(
+    connection_id: String,
+    org_name: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<NotificationRuleInfo>, String> {
+    debug!("获取 InfluxDB 2.x 通知规则列表: {} - {:?}", connection_id, org_name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb2_notification_rules(org_name.as_deref())
+        .await
+        .map_err(|e| format!("获取通知规则列表失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 创建通知规则
+#[tauri::command]
+pub async fn create_influxdb2_notification_rule(
+    connection_id: String,
+    request: SaveNotificationRuleRequest,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<NotificationRuleInfo, String> {
+    debug!("创建 InfluxDB 2.x 通知规则: {} - {}", connection_id, request.name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .create_influxdb2_notification_rule(&request)
+        .await
+        .map_err(|e| format!("创建通知规则失败: {}", e))?;
+
+    info!("通知规则 '{}' 创建成功", result.name);
+    Ok(result)
+}
+
+/// 更新通知规则
+#[tauri::command]
+pub async fn update_influxdb2_notification_rule(
+    connection_id: String,
+    rule_id: String,
+    request: SaveNotificationRuleRequest,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<NotificationRuleInfo, String> {
+    debug!("更新 InfluxDB 2.x 通知规则: {} - {}", connection_id, rule_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .update_influxdb2_notification_rule(&rule_id, &request)
+        .await
+        .map_err(|e| format!("更新通知规则失败: {}", e))?;
+
+    info!("通知规则 '{}' 更新成功", result.name);
+    Ok(result)
+}
+
+/// 删除通知规则
+#[tauri::command]
+pub async fn delete_influxdb2_notification_rule(
+    connection_id: String,
+    rule_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("删除 InfluxDB 2.x 通知规则: {} - {}", connection_id, rule_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .delete_influxdb2_notification_rule(&rule_id)
+        .await
+        .map_err(|e| format!("删除通知规则失败: {}", e))?;
+
+    info!("通知规则 '{}' 删除成功", rule_id);
+    Ok(())
+}
+
+/// 查询最近的检查状态（_monitoring 存储桶），默认最近 24 小时
+#[tauri::command]
+pub async fn get_influxdb2_check_statuses(
+    connection_id: String,
+    org_name: Option<String>,
+    check_id: Option<String>,
+    range: Option<String>,
+    limit: Option<u32>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<CheckStatusEntry>, String> {
+    debug!("查询检查状态: {} - {:?} - {:?}", connection_id, org_name, check_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb2_check_statuses(org_name.as_deref(), check_id.as_deref(), range.as_deref().unwrap_or("24h"), limit.unwrap_or(100))
+        .await
+        .map_err(|e| format!("查询检查状态失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 获取 API 令牌列表及其权限
+#[tauri::command]
+pub async fn get_influxdb2_authorizations(
+    connection_id: String,
+    org_name: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<AuthorizationInfo>, String> {
+    debug!("获取 InfluxDB 2.x 令牌列表: {} - {:?}", connection_id, org_name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb2_authorizations(org_name.as_deref())
+        .await
+        .map_err(|e| format!("获取令牌列表失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 创建存储桶范围的读写令牌
+#[tauri::command]
+pub async fn create_influxdb2_authorization(
+    connection_id: String,
+    request: CreateAuthorizationRequest,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<AuthorizationInfo, String> {
+    debug!("创建 InfluxDB 2.x 令牌: {} - {:?}", connection_id, request.bucket_names);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .create_influxdb2_authorization(&request)
+        .await
+        .map_err(|e| format!("创建令牌失败: {}", e))?;
+
+    info!("令牌 '{}' 创建成功", result.id);
+    Ok(result)
+}
+
+/// 为单个存储桶创建令牌
+#[tauri::command]
+pub async fn create_influxdb2_bucket_token(
+    connection_id: String,
+    bucket_name: String,
+    read: bool,
+    write: bool,
+    description: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<AuthorizationInfo, String> {
+    debug!("为存储桶创建令牌: {} - {}", connection_id, bucket_name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let mode = match (read, write) {
+        (true, true) => "读写",
+        (true, false) => "只读",
+        _ => "只写",
+    };
+    let request = CreateAuthorizationRequest {
+        description: description.or_else(|| Some(format!("{} {}令牌", bucket_name, mode))),
+        bucket_names: vec![bucket_name],
+        read,
+        write,
+    };
+
+    let result = client
+        .create_influxdb2_authorization(&request)
+        .await
+        .map_err(|e| format!("创建令牌失败: {}", e))?;
+
+    info!("令牌 '{}' 创建成功", result.id);
+    Ok(result)
+}
+
+/// 启用或停用令牌
+#[tauri::command]
+pub async fn set_influxdb2_authorization_active(
+    connection_id: String,
+    authorization_id: String,
+    active: bool,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<AuthorizationInfo, String> {
+    debug!("设置令牌状态: {} - {} - {}", connection_id, authorization_id, active);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .set_influxdb2_authorization_active(&authorization_id, active)
+        .await
+        .map_err(|e| format!("设置令牌状态失败: {}", e))?;
+
+    info!("令牌 '{}' 已{}", authorization_id, if active { "启用" } else { "停用" });
+    Ok(result)
+}
+
+/// 删除令牌
+#[tauri::command]
+pub async fn delete_influxdb2_authorization(
+    connection_id: String,
+    authorization_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("删除 InfluxDB 2.x 令牌: {} - {}", connection_id, authorization_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .delete_influxdb2_authorization(&authorization_id)
+        .await
+        .map_err(|e| format!("删除令牌失败: {}", e))?;
+
+    info!("令牌 '{}' 删除成功", authorization_id);
+    Ok(())
+}
+
+/// 获取当前连接使用的令牌，服务端不返回令牌值时为空
+#[tauri::command]
+pub async fn get_influxdb2_current_authorization(
+    connection_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Option<AuthorizationInfo>, String> {
+    debug!("获取当前连接令牌: {}", connection_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb2_current_authorization()
+        .await
+        .map_err(|e| format!("获取当前令牌失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 创建组织
+#[tauri::command]
+pub async fn create_influxdb2_organization(
+    connection_id: String,
+    name: String,
+    description: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+) -> Result<OrganizationInfo, String> {
+    debug!("创建 InfluxDB 2.x 组织: {} - {}", connection_id, name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .create_influxdb2_organization(&name, description.as_deref())
+        .await
+        .map_err(|e| format!("创建组织失败: {}", e))?;
+
+    info!("组织 '{}' 创建成功", result.name);
+    schema_cache.invalidate(&connection_id, None).await;
+    Ok(result)
+}
+
+/// 重命名组织或更新描述
+#[tauri::command]
+pub async fn update_influxdb2_organization(
+    connection_id: String,
+    org_name: String,
+    new_name: Option<String>,
+    description: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+) -> Result<OrganizationInfo, String> {
+    debug!("更新 InfluxDB 2.x 组织: {} - {}", connection_id, org_name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .update_influxdb2_organization(&org_name, new_name.as_deref(), description.as_deref())
+        .await
+        .map_err(|e| format!("更新组织失败: {}", e))?;
+
+    info!("组织 '{}' 更新成功", result.name);
+    schema_cache.invalidate(&connection_id, None).await;
+    Ok(result)
+}
+
+/// 删除组织
+#[tauri::command]
+pub async fn delete_influxdb2_organization(
+    connection_id: String,
+    org_name: String,
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+) -> Result<(), String> {
+    debug!("删除 InfluxDB 2.x 组织: {} - {}", connection_id, org_name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .delete_influxdb2_organization(&org_name)
+        .await
+        .map_err(|e| format!("删除组织失败: {}", e))?;
+
+    info!("组织 '{}' 删除成功", org_name);
+    schema_cache.invalidate(&connection_id, None).await;
+    Ok(())
+}
+
+/// 获取组织成员（含所有者）
+#[tauri::command]
+pub async fn get_influxdb2_org_members(
+    connection_id: String,
+    org_name: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<OrgMemberInfo>, String> {
+    debug!("获取组织成员: {} - {}", connection_id, org_name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb2_org_members(&org_name)
+        .await
+        .map_err(|e| format!("获取组织成员失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 添加组织成员或所有者
+#[tauri::command]
+pub async fn add_influxdb2_org_member(
+    connection_id: String,
+    org_name: String,
+    user_id: String,
+    owner: bool,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("添加组织成员: {} - {} - {} - {}", connection_id, org_name, user_id, owner);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .add_influxdb2_org_member(&org_name, &user_id, owner)
+        .await
+        .map_err(|e| format!("添加组织成员失败: {}", e))?;
+
+    info!("用户 '{}' 已加入组织 '{}'", user_id, org_name);
+    Ok(())
+}
+
+/// 移除组织成员或所有者
+#[tauri::command]
+pub async fn remove_influxdb2_org_member(
+    connection_id: String,
+    org_name: String,
+    user_id: String,
+    owner: bool,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("移除组织成员: {} - {} - {} - {}", connection_id, org_name, user_id, owner);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .remove_influxdb2_org_member(&org_name, &user_id, owner)
+        .await
+        .map_err(|e| format!("移除组织成员失败: {}", e))?;
+
+    info!("用户 '{}' 已移出组织 '{}'", user_id, org_name);
+    Ok(())
+}
+
+/// 获取用户列表
+#[tauri::command]
+pub async fn get_influxdb2_users(
+    connection_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<User2xInfo>, String> {
+    debug!("获取 InfluxDB 2.x 用户列表: {}", connection_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb2_users()
+        .await
+        .map_err(|e| format!("获取用户列表失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 创建用户，可选设置密码并加入组织
+#[tauri::command]
+pub async fn create_influxdb2_user(
+    connection_id: String,
+    name: String,
+    password: Option<String>,
+    org_name: Option<String>,
+    owner: Option<bool>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<User2xInfo, String> {
+    debug!("创建 InfluxDB 2.x 用户: {} - {}", connection_id, name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .create_influxdb2_user(&name, password.as_deref(), org_name.as_deref(), owner.unwrap_or(false))
+        .await
+        .map_err(|e| format!("创建用户失败: {}", e))?;
+
+    info!("用户 '{}' 创建成功", result.name);
+    Ok(result)
+}
+
+/// 重置用户密码
+#[tauri::command]
+pub async fn reset_influxdb2_user_password(
+    connection_id: String,
+    user_id: String,
+    password: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("重置用户密码: {} - {}", connection_id, user_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .reset_influxdb2_user_password(&user_id, &password)
+        .await
+        .map_err(|e| format!("重置密码失败: {}", e))?;
+
+    info!("用户 '{}' 密码已重置", user_id);
+    Ok(())
+}
+
+/// 删除用户
+#[tauri::command]
+pub async fn delete_influxdb2_user(
+    connection_id: String,
+    user_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("删除 InfluxDB 2.x 用户: {} - {}", connection_id, user_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .delete_influxdb2_user(&user_id)
+        .await
+        .map_err(|e| format!("删除用户失败: {}", e))?;
+
+    info!("用户 '{}' 删除成功", user_id);
+    Ok(())
+}
+
+/// 获取标签列表
+#[tauri::command]
+pub async fn get_influxdb2_labels(
+    connection_id: String,
+    org_name: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<LabelInfo>, String> {
+    debug!("获取 InfluxDB 2.x 标签列表: {} - {:?}", connection_id, org_name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb2_labels(org_name.as_deref())
+        .await
+        .map_err(|e| format!("获取标签列表失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 创建标签
+#[tauri::command]
+pub async fn create_influxdb2_label(
+    connection_id: String,
+    request: SaveLabelRequest,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<LabelInfo, String> {
+    debug!("创建 InfluxDB 2.x 标签: {} - {}", connection_id, request.name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .create_influxdb2_label(&request)
+        .await
+        .map_err(|e| format!("创建标签失败: {}", e))?;
+
+    info!("标签 '{}' 创建成功", result.name);
+    Ok(result)
+}
+
+/// 更新标签
+#[tauri::command]
+pub async fn update_influxdb2_label(
+    connection_id: String,
+    label_id: String,
+    request: SaveLabelRequest,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<LabelInfo, String> {
+    debug!("更新 InfluxDB 2.x 标签: {} - {}", connection_id, label_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .update_influxdb2_label(&label_id, &request)
+        .await
+        .map_err(|e| format!("更新标签失败: {}", e))?;
+
+    info!("标签 '{}' 更新成功", result.name);
+    Ok(result)
+}
+
+/// 删除标签
+#[tauri::command]
+pub async fn delete_influxdb2_label(
+    connection_id: String,
+    label_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("删除 InfluxDB 2.x 标签: {} - {}", connection_id, label_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .delete_influxdb2_label(&label_id)
+        .await
+        .map_err(|e| format!("删除标签失败: {}", e))?;
+
+    info!("标签 '{}' 删除成功", label_id);
+    Ok(())
+}
+
+/// 获取资源（存储桶、任务、仪表板）上的标签
+#[tauri::command]
+pub async fn get_influxdb2_resource_labels(
+    connection_id: String,
+    resource_type: String,
+    resource_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<LabelInfo>, String> {
+    debug!("获取资源标签: {} - {} - {}", connection_id, resource_type, resource_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb2_resource_labels(&resource_type, &resource_id)
+        .await
+        .map_err(|e| format!("获取资源标签失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 为资源分配标签
+#[tauri::command]
+pub async fn add_influxdb2_resource_label(
+    connection_id: String,
+    resource_type: String,
+    resource_id: String,
+    label_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("分配标签: {} - {} - {} - {}", connection_id, resource_type, resource_id, label_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .add_influxdb2_resource_label(&resource_type, &resource_id, &label_id)
+        .await
+        .map_err(|e| format!("分配标签失败: {}", e))?;
+
+    info!("标签 '{}' 已分配给 {} '{}'", label_id, resource_type, resource_id);
+    Ok(())
+}
+
+/// 移除资源上的标签
+#[tauri::command]
+pub async fn remove_influxdb2_resource_label(
+    connection_id: String,
+    resource_type: String,
+    resource_id: String,
+    label_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("移除标签: {} - {} - {} - {}", connection_id, resource_type, resource_id, label_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .remove_influxdb2_resource_label(&resource_type, &resource_id, &label_id)
+        .await
+        .map_err(|e| format!("移除标签失败: {}", e))?;
+
+    info!("标签 '{}' 已从 {} '{}' 移除", label_id, resource_type, resource_id);
+    Ok(())
+}
+
+/// 获取 Telegraf 配置列表
+#[tauri::command]
+pub async fn get_influxdb2_telegrafs(
+    connection_id: String,
+    org_name: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<TelegrafConfigInfo>, String> {
+    debug!("获取 Telegraf 配置列表: {} - {:?}", connection_id, org_name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb2_telegrafs(org_name.as_deref())
+        .await
+        .map_err(|e| format!("获取 Telegraf 配置列表失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 获取 Telegraf 配置详情
+#[tauri::command]
+pub async fn get_influxdb2_telegraf(
+    connection_id: String,
+    telegraf_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<TelegrafConfigInfo, String> {
+    debug!("获取 Telegraf 配置: {} - {}", connection_id, telegraf_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb2_telegraf(&telegraf_id)
+        .await
+        .map_err(|e| format!("获取 Telegraf 配置失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 创建 Telegraf 配置
+#[tauri::command]
+pub async fn create_influxdb2_telegraf(
+    connection_id: String,
+    request: SaveTelegrafRequest,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<TelegrafConfigInfo, String> {
+    debug!("创建 Telegraf 配置: {} - {}", connection_id, request.name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .create_influxdb2_telegraf(&request)
+        .await
+        .map_err(|e| format!("创建 Telegraf 配置失败: {}", e))?;
+
+    info!("Telegraf 配置 '{}' 创建成功", result.name);
+    Ok(result)
+}
+
+/// 更新 Telegraf 配置
+#[tauri::command]
+pub async fn update_influxdb2_telegraf(
+    connection_id: String,
+    telegraf_id: String,
+    request: SaveTelegrafRequest,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<TelegrafConfigInfo, String> {
+    debug!("更新 Telegraf 配置: {} - {}", connection_id, telegraf_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .update_influxdb2_telegraf(&telegraf_id, &request)
+        .await
+        .map_err(|e| format!("更新 Telegraf 配置失败: {}", e))?;
+
+    info!("Telegraf 配置 '{}' 更新成功", result.name);
+    Ok(result)
+}
+
+/// 删除 Telegraf 配置
+#[tauri::command]
+pub async fn delete_influxdb2_telegraf(
+    connection_id: String,
+    telegraf_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("删除 Telegraf 配置: {} - {}", connection_id, telegraf_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .delete_influxdb2_telegraf(&telegraf_id)
+        .await
+        .map_err(|e| format!("删除 Telegraf 配置失败: {}", e))?;
+
+    info!("Telegraf 配置 '{}' 删除成功", telegraf_id);
+    Ok(())
+}
+
+/// 校验 Telegraf TOML 配置
+#[tauri::command]
+pub async fn validate_telegraf_config(config: String) -> Result<TelegrafValidation, String> {
+    debug!("校验 Telegraf 配置");
+    Ok(crate::database::influxdb::telegraf::validate_telegraf_toml(&config))
+}
+
+/// 为存储桶生成 Telegraf 入门配置，默认同时创建只写令牌
+#[tauri::command]
+pub async fn generate_influxdb2_telegraf_config(
+    connection_id: String,
+    bucket_name: String,
+    create_token: Option<bool>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<TelegrafStarterConfig, String> {
+    debug!("生成 Telegraf 入门配置: {} - {}", connection_id, bucket_name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .generate_influxdb2_telegraf_config(&bucket_name, create_token.unwrap_or(true))
+        .await
+        .map_err(|e| format!("生成 Telegraf 配置失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 获取抓取器列表
+#[tauri::command]
+pub async fn get_influxdb2_scrapers(
+    connection_id: String,
+    org_name: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<ScraperInfo>, String> {
+    debug!("获取抓取器列表: {} - {:?}", connection_id, org_name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb2_scrapers(org_name.as_deref())
+        .await
+        .map_err(|e| format!("获取抓取器列表失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 创建抓取器
+#[tauri::command]
+pub async fn create_influxdb2_scraper(
+    connection_id: String,
+    request: SaveScraperRequest,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<ScraperInfo, String> {
+    debug!("创建抓取器: {} - {}", connection_id, request.name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .create_influxdb2_scraper(&request)
+        .await
+        .map_err(|e| format!("创建抓取器失败: {}", e))?;
+
+    info!("抓取器 '{}' 创建成功", result.name);
+    Ok(result)
+}
+
+/// 更新抓取器
+#[tauri::command]
+pub async fn update_influxdb2_scraper(
+    connection_id: String,
+    scraper_id: String,
+    request: SaveScraperRequest,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<ScraperInfo, String> {
+    debug!("更新抓取器: {} - {}", connection_id, scraper_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .update_influxdb2_scraper(&scraper_id, &request)
+        .await
+        .map_err(|e| format!("更新抓取器失败: {}", e))?;
+
+    info!("抓取器 '{}' 更新成功", result.name);
+    Ok(result)
+}
+
+/// 删除抓取器
+#[tauri::command]
+pub async fn delete_influxdb2_scraper(
+    connection_id: String,
+    scraper_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("删除抓取器: {} - {}", connection_id, scraper_id);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .delete_influxdb2_scraper(&scraper_id)
+        .await
+        .map_err(|e| format!("删除抓取器失败: {}", e))?;
+
+    info!("抓取器 '{}' 删除成功", scraper_id);
+    Ok(())
+}
@@ -0,0 +1,723 @@
+/**
+ * InfluxDB 3.x 特定命令
+ *
+ * 处理 InfluxDB 3.x 的最新值缓存(Last Value Cache)、去重值缓存(Distinct Value Cache)、处理引擎(Processing Engine)
+ * 以及 Arrow 列式查询结果相关操作
+ */
+
+use tauri::ipc::{Channel, InvokeResponseBody, Response};
+use tauri::State;
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use crate::database::influxdb::{ArrowColumnInfo, ArrowResult, QueryLanguage, V3ResponseFormat};
+use crate::models::QueryResult;
+use crate::services::connection_service::ConnectionService;
+use crate::services::SchemaCacheService;
+
+/// 同时保留的 Arrow 结果数量上限，超出时释放最早的结果
+const MAX_ARROW_RESULTS: usize = 16;
+
+lazy_static::lazy_static! {
+    // Arrow 查询结果：结果 ID → 列式数据，按创建顺序排列
+    static ref ARROW_RESULTS: Mutex<Vec<(String, Arc<ArrowResult>)>> = Mutex::new(Vec::new());
+}
+
+/// 最新值缓存(LVC)定义
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LastCacheInfo {
+    pub database: String,
+    pub table: String,
+    pub name: String,
+    pub key_columns: Vec<String>,
+    pub value_columns: Vec<String>, // 为空表示缓存全部非键列
+    pub count: u64,
+    pub ttl_seconds: Option<u64>,
+}
+
+/// 创建最新值缓存请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateLastCacheRequest {
+    pub database: String,
+    pub table: String,
+    pub name: Option<String>,          // 不指定时由服务器生成
+    #[serde(default)]
+    pub key_columns: Vec<String>,      // 不指定时使用表的全部标签列
+    pub value_columns: Option<Vec<String>>,
+    pub count: Option<u64>,
+    pub ttl_seconds: Option<u64>,
+}
+
+/// 去重值缓存(DVC)定义
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DistinctCacheInfo {
+    pub database: String,
+    pub table: String,
+    pub name: String,
+    pub columns: Vec<String>,
+    pub max_cardinality: Option<u64>,
+    pub max_age_seconds: Option<u64>,
+}
+
+/// 创建去重值缓存请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateDistinctCacheRequest {
+    pub database: String,
+    pub table: String,
+    pub name: Option<String>,
+    pub columns: Vec<String>,          // 列顺序即缓存的层级顺序
+    pub max_cardinality: Option<u64>,
+    pub max_age_seconds: Option<u64>,
+}
+
+/// 处理引擎触发器信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessingEngineTriggerInfo {
+    pub database: String,
+    pub trigger_name: String,
+    pub plugin_filename: String,
+    pub trigger_specification: String, // 如 table:cpu、every:1m、request:hook
+    pub trigger_type: String,          // wal / schedule / request
+    pub disabled: bool,
+}
+
+/// 创建处理引擎触发器请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateProcessingEngineTriggerRequest {
+    pub database: String,
+    pub trigger_name: String,
+    pub plugin_filename: String,
+    pub trigger_type: String,          // wal / schedule / request
+    pub table: Option<String>,         // wal：监听的表，不指定时监听全部表
+    pub every: Option<String>,         // schedule：执行间隔
+    pub cron: Option<String>,          // schedule：cron 表达式（含秒）
+    pub path: Option<String>,          // request：/api/v3/engine/ 下的路径
+    #[serde(default)]
+    pub arguments: HashMap<String, String>,
+    #[serde(default)]
+    pub disabled: bool,
+    #[serde(default)]
+    pub run_async: bool,
+    pub error_behavior: Option<String>, // log / retry / disable
+}
+
+/// 上传插件文件请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadProcessingEnginePluginRequest {
+    pub filename: String,
+    pub content: String,
+}
+
+/// 测试插件请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestProcessingEnginePluginRequest {
+    pub database: String,
+    pub filename: String,
+    pub trigger_type: String,          // wal / schedule
+    pub input_lp: Option<String>,      // wal：作为输入的行协议数据
+    pub schedule: Option<String>,      // schedule：cron 表达式
+    #[serde(default)]
+    pub arguments: HashMap<String, String>,
+}
+
+/// 插件测试结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginTestResult {
+    pub log_lines: Vec<String>,
+    pub database_writes: HashMap<String, Vec<String>>, // 数据库 → 插件写入的行协议
+    pub errors: Vec<String>,
+    pub trigger_time: Option<String>,
+}
+
+/// 处理引擎日志
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessingEngineLogEntry {
+    pub event_time: String,
+    pub trigger_name: String,
+    pub log_level: String,
+    pub log_text: String,
+}
+
+/// 获取最新值缓存列表，可按表过滤
+#[tauri::command]
+pub async fn get_influxdb3_last_caches(
+    connection_id: String,
+    database: String,
+    table: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<LastCacheInfo>, String> {
+    debug!("获取最新值缓存列表: {} - {}", connection_id, database);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb3_last_caches(&database, table.as_deref())
+        .await
+        .map_err(|e| format!("获取最新值缓存列表失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 创建最新值缓存
+#[tauri::command]
+pub async fn create_influxdb3_last_cache(
+    connection_id: String,
+    request: CreateLastCacheRequest,
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+) -> Result<LastCacheInfo, String> {
+    debug!("创建最新值缓存: {} - {}.{}", connection_id, request.database, request.table);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .create_influxdb3_last_cache(&request)
+        .await
+        .map_err(|e| format!("创建最新值缓存失败: {}", e))?;
+
+    info!("最新值缓存创建成功: {}", result.name);
+    schema_cache.invalidate(&connection_id, Some(&request.database)).await;
+    Ok(result)
+}
+
+/// 删除最新值缓存
+#[tauri::command]
+pub async fn delete_influxdb3_last_cache(
+    connection_id: String,
+    database: String,
+    table: String,
+    name: String,
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+) -> Result<(), String> {
+    debug!("删除最新值缓存: {} - {}.{}/{}", connection_id, database, table, name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .delete_influxdb3_last_cache(&database, &table, &name)
+        .await
+        .map_err(|e| format!("删除最新值缓存失败: {}", e))?;
+
+    info!("最新值缓存删除成功: {}", name);
+    schema_cache.invalidate(&connection_id, Some(&database)).await;
+    Ok(())
+}
+
+/// 读取最新值缓存内容（last_cache() 函数）
+#[tauri::command]
+pub async fn query_influxdb3_last_cache(
+    connection_id: String,
+    database: String,
+    table: String,
+    name: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<QueryResult, String> {
+    debug!("读取最新值缓存: {} - {}.{}", connection_id, database, table);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .query_influxdb3_last_cache(&database, &table, name.as_deref())
+        .await
+        .map_err(|e| format!("读取最新值缓存失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 获取去重值缓存列表，可按表过滤
+#[tauri::command]
+pub async fn get_influxdb3_distinct_caches(
+    connection_id: String,
+    database: String,
+    table: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<DistinctCacheInfo>, String> {
+    debug!("获取去重值缓存列表: {} - {}", connection_id, database);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb3_distinct_caches(&database, table.as_deref())
+        .await
+        .map_err(|e| format!("获取去重值缓存列表失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 创建去重值缓存
+#[tauri::command]
+pub async fn create_influxdb3_distinct_cache(
+    connection_id: String,
+    request: CreateDistinctCacheRequest,
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+) -> Result<DistinctCacheInfo, String> {
+    debug!("创建去重值缓存: {} - {}.{}", connection_id, request.database, request.table);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .create_influxdb3_distinct_cache(&request)
+        .await
+        .map_err(|e| format!("创建去重值缓存失败: {}", e))?;
+
+    info!("去重值缓存创建成功: {}", result.name);
+    schema_cache.invalidate(&connection_id, Some(&request.database)).await;
+    Ok(result)
+}
+
+/// 删除去重值缓存
+#[tauri::command]
+pub async fn delete_influxdb3_distinct_cache(
+    connection_id: String,
+    database: String,
+    table: String,
+    name: String,
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+) -> Result<(), String> {
+    debug!("删除去重值缓存: {} - {}.{}/{}", connection_id, database, table, name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .delete_influxdb3_distinct_cache(&database, &table, &name)
+        .await
+        .map_err(|e| format!("删除去重值缓存失败: {}", e))?;
+
+    info!("去重值缓存删除成功: {}", name);
+    schema_cache.invalidate(&connection_id, Some(&database)).await;
+    Ok(())
+}
+
+/// 读取去重值缓存内容（distinct_cache() 函数）
+#[tauri::command]
+pub async fn query_influxdb3_distinct_cache(
+    connection_id: String,
+    database: String,
+    table: String,
+    name: Option<String>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<QueryResult, String> {
+    debug!("读取去重值缓存: {} - {}.{}", connection_id, database, table);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .query_influxdb3_distinct_cache(&database, &table, name.as_deref())
+        .await
+        .map_err(|e| format!("读取去重值缓存失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 获取数据库上的处理引擎触发器
+#[tauri::command]
+pub async fn get_influxdb3_triggers(
+    connection_id: String,
+    database: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<ProcessingEngineTriggerInfo>, String> {
+    debug!("获取触发器列表: {} - {}", connection_id, database);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb3_triggers(&database)
+        .await
+        .map_err(|e| format!("获取触发器列表失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 创建处理引擎触发器
+#[tauri::command]
+pub async fn create_influxdb3_trigger(
+    connection_id: String,
+    request: CreateProcessingEngineTriggerRequest,
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+) -> Result<ProcessingEngineTriggerInfo, String> {
+    debug!("创建触发器: {} - {}/{}", connection_id, request.database, request.trigger_name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .create_influxdb3_trigger(&request)
+        .await
+        .map_err(|e| format!("创建触发器失败: {}", e))?;
+
+    info!("触发器创建成功: {}", result.trigger_name);
+    schema_cache.invalidate(&connection_id, Some(&request.database)).await;
+    Ok(result)
+}
+
+/// 启用处理引擎触发器
+#[tauri::command]
+pub async fn enable_influxdb3_trigger(
+    connection_id: String,
+    database: String,
+    trigger_name: String,
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+) -> Result<(), String> {
+    debug!("启用触发器: {} - {}/{}", connection_id, database, trigger_name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .set_influxdb3_trigger_enabled(&database, &trigger_name, true)
+        .await
+        .map_err(|e| format!("启用触发器失败: {}", e))?;
+
+    info!("触发器已启用: {}", trigger_name);
+    schema_cache.invalidate(&connection_id, Some(&database)).await;
+    Ok(())
+}
+
+/// 禁用处理引擎触发器
+#[tauri::command]
+pub async fn disable_influxdb3_trigger(
+    connection_id: String,
+    database: String,
+    trigger_name: String,
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+) -> Result<(), String> {
+    debug!("禁用触发器: {} - {}/{}", connection_id, database, trigger_name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .set_influxdb3_trigger_enabled(&database, &trigger_name, false)
+        .await
+        .map_err(|e| format!("禁用触发器失败: {}", e))?;
+
+    info!("触发器已禁用: {}", trigger_name);
+    schema_cache.invalidate(&connection_id, Some(&database)).await;
+    Ok(())
+}
+
+/// 删除处理引擎触发器，force 为 true 时即使触发器仍在运行也会删除
+#[tauri::command]
+pub async fn delete_influxdb3_trigger(
+    connection_id: String,
+    database: String,
+    trigger_name: String,
+    force: Option<bool>,
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+) -> Result<(), String> {
+    debug!("删除触发器: {} - {}/{}", connection_id, database, trigger_name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .delete_influxdb3_trigger(&database, &trigger_name, force.unwrap_or(false))
+        .await
+        .map_err(|e| format!("删除触发器失败: {}", e))?;
+
+    info!("触发器删除成功: {}", trigger_name);
+    schema_cache.invalidate(&connection_id, Some(&database)).await;
+    Ok(())
+}
+
+/// 上传 Python 插件文件到服务器插件目录
+#[tauri::command]
+pub async fn upload_influxdb3_plugin(
+    connection_id: String,
+    request: UploadProcessingEnginePluginRequest,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<(), String> {
+    debug!("上传插件: {} - {}", connection_id, request.filename);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .upload_influxdb3_plugin(&request)
+        .await
+        .map_err(|e| format!("上传插件失败: {}", e))?;
+
+    info!("插件上传成功: {}", request.filename);
+    Ok(())
+}
+
+/// 使用示例输入测试插件，不会创建触发器
+#[tauri::command]
+pub async fn test_influxdb3_plugin(
+    connection_id: String,
+    request: TestProcessingEnginePluginRequest,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<PluginTestResult, String> {
+    debug!("测试插件: {} - {}", connection_id, request.filename);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .test_influxdb3_plugin(&request)
+        .await
+        .map_err(|e| format!("测试插件失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// 获取处理引擎日志，可按触发器过滤
+#[tauri::command]
+pub async fn get_influxdb3_trigger_logs(
+    connection_id: String,
+    database: String,
+    trigger_name: Option<String>,
+    limit: Option<u32>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<ProcessingEngineLogEntry>, String> {
+    debug!("获取触发器日志: {} - {} {:?}", connection_id, database, trigger_name);
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .get_influxdb3_trigger_logs(&database, trigger_name.as_deref(), limit)
+        .await
+        .map_err(|e| format!("获取触发器日志失败: {}", e))?;
+
+    Ok(result)
+}
+
+/// Arrow 查询结果句柄，数据本身通过二进制通道或分页接口获取
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArrowQueryHandle {
+    pub result_id: String,
+    pub columns: Vec<ArrowColumnInfo>,
+    pub row_count: usize,
+    pub batch_count: usize,
+    pub execution_time: Option<u64>,
+}
+
+fn get_arrow_result(result_id: &str) -> Result<Arc<ArrowResult>, String> {
+    let results = ARROW_RESULTS
+        .lock()
+        .map_err(|e| format!("获取 Arrow 结果失败: {}", e))?;
+    results
+        .iter()
+        .find(|(id, _)| id == result_id)
+        .map(|(_, result)| result.clone())
+        .ok_or_else(|| format!("Arrow 结果不存在或已释放: {}", result_id))
+}
+
+/// 执行 Arrow 列式查询，每个批次以独立的 Arrow IPC 流通过 channel 推送给前端
+#[tauri::command]
+pub async fn execute_influxdb3_arrow_query(
+    connection_id: String,
+    query: String,
+    database: String,
+    language: Option<String>,
+    channel: Channel<InvokeResponseBody>,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<ArrowQueryHandle, String> {
+    debug!("执行 Arrow 查询: {} - {}", connection_id, database);
+
+    let language = match language {
+        Some(language) => QueryLanguage::from_str(&language)
+            .ok_or_else(|| format!("不支持的查询语言: {}", language))?,
+        None => QueryLanguage::Sql,
+    };
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .query_arrow(&query, &database, language)
+        .await
+        .map_err(|e| format!("执行 Arrow 查询失败: {}", e))?;
+
+    let batch_count = result.batch_count();
+    for index in 0..batch_count {
+        let bytes = result
+            .batch_ipc(index)
+            .map_err(|e| format!("编码 Arrow 批次失败: {}", e))?;
+        channel
+            .send(InvokeResponseBody::Raw(bytes))
+            .map_err(|e| format!("推送 Arrow 批次失败: {}", e))?;
+    }
+
+    let handle = ArrowQueryHandle {
+        result_id: uuid::Uuid::new_v4().to_string(),
+        columns: result.columns(),
+        row_count: result.row_count(),
+        batch_count,
+        execution_time: result.execution_time,
+    };
+
+    let mut results = ARROW_RESULTS
+        .lock()
+        .map_err(|e| format!("保存 Arrow 结果失败: {}", e))?;
+    results.push((handle.result_id.clone(), Arc::new(result)));
+    if results.len() > MAX_ARROW_RESULTS {
+        let excess = results.len() - MAX_ARROW_RESULTS;
+        results.drain(..excess);
+    }
+
+    info!("Arrow 查询完成: {} 行, {} 个批次", handle.row_count, handle.batch_count);
+    Ok(handle)
+}
+
+/// 将 Arrow 结果的一页转换为行式结果，只转换 [offset, offset + limit) 范围
+#[tauri::command]
+pub async fn get_influxdb3_arrow_page(
+    result_id: String,
+    offset: usize,
+    limit: usize,
+) -> Result<QueryResult, String> {
+    debug!("获取 Arrow 结果分页: {} [{}, +{}]", result_id, offset, limit);
+
+    let result = get_arrow_result(&result_id)?;
+    let dataset = result
+        .page(offset, limit)
+        .map_err(|e| format!("转换 Arrow 分页失败: {}", e))?;
+
+    Ok(QueryResult::new(
+        dataset.columns,
+        dataset.rows,
+        dataset.execution_time.unwrap_or(0),
+    ))
+}
+
+/// 以单个 Arrow IPC 流返回完整结果（用于导出或重新加载）
+#[tauri::command]
+pub async fn get_influxdb3_arrow_ipc(result_id: String) -> Result<Response, String> {
+    debug!("获取 Arrow IPC 数据: {}", result_id);
+
+    let result = get_arrow_result(&result_id)?;
+    let bytes = result
+        .to_ipc()
+        .map_err(|e| format!("编码 Arrow IPC 失败: {}", e))?;
+
+    Ok(Response::new(bytes))
+}
+
+/// 以 CSV / Parquet 等服务器原生格式执行查询，直接返回响应数据（用于导出）
+#[tauri::command]
+pub async fn export_influxdb3_query(
+    connection_id: String,
+    query: String,
+    database: String,
+    language: Option<String>,
+    format: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Response, String> {
+    debug!("导出 InfluxDB 3.x 查询结果: {} - {} ({})", connection_id, database, format);
+
+    let format = V3ResponseFormat::parse(&format)
+        .ok_or_else(|| format!("不支持的导出格式: {}. 有效值: json, jsonl, csv, parquet, pretty", format))?;
+    let language = match language {
+        Some(language) => QueryLanguage::from_str(&language)
+            .ok_or_else(|| format!("不支持的查询语言: {}", language))?,
+        None => QueryLanguage::Sql,
+    };
+
+    let manager = connection_service.get_manager();
+    let client = manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let bytes = client
+        .query_raw(&query, &database, language, format)
+        .await
+        .map_err(|e| format!("导出查询结果失败: {}", e))?;
+
+    info!("查询结果导出完成: {} 字节", bytes.len());
+    Ok(Response::new(bytes))
+}
+
+/// 释放 Arrow 结果
+#[tauri::command]
+pub async fn release_influxdb3_arrow_result(result_id: String) -> Result<(), String> {
+    debug!("释放 Arrow 结果: {}", result_id);
+
+    let mut results = ARROW_RESULTS
+        .lock()
+        .map_err(|e| format!("释放 Arrow 结果失败: {}", e))?;
+    results.retain(|(id, _)| id != &result_id);
+
+    Ok(())
+}
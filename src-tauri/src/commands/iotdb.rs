@@ -5,7 +5,15 @@
  */
 
 use crate::services::connection_service::ConnectionService;
+use crate::services::SchemaCacheService;
 use crate::models::QueryResult;
+use crate::database::client::DatabaseClient;
+use crate::database::iotdb::admin::{self, find_column_index};
+use crate::database::iotdb::template::{
+    self, TemplateActivationAction, TemplateDesign, TemplateDiff, TemplateMeasurement,
+};
+use crate::database::iotdb::types::TimeConverter;
+use crate::utils::query_params::validate_iotdb_path;
 use anyhow::Result;
 use log::{debug, info, warn};
 use serde::{Deserialize, Serialize};
@@ -273,6 +281,7 @@ pub async fn create_iotdb_storage_group(
     storage_group: String,
     ttl: Option<i64>,
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
 ) -> Result<(), String> {
     debug!("创建 IoTDB 存储组: {} - {}", connection_id, storage_group);
 
@@ -295,6 +304,7 @@ pub async fn create_iotdb_storage_group(
         .map_err(|e| format!("创建存储组失败: {}", e))?;
 
     info!("存储组 '{}' 创建成功", storage_group);
+    schema_cache.invalidate(&connection_id, Some(&storage_group)).await;
     Ok(())
 }
 
@@ -304,6 +314,7 @@ pub async fn delete_iotdb_storage_group(
     connection_id: String,
     storage_group: String,
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
 ) -> Result<(), String> {
     debug!("删除 IoTDB 存储组: {} - {}", connection_id, storage_group);
 
@@ -321,6 +332,7 @@ pub async fn delete_iotdb_storage_group(
         .map_err(|e| format!("删除存储组失败: {}", e))?;
 
     info!("存储组 '{}' 删除成功", storage_group);
+    schema_cache.invalidate(&connection_id, Some(&storage_group)).await;
     Ok(())
 }
 
@@ -333,6 +345,7 @@ pub async fn create_iotdb_timeseries(
     encoding: Option<String>,
     compression: Option<String>,
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
 ) -> Result<(), String> {
     debug!("创建 IoTDB 时间序列: {} - {}", connection_id, timeseries_path);
 
@@ -381,6 +394,7 @@ pub async fn create_iotdb_timeseries(
         .map_err(|e| format!("创建时间序列失败: {}", e))?;
 
     info!("时间序列 '{}' 创建成功", timeseries_path);
+    schema_cache.invalidate(&connection_id, None).await;
     Ok(())
 }
 
@@ -390,6 +404,7 @@ pub async fn delete_iotdb_timeseries(
     connection_id: String,
     timeseries_path: String,
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
 ) -> Result<(), String> {
     debug!("删除 IoTDB 时间序列: {} - {}", connection_id, timeseries_path);
 
@@ -407,6 +422,7 @@ pub async fn delete_iotdb_timeseries(
         .map_err(|e| format!("删除时间序列失败: {}", e))?;
 
     info!("时间序列 '{}' 删除成功", timeseries_path);
+    schema_cache.invalidate(&connection_id, None).await;
     Ok(())
 }
 
@@ -419,6 +435,7 @@ pub async fn insert_iotdb_data(
     measurements: Vec<String>,
     values: Vec<String>,
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
 ) -> Result<(), String> {
     debug!("插入 IoTDB 数据: {} - {}", connection_id, device_path);
 
@@ -446,6 +463,7 @@ pub async fn insert_iotdb_data(
         .map_err(|e| format!("插入数据失败: {}", e))?;
 
     info!("数据插入成功: {} 个测量点", measurements.len());
+    schema_cache.invalidate(&connection_id, None).await;
     Ok(())
 }
 
@@ -746,6 +764,7 @@ pub async fn create_iotdb_template(
     connection_id: String,
     template_info: TemplateInfo,
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
 ) -> Result<(), String> {
     debug!("创建 IoTDB 模板: {} - {}", connection_id, template_info.name);
 
@@ -823,6 +842,7 @@ pub async fn create_iotdb_template(
         .map_err(|e| format!("创建模板失败: {}", e))?;
 
     info!("模板 '{}' 创建成功", template_info.name);
+    schema_cache.invalidate(&connection_id, None).await;
     Ok(())
 }
 
@@ -833,6 +853,7 @@ pub async fn mount_iotdb_template(
     template_name: String,
     path: String,
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
 ) -> Result<(), String> {
     debug!("挂载 IoTDB 模板: {} - {} 到 {}", connection_id, template_name, path);
 
@@ -848,6 +869,7 @@ pub async fn mount_iotdb_template(
         .await
         .map_err(|e| format!("挂载模板失败: {}", e))?;
 
+    schema_cache.invalidate(&connection_id, None).await;
     Ok(())
 }
 
@@ -858,6 +880,7 @@ pub async fn unmount_iotdb_template(
     template_name: String,
     path: String,
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
 ) -> Result<(), String> {
     debug!("卸载 IoTDB 模板: {} - {} 从 {}", connection_id, template_name, path);
 
@@ -873,6 +896,7 @@ pub async fn unmount_iotdb_template(
         .await
         .map_err(|e| format!("卸载模板失败: {}", e))?;
 
+    schema_cache.invalidate(&connection_id, None).await;
     Ok(())
 }
 
@@ -882,6 +906,7 @@ pub async fn drop_iotdb_template(
     connection_id: String,
     template_name: String,
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
 ) -> Result<(), String> {
     debug!("删除 IoTDB 模板: {} - {}", connection_id, template_name);
 
@@ -897,6 +922,7 @@ pub async fn drop_iotdb_template(
         .await
         .map_err(|e| format!("删除模板失败: {}", e))?;
 
+    schema_cache.invalidate(&connection_id, None).await;
     Ok(())
 }
 
@@ -1011,3 +1037,608 @@ pub async fn get_iotdb_timeseries_statistics(
         "avg": avg_value
     }))
 }
+
+// ============================================================================
+// 连续查询（Continuous Query）管理
+// ============================================================================
+
+/// 校验 IoTDB 标识符（CQ ID、模板名称等）
+pub(crate) fn validate_identifier(name: &str, label: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err(format!("{}不能为空", label));
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!("{} '{}' 只能包含字母、数字和下划线", label, name));
+    }
+    Ok(())
+}
+
+// IoTDB 连续查询信息
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContinuousQueryInfo {
+    pub cq_id: String,
+    pub query: String,
+    pub state: String,
+    pub every_interval: Option<String>,
+    pub boundary_time: Option<String>,
+    pub range_start: Option<String>,
+    pub range_end: Option<String>,
+    pub timeout_policy: Option<String>,
+    pub body: Option<String>,
+}
+
+// 创建连续查询请求
+#[derive(Debug, Deserialize)]
+pub struct CreateContinuousQueryRequest {
+    pub cq_id: String,
+    /// SELECT ... INTO ... FROM ... GROUP BY(...) 查询体
+    pub body: String,
+    pub every_interval: Option<String>,
+    pub boundary_time: Option<String>,
+    pub range_start: Option<String>,
+    pub range_end: Option<String>,
+    pub timeout_policy: Option<String>,
+}
+
+/// 查询并解析全部连续查询
+async fn load_continuous_queries(
+    connection_id: &str,
+    connection_service: &State<'_, ConnectionService>,
+) -> Result<Vec<ContinuousQueryInfo>, String> {
+    let connection_manager = connection_service.get_manager();
+    let client = connection_manager
+        .get_connection(connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let result = client
+        .execute_query("SHOW CONTINUOUS QUERIES", None)
+        .await
+        .map_err(|e| format!("获取连续查询列表失败: {}", e))?;
+
+    Ok(admin::parse_continuous_queries(&result.columns(), &result.rows()))
+}
+
+/// 获取 IoTDB 连续查询列表
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_iotdb_continuous_queries(
+    connection_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<ContinuousQueryInfo>, String> {
+    debug!("获取 IoTDB 连续查询列表: {}", connection_id);
+
+    load_continuous_queries(&connection_id, &connection_service).await
+}
+
+/// 获取 IoTDB 连续查询详细信息
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_iotdb_continuous_query_info(
+    connection_id: String,
+    cq_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<ContinuousQueryInfo, String> {
+    debug!("获取 IoTDB 连续查询详细信息: {} - {}", connection_id, cq_id);
+
+    load_continuous_queries(&connection_id, &connection_service)
+        .await?
+        .into_iter()
+        .find(|cq| cq.cq_id.eq_ignore_ascii_case(&cq_id))
+        .ok_or_else(|| format!("连续查询 '{}' 不存在", cq_id))
+}
+
+/// 创建 IoTDB 连续查询
+#[tauri::command(rename_all = "camelCase")]
+pub async fn create_iotdb_continuous_query(
+    connection_id: String,
+    request: CreateContinuousQueryRequest,
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+) -> Result<(), String> {
+    debug!("创建 IoTDB 连续查询: {} - {}", connection_id, request.cq_id);
+
+    let query = admin::build_create_statement(&request).map_err(|e| e.to_string())?;
+    debug!("创建连续查询 SQL: {}", query);
+
+    let connection_manager = connection_service.get_manager();
+    let client = connection_manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .execute_query(&query, None)
+        .await
+        .map_err(|e| format!("创建连续查询失败: {}", e))?;
+
+    info!("连续查询 '{}' 创建成功", request.cq_id);
+    schema_cache.invalidate(&connection_id, None).await;
+    Ok(())
+}
+
+/// 删除 IoTDB 连续查询
+#[tauri::command(rename_all = "camelCase")]
+pub async fn drop_iotdb_continuous_query(
+    connection_id: String,
+    cq_id: String,
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+) -> Result<(), String> {
+    debug!("删除 IoTDB 连续查询: {} - {}", connection_id, cq_id);
+
+    validate_identifier(&cq_id, "连续查询 ID")?;
+
+    let connection_manager = connection_service.get_manager();
+    let client = connection_manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let query = format!("DROP CONTINUOUS QUERY {}", cq_id);
+    client
+        .execute_query(&query, None)
+        .await
+        .map_err(|e| format!("删除连续查询失败: {}", e))?;
+
+    info!("连续查询 '{}' 删除成功", cq_id);
+    schema_cache.invalidate(&connection_id, None).await;
+    Ok(())
+}
+
+// ============================================================================
+// TTL 管理
+// ============================================================================
+
+// IoTDB TTL 信息
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TtlInfo {
+    /// 数据库或路径
+    pub path: String,
+    /// 生效的 TTL（毫秒），None 表示永不过期
+    pub ttl_ms: Option<i64>,
+    /// 是否在该路径上直接设置了 TTL
+    pub is_set: bool,
+    /// 继承自哪个路径的 TTL 规则
+    pub inherited_from: Option<String>,
+    /// 是否为数据库（存储组）
+    pub is_database: bool,
+}
+
+/// 获取 IoTDB 生效 TTL 视图
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_iotdb_ttl(
+    connection_id: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<Vec<TtlInfo>, String> {
+    debug!("获取 IoTDB TTL 信息: {}", connection_id);
+
+    let connection_manager = connection_service.get_manager();
+    let client = connection_manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let databases = client
+        .get_databases()
+        .await
+        .map_err(|e| format!("获取存储组列表失败: {}", e))?;
+
+    let result = client
+        .execute_query("SHOW ALL TTL", None)
+        .await
+        .map_err(|e| format!("获取 TTL 列表失败: {}", e))?;
+
+    let rules = admin::parse_ttl_rules(&result.columns(), &result.rows());
+    Ok(admin::resolve_effective_ttl(&databases, &rules))
+}
+
+/// 设置 IoTDB TTL
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_iotdb_ttl(
+    connection_id: String,
+    path: String,
+    ttl_ms: i64,
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+) -> Result<(), String> {
+    debug!("设置 IoTDB TTL: {} - {} = {}ms", connection_id, path, ttl_ms);
+
+    validate_iotdb_path(&path, true).map_err(|e| e.to_string())?;
+    if ttl_ms <= 0 {
+        return Err("TTL 必须为正整数（毫秒）".to_string());
+    }
+
+    let connection_manager = connection_service.get_manager();
+    let client = connection_manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let query = format!("SET TTL TO {} {}", path, ttl_ms);
+    client
+        .execute_query(&query, None)
+        .await
+        .map_err(|e| format!("设置 TTL 失败: {}", e))?;
+
+    info!("路径 '{}' 的 TTL 已设置为 {}ms", path, ttl_ms);
+    schema_cache.invalidate(&connection_id, None).await;
+    Ok(())
+}
+
+/// 取消 IoTDB TTL
+#[tauri::command(rename_all = "camelCase")]
+pub async fn unset_iotdb_ttl(
+    connection_id: String,
+    path: String,
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+) -> Result<(), String> {
+    debug!("取消 IoTDB TTL: {} - {}", connection_id, path);
+
+    validate_iotdb_path(&path, true).map_err(|e| e.to_string())?;
+
+    let connection_manager = connection_service.get_manager();
+    let client = connection_manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    // IoTDB 1.3+ 使用 UNSET TTL FROM，更早版本使用 UNSET TTL TO
+    let query = format!("UNSET TTL FROM {}", path);
+    if let Err(e) = client.execute_query(&query, None).await {
+        debug!("UNSET TTL FROM 执行失败，尝试旧语法: {}", e);
+        let legacy_query = format!("UNSET TTL TO {}", path);
+        client
+            .execute_query(&legacy_query, None)
+            .await
+            .map_err(|e| format!("取消 TTL 失败: {}", e))?;
+    }
+
+    info!("路径 '{}' 的 TTL 已取消", path);
+    schema_cache.invalidate(&connection_id, None).await;
+    Ok(())
+}
+
+// ============================================================================
+// 时区管理
+// ============================================================================
+
+// IoTDB 会话时区信息
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TimeZoneInfo {
+    pub time_zone: String,
+    pub utc_offset_seconds: Option<i32>,
+    pub timestamp_precision: String,
+}
+
+/// 设置 IoTDB 会话时区
+///
+/// 对当前连接的所有会话生效；持久化的默认时区由连接配置中的 timeZone 决定
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_iotdb_time_zone(
+    connection_id: String,
+    time_zone: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<TimeZoneInfo, String> {
+    debug!("设置 IoTDB 时区: {} - {}", connection_id, time_zone);
+
+    let time_zone = time_zone.trim().to_string();
+    if time_zone.is_empty() {
+        return Err("时区不能为空".to_string());
+    }
+
+    let connection_manager = connection_service.get_manager();
+    let client = connection_manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    client
+        .set_iotdb_time_zone(&time_zone)
+        .await
+        .map_err(|e| format!("设置时区失败: {}", e))?;
+
+    let precision = client
+        .get_iotdb_timestamp_precision()
+        .await
+        .map_err(|e| format!("获取时间戳精度失败: {}", e))?;
+
+    info!("连接 '{}' 的会话时区已设置为 {}", connection_id, time_zone);
+    Ok(TimeZoneInfo {
+        utc_offset_seconds: TimeConverter::parse_time_zone(&time_zone)
+            .map(|zone| zone.offset_at(&chrono::Utc::now()).local_minus_utc()),
+        time_zone,
+        timestamp_precision: precision.as_str().to_string(),
+    })
+}
+
+// ============================================================================
+// 模板设计器
+// ============================================================================
+
+// 模板挂载与激活路径
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplatePathsInfo {
+    pub template_name: String,
+    pub set_paths: Vec<String>,
+    pub activated_paths: Vec<String>,
+}
+
+// 批量激活/反激活的单条结果
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateActivationItem {
+    pub path: String,
+    pub statement: String,
+    /// planned / skipped / succeeded / failed
+    pub status: String,
+    pub message: Option<String>,
+}
+
+// 批量激活/反激活结果
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateActivationResult {
+    pub template_name: String,
+    pub action: TemplateActivationAction,
+    pub dry_run: bool,
+    pub items: Vec<TemplateActivationItem>,
+}
+
+/// 校验并规范化模板设计
+fn validate_template_design(design: &TemplateDesign) -> Result<TemplateDesign, String> {
+    validate_identifier(&design.name, "模板名称")?;
+    let normalized = template::normalize_design(design)?;
+    for measurement in &normalized.measurements {
+        if let Some(encoding) = &measurement.encoding {
+            validate_encoding(encoding)?;
+        }
+        if let Some(compression) = &measurement.compression {
+            validate_compression(compression)?;
+        }
+    }
+    Ok(normalized)
+}
+
+/// 读取现有模板的测点定义，模板不存在时返回 None
+async fn load_template_measurements(
+    client: &DatabaseClient,
+    template_name: &str,
+) -> Result<Option<Vec<TemplateMeasurement>>, String> {
+    let templates = client
+        .execute_query("SHOW SCHEMA TEMPLATES", None)
+        .await
+        .map_err(|e| format!("获取模板列表失败: {}", e))?;
+    let exists = templates
+        .rows()
+        .iter()
+        .any(|row| extract_string(row, 0).as_deref() == Some(template_name));
+    if !exists {
+        return Ok(None);
+    }
+
+    let query = format!("SHOW NODES IN SCHEMA TEMPLATE {}", template_name);
+    let result = client
+        .execute_query(&query, None)
+        .await
+        .map_err(|e| format!("获取模板信息失败: {}", e))?;
+
+    let columns = result.columns();
+    let name_idx = find_column_index(&columns, &["childnodes", "child_nodes", "measurement"]).unwrap_or(0);
+    let type_idx = find_column_index(&columns, &["datatype", "data_type"]).unwrap_or(name_idx + 1);
+    let encoding_idx = find_column_index(&columns, &["encoding"]).unwrap_or(name_idx + 2);
+    let compression_idx = find_column_index(&columns, &["compression"]).unwrap_or(name_idx + 3);
+
+    let mut measurements = Vec::new();
+    for row in result.rows() {
+        let Some(name) = extract_string(&row, name_idx) else {
+            continue;
+        };
+        let measurement = TemplateMeasurement {
+            name,
+            data_type: extract_string_or(&row, type_idx, "TEXT"),
+            encoding: extract_string(&row, encoding_idx),
+            compression: extract_string(&row, compression_idx),
+        };
+        match measurement.normalized() {
+            Ok(m) => measurements.push(m),
+            Err(e) => warn!("跳过无法识别的模板测点: {}", e),
+        }
+    }
+
+    Ok(Some(measurements))
+}
+
+/// 查询模板挂载（SET）或激活（USING）路径
+async fn load_template_paths(
+    client: &DatabaseClient,
+    template_name: &str,
+    kind: &str,
+) -> Result<Vec<String>, String> {
+    let query = format!("SHOW PATHS {} SCHEMA TEMPLATE {}", kind, template_name);
+    let result = client
+        .execute_query(&query, None)
+        .await
+        .map_err(|e| format!("获取模板路径失败: {}", e))?;
+
+    let columns = result.columns();
+    let path_idx = find_column_index(&columns, &["child paths", "paths", "path"]).unwrap_or(0);
+    Ok(result
+        .rows()
+        .iter()
+        .filter_map(|row| extract_string(row, path_idx))
+        .collect())
+}
+
+/// 对比提议模板与现有模板
+#[tauri::command(rename_all = "camelCase")]
+pub async fn diff_iotdb_template(
+    connection_id: String,
+    design: TemplateDesign,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<TemplateDiff, String> {
+    debug!("对比 IoTDB 模板: {} - {}", connection_id, design.name);
+
+    let design = validate_template_design(&design)?;
+
+    let connection_manager = connection_service.get_manager();
+    let client = connection_manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let current = load_template_measurements(&client, &design.name).await?;
+    Ok(template::diff_template(current.as_deref(), &design))
+}
+
+/// 应用模板设计（创建模板或追加测点）
+#[tauri::command(rename_all = "camelCase")]
+pub async fn apply_iotdb_template_design(
+    connection_id: String,
+    design: TemplateDesign,
+    dry_run: bool,
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+) -> Result<TemplateDiff, String> {
+    debug!("应用 IoTDB 模板设计: {} - {} (dry_run: {})", connection_id, design.name, dry_run);
+
+    let design = validate_template_design(&design)?;
+
+    let connection_manager = connection_service.get_manager();
+    let client = connection_manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let current = load_template_measurements(&client, &design.name).await?;
+    let diff = template::diff_template(current.as_deref(), &design);
+
+    if dry_run {
+        return Ok(diff);
+    }
+
+    for statement in &diff.statements {
+        debug!("执行模板迁移 SQL: {}", statement);
+        client
+            .execute_query(statement, None)
+            .await
+            .map_err(|e| format!("执行模板迁移失败: {}", e))?;
+    }
+
+    info!("模板 '{}' 已应用 {} 条迁移语句", design.name, diff.statements.len());
+    schema_cache.invalidate(&connection_id, None).await;
+    Ok(diff)
+}
+
+/// 获取模板的挂载路径与激活路径
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_iotdb_template_paths(
+    connection_id: String,
+    template_name: String,
+    connection_service: State<'_, ConnectionService>,
+) -> Result<TemplatePathsInfo, String> {
+    debug!("获取 IoTDB 模板路径: {} - {}", connection_id, template_name);
+
+    validate_identifier(&template_name, "模板名称")?;
+
+    let connection_manager = connection_service.get_manager();
+    let client = connection_manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let set_paths = load_template_paths(&client, &template_name, "SET").await?;
+    let activated_paths = load_template_paths(&client, &template_name, "USING").await?;
+
+    Ok(TemplatePathsInfo {
+        template_name,
+        set_paths,
+        activated_paths,
+    })
+}
+
+/// 批量激活/反激活模板，dry_run 时仅返回计划执行的语句
+#[tauri::command(rename_all = "camelCase")]
+pub async fn batch_set_iotdb_template_activation(
+    connection_id: String,
+    template_name: String,
+    paths: Vec<String>,
+    action: TemplateActivationAction,
+    dry_run: bool,
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+) -> Result<TemplateActivationResult, String> {
+    debug!(
+        "批量{} IoTDB 模板: {} - {} ({} 个路径, dry_run: {})",
+        if action == TemplateActivationAction::Activate { "激活" } else { "反激活" },
+        connection_id,
+        template_name,
+        paths.len(),
+        dry_run
+    );
+
+    validate_identifier(&template_name, "模板名称")?;
+    if paths.is_empty() {
+        return Err("路径列表不能为空".to_string());
+    }
+
+    let connection_manager = connection_service.get_manager();
+    let client = connection_manager
+        .get_connection(&connection_id)
+        .await
+        .map_err(|e| format!("获取连接失败: {}", e))?;
+
+    let set_paths = load_template_paths(&client, &template_name, "SET").await?;
+    let activated_paths = load_template_paths(&client, &template_name, "USING").await?;
+
+    let mut items = Vec::with_capacity(paths.len());
+    for path in paths {
+        let path = path.trim().to_string();
+        // 预检查：路径必须合法，激活要求模板已挂载到祖先路径，反激活要求路径已激活
+        let (statement, skip_reason) = match template::build_activation_statement(action, &template_name, &path) {
+            Err(e) => (String::new(), Some(e)),
+            Ok(statement) => {
+                let skip_reason = match action {
+                    TemplateActivationAction::Activate if activated_paths.contains(&path) => {
+                        Some("路径已激活该模板".to_string())
+                    }
+                    TemplateActivationAction::Activate if !template::is_covered_by(&path, &set_paths) => {
+                        Some("模板未挂载到该路径或其祖先路径".to_string())
+                    }
+                    TemplateActivationAction::Deactivate if !activated_paths.contains(&path) => {
+                        Some("路径未激活该模板".to_string())
+                    }
+                    _ => None,
+                };
+                (statement, skip_reason)
+            }
+        };
+
+        let (status, message) = match (skip_reason, dry_run) {
+            (Some(reason), _) => ("skipped", Some(reason)),
+            (None, true) => ("planned", None),
+            (None, false) => match client.execute_query(&statement, None).await {
+                Ok(_) => ("succeeded", None),
+                Err(e) => {
+                    warn!("执行模板激活语句失败: {} - {}", statement, e);
+                    ("failed", Some(e.to_string()))
+                }
+            },
+        };
+
+        items.push(TemplateActivationItem {
+            path,
+            statement,
+            status: status.to_string(),
+            message,
+        });
+    }
+
+    if !dry_run {
+        schema_cache.invalidate(&connection_id, None).await;
+    }
+
+    Ok(TemplateActivationResult {
+        template_name,
+        action,
+        dry_run,
+        items,
+    })
+}
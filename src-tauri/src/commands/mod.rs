@@ -16,7 +16,9 @@ pub mod port_manager;
 pub mod embedded_server;
 pub mod workspace;
 pub mod iotdb;
+pub mod influxdb1;
 pub mod influxdb2;
+pub mod influxdb3;
 pub mod database_detection;
 pub mod multi_source_performance;
 pub mod window_theme;
@@ -683,6 +683,7 @@ async fn try_connect_local_influxdb() -> Option<crate::models::connection::Conne
             password,
             database: Some("_internal".to_string()),
             ssl: false,
+            skip_ssl_verification: false,
             timeout: 5, // 短超时用于快速检测
             connection_timeout: 5,
             query_timeout: 10,
@@ -738,6 +739,7 @@ async fn get_default_connection_for_internal_query() -> Result<crate::models::co
         password,
         database: Some("_internal".to_string()),
         ssl: false,
+        skip_ssl_verification: false,
         timeout: 30,
         connection_timeout: 30,
         query_timeout: 60,
@@ -1,6 +1,10 @@
-use crate::models::{QueryRequest, QueryResult, QueryResultItem, QueryValidationResult, QueryHistoryItem};
-use crate::services::{ConnectionService, PerformanceStatsService};
-use crate::utils::validation::ValidationUtils;
+use crate::models::{DatabaseType, QueryRequest, QueryResult, QueryResultItem, QueryValidationResult, QueryHistoryItem};
+use crate::services::{ConnectionService, PerformanceStatsService, SchemaCacheService};
+use crate::utils::validation::{QueryIssueKind, ValidationUtils};
+use crate::utils::query_formatter::{self, FormatOptions};
+use crate::utils::query_parser::{detect_dialect, QueryDialect};
+use crate::utils::completion::{self, CompletionAnalysis, CompletionContext, CompletionKind, CompletionResult};
+use crate::services::schema_cache::SchemaItem;
 use crate::database::client::DatabaseClient;
 use crate::commands::settings::SettingsStorage;
 use crate::commands::query_history::QueryHistoryStorage;
@@ -16,6 +20,7 @@ pub async fn execute_query(
     settings_storage: State<'_, SettingsStorage>,
     performance_stats: State<'_, Arc<PerformanceStatsService>>,
     query_history_storage: State<'_, QueryHistoryStorage>,
+    schema_cache: State<'_, SchemaCacheService>,
     request: QueryRequest,
 ) -> Result<QueryResult, String> {
     debug!("处理执行查询命令: {}", request.connection_id);
@@ -47,6 +52,13 @@ pub async fn execute_query(
     let database_name = request.database.clone().unwrap_or_else(|| "default".to_string());
     performance_stats.record_query_start(&request.connection_id, &database_name).await;
 
+    // IoTDB 的结构缓存不按存储组划分，结构变更时清除整个连接的缓存
+    let cache_scope = if matches!(client.get_database_type(), DatabaseType::IoTDB) {
+        None
+    } else {
+        request.database.clone()
+    };
+
     let start_time = std::time::Instant::now();
 
     // 根据SQL语句类型选择执行方式
@@ -54,6 +66,9 @@ pub async fn execute_query(
     debug!("检测到SQL语句类型: {}", statement_type);
 
     let result = match statement_type.as_str() {
+        "INSERT" | "DELETE" if !request.params.is_empty() => {
+            Err(format!("{}语句不支持查询参数", statement_type))
+        }
         "INSERT" => {
             // 处理INSERT语句
             execute_insert_statement(client, &request).await
@@ -62,6 +77,17 @@ pub async fn execute_query(
             // 处理DELETE语句
             execute_delete_statement(client, &request).await
         }
+        "EXPLAIN" if request.params.is_empty() => {
+            // 执行计划语句，结果中附带解析后的执行计划
+            let analyze = request.query.split_whitespace().nth(1).is_some_and(|w| w.eq_ignore_ascii_case("ANALYZE"));
+            let mut result = client.explain_query(&request.query, request.database.as_deref(), analyze).await
+                .map_err(|e| {
+                    error!("执行计划查询失败: {}", e);
+                    format!("执行计划查询失败: {}", e)
+                })?;
+            result.sql_type = Some(statement_type.clone());
+            Ok(result)
+        }
         "UPDATE" => {
             // InfluxDB不支持UPDATE语句
             Err("InfluxDB不支持UPDATE语句，请使用INSERT语句覆盖数据".to_string())
@@ -69,7 +95,7 @@ pub async fn execute_query(
         "SELECT" | "SELECT_AGGREGATE" | "SELECT_GROUP" | "SHOW" | "DESCRIBE" | "DESC" | "EXPLAIN" | "CREATE" | "DROP" | "ALTER" | "GRANT" | "REVOKE" => {
             // 处理查询和DDL语句
             let database_ref = request.database.as_deref();
-            let mut result = client.execute_query_with_database(&request.query, database_ref).await
+            let mut result = client.execute_query_with_params(&request.query, database_ref, &request.params).await
                 .map_err(|e| {
                     error!("查询执行失败: {}", e);
                     format!("查询执行失败: {}", e)
@@ -84,7 +110,7 @@ pub async fn execute_query(
             // 未知语句类型，尝试作为查询执行
             debug!("未知语句类型，尝试作为查询执行");
             let database_ref = request.database.as_deref();
-            let mut result = client.execute_query_with_database(&request.query, database_ref).await
+            let mut result = client.execute_query_with_params(&request.query, database_ref, &request.params).await
                 .map_err(|e| {
                     error!("查询执行失败: {}", e);
                     format!("查询执行失败: {}", e)
@@ -97,6 +123,11 @@ pub async fn execute_query(
         }
     };
 
+    // 结构可能已变化，清除缓存以免数据源树和自动补全显示过期内容
+    if result.is_ok() && ValidationUtils::changes_schema(&request.query) {
+        schema_cache.invalidate(&request.connection_id, cache_scope.as_deref()).await;
+    }
+
     // 记录查询完成
     let execution_time_ms = start_time.elapsed().as_millis() as f64;
     let success = result.is_ok();
@@ -152,20 +183,24 @@ pub async fn validate_query(
         suggestions: vec![],
     };
     
-    // 基本验证
-    match ValidationUtils::validate_query(&query) {
-        Ok(_) => {
-            debug!("查询验证通过");
-        }
-        Err(e) => {
+    // 语法与语义检查，错误位置来自解析结果；语法问题只提示，与执行前的检查一致不判为无效
+    for issue in ValidationUtils::inspect_query(&query, None) {
+        if issue.kind != QueryIssueKind::Syntax {
             result.valid = false;
-            result.errors.push(crate::models::QueryError {
-                line: 1,
-                column: 1,
-                message: e.to_string(),
-                error_type: crate::models::QueryErrorType::SyntaxError,
-            });
         }
+        result.errors.push(crate::models::QueryError {
+            line: issue.line,
+            column: issue.column,
+            message: issue.message,
+            error_type: match issue.kind {
+                QueryIssueKind::Syntax => crate::models::QueryErrorType::SyntaxError,
+                QueryIssueKind::Semantic => crate::models::QueryErrorType::SemanticError,
+                QueryIssueKind::Permission => crate::models::QueryErrorType::PermissionError,
+            },
+        });
+    }
+    if result.valid {
+        debug!("查询验证通过");
     }
     
     // 添加更详细的语法分析和建议
@@ -313,79 +348,177 @@ fn provide_optimization_suggestions(query: &str, result: &mut QueryValidationRes
 
 
 /// 获取查询建议
+///
+/// 以查询末尾为光标位置，返回补全候选的名称
 #[tauri::command(rename_all = "camelCase")]
 pub async fn get_query_suggestions(
     connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
     connection_id: String,
     database: Option<String>,
     partial_query: String,
 ) -> Result<Vec<String>, String> {
     debug!("处理获取查询建议命令: {}", connection_id);
-    
-    let mut suggestions = Vec::new();
-    
-    // 基本的SQL关键字建议（兼容InfluxDB和IoTDB）
-    let keywords = [
-        "SELECT", "FROM", "WHERE", "GROUP BY", "ORDER BY", "LIMIT",
-        "SHOW DATABASES", "SHOW MEASUREMENTS", "SHOW TIMESERIES", "SHOW DEVICES",
-        "SHOW FIELD KEYS", "SHOW TAG KEYS", "SHOW STORAGE GROUP",
-        "CREATE DATABASE", "DROP DATABASE", "CREATE RETENTION POLICY",
-        "SHOW RETENTION POLICIES", "SHOW SERIES", "SHOW STATS",
-    ];
-    
-    let partial_upper = partial_query.to_uppercase();
-    
-    for keyword in &keywords {
-        if keyword.starts_with(&partial_upper) {
-            suggestions.push(keyword.to_string());
+
+    let cursor = partial_query.chars().count();
+    let result = get_completions(connection_service, schema_cache, connection_id, database, partial_query, cursor, None).await?;
+
+    // 限制建议数量
+    Ok(result.items.into_iter().take(20).map(|item| item.label).collect())
+}
+
+/// 获取自动补全候选
+///
+/// `cursor` 为光标的字符偏移；根据光标所处的语法位置从结构信息缓存中取出测量、字段、标签、
+/// bucket 和设备等候选，`language` 未提供时根据连接类型和查询内容识别方言
+#[tauri::command]
+pub async fn get_completions(
+    connection_service: State<'_, ConnectionService>,
+    schema_cache: State<'_, SchemaCacheService>,
+    connection_id: String,
+    database: Option<String>,
+    query: String,
+    cursor: usize,
+    language: Option<String>,
+) -> Result<CompletionResult, String> {
+    debug!("处理自动补全命令: {} - 光标 {}", connection_id, cursor);
+
+    let manager = connection_service.get_manager();
+    let client = manager.get_connection(&connection_id).await
+        .map_err(|e| {
+            error!("获取连接失败: {}", e);
+            format!("获取连接失败: {}", e)
+        })?;
+
+    let dialect = match language.as_deref().and_then(QueryDialect::from_language) {
+        Some(dialect) => dialect,
+        None if matches!(client.get_database_type(), DatabaseType::IoTDB) => QueryDialect::IoTDB,
+        None => detect_dialect(&query),
+    };
+
+    let analysis = completion::analyze(&query, cursor, dialect);
+    let mut candidates = completion::static_candidates(&analysis);
+    candidates.extend(schema_candidates(&schema_cache, &connection_id, &client, database.as_deref(), &analysis).await);
+
+    Ok(completion::complete(&analysis, candidates))
+}
+
+/// 从缓存读取结构信息，加载失败时不影响其余候选
+async fn load_schema(schema_cache: &SchemaCacheService, connection_id: &str, client: &DatabaseClient, item: SchemaItem) -> Vec<String> {
+    schema_cache.get(connection_id, client, item).await.unwrap_or_else(|e| {
+        warn!("加载补全结构信息失败: {}", e);
+        Vec::new()
+    })
+}
+
+/// 按补全位置收集结构信息候选
+async fn schema_candidates(
+    schema_cache: &SchemaCacheService,
+    connection_id: &str,
+    client: &DatabaseClient,
+    database: Option<&str>,
+    analysis: &CompletionAnalysis,
+) -> Vec<(String, CompletionKind)> {
+    let load = |item| load_schema(schema_cache, connection_id, client, item);
+    let tagged = |values: Vec<String>, kind| values.into_iter().map(move |v| (v, kind));
+    let is_iotdb = analysis.dialect == QueryDialect::IoTDB;
+    let database = analysis.database.as_deref().or(database).map(|d| d.to_string());
+    let measurement = analysis.measurement.clone();
+    let mut candidates = Vec::new();
+
+    match &analysis.context {
+        CompletionContext::Database => candidates.extend(tagged(load(SchemaItem::Databases).await, CompletionKind::Database)),
+        CompletionContext::Bucket => candidates.extend(tagged(load(SchemaItem::Databases).await, CompletionKind::Bucket)),
+        CompletionContext::Measurement => {
+            if let Some(database) = database {
+                let kind = if is_iotdb { CompletionKind::Device } else { CompletionKind::Measurement };
+                candidates.extend(tagged(load(SchemaItem::Measurements { database }).await, kind));
+            }
         }
-    }
-    
-    // 如果有连接，尝试获取数据库、测量等信息作为建议
-    if let Ok(manager) = connection_service.get_manager().get_connection(&connection_id).await {
-        // 获取数据库列表
-        if let Ok(databases) = manager.get_databases().await {
-            for db in databases {
-                if db.to_uppercase().starts_with(&partial_upper) {
-                    suggestions.push(db);
+        CompletionContext::Field | CompletionContext::TagKey => {
+            let (Some(database), Some(measurement)) = (database, measurement) else {
+                return candidates;
+            };
+            let field_kind = if is_iotdb { CompletionKind::Timeseries } else { CompletionKind::Field };
+            let fields = load(SchemaItem::Fields { database: database.clone(), measurement: measurement.clone() });
+            let tag_keys = load(SchemaItem::TagKeys { database, measurement: Some(measurement) });
+            match (&analysis.context, analysis.dialect) {
+                // Flux 中字段名只出现在 _field 的比较值里，r. 之后是标签列
+                (CompletionContext::TagKey, QueryDialect::Flux) => candidates.extend(tagged(tag_keys.await, CompletionKind::TagKey)),
+                (CompletionContext::Field, QueryDialect::Flux) => candidates.extend(tagged(fields.await, field_kind)),
+                (CompletionContext::TagKey, _) => {
+                    candidates.extend(tagged(tag_keys.await, CompletionKind::TagKey));
+                    candidates.extend(tagged(fields.await, field_kind));
+                }
+                _ => {
+                    candidates.extend(tagged(fields.await, field_kind));
+                    candidates.extend(tagged(tag_keys.await, CompletionKind::TagKey));
                 }
             }
         }
-        
-        // 如果指定了数据库，获取测量列表
-        if let Some(db) = database {
-            if let Ok(measurements) = manager.get_measurements(&db).await {
-                for measurement in measurements {
-                    if measurement.to_uppercase().starts_with(&partial_upper) {
-                        suggestions.push(measurement);
+        // IoTDB 没有标签值可查
+        CompletionContext::TagValue { .. } if is_iotdb => {}
+        CompletionContext::TagValue { tag_key } => {
+            if let Some(database) = database {
+                let item = SchemaItem::TagValues { database, measurement, tag_key: tag_key.clone() };
+                candidates.extend(tagged(load(item).await, CompletionKind::TagValue));
+            }
+        }
+        CompletionContext::DevicePath => {
+            // 逐级展开：存储组 -> 设备 -> 时间序列，候选均为完整路径
+            let path = analysis.prefix.as_str();
+            for storage_group in load(SchemaItem::Databases).await {
+                candidates.push((storage_group.clone(), CompletionKind::Database));
+                if !path.starts_with(&format!("{}.", storage_group)) {
+                    continue;
+                }
+                for device in load(SchemaItem::Measurements { database: storage_group.clone() }).await {
+                    let device = if device.starts_with("root.") { device } else { format!("{}.{}", storage_group, device) };
+                    if path.starts_with(&format!("{}.", device)) {
+                        let item = SchemaItem::Fields { database: storage_group.clone(), measurement: device.clone() };
+                        for timeseries in load(item).await {
+                            candidates.push((format!("{}.{}", device, timeseries), CompletionKind::Timeseries));
+                        }
                     }
+                    candidates.push((device, CompletionKind::Device));
                 }
             }
         }
+        CompletionContext::Keyword | CompletionContext::Function | CompletionContext::None => {}
     }
-    
-    // 限制建议数量
-    suggestions.truncate(20);
-    
-    Ok(suggestions)
+
+    candidates
 }
 
 /// 格式化查询
+///
+/// `language` 为前端的查询语言名称（influxql、sql、iotdb-sql、flux），未提供时根据内容识别
 #[tauri::command]
 pub async fn format_query(
+    settings_storage: State<'_, SettingsStorage>,
     query: String,
+    language: Option<String>,
 ) -> Result<String, String> {
     debug!("处理格式化查询命令");
-    
-    // 简单的查询格式化
-    let formatted = query
-        .lines()
-        .map(|line| line.trim())
-        .filter(|line| !line.is_empty())
-        .collect::<Vec<_>>()
-        .join("\n");
-    
-    Ok(formatted)
+
+    let options = {
+        let settings = settings_storage.lock().map_err(|e| {
+            error!("获取设置锁失败: {}", e);
+            format!("获取设置锁失败: {}", e)
+        })?;
+        FormatOptions::from_editor_settings(&settings.editor)
+    };
+
+    let dialect = match language.as_deref() {
+        Some(language) => match QueryDialect::from_language(language) {
+            Some(dialect) => Some(dialect),
+            // 不支持格式化的语言（如 PromQL）保持原样
+            None => return Ok(query),
+        },
+        None => None,
+    };
+
+    Ok(query_formatter::format_query(&query, dialect, &options))
 }
 
 /// 批量执行查询
@@ -455,6 +588,7 @@ pub async fn execute_batch_queries(
                         query: query_str.to_string(),
                         database: Some(database.to_string()),
                         timeout: None,
+                        params: Default::default(),
                     };
                     execute_insert_statement(client.clone(), &request).await
                         .map_err(|e| format!("第 {} 条INSERT语句执行失败: {}", index + 1, e))?
@@ -466,6 +600,7 @@ pub async fn execute_batch_queries(
                         query: query_str.to_string(),
                         database: Some(database.to_string()),
                         timeout: None,
+                        params: Default::default(),
                     };
                     execute_delete_statement(client.clone(), &request).await
                         .map_err(|e| format!("第 {} 条DELETE语句执行失败: {}", index + 1, e))?
@@ -492,31 +627,34 @@ pub async fn execute_batch_queries(
 }
 
 /// 解释查询执行计划
+///
+/// analyze 为 true 时执行 EXPLAIN ANALYZE，查询会真正运行并返回各步骤的实际耗时和行数；
+/// Flux 查询始终通过 profiler 执行
 #[tauri::command]
 pub async fn explain_query(
-    _connection_service: State<'_, ConnectionService>,
+    connection_service: State<'_, ConnectionService>,
     connection_id: String,
     query: String,
-) -> Result<String, String> {
+    database: Option<String>,
+    analyze: Option<bool>,
+) -> Result<QueryResult, String> {
     debug!("处理解释查询命令: {}", connection_id);
-    
-    // InfluxDB 1.x 不支持 EXPLAIN，这里返回基本信息
-    let explanation = format!(
-        "查询分析:\n\
-        - 查询长度: {} 字符\n\
-        - 查询类型: {}\n\
-        - 建议: 使用适当的时间范围和 LIMIT 子句来优化性能",
-        query.len(),
-        if query.to_uppercase().starts_with("SELECT") {
-            "数据查询"
-        } else if query.to_uppercase().starts_with("SHOW") {
-            "元数据查询"
-        } else {
-            "其他操作"
-        }
-    );
-    
-    Ok(explanation)
+
+    let manager = connection_service.get_manager();
+    let client = manager.get_connection(&connection_id).await
+        .map_err(|e| {
+            error!("获取连接失败: {}", e);
+            format!("获取连接失败: {}", e)
+        })?;
+
+    let mut result = client.explain_query(&query, database.as_deref(), analyze.unwrap_or(false)).await
+        .map_err(|e| {
+            error!("执行计划查询失败: {}", e);
+            format!("执行计划查询失败: {}", e)
+        })?;
+    result.sql_type = Some("EXPLAIN".to_string());
+
+    Ok(result)
 }
 
 /// 执行INSERT语句
@@ -29,6 +29,18 @@ pub struct EditorSettings {
     pub word_wrap: bool,
     pub line_numbers: bool,
     pub minimap: bool,
+    #[serde(default = "default_keyword_case")]
+    pub keyword_case: String, // 格式化时的关键字大小写: "upper"、"lower" 或 "preserve"
+    #[serde(default = "default_align_where_conditions")]
+    pub align_where_conditions: bool,
+}
+
+fn default_keyword_case() -> String {
+    "upper".to_string()
+}
+
+fn default_align_where_conditions() -> bool {
+    true
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -113,6 +125,8 @@ impl Default for AppSettings {
                 word_wrap: true,
                 line_numbers: true,
                 minimap: true,
+                keyword_case: default_keyword_case(),
+                align_where_conditions: default_align_where_conditions(),
             },
             query: QuerySettings {
                 timeout: 30000,
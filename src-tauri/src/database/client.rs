@@ -1,11 +1,11 @@
-use crate::models::{ConnectionConfig, QueryResult, RetentionPolicy, DatabaseType, TagInfo, FieldInfo, FieldType, TableSchema};
+use crate::models::{ConnectionConfig, QueryParams, QueryResult, RetentionPolicy, DatabaseType, TagInfo, FieldInfo, FieldType, TableSchema};
+use crate::utils::query_params::{bind_inline, ParamDialect};
 use crate::database::iotdb_official_client::IoTDBOfficialClient;
 use crate::database::influxdb_client::InfluxDBClient;
 use crate::database::s3_database_client::S3DatabaseClient;
 use anyhow::Result;
 use influxdb::Client;
 use std::time::Instant;
-use tokio::sync::Mutex;
 use std::sync::Arc;
 use log::{debug, error, info, warn};
 use reqwest;
@@ -37,7 +37,7 @@ pub enum DatabaseClient {
     InfluxDB1x(InfluxClient),
     InfluxDB2x(InfluxDB2Client),
     InfluxDBUnified(InfluxDBClient), // 新的统一客户端
-    IoTDB(Arc<Mutex<IoTDBOfficialClient>>),
+    IoTDB(Arc<IoTDBOfficialClient>),
     ObjectStorage(S3DatabaseClient), // S3/MinIO 等对象存储
 }
 
@@ -49,7 +49,6 @@ impl DatabaseClient {
             DatabaseClient::InfluxDB2x(client) => client.test_connection().await,
             DatabaseClient::InfluxDBUnified(client) => client.test_connection().await,
             DatabaseClient::IoTDB(client) => {
-                let client = client.lock().await;
                 client.test_connection().await
             },
             DatabaseClient::ObjectStorage(client) => client.test_connection().await,
@@ -68,7 +67,6 @@ impl DatabaseClient {
                 client.execute_query_with_database(query, database).await
             },
             DatabaseClient::IoTDB(client) => {
-                let client = client.lock().await;
                 client.execute_query(query, None).await
             },
             DatabaseClient::ObjectStorage(client) => {
@@ -109,7 +107,6 @@ impl DatabaseClient {
                 client.list_databases().await
             },
             DatabaseClient::IoTDB(client) => {
-                let client = client.lock().await;
                 client.get_databases().await
             },
             DatabaseClient::ObjectStorage(client) => {
@@ -130,7 +127,6 @@ impl DatabaseClient {
                 client.list_measurements(database).await
             },
             DatabaseClient::IoTDB(client) => {
-                let client = client.lock().await;
                 client.get_devices(database).await
             },
             DatabaseClient::ObjectStorage(client) => {
@@ -156,7 +152,6 @@ impl DatabaseClient {
                 Ok(schema.fields.into_iter().map(|f| f.name).collect())
             },
             DatabaseClient::IoTDB(client) => {
-                let client = client.lock().await;
                 let device_path = build_iotdb_device_path(database, table);
                 client.get_timeseries(&device_path).await
             },
@@ -206,7 +201,6 @@ impl DatabaseClient {
                 }))
             },
             DatabaseClient::IoTDB(client) => {
-                let client = client.lock().await;
                 let server_info = client.get_server_info().await?;
                 let status = client.get_connection_status().await;
                 let protocol = client.get_current_protocol();
@@ -242,7 +236,6 @@ impl DatabaseClient {
                 client.close().await
             },
             DatabaseClient::IoTDB(client) => {
-                let client = client.lock().await;
                 client.disconnect().await
             },
             DatabaseClient::ObjectStorage(_) => Ok(()),
@@ -260,6 +253,30 @@ impl DatabaseClient {
         }
     }
 
+    /// 获取 IoTDB 会话池统计信息
+    pub async fn get_session_pool_stats(&self) -> Option<crate::database::iotdb::session_pool::SessionPoolStats> {
+        match self {
+            DatabaseClient::IoTDB(client) => Some(client.get_pool_stats().await),
+            _ => None,
+        }
+    }
+
+    /// 设置 IoTDB 会话时区
+    pub async fn set_iotdb_time_zone(&self, time_zone: &str) -> Result<()> {
+        match self {
+            DatabaseClient::IoTDB(client) => client.set_time_zone(time_zone).await,
+            _ => Err(anyhow::anyhow!("此操作仅支持 IoTDB")),
+        }
+    }
+
+    /// 获取 IoTDB 服务端时间戳精度
+    pub async fn get_iotdb_timestamp_precision(&self) -> Result<crate::database::iotdb::types::TimestampPrecision> {
+        match self {
+            DatabaseClient::IoTDB(client) => Ok(client.get_timestamp_precision().await),
+            _ => Err(anyhow::anyhow!("此操作仅支持 IoTDB")),
+        }
+    }
+
     /// 获取连接配置
     pub async fn get_config(&self) -> ConnectionConfig {
         match self {
@@ -267,7 +284,6 @@ impl DatabaseClient {
             DatabaseClient::InfluxDB2x(client) => client.config.clone(),
             DatabaseClient::InfluxDBUnified(client) => client.get_config().clone(),
             DatabaseClient::IoTDB(client) => {
-                let client = client.lock().await;
                 client.get_config().clone()
             },
             DatabaseClient::ObjectStorage(_) => {
@@ -288,7 +304,6 @@ impl DatabaseClient {
                 client.create_database(database_name).await
             },
             DatabaseClient::IoTDB(client) => {
-                let client = client.lock().await;
                 let sql = format!("CREATE STORAGE GROUP root.{}", database_name);
                 client.execute_query(&sql, None).await?;
                 Ok(())
@@ -311,7 +326,6 @@ impl DatabaseClient {
                 client.drop_database(database_name).await
             },
             DatabaseClient::IoTDB(client) => {
-                let client = client.lock().await;
                 let sql = format!("DELETE STORAGE GROUP root.{}", database_name);
                 client.execute_query(&sql, None).await?;
                 Ok(())
@@ -359,7 +373,6 @@ impl DatabaseClient {
                 client.list_measurements(database).await
             },
             DatabaseClient::IoTDB(client) => {
-                let client = client.lock().await;
                 client.get_devices(database).await
             },
             DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
@@ -382,7 +395,6 @@ impl DatabaseClient {
                 Ok(schema.fields.into_iter().map(|f| f.name).collect())
             },
             DatabaseClient::IoTDB(client) => {
-                let client = client.lock().await;
                 let device_path = build_iotdb_device_path(database, measurement);
                 client.get_timeseries(&device_path).await
             },
@@ -390,11 +402,138 @@ impl DatabaseClient {
         }
     }
 
+    /// 获取字段键列表，不指定测量时返回整个数据库的字段键，IoTDB 返回时间序列路径
+    pub async fn list_field_keys(&self, database: &str, measurement: Option<&str>) -> Result<Vec<String>> {
+        let query = if matches!(self.get_database_type(), DatabaseType::IoTDB) {
+            // IoTDB 使用 SHOW TIMESERIES 语法，不使用引号
+            match measurement {
+                Some(measurement) => format!("SHOW TIMESERIES {}.*", build_iotdb_device_path(database, measurement)),
+                None => format!("SHOW TIMESERIES {}.**", database),
+            }
+        } else {
+            match measurement {
+                Some(measurement) => format!("SHOW FIELD KEYS ON \"{}\" FROM \"{}\"", database, measurement),
+                None => format!("SHOW FIELD KEYS ON \"{}\"", database),
+            }
+        };
+        debug!("字段键查询: {}", query);
+        let result = self.execute_query(&query, Some(database)).await?;
+
+        Ok(result
+            .rows()
+            .iter()
+            .filter_map(|row| row.first().and_then(|v| v.as_str()).map(|s| s.to_string()))
+            .collect())
+    }
+
+    /// 获取标签键，IoTDB 没有标签概念，返回空列表
+    pub async fn get_tag_keys(&self, database: &str, measurement: Option<&str>) -> Result<Vec<String>> {
+        if matches!(self.get_database_type(), DatabaseType::IoTDB) {
+            return Ok(vec![]);
+        }
+
+        let query = match measurement {
+            Some(measurement) => format!("SHOW TAG KEYS ON \"{}\" FROM \"{}\"", database, measurement),
+            None => format!("SHOW TAG KEYS ON \"{}\"", database),
+        };
+        let result = self.execute_query(&query, Some(database)).await?;
+
+        Ok(result
+            .rows()
+            .iter()
+            .filter_map(|row| row.first().and_then(|v| v.as_str()).map(|s| s.to_string()))
+            .collect())
+    }
+
+    /// 获取标签值，IoTDB 返回设备列表
+    pub async fn get_tag_values(&self, database: &str, tag_key: &str, measurement: Option<&str>) -> Result<Vec<String>> {
+        let query = if matches!(self.get_database_type(), DatabaseType::IoTDB) {
+            match measurement {
+                Some(measurement) => format!("SHOW DEVICES {}.{}", database, measurement),
+                None => format!("SHOW DEVICES {}.**", database),
+            }
+        } else {
+            match measurement {
+                Some(measurement) => format!("SHOW TAG VALUES ON \"{}\" FROM \"{}\" WITH KEY = \"{}\"", database, measurement, tag_key),
+                None => format!("SHOW TAG VALUES ON \"{}\" WITH KEY = \"{}\"", database, tag_key),
+            }
+        };
+        let result = self.execute_query(&query, Some(database)).await?;
+
+        // 标签值通常在第二列
+        Ok(result
+            .rows()
+            .iter()
+            .filter_map(|row| row.get(1).and_then(|v| v.as_str()).map(|s| s.to_string()))
+            .collect())
+    }
+
     /// 执行查询（带数据库参数，向后兼容）
     pub async fn execute_query_with_database(&self, query: &str, database: Option<&str>) -> Result<QueryResult> {
         self.execute_query(query, database).await
     }
 
+    /// 执行参数化查询，查询中以 $name 引用参数
+    ///
+    /// 统一客户端由驱动在服务端绑定参数，其他客户端按查询方言转义后内联
+    pub async fn execute_query_with_params(&self, query: &str, database: Option<&str>, params: &QueryParams) -> Result<QueryResult> {
+        if params.is_empty() {
+            return self.execute_query(query, database).await;
+        }
+
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.execute_query_with_params(query, database, params).await
+            },
+            DatabaseClient::InfluxDB1x(client) => {
+                let query = bind_inline(query, params, ParamDialect::InfluxQL)?;
+                client.execute_query_with_database(&query, database).await
+            },
+            DatabaseClient::InfluxDB2x(client) => {
+                let dialect = if query.contains("|>") { ParamDialect::Flux } else { ParamDialect::InfluxQL };
+                let query = bind_inline(query, params, dialect)?;
+                client.execute_query(&query).await
+            },
+            DatabaseClient::IoTDB(client) => {
+                let query = bind_inline(query, params, ParamDialect::IoTDB)?;
+                client.execute_query(&query, None).await
+            },
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("对象存储不支持参数化查询")),
+        }
+    }
+
+    /// 执行 EXPLAIN / EXPLAIN ANALYZE，返回原始结果并附带解析后的执行计划
+    pub async fn explain_query(&self, query: &str, database: Option<&str>, analyze: bool) -> Result<QueryResult> {
+        use crate::database::explain::{self, PlanFormat};
+
+        let (format, mut result) = match self {
+            DatabaseClient::InfluxDBUnified(client) => return client.explain_query(query, database, analyze).await,
+            DatabaseClient::InfluxDB1x(client) => {
+                let text = explain::explain_statement(PlanFormat::InfluxQL, query, analyze);
+                (PlanFormat::InfluxQL, client.execute_query_with_database(&text, database).await?)
+            },
+            DatabaseClient::InfluxDB2x(client) => {
+                // 旧版客户端会把非 Flux 语句当作 Line Protocol 写入，只支持 Flux profiler
+                if !query.contains("|>") {
+                    return Err(anyhow::anyhow!("旧版 InfluxDB 2.x/3.x 客户端仅支持 Flux 查询的执行计划"));
+                }
+                let text = explain::explain_statement(PlanFormat::FluxProfiler, query, analyze);
+                (PlanFormat::FluxProfiler, client.execute_query(&text).await?)
+            },
+            DatabaseClient::IoTDB(client) => {
+                let text = explain::explain_statement(PlanFormat::IoTDB, query, analyze);
+                (PlanFormat::IoTDB, client.execute_query(&text, None).await?)
+            },
+            DatabaseClient::ObjectStorage(_) => return Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+        };
+
+        match explain::parse_plan(format, &result) {
+            Some(plan) => result.set_execution_plan(plan),
+            None => warn!("未能从返回结果中解析出执行计划，返回原始结果"),
+        }
+        Ok(result)
+    }
+
     /// 获取表结构信息
     pub async fn get_table_schema(&self, database: &str, measurement: &str) -> Result<TableSchema> {
         match self {
@@ -477,7 +616,6 @@ impl DatabaseClient {
                 Ok(client.capabilities().version.clone())
             },
             DatabaseClient::IoTDB(client) => {
-                let client = client.lock().await;
                 client.detect_version().await
             },
             DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
@@ -500,7 +638,6 @@ impl DatabaseClient {
                 Ok(vec![])
             },
             DatabaseClient::IoTDB(client) => {
-                let client = client.lock().await;
                 client.get_tree_nodes().await
             },
             DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
@@ -523,7 +660,6 @@ impl DatabaseClient {
                 client.get_tree_children(parent_node_id, node_type, metadata).await
             },
             DatabaseClient::IoTDB(client) => {
-                let client = client.lock().await;
                 // IoTDB 子节点获取逻辑
                 client.get_tree_children(parent_node_id, node_type, metadata).await
             },
@@ -648,6 +784,1179 @@ impl DatabaseClient {
             _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x/3.x")),
         }
     }
+
+    /// 获取 InfluxDB 2.x 任务列表
+    pub async fn get_influxdb2_tasks(&self, org_name: Option<&str>) -> Result<Vec<crate::commands::influxdb2::TaskInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb2_tasks(org_name).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持任务管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 2.x 任务详细信息
+    pub async fn get_influxdb2_task(&self, task_id: &str) -> Result<crate::commands::influxdb2::TaskInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb2_task(task_id).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持任务管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 创建 InfluxDB 2.x 任务
+    pub async fn create_influxdb2_task(&self, request: &crate::commands::influxdb2::CreateTaskRequest) -> Result<crate::commands::influxdb2::TaskInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.create_influxdb2_task(request).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持任务管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 更新 InfluxDB 2.x 任务
+    pub async fn update_influxdb2_task(&self, task_id: &str, request: &crate::commands::influxdb2::UpdateTaskRequest) -> Result<crate::commands::influxdb2::TaskInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.update_influxdb2_task(task_id, request).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持任务管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 删除 InfluxDB 2.x 任务
+    pub async fn delete_influxdb2_task(&self, task_id: &str) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.delete_influxdb2_task(task_id).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持任务管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 手动触发 InfluxDB 2.x 任务运行
+    pub async fn run_influxdb2_task(&self, task_id: &str) -> Result<crate::commands::influxdb2::TaskRunInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.run_influxdb2_task(task_id).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持任务管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 2.x 任务运行历史
+    pub async fn get_influxdb2_task_runs(&self, task_id: &str, limit: u32) -> Result<Vec<crate::commands::influxdb2::TaskRunInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb2_task_runs(task_id, limit).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持任务管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 2.x 任务日志
+    pub async fn get_influxdb2_task_logs(&self, task_id: &str, run_id: Option<&str>) -> Result<Vec<crate::commands::influxdb2::TaskLogEntry>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb2_task_logs(task_id, run_id).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持任务管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 2.x 检查列表
+    pub async fn get_influxdb2_checks(&self, org_name: Option<&str>) -> Result<Vec<crate::commands::influxdb2::CheckInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb2_checks(org_name).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持监控告警")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 创建 InfluxDB 2.x 检查
+    pub async fn create_influxdb2_check(&self, request: &crate::commands::influxdb2::SaveCheckRequest) -> Result<crate::commands::influxdb2::CheckInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.create_influxdb2_check(request).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持监控告警")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 更新 InfluxDB 2.x 检查
+    pub async fn update_influxdb2_check(&self, check_id: &str, request: &crate::commands::influxdb2::SaveCheckRequest) -> Result<crate::commands::influxdb2::CheckInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.update_influxdb2_check(check_id, request).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持监控告警")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 删除 InfluxDB 2.x 检查
+    pub async fn delete_influxdb2_check(&self, check_id: &str) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.delete_influxdb2_check(check_id).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持监控告警")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 2.x 通知端点列表
+    pub async fn get_influxdb2_notification_endpoints(&self, org_name: Option<&str>) -> Result<Vec<crate::commands::influxdb2::NotificationEndpointInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb2_notification_endpoints(org_name).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持监控告警")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 创建 InfluxDB 2.x 通知端点
+    pub async fn create_influxdb2_notification_endpoint(&self, request: &crate::commands::influxdb2::SaveNotificationEndpointRequest) -> Result<crate::commands::influxdb2::NotificationEndpointInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.create_influxdb2_notification_endpoint(request).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持监控告警")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 更新 InfluxDB 2.x 通知端点
+    pub async fn update_influxdb2_notification_endpoint(&self, endpoint_id: &str, request: &crate::commands::influxdb2::SaveNotificationEndpointRequest) -> Result<crate::commands::influxdb2::NotificationEndpointInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.update_influxdb2_notification_endpoint(endpoint_id, request).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持监控告警")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 删除 InfluxDB 2.x 通知端点
+    pub async fn delete_influxdb2_notification_endpoint(&self, endpoint_id: &str) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.delete_influxdb2_notification_endpoint(endpoint_id).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持监控告警")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 2.x 通知规则列表
+    pub async fn get_influxdb2_notification_rules(&self, org_name: Option<&str>) -> Result<Vec<crate::commands::influxdb2::NotificationRuleInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb2_notification_rules(org_name).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持监控告警")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 创建 InfluxDB 2.x 通知规则
+    pub async fn create_influxdb2_notification_rule(&self, request: &crate::commands::influxdb2::SaveNotificationRuleRequest) -> Result<crate::commands::influxdb2::NotificationRuleInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.create_influxdb2_notification_rule(request).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持监控告警")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 更新 InfluxDB 2.x 通知规则
+    pub async fn update_influxdb2_notification_rule(&self, rule_id: &str, request: &crate::commands::influxdb2::SaveNotificationRuleRequest) -> Result<crate::commands::influxdb2::NotificationRuleInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.update_influxdb2_notification_rule(rule_id, request).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持监控告警")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 删除 InfluxDB 2.x 通知规则
+    pub async fn delete_influxdb2_notification_rule(&self, rule_id: &str) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.delete_influxdb2_notification_rule(rule_id).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持监控告警")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 查询 InfluxDB 2.x 最近的检查状态
+    pub async fn get_influxdb2_check_statuses(&self, org_name: Option<&str>, check_id: Option<&str>, range: &str, limit: u32) -> Result<Vec<crate::commands::influxdb2::CheckStatusEntry>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb2_check_statuses(org_name, check_id, range, limit).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持监控告警")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 2.x 令牌列表
+    pub async fn get_influxdb2_authorizations(&self, org_name: Option<&str>) -> Result<Vec<crate::commands::influxdb2::AuthorizationInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb2_authorizations(org_name).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持令牌管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 创建 InfluxDB 2.x 存储桶范围令牌
+    pub async fn create_influxdb2_authorization(&self, request: &crate::commands::influxdb2::CreateAuthorizationRequest) -> Result<crate::commands::influxdb2::AuthorizationInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.create_influxdb2_authorization(request).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持令牌管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 启用或停用 InfluxDB 2.x 令牌
+    pub async fn set_influxdb2_authorization_active(&self, authorization_id: &str, active: bool) -> Result<crate::commands::influxdb2::AuthorizationInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.set_influxdb2_authorization_active(authorization_id, active).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持令牌管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 删除 InfluxDB 2.x 令牌
+    pub async fn delete_influxdb2_authorization(&self, authorization_id: &str) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.delete_influxdb2_authorization(authorization_id).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持令牌管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 获取当前连接使用的 InfluxDB 2.x 令牌
+    pub async fn get_influxdb2_current_authorization(&self) -> Result<Option<crate::commands::influxdb2::AuthorizationInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb2_current_authorization().await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持令牌管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 创建 InfluxDB 2.x 组织
+    pub async fn create_influxdb2_organization(&self, name: &str, description: Option<&str>) -> Result<crate::commands::influxdb2::OrganizationInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.create_influxdb2_organization(name, description).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持用户和组织管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 更新 InfluxDB 2.x 组织
+    pub async fn update_influxdb2_organization(&self, org_name: &str, new_name: Option<&str>, description: Option<&str>) -> Result<crate::commands::influxdb2::OrganizationInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.update_influxdb2_organization(org_name, new_name, description).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持用户和组织管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 删除 InfluxDB 2.x 组织
+    pub async fn delete_influxdb2_organization(&self, org_name: &str) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.delete_influxdb2_organization(org_name).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持用户和组织管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 2.x 组织成员
+    pub async fn get_influxdb2_org_members(&self, org_name: &str) -> Result<Vec<crate::commands::influxdb2::OrgMemberInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb2_org_members(org_name).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持用户和组织管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 添加 InfluxDB 2.x 组织成员
+    pub async fn add_influxdb2_org_member(&self, org_name: &str, user_id: &str, owner: bool) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.add_influxdb2_org_member(org_name, user_id, owner).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持用户和组织管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 移除 InfluxDB 2.x 组织成员
+    pub async fn remove_influxdb2_org_member(&self, org_name: &str, user_id: &str, owner: bool) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.remove_influxdb2_org_member(org_name, user_id, owner).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持用户和组织管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 2.x 用户列表
+    pub async fn get_influxdb2_users(&self) -> Result<Vec<crate::commands::influxdb2::User2xInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb2_users().await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持用户和组织管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 创建 InfluxDB 2.x 用户
+    pub async fn create_influxdb2_user(&self, name: &str, password: Option<&str>, org_name: Option<&str>, owner: bool) -> Result<crate::commands::influxdb2::User2xInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.create_influxdb2_user(name, password, org_name, owner).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持用户和组织管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 重置 InfluxDB 2.x 用户密码
+    pub async fn reset_influxdb2_user_password(&self, user_id: &str, password: &str) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.reset_influxdb2_user_password(user_id, password).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持用户和组织管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 删除 InfluxDB 2.x 用户
+    pub async fn delete_influxdb2_user(&self, user_id: &str) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.delete_influxdb2_user(user_id).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持用户和组织管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 2.x 标签列表
+    pub async fn get_influxdb2_labels(&self, org_name: Option<&str>) -> Result<Vec<crate::commands::influxdb2::LabelInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb2_labels(org_name).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持用户和组织管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 创建 InfluxDB 2.x 标签
+    pub async fn create_influxdb2_label(&self, request: &crate::commands::influxdb2::SaveLabelRequest) -> Result<crate::commands::influxdb2::LabelInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.create_influxdb2_label(request).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持用户和组织管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 更新 InfluxDB 2.x 标签
+    pub async fn update_influxdb2_label(&self, label_id: &str, request: &crate::commands::influxdb2::SaveLabelRequest) -> Result<crate::commands::influxdb2::LabelInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.update_influxdb2_label(label_id, request).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持用户和组织管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 删除 InfluxDB 2.x 标签
+    pub async fn delete_influxdb2_label(&self, label_id: &str) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.delete_influxdb2_label(label_id).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持用户和组织管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 2.x 资源上的标签
+    pub async fn get_influxdb2_resource_labels(&self, resource_type: &str, resource_id: &str) -> Result<Vec<crate::commands::influxdb2::LabelInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb2_resource_labels(resource_type, resource_id).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持用户和组织管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 为 InfluxDB 2.x 资源分配标签
+    pub async fn add_influxdb2_resource_label(&self, resource_type: &str, resource_id: &str, label_id: &str) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.add_influxdb2_resource_label(resource_type, resource_id, label_id).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持用户和组织管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 移除 InfluxDB 2.x 资源上的标签
+    pub async fn remove_influxdb2_resource_label(&self, resource_type: &str, resource_id: &str, label_id: &str) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.remove_influxdb2_resource_label(resource_type, resource_id, label_id).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持用户和组织管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 2.x Telegraf 配置列表
+    pub async fn get_influxdb2_telegrafs(&self, org_name: Option<&str>) -> Result<Vec<crate::commands::influxdb2::TelegrafConfigInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb2_telegrafs(org_name).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持Telegraf 和抓取器管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 2.x Telegraf 配置
+    pub async fn get_influxdb2_telegraf(&self, telegraf_id: &str) -> Result<crate::commands::influxdb2::TelegrafConfigInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb2_telegraf(telegraf_id).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持Telegraf 和抓取器管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 创建 InfluxDB 2.x Telegraf 配置
+    pub async fn create_influxdb2_telegraf(&self, request: &crate::commands::influxdb2::SaveTelegrafRequest) -> Result<crate::commands::influxdb2::TelegrafConfigInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.create_influxdb2_telegraf(request).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持Telegraf 和抓取器管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 更新 InfluxDB 2.x Telegraf 配置
+    pub async fn update_influxdb2_telegraf(&self, telegraf_id: &str, request: &crate::commands::influxdb2::SaveTelegrafRequest) -> Result<crate::commands::influxdb2::TelegrafConfigInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.update_influxdb2_telegraf(telegraf_id, request).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持Telegraf 和抓取器管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 删除 InfluxDB 2.x Telegraf 配置
+    pub async fn delete_influxdb2_telegraf(&self, telegraf_id: &str) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.delete_influxdb2_telegraf(telegraf_id).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持Telegraf 和抓取器管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 为 InfluxDB 2.x 存储桶生成 Telegraf 入门配置
+    pub async fn generate_influxdb2_telegraf_config(&self, bucket_name: &str, create_token: bool) -> Result<crate::commands::influxdb2::TelegrafStarterConfig> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.generate_influxdb2_telegraf_config(bucket_name, create_token).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持Telegraf 和抓取器管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 2.x 抓取器列表
+    pub async fn get_influxdb2_scrapers(&self, org_name: Option<&str>) -> Result<Vec<crate::commands::influxdb2::ScraperInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb2_scrapers(org_name).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持Telegraf 和抓取器管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 创建 InfluxDB 2.x 抓取器
+    pub async fn create_influxdb2_scraper(&self, request: &crate::commands::influxdb2::SaveScraperRequest) -> Result<crate::commands::influxdb2::ScraperInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.create_influxdb2_scraper(request).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持Telegraf 和抓取器管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 更新 InfluxDB 2.x 抓取器
+    pub async fn update_influxdb2_scraper(&self, scraper_id: &str, request: &crate::commands::influxdb2::SaveScraperRequest) -> Result<crate::commands::influxdb2::ScraperInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.update_influxdb2_scraper(scraper_id, request).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持Telegraf 和抓取器管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 删除 InfluxDB 2.x 抓取器
+    pub async fn delete_influxdb2_scraper(&self, scraper_id: &str) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.delete_influxdb2_scraper(scraper_id).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x 客户端不支持Telegraf 和抓取器管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 2.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 1.x 连续查询列表
+    pub async fn get_influxdb1_continuous_queries(&self) -> Result<Vec<crate::commands::influxdb1::InfluxContinuousQueryInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb1_continuous_queries().await
+            },
+            DatabaseClient::InfluxDB1x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 1.x 客户端不支持连续查询管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x")),
+        }
+    }
+
+    /// 创建 InfluxDB 1.x 连续查询
+    pub async fn create_influxdb1_continuous_query(&self, request: &crate::commands::influxdb1::CreateInfluxContinuousQueryRequest) -> Result<crate::commands::influxdb1::InfluxContinuousQueryInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.create_influxdb1_continuous_query(request).await
+            },
+            DatabaseClient::InfluxDB1x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 1.x 客户端不支持连续查询管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x")),
+        }
+    }
+
+    /// 删除 InfluxDB 1.x 连续查询
+    pub async fn drop_influxdb1_continuous_query(&self, database: &str, name: &str) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.drop_influxdb1_continuous_query(database, name).await
+            },
+            DatabaseClient::InfluxDB1x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 1.x 客户端不支持连续查询管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x")),
+        }
+    }
+
+    /// 回填 InfluxDB 1.x 连续查询
+    pub async fn backfill_influxdb1_continuous_query<F>(&self, request: &crate::commands::influxdb1::BackfillContinuousQueryRequest, on_progress: F) -> Result<crate::commands::influxdb1::ContinuousQueryBackfillResult>
+    where
+        F: Fn(&crate::commands::influxdb1::ContinuousQueryBackfillProgress),
+    {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.backfill_influxdb1_continuous_query(request, on_progress).await
+            },
+            DatabaseClient::InfluxDB1x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 1.x 客户端不支持连续查询管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 1.x 用户列表
+    pub async fn get_influxdb1_users(&self) -> Result<Vec<crate::commands::influxdb1::InfluxUser1xInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb1_users().await
+            },
+            DatabaseClient::InfluxDB1x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 1.x 客户端不支持用户和权限管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x")),
+        }
+    }
+
+    /// 创建 InfluxDB 1.x 用户
+    pub async fn create_influxdb1_user(&self, request: &crate::commands::influxdb1::CreateInfluxUser1xRequest) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.create_influxdb1_user(request).await
+            },
+            DatabaseClient::InfluxDB1x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 1.x 客户端不支持用户和权限管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x")),
+        }
+    }
+
+    /// 修改 InfluxDB 1.x 用户密码
+    pub async fn set_influxdb1_user_password(&self, username: &str, password: &str) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.set_influxdb1_user_password(username, password).await
+            },
+            DatabaseClient::InfluxDB1x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 1.x 客户端不支持用户和权限管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x")),
+        }
+    }
+
+    /// 授予或收回 InfluxDB 1.x 管理员权限
+    pub async fn set_influxdb1_user_admin(&self, username: &str, admin: bool) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.set_influxdb1_user_admin(username, admin).await
+            },
+            DatabaseClient::InfluxDB1x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 1.x 客户端不支持用户和权限管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x")),
+        }
+    }
+
+    /// 删除 InfluxDB 1.x 用户
+    pub async fn drop_influxdb1_user(&self, username: &str) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.drop_influxdb1_user(username).await
+            },
+            DatabaseClient::InfluxDB1x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 1.x 客户端不支持用户和权限管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 1.x 用户权限
+    pub async fn get_influxdb1_user_grants(&self, username: &str) -> Result<Vec<crate::commands::influxdb1::DatabaseGrant1x>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb1_user_grants(username).await
+            },
+            DatabaseClient::InfluxDB1x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 1.x 客户端不支持用户和权限管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x")),
+        }
+    }
+
+    /// 授予 InfluxDB 1.x 数据库权限
+    pub async fn grant_influxdb1_privilege(&self, username: &str, database: &str, privilege: &str) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.grant_influxdb1_privilege(username, database, privilege).await
+            },
+            DatabaseClient::InfluxDB1x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 1.x 客户端不支持用户和权限管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x")),
+        }
+    }
+
+    /// 收回 InfluxDB 1.x 数据库权限
+    pub async fn revoke_influxdb1_privilege(&self, username: &str, database: &str, privilege: &str) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.revoke_influxdb1_privilege(username, database, privilege).await
+            },
+            DatabaseClient::InfluxDB1x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 1.x 客户端不支持用户和权限管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x")),
+        }
+    }
+
+    /// 设置 InfluxDB 1.x 数据库读写权限
+    pub async fn set_influxdb1_privileges(&self, username: &str, database: &str, read: bool, write: bool) -> Result<crate::commands::influxdb1::DatabaseGrant1x> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.set_influxdb1_privileges(username, database, read, write).await
+            },
+            DatabaseClient::InfluxDB1x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 1.x 客户端不支持用户和权限管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 1.x 权限矩阵
+    pub async fn get_influxdb1_privilege_matrix(&self) -> Result<crate::commands::influxdb1::PrivilegeMatrix> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb1_privilege_matrix().await
+            },
+            DatabaseClient::InfluxDB1x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 1.x 客户端不支持用户和权限管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 1.x 分片列表
+    pub async fn get_influxdb1_shards(&self, database: Option<&str>) -> Result<Vec<crate::commands::influxdb1::ShardInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb1_shards(database).await
+            },
+            DatabaseClient::InfluxDB1x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 1.x 客户端不支持分片管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 1.x 分片组列表
+    pub async fn get_influxdb1_shard_groups(&self, database: Option<&str>) -> Result<Vec<crate::commands::influxdb1::ShardGroupInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb1_shard_groups(database).await
+            },
+            DatabaseClient::InfluxDB1x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 1.x 客户端不支持分片管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 1.x 分片时间线
+    pub async fn get_influxdb1_shard_timeline(&self, database: Option<&str>) -> Result<Vec<crate::commands::influxdb1::RetentionPolicyTimeline>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb1_shard_timeline(database).await
+            },
+            DatabaseClient::InfluxDB1x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 1.x 客户端不支持分片管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x")),
+        }
+    }
+
+    /// 删除 InfluxDB 1.x 分片
+    pub async fn drop_influxdb1_shard(&self, shard_id: u64) -> Result<crate::commands::influxdb1::ShardInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.drop_influxdb1_shard(shard_id).await
+            },
+            DatabaseClient::InfluxDB1x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 1.x 客户端不支持分片管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 1.x 运行中查询
+    pub async fn get_influxdb1_running_queries(&self) -> Result<Vec<crate::commands::influxdb1::RunningQueryInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb1_running_queries().await
+            },
+            DatabaseClient::InfluxDB1x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 1.x 客户端不支持运行中查询管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x")),
+        }
+    }
+
+    /// 终止 InfluxDB 1.x 运行中查询
+    pub async fn kill_influxdb1_query(&self, qid: u64, host: Option<&str>) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.kill_influxdb1_query(qid, host).await
+            },
+            DatabaseClient::InfluxDB1x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 1.x 客户端不支持运行中查询管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 3.x 最新值缓存列表
+    pub async fn get_influxdb3_last_caches(&self, database: &str, table: Option<&str>) -> Result<Vec<crate::commands::influxdb3::LastCacheInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb3_last_caches(database, table).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x/3.x 客户端不支持缓存管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x")),
+        }
+    }
+
+    /// 创建 InfluxDB 3.x 最新值缓存
+    pub async fn create_influxdb3_last_cache(&self, request: &crate::commands::influxdb3::CreateLastCacheRequest) -> Result<crate::commands::influxdb3::LastCacheInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.create_influxdb3_last_cache(request).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x/3.x 客户端不支持缓存管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x")),
+        }
+    }
+
+    /// 删除 InfluxDB 3.x 最新值缓存
+    pub async fn delete_influxdb3_last_cache(&self, database: &str, table: &str, name: &str) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.delete_influxdb3_last_cache(database, table, name).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x/3.x 客户端不支持缓存管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x")),
+        }
+    }
+
+    /// 读取 InfluxDB 3.x 最新值缓存内容
+    pub async fn query_influxdb3_last_cache(&self, database: &str, table: &str, name: Option<&str>) -> Result<QueryResult> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.query_influxdb3_last_cache(database, table, name).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x/3.x 客户端不支持缓存管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 3.x 去重值缓存列表
+    pub async fn get_influxdb3_distinct_caches(&self, database: &str, table: Option<&str>) -> Result<Vec<crate::commands::influxdb3::DistinctCacheInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb3_distinct_caches(database, table).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x/3.x 客户端不支持缓存管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x")),
+        }
+    }
+
+    /// 创建 InfluxDB 3.x 去重值缓存
+    pub async fn create_influxdb3_distinct_cache(&self, request: &crate::commands::influxdb3::CreateDistinctCacheRequest) -> Result<crate::commands::influxdb3::DistinctCacheInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.create_influxdb3_distinct_cache(request).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x/3.x 客户端不支持缓存管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x")),
+        }
+    }
+
+    /// 删除 InfluxDB 3.x 去重值缓存
+    pub async fn delete_influxdb3_distinct_cache(&self, database: &str, table: &str, name: &str) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.delete_influxdb3_distinct_cache(database, table, name).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x/3.x 客户端不支持缓存管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x")),
+        }
+    }
+
+    /// 读取 InfluxDB 3.x 去重值缓存内容
+    pub async fn query_influxdb3_distinct_cache(&self, database: &str, table: &str, name: Option<&str>) -> Result<QueryResult> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.query_influxdb3_distinct_cache(database, table, name).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x/3.x 客户端不支持缓存管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 3.x 处理引擎触发器
+    pub async fn get_influxdb3_triggers(&self, database: &str) -> Result<Vec<crate::commands::influxdb3::ProcessingEngineTriggerInfo>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb3_triggers(database).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x/3.x 客户端不支持处理引擎管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x")),
+        }
+    }
+
+    /// 创建 InfluxDB 3.x 处理引擎触发器
+    pub async fn create_influxdb3_trigger(&self, request: &crate::commands::influxdb3::CreateProcessingEngineTriggerRequest) -> Result<crate::commands::influxdb3::ProcessingEngineTriggerInfo> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.create_influxdb3_trigger(request).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x/3.x 客户端不支持处理引擎管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x")),
+        }
+    }
+
+    /// 启用或禁用 InfluxDB 3.x 处理引擎触发器
+    pub async fn set_influxdb3_trigger_enabled(&self, database: &str, trigger_name: &str, enabled: bool) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.set_influxdb3_trigger_enabled(database, trigger_name, enabled).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x/3.x 客户端不支持处理引擎管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x")),
+        }
+    }
+
+    /// 删除 InfluxDB 3.x 处理引擎触发器
+    pub async fn delete_influxdb3_trigger(&self, database: &str, trigger_name: &str, force: bool) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.delete_influxdb3_trigger(database, trigger_name, force).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x/3.x 客户端不支持处理引擎管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x")),
+        }
+    }
+
+    /// 上传 InfluxDB 3.x 处理引擎插件
+    pub async fn upload_influxdb3_plugin(&self, request: &crate::commands::influxdb3::UploadProcessingEnginePluginRequest) -> Result<()> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.upload_influxdb3_plugin(request).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x/3.x 客户端不支持处理引擎管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x")),
+        }
+    }
+
+    /// 测试 InfluxDB 3.x 处理引擎插件
+    pub async fn test_influxdb3_plugin(&self, request: &crate::commands::influxdb3::TestProcessingEnginePluginRequest) -> Result<crate::commands::influxdb3::PluginTestResult> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.test_influxdb3_plugin(request).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x/3.x 客户端不支持处理引擎管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x")),
+        }
+    }
+
+    /// 获取 InfluxDB 3.x 处理引擎日志
+    pub async fn get_influxdb3_trigger_logs(&self, database: &str, trigger_name: Option<&str>, limit: Option<u32>) -> Result<Vec<crate::commands::influxdb3::ProcessingEngineLogEntry>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.get_influxdb3_trigger_logs(database, trigger_name, limit).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x/3.x 客户端不支持处理引擎管理")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x")),
+        }
+    }
+
+    /// 执行 InfluxDB 3.x 查询并返回 Arrow 列式结果
+    pub async fn query_arrow(&self, query: &str, database: &str, language: crate::database::influxdb::QueryLanguage) -> Result<crate::database::influxdb::ArrowResult> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.query_arrow(query, database, language).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x/3.x 客户端不支持 Arrow 列式结果")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x")),
+        }
+    }
+
+    /// 以 CSV / Parquet 等原生格式执行 InfluxDB 3.x 查询，返回服务器的原始响应
+    pub async fn query_raw(&self, query: &str, database: &str, language: crate::database::influxdb::QueryLanguage, format: crate::database::influxdb::V3ResponseFormat) -> Result<Vec<u8>> {
+        match self {
+            DatabaseClient::InfluxDBUnified(client) => {
+                client.query_raw(query, database, language, format).await
+            },
+            DatabaseClient::InfluxDB2x(_) => Err(anyhow::anyhow!("旧版 InfluxDB 2.x/3.x 客户端不支持原始格式查询")),
+            DatabaseClient::ObjectStorage(_) => Err(anyhow::anyhow!("此操作暂不支持对象存储")),
+
+            _ => Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x")),
+        }
+    }
 }
 
 /// InfluxDB 2.x/3.x 客户端封装
@@ -4478,7 +5787,7 @@ impl DatabaseClientFactory {
             DatabaseType::IoTDB => {
                 info!("创建IoTDB官方客户端: {}:{}", config.host, config.port);
                 let client = IoTDBOfficialClient::new(config).await?;
-                Ok(DatabaseClient::IoTDB(Arc::new(Mutex::new(client))))
+                Ok(DatabaseClient::IoTDB(Arc::new(client)))
             },
             DatabaseType::ObjectStorage => {
                 info!("创建S3/对象存储客户端: {}:{}", config.host, config.port);
@@ -0,0 +1,746 @@
+/**
+ * 执行计划解析
+ *
+ * 各后端的 EXPLAIN 输出格式差异很大，这里负责生成对应的解释语句，
+ * 并把返回的文本或表格统一解析为 ExecutionPlan 树
+ */
+
+use crate::models::{ExecutionPlan, ExecutionPlanStep, QueryResult};
+use serde_json::Value;
+
+/// 执行计划输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanFormat {
+    /// InfluxDB 1.x/2.x InfluxQL 的 EXPLAIN / EXPLAIN ANALYZE 文本
+    InfluxQL,
+    /// InfluxDB 3.x DataFusion 的 plan_type / plan 表
+    DataFusion,
+    /// Flux profiler 输出的 profiler/query 与 profiler/operator 表
+    FluxProfiler,
+    /// IoTDB 的 EXPLAIN / EXPLAIN ANALYZE 文本
+    IoTDB,
+}
+
+/// 去掉查询开头已有的 EXPLAIN [ANALYZE] [VERBOSE]，避免重复
+fn strip_explain(query: &str) -> &str {
+    let mut rest = query.trim();
+    for keyword in ["EXPLAIN", "ANALYZE", "VERBOSE"] {
+        let head = rest.split_whitespace().next().unwrap_or("");
+        if head.eq_ignore_ascii_case(keyword) {
+            rest = rest[head.len()..].trim_start();
+        }
+    }
+    rest
+}
+
+/// 按输出格式生成解释语句
+///
+/// Flux 没有 EXPLAIN，启用 profiler 后查询会真正执行，analyze 参数不起作用
+pub fn explain_statement(format: PlanFormat, query: &str, analyze: bool) -> String {
+    if format == PlanFormat::FluxProfiler {
+        return with_flux_profiler(query);
+    }
+    let query = strip_explain(query).trim_end_matches(';');
+    if analyze {
+        format!("EXPLAIN ANALYZE {}", query)
+    } else {
+        format!("EXPLAIN {}", query)
+    }
+}
+
+/// 在 Flux 脚本中启用 profiler，option 必须位于所有 import 之后
+fn with_flux_profiler(query: &str) -> String {
+    let lines: Vec<&str> = query.lines().collect();
+    let header_len = lines
+        .iter()
+        .take_while(|line| {
+            let line = line.trim();
+            line.is_empty() || line.starts_with("//") || line.starts_with("import ")
+        })
+        .count();
+
+    let mut script = String::from("import \"profiler\"\n");
+    for line in &lines[..header_len] {
+        if line.trim() != "import \"profiler\"" {
+            script.push_str(line);
+            script.push('\n');
+        }
+    }
+    script.push_str("option profiler.enabledProfilers = [\"query\", \"operator\"]\n\n");
+    script.push_str(&lines[header_len..].join("\n"));
+    script
+}
+
+/// 将解释语句的结果解析为执行计划
+///
+/// 输出格式无法识别时返回 None，调用方仍应返回原始结果
+pub fn parse_plan(format: PlanFormat, result: &QueryResult) -> Option<ExecutionPlan> {
+    let columns = result.get_columns();
+    let rows = result.get_rows();
+    let plan = match format {
+        PlanFormat::InfluxQL => parse_influxql_plan(&text_lines(&rows)),
+        PlanFormat::DataFusion => parse_datafusion_plan(&columns, &rows),
+        PlanFormat::FluxProfiler => parse_flux_profile(&columns, &rows),
+        PlanFormat::IoTDB => parse_iotdb_plan(&text_lines(&rows)),
+    };
+    if plan.steps.is_empty() {
+        return None;
+    }
+    Some(plan)
+}
+
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn cell_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// 单列文本结果的每一行
+fn text_lines(rows: &[Vec<Value>]) -> Vec<String> {
+    rows.iter()
+        .filter_map(|row| row.first())
+        .flat_map(|cell| cell_text(cell).lines().map(|l| l.trim_end().to_string()).collect::<Vec<_>>())
+        .collect()
+}
+
+/// 解析 Go / DataFusion 风格的时长（如 1m2.5s、2.25ms、13.479µs），返回毫秒
+fn parse_duration_ms(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let mut total = 0.0;
+    let mut rest = text;
+    let mut matched = false;
+    while !rest.is_empty() {
+        let number_len = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len());
+        let value: f64 = rest[..number_len].parse().ok()?;
+        rest = rest[number_len..].trim_start();
+        let unit_len = rest.find(|c: char| c.is_ascii_digit() || c.is_whitespace()).unwrap_or(rest.len());
+        let factor = match &rest[..unit_len] {
+            "ns" => 1e-6,
+            "us" | "µs" | "μs" => 1e-3,
+            "ms" => 1.0,
+            "s" => 1e3,
+            "m" => 60e3,
+            "h" => 3600e3,
+            _ => return None,
+        };
+        total += value * factor;
+        matched = true;
+        rest = rest[unit_len..].trim_start();
+    }
+    matched.then_some(total)
+}
+
+fn step(operation: &str) -> ExecutionPlanStep {
+    ExecutionPlanStep {
+        operation: operation.to_string(),
+        cost: None,
+        rows: None,
+        details: None,
+        children: None,
+    }
+}
+
+/// 按缩进深度组装的计划节点
+struct PlanNode {
+    depth: usize,
+    step: ExecutionPlanStep,
+    attrs: Vec<String>,
+}
+
+impl PlanNode {
+    fn new(depth: usize, step: ExecutionPlanStep) -> Self {
+        Self { depth, step, attrs: Vec::new() }
+    }
+}
+
+/// 把按出现顺序排列的节点按深度组装为树，深度更大的节点是前一个较浅节点的子节点
+fn build_tree(nodes: Vec<PlanNode>) -> Vec<ExecutionPlanStep> {
+    fn attach(roots: &mut Vec<ExecutionPlanStep>, stack: &mut [(usize, ExecutionPlanStep)], child: ExecutionPlanStep) {
+        match stack.last_mut() {
+            Some((_, parent)) => parent.children.get_or_insert_with(Vec::new).push(child),
+            None => roots.push(child),
+        }
+    }
+
+    let mut roots = Vec::new();
+    let mut stack: Vec<(usize, ExecutionPlanStep)> = Vec::new();
+    for node in nodes {
+        let mut step = node.step;
+        if !node.attrs.is_empty() {
+            let attrs = node.attrs.join(", ");
+            step.details = Some(match step.details.take() {
+                Some(details) => format!("{}, {}", details, attrs),
+                None => attrs,
+            });
+        }
+        while stack.last().is_some_and(|(depth, _)| *depth >= node.depth) {
+            let (_, done) = stack.pop().expect("stack is not empty");
+            attach(&mut roots, &mut stack, done);
+        }
+        stack.push((node.depth, step));
+    }
+    while let Some((_, done)) = stack.pop() {
+        attach(&mut roots, &mut stack, done);
+    }
+    roots
+}
+
+fn sum_costs(steps: &[ExecutionPlanStep]) -> Option<f64> {
+    let mut total = None;
+    for step in steps {
+        let own = step.cost;
+        let children = step.children.as_deref().and_then(sum_costs);
+        for cost in [own, children].into_iter().flatten() {
+            *total.get_or_insert(0.0) += cost;
+        }
+    }
+    total
+}
+
+/// InfluxQL EXPLAIN ANALYZE 输出树形文本，EXPLAIN 输出以 EXPRESSION 开头的键值块
+fn parse_influxql_plan(lines: &[String]) -> ExecutionPlan {
+    if lines.iter().any(|l| l.contains("├── ") || l.contains("└── ")) {
+        parse_influxql_analyze(lines)
+    } else {
+        parse_influxql_explain(lines)
+    }
+}
+
+fn parse_influxql_analyze(lines: &[String]) -> ExecutionPlan {
+    let mut nodes: Vec<PlanNode> = Vec::new();
+    let mut labels_depth: Option<usize> = None;
+    let mut total_time = None;
+
+    for line in lines {
+        let Some(connector) = line.find("├── ").or_else(|| line.find("└── ")) else {
+            continue;
+        };
+        // 每层缩进为 "│   " 或 "    "，均为 4 个字符
+        let depth = line[..connector].chars().count() / 4;
+        let text = line[connector..].chars().skip(4).collect::<String>();
+        let text = text.trim();
+
+        if labels_depth.is_some_and(|d| depth <= d) {
+            labels_depth = None;
+        }
+        if text == "labels" {
+            labels_depth = Some(depth);
+            continue;
+        }
+
+        match text.split_once(": ") {
+            Some((key, value)) => {
+                // labels 下的键值归属于 labels 的父节点
+                let effective = labels_depth.unwrap_or(depth);
+                let Some(owner) = nodes.iter_mut().rev().find(|n| n.depth < effective) else {
+                    continue;
+                };
+                match key {
+                    "execution_time" => owner.step.cost = parse_duration_ms(value),
+                    "total_time" if owner.depth == 0 => total_time = parse_duration_ms(value),
+                    _ => {}
+                }
+                owner.attrs.push(format!("{}: {}", key, value));
+            }
+            None => nodes.push(PlanNode::new(depth, step(text))),
+        }
+    }
+
+    let steps = build_tree(nodes);
+    let total_cost = total_time.or_else(|| sum_costs(&steps));
+    ExecutionPlan { steps, total_cost, estimated_rows: None }
+}
+
+fn parse_influxql_explain(lines: &[String]) -> ExecutionPlan {
+    let mut steps: Vec<ExecutionPlanStep> = Vec::new();
+    let mut attrs: Vec<String> = Vec::new();
+
+    // 每个 EXPRESSION 块对应一个迭代器
+    let flush = |attrs: &mut Vec<String>, steps: &mut Vec<ExecutionPlanStep>| {
+        if !attrs.is_empty() {
+            let mut s = step("create_iterator");
+            s.details = Some(attrs.join(", "));
+            steps.push(s);
+            attrs.clear();
+        }
+    };
+
+    for line in lines.iter().map(|l| l.trim()) {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        if key == "EXPRESSION" {
+            flush(&mut attrs, &mut steps);
+        }
+        attrs.push(format!("{}: {}", key, value.trim()));
+    }
+    flush(&mut attrs, &mut steps);
+
+    ExecutionPlan { steps, total_cost: None, estimated_rows: None }
+}
+
+/// 找到与左括号匹配的右括号位置
+fn matching_bracket(text: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in text[open..].char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// 在顶层逗号处切分
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(text[start..].trim());
+    parts.retain(|p| !p.is_empty());
+    parts
+}
+
+/// 解析 DataFusion 计划的一行，如 `AggregateExec: mode=Final, metrics=[output_rows=1, elapsed_compute=12µs]`
+fn parse_datafusion_line(line: &str) -> ExecutionPlanStep {
+    let line = line.trim();
+    let name_end = line.find([':', ',']).unwrap_or(line.len());
+    let mut s = step(line[..name_end].trim());
+    let mut rest = line[name_end..].trim_start_matches([':', ',']).trim().to_string();
+
+    if let Some(start) = rest.find("metrics=[") {
+        let open = start + "metrics=".len();
+        let end = matching_bracket(&rest, open).unwrap_or(rest.len() - 1);
+        let mut elapsed = None;
+        for metric in split_top_level(&rest[open + 1..end]) {
+            match metric.split_once('=') {
+                Some(("output_rows", value)) => s.rows = value.trim().parse().ok(),
+                Some(("elapsed_compute", value)) => elapsed = parse_duration_ms(value),
+                _ => {}
+            }
+        }
+        s.cost = elapsed;
+        let metrics = rest[start..=end].to_string();
+        rest = rest.replacen(&metrics, "", 1).trim().trim_end_matches(',').trim().to_string();
+        rest = if rest.is_empty() { metrics } else { format!("{}, {}", rest, metrics) };
+    }
+    if !rest.is_empty() {
+        s.details = Some(rest);
+    }
+    s
+}
+
+/// DataFusion 返回 plan_type / plan 两列，优先使用带运行指标的物理计划
+fn parse_datafusion_plan(columns: &[String], rows: &[Vec<Value>]) -> ExecutionPlan {
+    let type_index = columns.iter().position(|c| c == "plan_type");
+    let plan_index = columns.iter().position(|c| c == "plan");
+
+    let plans: Vec<(String, String)> = match (type_index, plan_index) {
+        (Some(t), Some(p)) => rows
+            .iter()
+            .map(|row| {
+                let get = |i: usize| row.get(i).map(cell_text).unwrap_or_default();
+                (get(t), get(p))
+            })
+            .collect(),
+        // 没有 plan_type 列时整段文本视为一个计划
+        _ => vec![(String::new(), text_lines(rows).join("\n"))],
+    };
+
+    let preferred = ["Plan with Metrics", "physical_plan", "logical_plan"];
+    let plan_text = preferred
+        .iter()
+        .find_map(|name| plans.iter().find(|(t, _)| t == name))
+        .or_else(|| plans.first())
+        .map(|(_, plan)| plan.as_str())
+        .unwrap_or("");
+
+    let nodes: Vec<PlanNode> = plan_text
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let indent = l.len() - l.trim_start().len();
+            PlanNode::new(indent, parse_datafusion_line(l))
+        })
+        .collect();
+
+    let steps = build_tree(nodes);
+    let total_cost = sum_costs(&steps);
+    let estimated_rows = steps.first().and_then(|s| s.rows);
+    ExecutionPlan { steps, total_cost, estimated_rows }
+}
+
+/// 解析 Flux profiler 输出
+///
+/// 多张表依次返回，后续表的表头和注释行可能混在数据行中，这里逐行识别表头
+fn parse_flux_profile(columns: &[String], rows: &[Vec<Value>]) -> ExecutionPlan {
+    let mut header: Vec<String> = columns.to_vec();
+    let mut query_step: Option<ExecutionPlanStep> = None;
+    let mut total_cost = None;
+    let mut operators = Vec::new();
+
+    for row in rows {
+        let cells: Vec<String> = row.iter().map(cell_text).collect();
+        if cells.first().is_some_and(|c| c.starts_with('#')) {
+            continue;
+        }
+        if cells.iter().any(|c| c == "_measurement") && cells.iter().any(|c| c == "table") {
+            header = cells;
+            continue;
+        }
+
+        let field = |name: &str| header.iter().position(|h| h == name).and_then(|i| row.get(i));
+        let nanos = |name: &str| field(name).and_then(cell_number).map(|ns| ns / 1e6);
+        match field("_measurement").map(cell_text).as_deref() {
+            Some("profiler/query") => {
+                let mut s = step("query");
+                s.cost = nanos("TotalDuration");
+                total_cost = s.cost;
+                let phases: Vec<String> = [
+                    ("compile", "CompileDuration"),
+                    ("queue", "QueueDuration"),
+                    ("plan", "PlanDuration"),
+                    ("execute", "ExecuteDuration"),
+                ]
+                .iter()
+                .filter_map(|(label, column)| nanos(column).map(|ms| format!("{}: {:.3}ms", label, ms)))
+                .collect();
+                if !phases.is_empty() {
+                    s.details = Some(phases.join(", "));
+                }
+                query_step = Some(s);
+            }
+            Some("profiler/operator") => {
+                let mut s = step(&field("Type").map(cell_text).unwrap_or_default());
+                s.cost = nanos("DurationSum");
+                let details: Vec<String> = [("label", "Label"), ("count", "Count")]
+                    .iter()
+                    .filter_map(|(label, column)| field(column).map(|v| format!("{}: {}", label, cell_text(v))))
+                    .collect();
+                if !details.is_empty() {
+                    s.details = Some(details.join(", "));
+                }
+                operators.push(s);
+            }
+            _ => {}
+        }
+    }
+
+    let steps = match query_step {
+        Some(mut query) => {
+            if !operators.is_empty() {
+                query.children = Some(operators);
+            }
+            vec![query]
+        }
+        None => operators,
+    };
+    let total_cost = total_cost.or_else(|| sum_costs(&steps));
+    ExecutionPlan { steps, total_cost, estimated_rows: None }
+}
+
+/// IoTDB EXPLAIN 输出分布式计划框图，EXPLAIN ANALYZE 输出带运行指标的缩进文本
+fn parse_iotdb_plan(lines: &[String]) -> ExecutionPlan {
+    if lines.iter().any(|l| l.contains("[PlanNodeId ") || l.contains("FRAGMENT-INSTANCE")) {
+        parse_iotdb_analyze(lines)
+    } else {
+        parse_iotdb_explain(lines)
+    }
+}
+
+/// 框图中同层节点横向排列，只按自上而下、自左向右的顺序提取节点
+fn parse_iotdb_explain(lines: &[String]) -> ExecutionPlan {
+    let mut steps = Vec::new();
+    for line in lines {
+        for cell in line.split('│').map(str::trim) {
+            let Some((name, id)) = cell.rsplit_once('-') else {
+                continue;
+            };
+            if !name.is_empty()
+                && name.chars().all(|c| c.is_ascii_alphanumeric())
+                && !id.is_empty()
+                && id.chars().all(|c| c.is_ascii_digit())
+            {
+                let mut s = step(name);
+                s.details = Some(format!("PlanNodeId: {}", id));
+                steps.push(s);
+            }
+        }
+    }
+    ExecutionPlan { steps, total_cost: None, estimated_rows: None }
+}
+
+fn parse_iotdb_analyze(lines: &[String]) -> ExecutionPlan {
+    let mut nodes: Vec<PlanNode> = Vec::new();
+    let mut planning = PlanNode::new(0, step("Planning"));
+
+    for line in lines {
+        let text = line.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+
+        if let Some(rest) = text.strip_prefix("FRAGMENT-INSTANCE") {
+            let mut s = step("FRAGMENT-INSTANCE");
+            let details = rest.replace("][", ", ").trim_matches(['[', ']']).to_string();
+            if !details.is_empty() {
+                s.details = Some(details);
+            }
+            nodes.push(PlanNode::new(indent, s));
+        } else if let Some(rest) = text.strip_prefix("[PlanNodeId ") {
+            let (id, operation) = rest.split_once("]:").unwrap_or((rest.trim_end_matches(']'), ""));
+            let mut s = step(operation.trim());
+            s.details = Some(format!("PlanNodeId: {}", id.trim()));
+            nodes.push(PlanNode::new(indent, s));
+        } else {
+            // 第一个分片之前是规划阶段的耗时统计
+            let owner = match nodes.last_mut() {
+                None => &mut planning,
+                Some(node) if indent > node.depth => node,
+                Some(_) => continue,
+            };
+            let (key, value) = text.split_once(':').map_or((text, ""), |(k, v)| (k.trim(), v.trim()));
+            match key {
+                "Analyze Cost" | "Total Wall Time" | "CPU Time" => {
+                    owner.step.cost = value.split_whitespace().next().and_then(|v| v.parse().ok());
+                }
+                "output" => {
+                    owner.step.rows = value.split_whitespace().next().and_then(|v| v.parse().ok());
+                }
+                _ => {}
+            }
+            owner.attrs.push(text.to_string());
+        }
+    }
+
+    let fragment_cost = nodes
+        .iter()
+        .filter(|n| n.step.operation == "FRAGMENT-INSTANCE")
+        .filter_map(|n| n.step.cost)
+        .reduce(|a, b| a + b);
+    let planning_cost = planning.step.cost;
+
+    let mut all = Vec::new();
+    if !planning.attrs.is_empty() {
+        all.push(planning);
+    }
+    all.extend(nodes);
+    let steps = build_tree(all);
+    let total_cost = match (planning_cost, fragment_cost) {
+        (None, None) => None,
+        (a, b) => Some(a.unwrap_or(0.0) + b.unwrap_or(0.0)),
+    };
+    let estimated_rows = steps
+        .iter()
+        .filter(|s| s.operation == "FRAGMENT-INSTANCE")
+        .filter_map(|s| s.children.as_ref()?.first()?.rows)
+        .reduce(|a, b| a + b);
+    ExecutionPlan { steps, total_cost, estimated_rows }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn single_column(name: &str, text: &str) -> QueryResult {
+        let rows = text.lines().map(|l| vec![Value::String(l.to_string())]).collect();
+        QueryResult::new(vec![name.to_string()], rows, 0)
+    }
+
+    #[test]
+    fn test_explain_statement() {
+        assert_eq!(explain_statement(PlanFormat::InfluxQL, "SELECT * FROM cpu;", true), "EXPLAIN ANALYZE SELECT * FROM cpu");
+        assert_eq!(explain_statement(PlanFormat::DataFusion, "explain analyze SELECT 1", false), "EXPLAIN SELECT 1");
+
+        let flux = explain_statement(PlanFormat::FluxProfiler, "import \"strings\"\nfrom(bucket: \"b\") |> range(start: -1h)", true);
+        assert_eq!(
+            flux,
+            "import \"profiler\"\nimport \"strings\"\noption profiler.enabledProfilers = [\"query\", \"operator\"]\n\nfrom(bucket: \"b\") |> range(start: -1h)"
+        );
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration_ms("2.5ms"), Some(2.5));
+        assert_eq!(parse_duration_ms("1m2s"), Some(62_000.0));
+        assert!((parse_duration_ms("13.479µs").unwrap() - 0.013479).abs() < 1e-9);
+        assert_eq!(parse_duration_ms("fast"), None);
+    }
+
+    #[test]
+    fn test_influxql_analyze() {
+        let text = ".
+└── select
+    ├── execution_time: 2.25823ms
+    ├── planning_time: 18.381616ms
+    ├── total_time: 20.639846ms
+    └── field_iterators
+        ├── labels
+        │   └── statement: SELECT max(usage_idle) FROM telegraf.\"default\".cpu
+        └── expression
+            ├── labels
+            │   └── expr: max(usage_idle::float)
+            └── create_iterator
+                ├── labels
+                │   ├── measurement: cpu
+                │   └── shard_id: 608
+                ├── cursors_ref: 779
+                ├── planning_time: 1.401099ms
+                └── execution_time: 13.479µs";
+        let plan = parse_plan(PlanFormat::InfluxQL, &single_column("EXPLAIN ANALYZE", text)).unwrap();
+        assert_eq!(plan.total_cost, Some(20.639846));
+
+        let select = &plan.steps[0];
+        assert_eq!(select.operation, "select");
+        assert_eq!(select.cost, Some(2.25823));
+        let fields = &select.children.as_ref().unwrap()[0];
+        assert!(fields.details.as_deref().unwrap().starts_with("statement: SELECT max(usage_idle)"));
+        let expression = &fields.children.as_ref().unwrap()[0];
+        assert_eq!(expression.details.as_deref(), Some("expr: max(usage_idle::float)"));
+        let iterator = &expression.children.as_ref().unwrap()[0];
+        assert_eq!(iterator.operation, "create_iterator");
+        assert!(iterator.details.as_deref().unwrap().starts_with("measurement: cpu, shard_id: 608, cursors_ref: 779"));
+        assert!((iterator.cost.unwrap() - 0.013479).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_influxql_explain() {
+        let text = "EXPRESSION: max(usage_idle::float)\nNUMBER OF SHARDS: 1\nNUMBER OF SERIES: 9\nEXPRESSION: <nil>\nAUXILIARY FIELDS: value::float";
+        let plan = parse_plan(PlanFormat::InfluxQL, &single_column("QUERY PLAN", text)).unwrap();
+        assert_eq!(plan.steps.len(), 2);
+        assert_eq!(
+            plan.steps[0].details.as_deref(),
+            Some("EXPRESSION: max(usage_idle::float), NUMBER OF SHARDS: 1, NUMBER OF SERIES: 9")
+        );
+    }
+
+    #[test]
+    fn test_datafusion_analyze() {
+        let plan_text = "ProjectionExec: expr=[count(*)@0 as count], metrics=[output_rows=1, elapsed_compute=1.5µs]
+  AggregateExec: mode=Final, gby=[], aggr=[count(*)], metrics=[output_rows=1, elapsed_compute=20µs]
+    CoalescePartitionsExec, metrics=[output_rows=4, elapsed_compute=10µs]
+      ParquetExec: file_groups={4 groups}, metrics=[output_rows=1000, elapsed_compute=2ms]";
+        let result = QueryResult::new(
+            vec!["plan_type".to_string(), "plan".to_string()],
+            vec![vec![Value::String("Plan with Metrics".to_string()), Value::String(plan_text.to_string())]],
+            0,
+        );
+        let plan = parse_plan(PlanFormat::DataFusion, &result).unwrap();
+        assert_eq!(plan.estimated_rows, Some(1));
+        assert!((plan.total_cost.unwrap() - 2.0315).abs() < 1e-9);
+
+        let projection = &plan.steps[0];
+        assert_eq!(projection.operation, "ProjectionExec");
+        let aggregate = &projection.children.as_ref().unwrap()[0];
+        assert_eq!(
+            aggregate.details.as_deref(),
+            Some("mode=Final, gby=[], aggr=[count(*)], metrics=[output_rows=1, elapsed_compute=20µs]")
+        );
+        let coalesce = &aggregate.children.as_ref().unwrap()[0];
+        assert_eq!(coalesce.operation, "CoalescePartitionsExec");
+        let scan = &coalesce.children.as_ref().unwrap()[0];
+        assert_eq!((scan.operation.as_str(), scan.rows, scan.cost), ("ParquetExec", Some(1000), Some(2.0)));
+    }
+
+    #[test]
+    fn test_flux_profile() {
+        let columns = ["", "result", "table", "_measurement", "TotalDuration", "CompileDuration", "ExecuteDuration"]
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        let text = |s: &str| Value::String(s.to_string());
+        let rows = vec![
+            vec![text(""), text("_profiler"), Value::from(0), text("profiler/query"), Value::from(3_000_000), Value::from(500_000), Value::from(2_000_000)],
+            vec![text("#datatype"), text("string")],
+            vec![text(""), text("result"), text("table"), text("_measurement"), text("Type"), text("Label"), text("Count"), text("DurationSum")],
+            vec![text(""), text("_profiler"), Value::from(1), text("profiler/operator"), text("*influxdb.readFilterSource"), text("ReadRange2"), Value::from(1), Value::from(1_200_000)],
+        ];
+        let plan = parse_plan(PlanFormat::FluxProfiler, &QueryResult::new(columns, rows, 0)).unwrap();
+        assert_eq!(plan.total_cost, Some(3.0));
+        let query = &plan.steps[0];
+        assert_eq!(query.details.as_deref(), Some("compile: 0.500ms, execute: 2.000ms"));
+        let operator = &query.children.as_ref().unwrap()[0];
+        assert_eq!(operator.operation, "*influxdb.readFilterSource");
+        assert_eq!(operator.cost, Some(1.2));
+        assert_eq!(operator.details.as_deref(), Some("label: ReadRange2, count: 1"));
+    }
+
+    #[test]
+    fn test_iotdb_analyze() {
+        let text = "Analyze Cost: 24.759 ms
+Fetch Schema Cost: 8.339 ms
+Fragment Instances Count: 1
+
+FRAGMENT-INSTANCE[Id: 20240311_041502_00001_1.2.0][IP: 127.0.0.1][State: FINISHED]
+  Total Wall Time: 25 ms
+  ready queued time: 0.280 ms, blocked queued time: 2.456 ms
+    [PlanNodeId 10]: IdentitySinkNode(IdentitySinkOperator)
+        CPU Time: 0.367 ms
+        output: 1 rows
+      [PlanNodeId 5]: TransformNode(TransformOperator)
+        CPU Time: 0.375 ms
+        output: 1 rows
+        [PlanNodeId 3]: SeriesScanNode(SeriesScanOperator)
+          CPU Time: 1.2 ms
+          output: 120 rows";
+        let plan = parse_plan(PlanFormat::IoTDB, &single_column("Explain Analyze", text)).unwrap();
+        assert_eq!(plan.steps.len(), 2);
+        assert_eq!(plan.steps[0].operation, "Planning");
+        assert_eq!(plan.total_cost, Some(49.759));
+        assert_eq!(plan.estimated_rows, Some(1));
+
+        let fragment = &plan.steps[1];
+        assert_eq!(fragment.cost, Some(25.0));
+        assert!(fragment.details.as_deref().unwrap().starts_with("Id: 20240311_041502_00001_1.2.0, IP: 127.0.0.1"));
+        let sink = &fragment.children.as_ref().unwrap()[0];
+        assert_eq!(sink.operation, "IdentitySinkNode(IdentitySinkOperator)");
+        let transform = &sink.children.as_ref().unwrap()[0];
+        let scan = &transform.children.as_ref().unwrap()[0];
+        assert_eq!((scan.cost, scan.rows), (Some(1.2), Some(120)));
+    }
+
+    #[test]
+    fn test_iotdb_explain() {
+        let text = "┌───────────────────┐
+│IdentitySink-10    │
+│PartitionId: 1     │
+└───────────────────┘
+┌───────────────────┐ ┌───────────────────┐
+│SeriesScan-3       │ │SeriesScan-4       │
+└───────────────────┘ └───────────────────┘";
+        let plan = parse_plan(PlanFormat::IoTDB, &single_column("distribution plan", text)).unwrap();
+        let names: Vec<_> = plan.steps.iter().map(|s| s.operation.as_str()).collect();
+        assert_eq!(names, vec!["IdentitySink", "SeriesScan", "SeriesScan"]);
+        assert_eq!(plan.steps[2].details.as_deref(), Some("PlanNodeId: 4"));
+
+        assert!(parse_plan(PlanFormat::IoTDB, &single_column("x", "")).is_none());
+    }
+}
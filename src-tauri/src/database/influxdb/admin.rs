@@ -0,0 +1,182 @@
+/**
+ * InfluxDB 2.x 管理辅助函数
+ *
+ * 负责用户、组织成员和标签(Label)的请求体构建与响应解析
+ */
+
+use crate::commands::influxdb2::{LabelInfo, OrgMemberInfo, OrganizationInfo, User2xInfo};
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+
+/// InfluxDB 2.x 要求的最短密码长度
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+fn text(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+/// 校验名称（组织、用户、标签）
+pub fn validate_name(name: &str, label: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        Err(anyhow!("{}名称不能为空", label))
+    } else {
+        Ok(())
+    }
+}
+
+/// 校验密码长度
+pub fn validate_password(password: &str) -> Result<()> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        Err(anyhow!("密码长度不能少于 {} 个字符", MIN_PASSWORD_LENGTH))
+    } else {
+        Ok(())
+    }
+}
+
+/// 成员角色对应的 API 路径段
+pub fn member_segment(owner: bool) -> &'static str {
+    if owner {
+        "owners"
+    } else {
+        "members"
+    }
+}
+
+/// 可分配标签的资源类型对应的 API 路径段
+pub fn label_resource_segment(resource_type: &str) -> Result<&'static str> {
+    match resource_type.to_lowercase().as_str() {
+        "bucket" | "buckets" => Ok("buckets"),
+        "task" | "tasks" => Ok("tasks"),
+        "dashboard" | "dashboards" => Ok("dashboards"),
+        other => Err(anyhow!("不支持为 {} 分配标签", other)),
+    }
+}
+
+/// 构建标签属性，颜色需为 #RRGGBB 格式
+pub fn build_label_properties(color: Option<&str>, description: Option<&str>) -> Result<Value> {
+    let mut properties = json!({});
+
+    if let Some(color) = color.filter(|c| !c.is_empty()) {
+        let valid = color.len() == 7
+            && color.starts_with('#')
+            && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+        if !valid {
+            return Err(anyhow!("无效的标签颜色: {}", color));
+        }
+        properties["color"] = json!(color);
+    }
+
+    if let Some(description) = description {
+        properties["description"] = json!(description);
+    }
+
+    Ok(properties)
+}
+
+/// 解析组织响应
+pub fn parse_organization(org: &Value) -> OrganizationInfo {
+    OrganizationInfo {
+        id: text(org, "id").unwrap_or_default(),
+        name: text(org, "name").unwrap_or_default(),
+        description: text(org, "description"),
+        created_at: text(org, "createdAt"),
+        updated_at: text(org, "updatedAt"),
+    }
+}
+
+/// 解析用户响应
+pub fn parse_user(user: &Value) -> User2xInfo {
+    User2xInfo {
+        id: text(user, "id").unwrap_or_default(),
+        name: text(user, "name").unwrap_or_default(),
+        status: text(user, "status").unwrap_or_else(|| "active".to_string()),
+    }
+}
+
+/// 解析组织成员响应
+pub fn parse_member(member: &Value) -> OrgMemberInfo {
+    OrgMemberInfo {
+        id: text(member, "id").unwrap_or_default(),
+        name: text(member, "name").unwrap_or_default(),
+        status: text(member, "status").unwrap_or_else(|| "active".to_string()),
+        role: text(member, "role").unwrap_or_else(|| "member".to_string()),
+    }
+}
+
+/// 解析标签响应
+pub fn parse_label(label: &Value) -> LabelInfo {
+    let properties = label.get("properties").cloned().unwrap_or(Value::Null);
+
+    LabelInfo {
+        id: text(label, "id").unwrap_or_default(),
+        org_id: text(label, "orgID").unwrap_or_default(),
+        name: text(label, "name").unwrap_or_default(),
+        color: text(&properties, "color"),
+        description: text(&properties, "description"),
+    }
+}
+
+/// 解析列表响应中的指定数组
+pub fn parse_list<T>(response: &Value, key: &str, parse: fn(&Value) -> T) -> Vec<T> {
+    response
+        .get(key)
+        .and_then(|v| v.as_array())
+        .map(|items| items.iter().map(parse).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        assert!(validate_name("ops", "组织").is_ok());
+        assert!(validate_name("  ", "组织").is_err());
+        assert!(validate_password("short").is_err());
+        assert!(validate_password("long-enough").is_ok());
+    }
+
+    #[test]
+    fn test_label_resource_segment() {
+        assert_eq!(label_resource_segment("Bucket").unwrap(), "buckets");
+        assert_eq!(label_resource_segment("tasks").unwrap(), "tasks");
+        assert_eq!(label_resource_segment("dashboard").unwrap(), "dashboards");
+        assert!(label_resource_segment("check").is_err());
+        assert_eq!(member_segment(true), "owners");
+    }
+
+    #[test]
+    fn test_build_label_properties() {
+        let properties = build_label_properties(Some("#FF8800"), Some("生产环境")).unwrap();
+        assert_eq!(properties["color"], "#FF8800");
+        assert_eq!(properties["description"], "生产环境");
+        assert!(build_label_properties(Some("red"), None).is_err());
+        assert!(build_label_properties(Some("#GG0000"), None).is_err());
+        assert_eq!(build_label_properties(None, None).unwrap(), json!({}));
+    }
+
+    #[test]
+    fn test_parse_responses() {
+        let response = json!({
+            "users": [{"id": "u1", "name": "alice", "role": "owner"}, {"id": "u2", "name": "bob"}]
+        });
+        let members = parse_list(&response, "users", parse_member);
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[0].role, "owner");
+        assert_eq!(members[1].role, "member");
+        assert_eq!(parse_list(&response, "users", parse_user)[1].status, "active");
+
+        let label = parse_label(&json!({
+            "id": "l1", "orgID": "o1", "name": "prod",
+            "properties": {"color": "#326BBA", "description": "production"}
+        }));
+        assert_eq!(label.color.as_deref(), Some("#326BBA"));
+        assert_eq!(label.description.as_deref(), Some("production"));
+        assert!(parse_list(&json!({}), "labels", parse_label).is_empty());
+
+        let org = parse_organization(&json!({"id": "o1", "name": "ops", "createdAt": "2024-01-01T00:00:00Z"}));
+        assert_eq!(org.name, "ops");
+        assert_eq!(org.description, None);
+    }
+}
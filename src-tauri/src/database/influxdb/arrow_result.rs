@@ -0,0 +1,310 @@
+/**
+ * InfluxDB 3.x Arrow 列式查询结果
+ *
+ * FlightSQL 返回的 RecordBatch 保持列式存储，前端通过 Arrow IPC 获取带类型的列数据，
+ * 只有表格当前可见的一页才会转换为 JSON 行
+ */
+
+use super::capability::DataSet;
+use anyhow::Result;
+use arrow::array::{Array, ArrayRef, AsArray};
+use arrow::datatypes::{DataType, Float64Type, Int64Type, Schema, SchemaRef, TimeUnit, UInt64Type};
+use arrow::ipc::writer::StreamWriter;
+use arrow::record_batch::RecordBatch;
+use arrow::util::display::{ArrayFormatter, FormatOptions};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Arrow 列描述
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArrowColumnInfo {
+    pub name: String,
+    pub data_type: String, // Arrow 类型，如 Timestamp(Nanosecond, None)、Dictionary(Int32, Utf8)
+    pub nullable: bool,
+}
+
+/// 列式查询结果
+#[derive(Debug, Clone)]
+pub struct ArrowResult {
+    schema: SchemaRef,
+    batches: Vec<RecordBatch>,
+    row_count: usize,
+    pub execution_time: Option<u64>,
+}
+
+impl ArrowResult {
+    /// 由 RecordBatch 构建结果；没有批次时 schema 为空
+    pub fn new(schema: Option<SchemaRef>, batches: Vec<RecordBatch>) -> Self {
+        let schema = schema
+            .or_else(|| batches.first().map(|b| b.schema()))
+            .unwrap_or_else(|| Arc::new(Schema::empty()));
+        let row_count = batches.iter().map(|b| b.num_rows()).sum();
+        Self {
+            schema,
+            batches,
+            row_count,
+            execution_time: None,
+        }
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    pub fn batch_count(&self) -> usize {
+        self.batches.len()
+    }
+
+    /// 列描述
+    pub fn columns(&self) -> Vec<ArrowColumnInfo> {
+        self.schema
+            .fields()
+            .iter()
+            .map(|field| ArrowColumnInfo {
+                name: field.name().clone(),
+                data_type: format!("{:?}", field.data_type()),
+                nullable: field.is_nullable(),
+            })
+            .collect()
+    }
+
+    /// 将全部批次编码为一个 Arrow IPC 流
+    pub fn to_ipc(&self) -> Result<Vec<u8>> {
+        encode_ipc(&self.schema, &self.batches)
+    }
+
+    /// 将单个批次编码为独立的 Arrow IPC 流（包含 schema），便于分块推送
+    pub fn batch_ipc(&self, index: usize) -> Result<Vec<u8>> {
+        let batch = self
+            .batches
+            .get(index)
+            .ok_or_else(|| anyhow::anyhow!("批次 {} 不存在", index))?;
+        encode_ipc(&self.schema, std::slice::from_ref(batch))
+    }
+
+    /// 只转换 [offset, offset + limit) 范围内的行
+    pub fn page(&self, offset: usize, limit: usize) -> Result<DataSet> {
+        let columns: Vec<String> = self.schema.fields().iter().map(|f| f.name().clone()).collect();
+        let end = offset.saturating_add(limit).min(self.row_count);
+        let mut rows = Vec::with_capacity(end.saturating_sub(offset));
+
+        let mut batch_start = 0;
+        for batch in &self.batches {
+            let batch_end = batch_start + batch.num_rows();
+            if batch_end <= offset {
+                batch_start = batch_end;
+                continue;
+            }
+            if batch_start >= end {
+                break;
+            }
+
+            let slice_start = offset.saturating_sub(batch_start);
+            let slice_len = end.min(batch_end) - batch_start - slice_start;
+            let slice = batch.slice(slice_start, slice_len);
+
+            let converted = slice
+                .columns()
+                .iter()
+                .map(array_to_json)
+                .collect::<Result<Vec<_>>>()?;
+            for row in 0..slice_len {
+                rows.push(converted.iter().map(|column| column[row].clone()).collect());
+            }
+
+            batch_start = batch_end;
+        }
+
+        let mut dataset = DataSet::new(columns, rows);
+        dataset.execution_time = self.execution_time;
+        Ok(dataset)
+    }
+
+    /// 转换全部行（仅用于兼容原有 DataSet 接口）
+    pub fn to_dataset(&self) -> Result<DataSet> {
+        self.page(0, self.row_count)
+    }
+}
+
+fn encode_ipc(schema: &SchemaRef, batches: &[RecordBatch]) -> Result<Vec<u8>> {
+    let mut writer = StreamWriter::try_new(Vec::new(), schema)
+        .map_err(|e| anyhow::anyhow!("创建 Arrow IPC 写入器失败: {}", e))?;
+    for batch in batches {
+        writer
+            .write(batch)
+            .map_err(|e| anyhow::anyhow!("写入 Arrow IPC 失败: {}", e))?;
+    }
+    writer
+        .finish()
+        .map_err(|e| anyhow::anyhow!("完成 Arrow IPC 编码失败: {}", e))?;
+    writer
+        .into_inner()
+        .map_err(|e| anyhow::anyhow!("完成 Arrow IPC 编码失败: {}", e))
+}
+
+fn float_to_json(value: f64) -> Value {
+    // NaN / Infinity 无法用 JSON 表示
+    serde_json::Number::from_f64(value).map(Value::Number).unwrap_or(Value::Null)
+}
+
+/// 将整列转换为 JSON 值；数值、布尔和字符串保持原类型，
+/// 时间戳转为纳秒整数，字典列先解码为值类型，Decimal 等其余类型使用 Arrow 的文本格式
+pub fn array_to_json(array: &ArrayRef) -> Result<Vec<Value>> {
+    let cast = |to: &DataType| {
+        arrow::compute::cast(array, to).map_err(|e| anyhow::anyhow!("转换 {} 列失败: {}", array.data_type(), e))
+    };
+
+    let values = match array.data_type() {
+        DataType::Null => vec![Value::Null; array.len()],
+        DataType::Boolean => array
+            .as_boolean()
+            .iter()
+            .map(|v| v.map(Value::Bool).unwrap_or(Value::Null))
+            .collect(),
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => {
+            let array = cast(&DataType::Int64)?;
+            array
+                .as_primitive::<Int64Type>()
+                .iter()
+                .map(|v| v.map(Value::from).unwrap_or(Value::Null))
+                .collect()
+        }
+        DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => {
+            let array = cast(&DataType::UInt64)?;
+            array
+                .as_primitive::<UInt64Type>()
+                .iter()
+                .map(|v| v.map(Value::from).unwrap_or(Value::Null))
+                .collect()
+        }
+        DataType::Float16 | DataType::Float32 | DataType::Float64 => {
+            let array = cast(&DataType::Float64)?;
+            array
+                .as_primitive::<Float64Type>()
+                .iter()
+                .map(|v| v.map(float_to_json).unwrap_or(Value::Null))
+                .collect()
+        }
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => {
+            let array = cast(&DataType::Utf8)?;
+            array
+                .as_string::<i32>()
+                .iter()
+                .map(|v| v.map(|s| Value::String(s.to_string())).unwrap_or(Value::Null))
+                .collect()
+        }
+        DataType::Timestamp(_, _) => {
+            // 与行式接口保持一致：时间戳统一为纳秒整数
+            let nanos = cast(&DataType::Timestamp(TimeUnit::Nanosecond, None))?;
+            let array = arrow::compute::cast(&nanos, &DataType::Int64)
+                .map_err(|e| anyhow::anyhow!("转换时间戳列失败: {}", e))?;
+            array
+                .as_primitive::<Int64Type>()
+                .iter()
+                .map(|v| v.map(Value::from).unwrap_or(Value::Null))
+                .collect()
+        }
+        DataType::Dictionary(_, value_type) => {
+            let decoded = cast(value_type)?;
+            array_to_json(&decoded)?
+        }
+        _ => {
+            let formatter = ArrayFormatter::try_new(array.as_ref(), &FormatOptions::default())
+                .map_err(|e| anyhow::anyhow!("格式化 {} 列失败: {}", array.data_type(), e))?;
+            (0..array.len())
+                .map(|i| {
+                    if array.is_null(i) {
+                        Value::Null
+                    } else {
+                        Value::String(formatter.value(i).to_string())
+                    }
+                })
+                .collect()
+        }
+    };
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{
+        Decimal128Array, DictionaryArray, Float64Array, Int64Array, StringArray, TimestampNanosecondArray,
+    };
+    use arrow::datatypes::{Field, Int32Type};
+    use serde_json::json;
+
+    fn sample_batch(start: i64, hosts: Vec<&str>) -> RecordBatch {
+        let len = hosts.len() as i64;
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("time", DataType::Timestamp(TimeUnit::Nanosecond, None), false),
+            Field::new("host", DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)), true),
+            Field::new("usage", DataType::Float64, true),
+        ]));
+        let time = TimestampNanosecondArray::from((start..start + len).map(|i| i * 1_000_000_000).collect::<Vec<_>>());
+        let host: DictionaryArray<Int32Type> = hosts.into_iter().collect();
+        let usage = Float64Array::from((start..start + len).map(|i| if i == 1 { None } else { Some(i as f64 / 2.0) }).collect::<Vec<_>>());
+        RecordBatch::try_new(schema, vec![Arc::new(time), Arc::new(host), Arc::new(usage)]).unwrap()
+    }
+
+    #[test]
+    fn test_page_spans_batches() {
+        let result = ArrowResult::new(None, vec![sample_batch(0, vec!["a", "b", "a"]), sample_batch(3, vec!["c", "d"])]);
+        assert_eq!(result.row_count(), 5);
+        assert_eq!(result.columns()[1].data_type, "Dictionary(Int32, Utf8)");
+
+        let page = result.page(2, 2).unwrap();
+        assert_eq!(page.columns, vec!["time", "host", "usage"]);
+        assert_eq!(page.row_count, 2);
+        assert_eq!(page.rows[0][0], json!(2_000_000_000i64));
+        assert_eq!(page.rows[0][1], json!("a"));
+        assert_eq!(page.rows[1][1], json!("c"));
+        assert_eq!(page.rows[1][2], json!(1.5));
+
+        assert_eq!(result.page(1, 1).unwrap().rows[0][2], Value::Null);
+        assert_eq!(result.page(10, 5).unwrap().row_count, 0);
+        assert_eq!(result.to_dataset().unwrap().row_count, 5);
+    }
+
+    #[test]
+    fn test_ipc_round_trip() {
+        let result = ArrowResult::new(None, vec![sample_batch(0, vec!["a", "b"]), sample_batch(2, vec!["c"])]);
+        let bytes = result.to_ipc().unwrap();
+        let reader = arrow::ipc::reader::StreamReader::try_new(std::io::Cursor::new(bytes), None).unwrap();
+        let batches: Vec<RecordBatch> = reader.collect::<std::result::Result<_, _>>().unwrap();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].schema(), result.schema);
+
+        let single = result.batch_ipc(1).unwrap();
+        let reader = arrow::ipc::reader::StreamReader::try_new(std::io::Cursor::new(single), None).unwrap();
+        assert_eq!(reader.map(|b| b.unwrap().num_rows()).sum::<usize>(), 1);
+        assert_eq!(result.batch_count(), 2);
+        assert!(result.batch_ipc(2).is_err());
+    }
+
+    #[test]
+    fn test_array_to_json_types() {
+        let ints: ArrayRef = Arc::new(Int64Array::from(vec![Some(1), None]));
+        assert_eq!(array_to_json(&ints).unwrap(), vec![json!(1), Value::Null]);
+
+        let strings: ArrayRef = Arc::new(StringArray::from(vec![Some("x"), None]));
+        assert_eq!(array_to_json(&strings).unwrap(), vec![json!("x"), Value::Null]);
+
+        let decimals: ArrayRef = Arc::new(
+            Decimal128Array::from(vec![Some(12345), None])
+                .with_precision_and_scale(10, 2)
+                .unwrap(),
+        );
+        assert_eq!(array_to_json(&decimals).unwrap(), vec![json!("123.45"), Value::Null]);
+
+        let nan: ArrayRef = Arc::new(Float64Array::from(vec![f64::NAN]));
+        assert_eq!(array_to_json(&nan).unwrap(), vec![Value::Null]);
+
+        let empty = ArrowResult::new(None, vec![]);
+        assert_eq!(empty.row_count(), 0);
+        assert!(empty.columns().is_empty());
+        assert_eq!(empty.page(0, 10).unwrap().row_count, 0);
+    }
+}
@@ -0,0 +1,147 @@
+/**
+ * InfluxDB 2.x 授权(Authorization)辅助函数
+ *
+ * 负责 API 令牌权限的构建、响应解析和令牌脱敏
+ */
+
+use crate::commands::influxdb2::{AuthorizationInfo, PermissionInfo};
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+
+fn text(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+/// 令牌脱敏，仅保留首尾各 4 个字符
+pub fn mask_token(token: &str) -> String {
+    let chars: Vec<char> = token.chars().collect();
+    if chars.len() <= 12 {
+        return "*".repeat(chars.len());
+    }
+
+    let head: String = chars[..4].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}…{}", head, tail)
+}
+
+/// 构建存储桶范围的读写权限列表
+pub fn build_bucket_permissions(org_id: &str, bucket_ids: &[String], read: bool, write: bool) -> Result<Vec<Value>> {
+    if bucket_ids.is_empty() {
+        return Err(anyhow!("至少需要选择一个存储桶"));
+    }
+    if !read && !write {
+        return Err(anyhow!("至少需要读或写权限之一"));
+    }
+
+    let actions = [("read", read), ("write", write)];
+    Ok(bucket_ids
+        .iter()
+        .flat_map(|bucket_id| {
+            actions.iter().filter(|(_, enabled)| *enabled).map(move |(action, _)| {
+                json!({
+                    "action": action,
+                    "resource": {"type": "buckets", "id": bucket_id, "orgID": org_id},
+                })
+            })
+        })
+        .collect())
+}
+
+/// 解析授权响应；`reveal_token` 为 true 时返回完整令牌（仅用于刚创建的令牌）
+pub fn parse_authorization(authorization: &Value, current_token: Option<&str>, reveal_token: bool) -> AuthorizationInfo {
+    let token = text(authorization, "token");
+
+    let permissions = authorization
+        .get("permissions")
+        .and_then(|p| p.as_array())
+        .map(|permissions| {
+            permissions
+                .iter()
+                .map(|permission| {
+                    let resource = permission.get("resource").cloned().unwrap_or(Value::Null);
+                    PermissionInfo {
+                        action: text(permission, "action").unwrap_or_default(),
+                        resource_type: text(&resource, "type").unwrap_or_default(),
+                        resource_id: text(&resource, "id"),
+                        resource_name: text(&resource, "name"),
+                        org_id: text(&resource, "orgID"),
+                        org_name: text(&resource, "org"),
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let is_current = matches!((&token, current_token), (Some(token), Some(current)) if token == current);
+
+    AuthorizationInfo {
+        id: text(authorization, "id").unwrap_or_default(),
+        description: text(authorization, "description"),
+        status: text(authorization, "status").unwrap_or_else(|| "active".to_string()),
+        org_id: text(authorization, "orgID").unwrap_or_default(),
+        org_name: text(authorization, "org"),
+        user_id: text(authorization, "userID"),
+        user_name: text(authorization, "user"),
+        token: token.map(|t| if reveal_token { t } else { mask_token(&t) }),
+        is_current,
+        permissions,
+        created_at: text(authorization, "createdAt"),
+        updated_at: text(authorization, "updatedAt"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_token() {
+        assert_eq!(mask_token("short"), "*****");
+        assert_eq!(mask_token("abcdefghijklmnopqrstuvwxyz=="), "abcd…yz==");
+    }
+
+    #[test]
+    fn test_build_bucket_permissions() {
+        let buckets = vec!["b1".to_string(), "b2".to_string()];
+        let permissions = build_bucket_permissions("org1", &buckets, true, true).unwrap();
+        assert_eq!(permissions.len(), 4);
+        assert_eq!(permissions[0]["action"], "read");
+        assert_eq!(permissions[1]["action"], "write");
+        assert_eq!(permissions[2]["resource"]["id"], "b2");
+        assert_eq!(permissions[2]["resource"]["orgID"], "org1");
+
+        let read_only = build_bucket_permissions("org1", &buckets, true, false).unwrap();
+        assert_eq!(read_only.len(), 2);
+
+        assert!(build_bucket_permissions("org1", &[], true, true).is_err());
+        assert!(build_bucket_permissions("org1", &buckets, false, false).is_err());
+    }
+
+    #[test]
+    fn test_parse_authorization() {
+        let value = json!({
+            "id": "a1",
+            "token": "abcdefghijklmnopqrstuvwxyz==",
+            "status": "inactive",
+            "orgID": "org1",
+            "org": "my-org",
+            "user": "admin",
+            "permissions": [
+                {"action": "read", "resource": {"type": "buckets", "id": "b1", "name": "telegraf", "orgID": "org1"}},
+                {"action": "write", "resource": {"type": "orgs"}}
+            ]
+        });
+
+        let parsed = parse_authorization(&value, Some("abcdefghijklmnopqrstuvwxyz=="), false);
+        assert!(parsed.is_current);
+        assert_eq!(parsed.status, "inactive");
+        assert_eq!(parsed.token.as_deref(), Some("abcd…yz=="));
+        assert_eq!(parsed.permissions.len(), 2);
+        assert_eq!(parsed.permissions[0].resource_name.as_deref(), Some("telegraf"));
+        assert_eq!(parsed.permissions[1].resource_id, None);
+
+        let other = parse_authorization(&value, Some("another"), true);
+        assert!(!other.is_current);
+        assert_eq!(other.token.as_deref(), Some("abcdefghijklmnopqrstuvwxyz=="));
+    }
+}
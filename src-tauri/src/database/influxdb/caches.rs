@@ -0,0 +1,326 @@
+/**
+ * InfluxDB 3.x 最新值缓存(Last Value Cache)与去重值缓存(Distinct Value Cache)辅助函数
+ *
+ * 负责缓存管理接口请求体构建、system.last_caches / system.distinct_caches 系统表解析，
+ * 以及 last_cache() / distinct_cache() 查询语句构建
+ */
+
+use crate::commands::influxdb3::{
+    CreateDistinctCacheRequest, CreateLastCacheRequest, DistinctCacheInfo, LastCacheInfo,
+};
+use crate::database::influxdb::DataSet;
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+
+/// 最新值缓存管理接口
+pub const LAST_CACHE_PATH: &str = "/api/v3/configure/last_cache";
+
+/// 去重值缓存管理接口
+pub const DISTINCT_CACHE_PATH: &str = "/api/v3/configure/distinct_cache";
+
+/// 为 SQL 字符串字面量加单引号
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+fn validate_target(database: &str, table: &str) -> Result<()> {
+    if database.trim().is_empty() {
+        return Err(anyhow!("数据库名称不能为空"));
+    }
+    if table.trim().is_empty() {
+        return Err(anyhow!("表名不能为空"));
+    }
+    Ok(())
+}
+
+/// 构建创建最新值缓存的请求体
+pub fn build_last_cache_body(request: &CreateLastCacheRequest) -> Result<Value> {
+    validate_target(&request.database, &request.table)?;
+    if request.count == Some(0) {
+        return Err(anyhow!("缓存条数必须大于 0"));
+    }
+    if let Some(values) = &request.value_columns {
+        if let Some(column) = values.iter().find(|c| request.key_columns.contains(c)) {
+            return Err(anyhow!("列 {} 不能同时作为键列和值列", column));
+        }
+    }
+
+    let mut body = json!({
+        "db": request.database,
+        "table": request.table,
+    });
+    if let Some(name) = request.name.as_ref().filter(|n| !n.is_empty()) {
+        body["name"] = json!(name);
+    }
+    if !request.key_columns.is_empty() {
+        body["key_columns"] = json!(request.key_columns);
+    }
+    if let Some(values) = request.value_columns.as_ref().filter(|v| !v.is_empty()) {
+        body["value_columns"] = json!(values);
+    }
+    if let Some(count) = request.count {
+        body["count"] = json!(count);
+    }
+    if let Some(ttl) = request.ttl_seconds {
+        body["ttl"] = json!(ttl);
+    }
+    Ok(body)
+}
+
+/// 构建创建去重值缓存的请求体
+pub fn build_distinct_cache_body(request: &CreateDistinctCacheRequest) -> Result<Value> {
+    validate_target(&request.database, &request.table)?;
+    if request.columns.is_empty() {
+        return Err(anyhow!("去重值缓存至少需要一列"));
+    }
+    if request.max_cardinality == Some(0) {
+        return Err(anyhow!("最大基数必须大于 0"));
+    }
+
+    let mut body = json!({
+        "db": request.database,
+        "table": request.table,
+        "columns": request.columns,
+    });
+    if let Some(name) = request.name.as_ref().filter(|n| !n.is_empty()) {
+        body["name"] = json!(name);
+    }
+    if let Some(max_cardinality) = request.max_cardinality {
+        body["max_cardinality"] = json!(max_cardinality);
+    }
+    if let Some(max_age) = request.max_age_seconds {
+        body["max_age"] = json!(max_age);
+    }
+    Ok(body)
+}
+
+/// 构建删除缓存的查询参数
+pub fn build_delete_query(database: &str, table: &str, name: &str) -> Result<String> {
+    validate_target(database, table)?;
+    if name.trim().is_empty() {
+        return Err(anyhow!("缓存名称不能为空"));
+    }
+    Ok(format!(
+        "?db={}&table={}&name={}",
+        urlencoding::encode(database),
+        urlencoding::encode(table),
+        urlencoding::encode(name)
+    ))
+}
+
+/// 构建查询缓存定义系统表的 SQL，可按表过滤
+pub fn build_list_query(system_table: &str, table: Option<&str>) -> String {
+    match table.filter(|t| !t.is_empty()) {
+        Some(table) => format!(
+            "SELECT * FROM system.{} WHERE \"table\" = {} ORDER BY name",
+            system_table,
+            quote_literal(table)
+        ),
+        None => format!("SELECT * FROM system.{} ORDER BY \"table\", name", system_table),
+    }
+}
+
+/// 构建读取最新值缓存的 SQL；未指定缓存名称时由服务器选择表上唯一的缓存
+pub fn build_last_cache_query(table: &str, name: Option<&str>) -> String {
+    build_cache_function_query("last_cache", table, name)
+}
+
+/// 构建读取去重值缓存的 SQL
+pub fn build_distinct_cache_query(table: &str, name: Option<&str>) -> String {
+    build_cache_function_query("distinct_cache", table, name)
+}
+
+fn build_cache_function_query(function: &str, table: &str, name: Option<&str>) -> String {
+    match name.filter(|n| !n.is_empty()) {
+        Some(name) => format!("SELECT * FROM {}({}, {})", function, quote_literal(table), quote_literal(name)),
+        None => format!("SELECT * FROM {}({})", function, quote_literal(table)),
+    }
+}
+
+fn cell<'a>(dataset: &DataSet, row: &'a [Value], column: &str) -> Option<&'a Value> {
+    let index = dataset.columns.iter().position(|c| c == column)?;
+    row.get(index).filter(|v| !v.is_null())
+}
+
+fn cell_string(dataset: &DataSet, row: &[Value], column: &str) -> Option<String> {
+    cell(dataset, row, column).map(|v| match v {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    })
+}
+
+fn cell_u64(dataset: &DataSet, row: &[Value], column: &str) -> Option<u64> {
+    cell(dataset, row, column).and_then(|v| match v {
+        Value::String(s) => s.parse().ok(),
+        other => other.as_u64(),
+    })
+}
+
+/// 读取列名列表；HTTP 接口返回 JSON 数组，FlightSQL 驱动可能返回 `[a, b]` 形式的字符串
+fn cell_list(dataset: &DataSet, row: &[Value], column: &str) -> Vec<String> {
+    match cell(dataset, row, column) {
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|v| v.as_str().map(|s| s.to_string()).unwrap_or_else(|| v.to_string()))
+            .collect(),
+        Some(Value::String(s)) => s
+            .trim()
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .split(',')
+            .map(|item| item.trim().trim_matches('"').to_string())
+            .filter(|item| !item.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// 解析 system.last_caches 查询结果
+pub fn parse_last_caches(database: &str, dataset: &DataSet) -> Vec<LastCacheInfo> {
+    dataset
+        .rows
+        .iter()
+        .filter_map(|row| {
+            Some(LastCacheInfo {
+                database: database.to_string(),
+                table: cell_string(dataset, row, "table")?,
+                name: cell_string(dataset, row, "name")?,
+                key_columns: cell_list(dataset, row, "key_column_names"),
+                value_columns: cell_list(dataset, row, "value_column_names"),
+                count: cell_u64(dataset, row, "count").unwrap_or(1),
+                ttl_seconds: cell_u64(dataset, row, "ttl"),
+            })
+        })
+        .collect()
+}
+
+/// 解析 system.distinct_caches 查询结果
+pub fn parse_distinct_caches(database: &str, dataset: &DataSet) -> Vec<DistinctCacheInfo> {
+    dataset
+        .rows
+        .iter()
+        .filter_map(|row| {
+            Some(DistinctCacheInfo {
+                database: database.to_string(),
+                table: cell_string(dataset, row, "table")?,
+                name: cell_string(dataset, row, "name")?,
+                columns: cell_list(dataset, row, "column_names"),
+                max_cardinality: cell_u64(dataset, row, "max_cardinality"),
+                max_age_seconds: cell_u64(dataset, row, "max_age_seconds"),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns(names: &[&str]) -> Vec<String> {
+        names.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn test_build_last_cache_body() {
+        let mut request = CreateLastCacheRequest {
+            database: "metrics".to_string(),
+            table: "cpu".to_string(),
+            name: Some("cpu_by_host".to_string()),
+            key_columns: vec!["host".to_string()],
+            value_columns: Some(vec!["usage".to_string()]),
+            count: Some(5),
+            ttl_seconds: Some(3600),
+        };
+        assert_eq!(
+            build_last_cache_body(&request).unwrap(),
+            json!({"db": "metrics", "table": "cpu", "name": "cpu_by_host", "key_columns": ["host"], "value_columns": ["usage"], "count": 5, "ttl": 3600})
+        );
+
+        request.value_columns = Some(vec!["host".to_string()]);
+        assert!(build_last_cache_body(&request).is_err());
+
+        request.value_columns = None;
+        request.name = None;
+        request.key_columns.clear();
+        request.count = None;
+        request.ttl_seconds = None;
+        assert_eq!(build_last_cache_body(&request).unwrap(), json!({"db": "metrics", "table": "cpu"}));
+
+        request.count = Some(0);
+        assert!(build_last_cache_body(&request).is_err());
+    }
+
+    #[test]
+    fn test_build_distinct_cache_body() {
+        let mut request = CreateDistinctCacheRequest {
+            database: "metrics".to_string(),
+            table: "cpu".to_string(),
+            name: None,
+            columns: vec!["region".to_string(), "host".to_string()],
+            max_cardinality: Some(10_000),
+            max_age_seconds: Some(86_400),
+        };
+        assert_eq!(
+            build_distinct_cache_body(&request).unwrap(),
+            json!({"db": "metrics", "table": "cpu", "columns": ["region", "host"], "max_cardinality": 10000, "max_age": 86400})
+        );
+
+        request.columns.clear();
+        assert!(build_distinct_cache_body(&request).is_err());
+        request.columns = vec!["host".to_string()];
+        request.table = " ".to_string();
+        assert!(build_distinct_cache_body(&request).is_err());
+    }
+
+    #[test]
+    fn test_build_queries() {
+        assert_eq!(
+            build_delete_query("metrics", "cpu load", "c1").unwrap(),
+            "?db=metrics&table=cpu%20load&name=c1"
+        );
+        assert!(build_delete_query("metrics", "cpu", "").is_err());
+        assert_eq!(
+            build_list_query("last_caches", Some("o'brien")),
+            "SELECT * FROM system.last_caches WHERE \"table\" = 'o''brien' ORDER BY name"
+        );
+        assert_eq!(
+            build_list_query("distinct_caches", None),
+            "SELECT * FROM system.distinct_caches ORDER BY \"table\", name"
+        );
+        assert_eq!(
+            build_last_cache_query("cpu", Some("cpu_by_host")),
+            "SELECT * FROM last_cache('cpu', 'cpu_by_host')"
+        );
+        assert_eq!(build_distinct_cache_query("cpu", None), "SELECT * FROM distinct_cache('cpu')");
+    }
+
+    #[test]
+    fn test_parse_caches() {
+        let last = DataSet::new(
+            columns(&["table", "name", "key_column_ids", "key_column_names", "value_column_ids", "value_column_names", "count", "ttl"]),
+            vec![
+                vec![json!("cpu"), json!("cpu_host_last_cache"), json!([1]), json!(["host"]), Value::Null, Value::Null, json!(1), json!(14400)],
+                vec![json!("mem"), json!("mem_cache"), json!("[2, 3]"), json!("[region, host]"), json!("[4]"), json!("[used]"), json!(3), json!(60)],
+            ],
+        );
+        let caches = parse_last_caches("metrics", &last);
+        assert_eq!(caches.len(), 2);
+        assert_eq!(caches[0].key_columns, vec!["host"]);
+        assert!(caches[0].value_columns.is_empty());
+        assert_eq!(caches[0].ttl_seconds, Some(14400));
+        assert_eq!(caches[1].key_columns, vec!["region", "host"]);
+        assert_eq!(caches[1].value_columns, vec!["used"]);
+        assert_eq!(caches[1].count, 3);
+        assert_eq!(caches[1].database, "metrics");
+
+        let distinct = DataSet::new(
+            columns(&["table", "name", "column_ids", "column_names", "max_cardinality", "max_age_seconds"]),
+            vec![vec![json!("cpu"), json!("cpu_region_host_distinct_cache"), json!([0, 1]), json!(["region", "host"]), json!(100000), json!(86400)]],
+        );
+        let caches = parse_distinct_caches("metrics", &distinct);
+        assert_eq!(caches.len(), 1);
+        assert_eq!(caches[0].columns, vec!["region", "host"]);
+        assert_eq!(caches[0].max_cardinality, Some(100_000));
+        assert_eq!(caches[0].max_age_seconds, Some(86_400));
+    }
+}
@@ -5,6 +5,7 @@
  */
 
 use serde::{Deserialize, Serialize};
+use crate::models::QueryParams;
 
 /// InfluxDB 能力描述
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -147,6 +148,7 @@ pub struct Query {
     pub text: String,
     pub database: Option<String>,
     pub timeout: Option<u64>,
+    pub params: QueryParams, // $name 占位符对应的参数，由驱动按协议绑定
 }
 
 impl Query {
@@ -156,6 +158,7 @@ impl Query {
             text,
             database: None,
             timeout: None,
+            params: QueryParams::new(),
         }
     }
     
@@ -168,6 +171,11 @@ impl Query {
         self.timeout = Some(timeout);
         self
     }
+
+    pub fn with_params(mut self, params: QueryParams) -> Self {
+        self.params = params;
+        self
+    }
 }
 
 /// 存储桶信息（用于写入操作）
@@ -0,0 +1,332 @@
+/**
+ * InfluxDB 1.x 连续查询(Continuous Query)辅助函数
+ *
+ * 负责 CREATE/DROP CONTINUOUS QUERY 语句构建、SHOW CONTINUOUS QUERIES 结果解析，
+ * 以及按时间窗口分段回填(backfill)历史数据的语句生成
+ */
+
+use crate::commands::influxdb1::{CreateInfluxContinuousQueryRequest, InfluxContinuousQueryInfo};
+use crate::database::influxdb::influxql::{find_keyword, parse_duration_secs, quote_identifier, InfluxQLSeries};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+
+/// 回填时默认的分段时长
+pub const DEFAULT_BACKFILL_CHUNK: &str = "1d";
+
+/// 提取 BEGIN ... END 之间的查询体
+fn extract_body(statement: &str) -> Option<String> {
+    let begin = find_keyword(statement, "BEGIN")?;
+    let rest = &statement[begin + "BEGIN".len()..];
+    let end = rest.to_ascii_uppercase().rfind("END").unwrap_or(rest.len());
+    Some(rest[..end].trim().to_string())
+}
+
+/// 提取 RESAMPLE 子句中指定关键字后的时长
+fn extract_resample(header: &str, keyword: &str) -> Option<String> {
+    let resample = find_keyword(header, "RESAMPLE")?;
+    let clause = &header[resample..];
+    let pos = find_keyword(clause, keyword)?;
+    clause[pos + keyword.len()..]
+        .split_whitespace()
+        .next()
+        .map(|s| s.to_string())
+}
+
+/// 提取 GROUP BY time(...) 的分组间隔
+pub fn extract_group_by_interval(select: &str) -> Option<String> {
+    let group_by = find_keyword(select, "GROUP BY")?;
+    let clause = &select[group_by..];
+    let time = find_keyword(clause, "time")?;
+    let args = clause[time + "time".len()..].trim_start().strip_prefix('(')?;
+    let end = args.find(')')?;
+    args[..end].split(',').next().map(|s| s.trim().to_string())
+}
+
+/// 提取 INTO 目标
+fn extract_target(select: &str) -> Option<String> {
+    let into = find_keyword(select, "INTO")?;
+    let rest = &select[into + "INTO".len()..];
+    let end = find_keyword(rest, "FROM").unwrap_or(rest.len());
+    Some(rest[..end].trim().to_string()).filter(|s| !s.is_empty())
+}
+
+/// 从完整的 CREATE CONTINUOUS QUERY 语句解析连续查询信息
+pub fn parse_continuous_query(database: &str, name: &str, statement: &str) -> InfluxContinuousQueryInfo {
+    let header = find_keyword(statement, "BEGIN")
+        .map(|pos| &statement[..pos])
+        .unwrap_or(statement);
+    let select = extract_body(statement).unwrap_or_default();
+
+    InfluxContinuousQueryInfo {
+        name: name.to_string(),
+        database: database.to_string(),
+        resample_every: extract_resample(header, "EVERY"),
+        resample_for: extract_resample(header, "FOR"),
+        group_by_interval: extract_group_by_interval(&select),
+        target: extract_target(&select),
+        select,
+        query: statement.to_string(),
+    }
+}
+
+/// 解析 SHOW CONTINUOUS QUERIES 结果，每个 series 对应一个数据库
+pub fn parse_continuous_queries(series: &[InfluxQLSeries]) -> Vec<InfluxContinuousQueryInfo> {
+    series
+        .iter()
+        .flat_map(|s| {
+            let database = s.name.clone().unwrap_or_default();
+            s.values.iter().filter_map(move |row| {
+                let name = s.string(row, "name")?;
+                let query = s.string(row, "query").unwrap_or_default();
+                Some(parse_continuous_query(&database, &name, &query))
+            })
+        })
+        .collect()
+}
+
+/// 校验连续查询体：必须是带 INTO 和 GROUP BY time() 的 SELECT 语句
+pub fn validate_select(select: &str) -> Result<String> {
+    let select = select.trim().trim_end_matches(';').trim();
+    if !select.to_uppercase().starts_with("SELECT") || find_keyword(select, "INTO").is_none() {
+        return Err(anyhow!("连续查询体必须是 SELECT ... INTO ... 语句"));
+    }
+    if find_keyword(select, "FROM").is_none() {
+        return Err(anyhow!("连续查询体缺少 FROM 子句"));
+    }
+    match extract_group_by_interval(select) {
+        Some(interval) => {
+            parse_duration_secs(&interval)?;
+        }
+        None => return Err(anyhow!("连续查询体必须包含 GROUP BY time(...) 子句")),
+    }
+    Ok(select.to_string())
+}
+
+/// 构建 CREATE CONTINUOUS QUERY 语句
+pub fn build_create_statement(request: &CreateInfluxContinuousQueryRequest) -> Result<String> {
+    if request.name.trim().is_empty() {
+        return Err(anyhow!("连续查询名称不能为空"));
+    }
+    if request.database.trim().is_empty() {
+        return Err(anyhow!("数据库名称不能为空"));
+    }
+    let select = validate_select(&request.select)?;
+
+    let every = request.resample_every.as_deref().filter(|s| !s.is_empty());
+    let for_ = request.resample_for.as_deref().filter(|s| !s.is_empty());
+
+    let mut statement = format!(
+        "CREATE CONTINUOUS QUERY {} ON {}",
+        quote_identifier(&request.name),
+        quote_identifier(&request.database)
+    );
+
+    if every.is_some() || for_.is_some() {
+        statement.push_str(" RESAMPLE");
+        if let Some(every) = every {
+            parse_duration_secs(every).map_err(|e| anyhow!("RESAMPLE EVERY {}", e))?;
+            statement.push_str(&format!(" EVERY {}", every));
+        }
+        if let Some(for_) = for_ {
+            let for_secs = parse_duration_secs(for_).map_err(|e| anyhow!("RESAMPLE FOR {}", e))?;
+            let interval = extract_group_by_interval(&select).unwrap_or_default();
+            if for_secs < parse_duration_secs(&interval)? {
+                return Err(anyhow!("RESAMPLE FOR 不能小于 GROUP BY time() 间隔 {}", interval));
+            }
+            statement.push_str(&format!(" FOR {}", for_));
+        }
+    }
+
+    statement.push_str(&format!(" BEGIN {} END", select));
+    Ok(statement)
+}
+
+/// 构建 DROP CONTINUOUS QUERY 语句
+pub fn build_drop_statement(name: &str, database: &str) -> String {
+    format!(
+        "DROP CONTINUOUS QUERY {} ON {}",
+        quote_identifier(name),
+        quote_identifier(database)
+    )
+}
+
+/// 按分组间隔对齐的回填时间窗口 [start, end)
+pub fn plan_backfill_windows(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    interval_secs: i64,
+    chunk_secs: i64,
+) -> Result<Vec<(DateTime<Utc>, DateTime<Utc>)>> {
+    if end <= start {
+        return Err(anyhow!("回填结束时间必须晚于开始时间"));
+    }
+    if interval_secs <= 0 {
+        return Err(anyhow!("GROUP BY time() 间隔必须大于 0"));
+    }
+
+    // 分段时长取分组间隔的整数倍，避免同一个分组被拆到两个窗口
+    let chunk_secs = chunk_secs.max(interval_secs);
+    let chunk_secs = (chunk_secs + interval_secs - 1) / interval_secs * interval_secs;
+
+    let align_down = |ts: i64| ts.div_euclid(interval_secs) * interval_secs;
+    let first = align_down(start.timestamp());
+    let last = align_down(end.timestamp() + interval_secs - 1);
+
+    let mut windows = Vec::new();
+    let mut cursor = first;
+    while cursor < last {
+        let next = (cursor + chunk_secs).min(last);
+        let window_start = Utc.timestamp_opt(cursor, 0).single().ok_or_else(|| anyhow!("无效的时间: {}", cursor))?;
+        let window_end = Utc.timestamp_opt(next, 0).single().ok_or_else(|| anyhow!("无效的时间: {}", next))?;
+        windows.push((window_start, window_end));
+        cursor = next;
+    }
+    Ok(windows)
+}
+
+/// 为连续查询体追加时间范围条件，生成单个窗口的 SELECT ... INTO 语句
+pub fn build_backfill_statement(select: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<String> {
+    let group_by = find_keyword(select, "GROUP BY").ok_or_else(|| anyhow!("连续查询体缺少 GROUP BY 子句"))?;
+    let time_range = format!(
+        "time >= '{}' AND time < '{}'",
+        start.to_rfc3339_opts(SecondsFormat::Secs, true),
+        end.to_rfc3339_opts(SecondsFormat::Secs, true)
+    );
+
+    let statement = match find_keyword(select, "WHERE").filter(|pos| *pos < group_by) {
+        Some(where_pos) => {
+            let condition = select[where_pos + "WHERE".len()..group_by].trim();
+            format!(
+                "{}WHERE ({}) AND {} {}",
+                &select[..where_pos],
+                condition,
+                time_range,
+                &select[group_by..]
+            )
+        }
+        None => format!(
+            "{} WHERE {} {}",
+            select[..group_by].trim_end(),
+            time_range,
+            &select[group_by..]
+        ),
+    };
+    Ok(statement)
+}
+
+/// 统计 SELECT ... INTO 写入的点数
+pub fn points_written(series: &[InfluxQLSeries]) -> i64 {
+    series
+        .iter()
+        .flat_map(|s| s.values.iter().filter_map(move |row| s.integer(row, "written")))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const CQ: &str = "CREATE CONTINUOUS QUERY cq_1h ON telegraf RESAMPLE EVERY 30m FOR 2h BEGIN SELECT mean(usage_idle) AS usage_idle INTO telegraf.\"1y\".cpu_1h FROM telegraf.autogen.cpu WHERE cpu = 'cpu-total' GROUP BY time(1h), * fill(none) END";
+
+    fn request(select: &str, every: Option<&str>, for_: Option<&str>) -> CreateInfluxContinuousQueryRequest {
+        CreateInfluxContinuousQueryRequest {
+            name: "cq_1h".to_string(),
+            database: "telegraf".to_string(),
+            select: select.to_string(),
+            resample_every: every.map(|s| s.to_string()),
+            resample_for: for_.map(|s| s.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_parse_continuous_queries() {
+        let series = vec![
+            InfluxQLSeries {
+                name: Some("telegraf".to_string()),
+                columns: vec!["name".to_string(), "query".to_string()],
+                values: vec![vec![json!("cq_1h"), json!(CQ)]],
+                ..Default::default()
+            },
+            InfluxQLSeries {
+                name: Some("_internal".to_string()),
+                columns: vec!["name".to_string(), "query".to_string()],
+                ..Default::default()
+            },
+        ];
+
+        let cqs = parse_continuous_queries(&series);
+        assert_eq!(cqs.len(), 1);
+        let cq = &cqs[0];
+        assert_eq!(cq.database, "telegraf");
+        assert_eq!(cq.resample_every.as_deref(), Some("30m"));
+        assert_eq!(cq.resample_for.as_deref(), Some("2h"));
+        assert_eq!(cq.group_by_interval.as_deref(), Some("1h"));
+        assert_eq!(cq.target.as_deref(), Some("telegraf.\"1y\".cpu_1h"));
+        assert!(cq.select.starts_with("SELECT mean(usage_idle)"));
+        assert!(cq.select.ends_with("fill(none)"));
+    }
+
+    #[test]
+    fn test_build_create_statement() {
+        let select = "SELECT mean(v) INTO m_1h FROM m GROUP BY time(1h);";
+        assert_eq!(
+            build_create_statement(&request(select, Some("30m"), Some("2h"))).unwrap(),
+            "CREATE CONTINUOUS QUERY \"cq_1h\" ON \"telegraf\" RESAMPLE EVERY 30m FOR 2h BEGIN SELECT mean(v) INTO m_1h FROM m GROUP BY time(1h) END"
+        );
+        assert!(!build_create_statement(&request(select, None, None)).unwrap().contains("RESAMPLE"));
+
+        assert!(build_create_statement(&request(select, None, Some("30m"))).is_err());
+        assert!(build_create_statement(&request(select, Some("soon"), None)).is_err());
+        assert!(build_create_statement(&request("SELECT mean(v) FROM m GROUP BY time(1h)", None, None)).is_err());
+        assert!(build_create_statement(&request("SELECT mean(v) INTO x FROM m", None, None)).is_err());
+        assert_eq!(build_drop_statement("cq_1h", "telegraf"), "DROP CONTINUOUS QUERY \"cq_1h\" ON \"telegraf\"");
+    }
+
+    #[test]
+    fn test_plan_backfill_windows() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 20, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 1, 3, 5, 10, 0).unwrap();
+
+        let windows = plan_backfill_windows(start, end, 3600, 86_400).unwrap();
+        assert_eq!(windows.len(), 3);
+        assert_eq!(windows[0].0, Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(windows[1].0, Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap());
+        assert_eq!(windows[2].1, Utc.with_ymd_and_hms(2024, 1, 3, 6, 0, 0).unwrap());
+
+        // 分段小于分组间隔时按一个间隔处理
+        let small = plan_backfill_windows(start, start + chrono::Duration::hours(2), 3600, 60).unwrap();
+        assert_eq!(small.len(), 3);
+
+        assert!(plan_backfill_windows(end, start, 3600, 86_400).is_err());
+    }
+
+    #[test]
+    fn test_build_backfill_statement() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap();
+
+        let plain = build_backfill_statement("SELECT mean(v) INTO m_1h FROM m GROUP BY time(1h)", start, end).unwrap();
+        assert_eq!(
+            plain,
+            "SELECT mean(v) INTO m_1h FROM m WHERE time >= '2024-01-01T00:00:00Z' AND time < '2024-01-02T00:00:00Z' GROUP BY time(1h)"
+        );
+
+        let select = parse_continuous_query("telegraf", "cq_1h", CQ).select;
+        let filtered = build_backfill_statement(&select, start, end).unwrap();
+        assert!(filtered.contains("WHERE (cpu = 'cpu-total') AND time >= '2024-01-01T00:00:00Z'"));
+        assert!(filtered.ends_with("GROUP BY time(1h), * fill(none)"));
+    }
+
+    #[test]
+    fn test_points_written() {
+        let series = vec![InfluxQLSeries {
+            name: Some("result".to_string()),
+            columns: vec!["time".to_string(), "written".to_string()],
+            values: vec![vec![json!("1970-01-01T00:00:00Z"), json!(42)]],
+            ..Default::default()
+        }];
+        assert_eq!(points_written(&series), 42);
+        assert_eq!(points_written(&[]), 0);
+    }
+}
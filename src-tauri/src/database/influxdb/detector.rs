@@ -91,7 +91,7 @@ impl InfluxDetector {
     fn create_http_client(config: &ConnectionConfig) -> Result<Client> {
         let mut builder = Client::builder()
             .timeout(Duration::from_secs(config.timeout as u64))
-            .danger_accept_invalid_certs(!config.ssl); // 开发环境可能使用自签名证书
+            .danger_accept_invalid_certs(config.skip_ssl_verification);
         
         // 如果有代理配置，添加代理设置
         if let Some(proxy_config) = &config.proxy_config {
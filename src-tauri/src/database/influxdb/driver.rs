@@ -52,6 +52,18 @@ pub trait InfluxDriver: Send + Sync {
     
     /// 删除保留策略（1.x 专用）
     async fn drop_retention_policy(&self, database: &str, policy_name: &str) -> Result<()>;
+
+    /// 执行查询并返回 Arrow 列式结果（3.x FlightSQL 专用）
+    #[cfg(feature = "influxdb-v3")]
+    async fn query_arrow(&self, _query: &Query) -> Result<super::arrow_result::ArrowResult> {
+        Err(anyhow::anyhow!("当前驱动不支持 Arrow 列式结果"))
+    }
+
+    /// 以服务器原生格式执行查询并返回原始响应，用于 CSV / Parquet 导出（3.x HTTP API 专用）
+    #[cfg(feature = "influxdb-v3")]
+    async fn query_raw(&self, _query: &Query, _format: super::v3_http_driver::V3ResponseFormat) -> Result<Vec<u8>> {
+        Err(anyhow::anyhow!("当前驱动不支持原始格式查询"))
+    }
 }
 
 /// 测量模式信息
@@ -0,0 +1,274 @@
+/**
+ * InfluxDB 1.x InfluxQL 响应解析与语句构建工具
+ *
+ * 驱动层的查询结果只保留第一个 series，而 SHOW CONTINUOUS QUERIES、SHOW SHARDS 等
+ * 管理语句会按数据库返回多个 series，这里提供完整的解析
+ */
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// InfluxQL 响应中的单个 series
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InfluxQLSeries {
+    pub name: Option<String>,
+    pub tags: HashMap<String, String>,
+    pub columns: Vec<String>,
+    pub values: Vec<Vec<Value>>,
+}
+
+impl InfluxQLSeries {
+    /// 列索引（忽略大小写）
+    pub fn column_index(&self, column: &str) -> Option<usize> {
+        self.columns.iter().position(|c| c.eq_ignore_ascii_case(column))
+    }
+
+    /// 读取指定行的列值
+    pub fn value<'a>(&self, row: &'a [Value], column: &str) -> Option<&'a Value> {
+        self.column_index(column).and_then(|i| row.get(i)).filter(|v| !v.is_null())
+    }
+
+    /// 读取指定行的字符串列
+    pub fn string(&self, row: &[Value], column: &str) -> Option<String> {
+        self.value(row, column).map(|v| match v {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        })
+    }
+
+    /// 读取指定行的整数列
+    pub fn integer(&self, row: &[Value], column: &str) -> Option<i64> {
+        self.value(row, column).and_then(|v| v.as_i64().or_else(|| v.as_f64().map(|f| f as i64)))
+    }
+
+    /// 读取指定行的布尔列
+    pub fn boolean(&self, row: &[Value], column: &str) -> Option<bool> {
+        self.value(row, column).and_then(|v| v.as_bool())
+    }
+}
+
+/// 解析 /query 接口的 JSON 响应，任一语句出错时返回该错误
+pub fn parse_influxql_response(json: &Value) -> Result<Vec<InfluxQLSeries>> {
+    if let Some(error) = json.get("error").and_then(|e| e.as_str()) {
+        return Err(anyhow!(error.to_string()));
+    }
+
+    let results = json
+        .get("results")
+        .and_then(|r| r.as_array())
+        .ok_or_else(|| anyhow!("响应格式错误：缺少 results 字段"))?;
+
+    let mut series = Vec::new();
+    for result in results {
+        if let Some(error) = result.get("error").and_then(|e| e.as_str()) {
+            return Err(anyhow!(error.to_string()));
+        }
+
+        for item in result.get("series").and_then(|s| s.as_array()).into_iter().flatten() {
+            series.push(InfluxQLSeries {
+                name: item.get("name").and_then(|n| n.as_str()).map(|s| s.to_string()),
+                tags: item
+                    .get("tags")
+                    .and_then(|t| t.as_object())
+                    .map(|tags| {
+                        tags.iter()
+                            .map(|(k, v)| (k.clone(), v.as_str().map(|s| s.to_string()).unwrap_or_else(|| v.to_string())))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                columns: item
+                    .get("columns")
+                    .and_then(|c| c.as_array())
+                    .map(|columns| {
+                        columns
+                            .iter()
+                            .map(|c| c.as_str().unwrap_or("").to_string())
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                values: item
+                    .get("values")
+                    .and_then(|v| v.as_array())
+                    .map(|rows| {
+                        rows.iter()
+                            .map(|row| row.as_array().cloned().unwrap_or_default())
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            });
+        }
+    }
+
+    Ok(series)
+}
+
+/// 为 InfluxQL 标识符加双引号
+pub fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// 为 InfluxQL 字符串字面量加单引号
+pub fn quote_string(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// 将 InfluxQL 时长字面量（如 `1h30m`、`7d`、`1w`）换算为秒
+pub fn parse_duration_secs(duration: &str) -> Result<i64> {
+    let duration = duration.trim();
+    if duration.eq_ignore_ascii_case("inf") {
+        return Err(anyhow!("INF 不是有限时长"));
+    }
+
+    let mut rest = duration;
+    let mut nanos: i128 = 0;
+    if rest.is_empty() {
+        return Err(anyhow!("时长不能为空"));
+    }
+
+    while !rest.is_empty() {
+        let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+        if digits == 0 {
+            return Err(anyhow!("无效的时长: {}", duration));
+        }
+        let amount: i128 = rest[..digits].parse().map_err(|_| anyhow!("无效的时长: {}", duration))?;
+        rest = &rest[digits..];
+
+        let (unit_nanos, unit_len): (i128, usize) = if rest.starts_with("ns") {
+            (1, 2)
+        } else if rest.starts_with("us") {
+            (1_000, 2)
+        } else if rest.starts_with("µ") {
+            (1_000, "µ".len())
+        } else if rest.starts_with("ms") {
+            (1_000_000, 2)
+        } else if rest.starts_with('s') {
+            (1_000_000_000, 1)
+        } else if rest.starts_with('m') {
+            (60_000_000_000, 1)
+        } else if rest.starts_with('h') {
+            (3_600_000_000_000, 1)
+        } else if rest.starts_with('d') {
+            (86_400_000_000_000, 1)
+        } else if rest.starts_with('w') {
+            (604_800_000_000_000, 1)
+        } else {
+            return Err(anyhow!("无效的时长单位: {}", duration));
+        };
+
+        nanos += amount * unit_nanos;
+        rest = &rest[unit_len..];
+    }
+
+    Ok((nanos / 1_000_000_000) as i64)
+}
+
+/// 在语句中查找不位于引号内的关键字（忽略大小写，按单词边界匹配），返回字节位置
+pub fn find_keyword(statement: &str, keyword: &str) -> Option<usize> {
+    let upper = statement.to_ascii_uppercase();
+    let keyword = keyword.to_ascii_uppercase();
+    let bytes = statement.as_bytes();
+
+    let mut quote: Option<u8> = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        match quote {
+            Some(q) => {
+                if c == b'\\' {
+                    i += 1;
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None => {
+                if c == b'\'' || c == b'"' {
+                    quote = Some(c);
+                } else if upper.is_char_boundary(i)
+                    && upper[i..].starts_with(&keyword)
+                    && (i == 0 || !is_word_byte(bytes[i - 1]))
+                    && bytes.get(i + keyword.len()).is_none_or(|b| !is_word_byte(*b))
+                {
+                    return Some(i);
+                }
+            }
+        }
+        i += 1;
+    }
+    None
+}
+
+fn is_word_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_multi_series_response() {
+        let json = json!({
+            "results": [{
+                "statement_id": 0,
+                "series": [
+                    {"name": "db1", "columns": ["name", "query"], "values": [["cq1", "CREATE ..."]]},
+                    {"name": "db2", "columns": ["name", "query"]}
+                ]
+            }]
+        });
+        let series = parse_influxql_response(&json).unwrap();
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].name.as_deref(), Some("db1"));
+        assert_eq!(series[0].string(&series[0].values[0], "NAME").as_deref(), Some("cq1"));
+        assert!(series[1].values.is_empty());
+
+        let error = json!({"results": [{"statement_id": 0, "error": "database not found: x"}]});
+        assert!(parse_influxql_response(&error).unwrap_err().to_string().contains("database not found"));
+
+        let empty = json!({"results": [{"statement_id": 0}]});
+        assert!(parse_influxql_response(&empty).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_series_accessors() {
+        let series = InfluxQLSeries {
+            columns: vec!["id".to_string(), "admin".to_string(), "size".to_string()],
+            values: vec![vec![json!(3), json!(true), json!(1024.0)]],
+            ..Default::default()
+        };
+        let row = &series.values[0];
+        assert_eq!(series.integer(row, "id"), Some(3));
+        assert_eq!(series.boolean(row, "admin"), Some(true));
+        assert_eq!(series.integer(row, "size"), Some(1024));
+        assert_eq!(series.string(row, "id").as_deref(), Some("3"));
+        assert_eq!(series.string(row, "missing"), None);
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote_identifier("my \"db\""), "\"my \\\"db\\\"\"");
+        assert_eq!(quote_string("it's"), "'it\\'s'");
+    }
+
+    #[test]
+    fn test_parse_duration_secs() {
+        assert_eq!(parse_duration_secs("10s").unwrap(), 10);
+        assert_eq!(parse_duration_secs("1h30m").unwrap(), 5400);
+        assert_eq!(parse_duration_secs("7d").unwrap(), 604_800);
+        assert_eq!(parse_duration_secs("1w").unwrap(), 604_800);
+        assert_eq!(parse_duration_secs("500ms").unwrap(), 0);
+        assert!(parse_duration_secs("INF").is_err());
+        assert!(parse_duration_secs("1y").is_err());
+        assert!(parse_duration_secs("").is_err());
+    }
+
+    #[test]
+    fn test_find_keyword() {
+        let query = "SELECT mean(\"where\") INTO \"a\" FROM cpu WHERE host = 'group by' GROUP BY time(1m)";
+        assert_eq!(find_keyword(query, "WHERE"), query.find("WHERE host"));
+        assert_eq!(find_keyword(query, "group by"), query.find("GROUP BY time"));
+        assert_eq!(find_keyword("SELECT * FROM elsewhere", "WHERE"), None);
+    }
+}
@@ -11,6 +11,18 @@ pub mod driver;
 pub mod utils;
 pub mod pool;
 pub mod metrics;
+pub mod tasks;
+pub mod monitoring;
+pub mod authorizations;
+pub mod admin;
+pub mod telegraf;
+pub mod influxql;
+pub mod continuous_queries;
+pub mod users;
+pub mod shards;
+pub mod running_queries;
+pub mod caches;
+pub mod processing_engine;
 
 #[cfg(feature = "influxdb-v1")]
 pub mod v1_driver;
@@ -21,6 +33,12 @@ pub mod v2_driver;
 #[cfg(feature = "influxdb-v3")]
 pub mod v3_driver;
 
+#[cfg(feature = "influxdb-v3")]
+pub mod v3_http_driver;
+
+#[cfg(feature = "influxdb-v3")]
+pub mod arrow_result;
+
 pub use capability::*;
 pub use detector::*;
 pub use driver::*;
@@ -39,6 +57,12 @@ pub use v2_driver::V2HttpDriver;
 #[cfg(feature = "influxdb-v3")]
 pub use v3_driver::FlightSqlDriver;
 
+#[cfg(feature = "influxdb-v3")]
+pub use v3_http_driver::{V3HttpDriver, V3ResponseFormat};
+
+#[cfg(feature = "influxdb-v3")]
+pub use arrow_result::{ArrowResult, ArrowColumnInfo};
+
 // use anyhow::Result; // 暂时未使用
 use std::sync::Arc;
 use crate::models::ConnectionConfig;
@@ -65,9 +89,8 @@ impl InfluxDriverFactory {
             
             #[cfg(feature = "influxdb-v3")]
             3 => {
-                // 3.x 但没有 FlightSQL，使用轻量 HTTP 客户端
-                // 这里可以实现一个简化的 V3LiteDriver
-                return Err(anyhow::anyhow!("InfluxDB 3.x 轻量客户端暂未实现"));
+                // 3.x 但没有 FlightSQL，使用 /api/v3 HTTP 接口
+                Arc::new(V3HttpDriver::new(config.clone(), capability.version.clone())?)
             },
             
             _ => return Err(anyhow::anyhow!("不支持的 InfluxDB 版本: {}", capability.major)),
@@ -0,0 +1,722 @@
+/**
+ * InfluxDB 2.x 监控告警辅助函数
+ *
+ * 负责检查(Check)、通知端点(NotificationEndpoint)和通知规则(NotificationRule)
+ * 的请求体构建、响应解析，以及 _monitoring 存储桶状态查询
+ */
+
+use crate::commands::influxdb2::{
+    CheckInfo, CheckStatusEntry, CheckThreshold, DeadmanConfig, NotificationEndpointInfo,
+    NotificationRuleInfo, SaveCheckRequest, SaveNotificationEndpointRequest,
+    SaveNotificationRuleRequest, StatusRule, TagRule,
+};
+use super::tasks::validate_duration;
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// 检查状态级别
+const CHECK_LEVELS: [&str; 5] = ["CRIT", "WARN", "INFO", "OK", "UNKNOWN"];
+
+/// 标签规则操作符
+const TAG_OPERATORS: [&str; 4] = ["equal", "notequal", "equalregex", "notequalregex"];
+
+/// 默认状态消息模板
+const DEFAULT_STATUS_MESSAGE: &str = "Check: ${ r._check_name } is: ${ r._level }";
+
+fn text(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+fn status_of(active: Option<bool>) -> &'static str {
+    if active.unwrap_or(true) {
+        "active"
+    } else {
+        "inactive"
+    }
+}
+
+fn require_name(name: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        Err(anyhow!("名称不能为空"))
+    } else {
+        Ok(())
+    }
+}
+
+/// 校验并规范化状态级别
+pub fn normalize_level(level: &str) -> Result<String> {
+    let level = level.trim().to_uppercase();
+    if CHECK_LEVELS.contains(&level.as_str()) {
+        Ok(level)
+    } else {
+        Err(anyhow!("无效的状态级别: {}", level))
+    }
+}
+
+// ========== 检查 ==========
+
+fn build_threshold(threshold: &CheckThreshold) -> Result<Value> {
+    let level = normalize_level(&threshold.level)?;
+
+    match threshold.threshold_type.as_str() {
+        "greater" | "lesser" => {
+            let value = threshold
+                .value
+                .ok_or_else(|| anyhow!("{} 阈值缺少 value", threshold.threshold_type))?;
+            Ok(json!({
+                "type": threshold.threshold_type,
+                "level": level,
+                "value": value,
+                "allValues": threshold.all_values,
+            }))
+        }
+        "range" => {
+            let (min, max) = match (threshold.min, threshold.max) {
+                (Some(min), Some(max)) if min <= max => (min, max),
+                (Some(_), Some(_)) => return Err(anyhow!("range 阈值的 min 不能大于 max")),
+                _ => return Err(anyhow!("range 阈值缺少 min 或 max")),
+            };
+            Ok(json!({
+                "type": "range",
+                "level": level,
+                "min": min,
+                "max": max,
+                "within": threshold.within.unwrap_or(true),
+                "allValues": threshold.all_values,
+            }))
+        }
+        other => Err(anyhow!("不支持的阈值类型: {}", other)),
+    }
+}
+
+/// 构建检查请求体
+pub fn build_check_body(request: &SaveCheckRequest, org_id: &str) -> Result<Value> {
+    require_name(&request.name)?;
+    if request.query.trim().is_empty() {
+        return Err(anyhow!("检查查询不能为空"));
+    }
+    validate_duration(&request.every)?;
+
+    let mut body = json!({
+        "name": request.name.trim(),
+        "orgID": org_id,
+        "type": request.check_type,
+        "status": status_of(request.active),
+        "every": request.every,
+        "query": {
+            "name": "",
+            "text": request.query,
+            "editMode": "advanced",
+            "builderConfig": {"buckets": [], "tags": [], "functions": []},
+        },
+        "statusMessageTemplate": request
+            .status_message_template
+            .as_deref()
+            .filter(|t| !t.trim().is_empty())
+            .unwrap_or(DEFAULT_STATUS_MESSAGE),
+    });
+
+    if let Some(offset) = request.offset.as_deref().filter(|o| !o.is_empty()) {
+        validate_duration(offset)?;
+        body["offset"] = json!(offset);
+    }
+
+    if let Some(desc) = &request.description {
+        body["description"] = json!(desc);
+    }
+
+    match request.check_type.as_str() {
+        "threshold" => {
+            if request.thresholds.is_empty() {
+                return Err(anyhow!("阈值检查至少需要一个阈值"));
+            }
+            let thresholds = request
+                .thresholds
+                .iter()
+                .map(build_threshold)
+                .collect::<Result<Vec<_>>>()?;
+            body["thresholds"] = json!(thresholds);
+        }
+        "deadman" => {
+            let deadman = request
+                .deadman
+                .as_ref()
+                .ok_or_else(|| anyhow!("deadman 检查缺少参数"))?;
+            validate_duration(&deadman.time_since)?;
+            validate_duration(&deadman.stale_time)?;
+            body["timeSince"] = json!(deadman.time_since);
+            body["staleTime"] = json!(deadman.stale_time);
+            body["level"] = json!(normalize_level(&deadman.level)?);
+            body["reportZero"] = json!(deadman.report_zero);
+        }
+        other => return Err(anyhow!("不支持的检查类型: {}", other)),
+    }
+
+    Ok(body)
+}
+
+/// 解析检查响应
+pub fn parse_check(check: &Value) -> CheckInfo {
+    let check_type = text(check, "type").unwrap_or_default();
+
+    let thresholds = check
+        .get("thresholds")
+        .and_then(|t| t.as_array())
+        .map(|thresholds| {
+            thresholds
+                .iter()
+                .map(|t| CheckThreshold {
+                    threshold_type: text(t, "type").unwrap_or_default(),
+                    level: text(t, "level").unwrap_or_default(),
+                    value: t.get("value").and_then(|v| v.as_f64()),
+                    min: t.get("min").and_then(|v| v.as_f64()),
+                    max: t.get("max").and_then(|v| v.as_f64()),
+                    within: t.get("within").and_then(|v| v.as_bool()),
+                    all_values: t.get("allValues").and_then(|v| v.as_bool()).unwrap_or(false),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let deadman = (check_type == "deadman").then(|| DeadmanConfig {
+        time_since: text(check, "timeSince").unwrap_or_default(),
+        stale_time: text(check, "staleTime").unwrap_or_default(),
+        level: text(check, "level").unwrap_or_default(),
+        report_zero: check.get("reportZero").and_then(|v| v.as_bool()).unwrap_or(false),
+    });
+
+    CheckInfo {
+        id: text(check, "id").unwrap_or_default(),
+        name: text(check, "name").unwrap_or_default(),
+        org_id: text(check, "orgID").unwrap_or_default(),
+        check_type,
+        status: text(check, "status").unwrap_or_else(|| "active".to_string()),
+        every: text(check, "every"),
+        offset: text(check, "offset"),
+        query: check
+            .get("query")
+            .and_then(|q| q.get("text"))
+            .and_then(|t| t.as_str())
+            .unwrap_or("")
+            .to_string(),
+        status_message_template: text(check, "statusMessageTemplate"),
+        description: text(check, "description"),
+        thresholds,
+        deadman,
+        last_run_status: text(check, "lastRunStatus"),
+        last_run_error: text(check, "lastRunError"),
+        latest_completed: text(check, "latestCompleted"),
+        created_at: text(check, "createdAt"),
+        updated_at: text(check, "updatedAt"),
+    }
+}
+
+// ========== 通知端点 ==========
+
+/// 构建通知端点请求体
+pub fn build_endpoint_body(request: &SaveNotificationEndpointRequest, org_id: &str) -> Result<Value> {
+    require_name(&request.name)?;
+
+    let non_empty = |value: &Option<String>| value.as_deref().filter(|v| !v.trim().is_empty()).map(|v| v.to_string());
+
+    let mut body = json!({
+        "name": request.name.trim(),
+        "orgID": org_id,
+        "type": request.endpoint_type,
+        "status": status_of(request.active),
+    });
+
+    if let Some(desc) = &request.description {
+        body["description"] = json!(desc);
+    }
+
+    match request.endpoint_type.as_str() {
+        "http" => {
+            let url = non_empty(&request.url).ok_or_else(|| anyhow!("HTTP 端点缺少 URL"))?;
+            let method = non_empty(&request.method).unwrap_or_else(|| "POST".to_string()).to_uppercase();
+            if !["POST", "GET", "PUT"].contains(&method.as_str()) {
+                return Err(anyhow!("不支持的 HTTP 方法: {}", method));
+            }
+            let auth_method = non_empty(&request.auth_method).unwrap_or_else(|| "none".to_string());
+
+            body["url"] = json!(url);
+            body["method"] = json!(method);
+            body["authMethod"] = json!(auth_method);
+
+            match auth_method.as_str() {
+                "none" => {}
+                "basic" => {
+                    let username = non_empty(&request.username).ok_or_else(|| anyhow!("Basic 认证缺少用户名"))?;
+                    let password = non_empty(&request.password).ok_or_else(|| anyhow!("Basic 认证缺少密码"))?;
+                    body["username"] = json!(username);
+                    body["password"] = json!(password);
+                }
+                "bearer" => {
+                    let token = non_empty(&request.token).ok_or_else(|| anyhow!("Bearer 认证缺少令牌"))?;
+                    body["token"] = json!(token);
+                }
+                other => return Err(anyhow!("不支持的认证方式: {}", other)),
+            }
+
+            if !request.headers.is_empty() {
+                body["headers"] = json!(request.headers);
+            }
+        }
+        "slack" => {
+            let url = non_empty(&request.url);
+            let token = non_empty(&request.token);
+            if url.is_none() && token.is_none() {
+                return Err(anyhow!("Slack 端点需要 Webhook URL 或令牌"));
+            }
+            if let Some(url) = url {
+                body["url"] = json!(url);
+            }
+            if let Some(token) = token {
+                body["token"] = json!(token);
+            }
+        }
+        "pagerduty" => {
+            let routing_key = non_empty(&request.routing_key).ok_or_else(|| anyhow!("PagerDuty 端点缺少 routing key"))?;
+            body["routingKey"] = json!(routing_key);
+            if let Some(client_url) = non_empty(&request.client_url) {
+                body["clientURL"] = json!(client_url);
+            }
+        }
+        other => return Err(anyhow!("不支持的通知端点类型: {}", other)),
+    }
+
+    Ok(body)
+}
+
+/// 解析通知端点响应
+pub fn parse_endpoint(endpoint: &Value) -> NotificationEndpointInfo {
+    let headers = endpoint
+        .get("headers")
+        .and_then(|h| h.as_object())
+        .map(|headers| {
+            headers
+                .iter()
+                .filter_map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    NotificationEndpointInfo {
+        id: text(endpoint, "id").unwrap_or_default(),
+        name: text(endpoint, "name").unwrap_or_default(),
+        org_id: text(endpoint, "orgID").unwrap_or_default(),
+        endpoint_type: text(endpoint, "type").unwrap_or_default(),
+        status: text(endpoint, "status").unwrap_or_else(|| "active".to_string()),
+        description: text(endpoint, "description"),
+        url: text(endpoint, "url"),
+        method: text(endpoint, "method"),
+        auth_method: text(endpoint, "authMethod"),
+        username: text(endpoint, "username").filter(|u| !u.starts_with("secret: ")),
+        headers,
+        client_url: text(endpoint, "clientURL"),
+        created_at: text(endpoint, "createdAt"),
+        updated_at: text(endpoint, "updatedAt"),
+    }
+}
+
+// ========== 通知规则 ==========
+
+/// 构建通知规则请求体，`rule_type` 取自目标端点的类型
+pub fn build_rule_body(request: &SaveNotificationRuleRequest, org_id: &str, rule_type: &str) -> Result<Value> {
+    require_name(&request.name)?;
+    if request.endpoint_id.trim().is_empty() {
+        return Err(anyhow!("通知规则缺少端点"));
+    }
+    validate_duration(&request.every)?;
+    if request.status_rules.is_empty() {
+        return Err(anyhow!("通知规则至少需要一个状态条件"));
+    }
+
+    let status_rules = request
+        .status_rules
+        .iter()
+        .map(|rule| -> Result<Value> {
+            let mut value = json!({"currentLevel": normalize_level(&rule.current_level)?});
+            if let Some(previous) = rule.previous_level.as_deref().filter(|p| !p.is_empty()) {
+                value["previousLevel"] = json!(normalize_level(previous)?);
+            }
+            Ok(value)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let tag_rules = request
+        .tag_rules
+        .iter()
+        .map(|rule| -> Result<Value> {
+            if rule.key.trim().is_empty() {
+                return Err(anyhow!("标签条件缺少键"));
+            }
+            if !TAG_OPERATORS.contains(&rule.operator.as_str()) {
+                return Err(anyhow!("不支持的标签操作符: {}", rule.operator));
+            }
+            Ok(json!({"key": rule.key, "value": rule.value, "operator": rule.operator}))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut body = json!({
+        "name": request.name.trim(),
+        "orgID": org_id,
+        "type": rule_type,
+        "endpointID": request.endpoint_id,
+        "every": request.every,
+        "status": status_of(request.active),
+        "statusRules": status_rules,
+        "tagRules": tag_rules,
+    });
+
+    if let Some(offset) = request.offset.as_deref().filter(|o| !o.is_empty()) {
+        validate_duration(offset)?;
+        body["offset"] = json!(offset);
+    }
+
+    if let Some(desc) = &request.description {
+        body["description"] = json!(desc);
+    }
+
+    let template = request.message_template.as_deref().filter(|t| !t.trim().is_empty());
+    match rule_type {
+        "http" => {}
+        "slack" | "pagerduty" => {
+            let template = template.ok_or_else(|| anyhow!("{} 通知规则缺少消息模板", rule_type))?;
+            body["messageTemplate"] = json!(template);
+            if rule_type == "slack" {
+                if let Some(channel) = request.channel.as_deref().filter(|c| !c.is_empty()) {
+                    body["channel"] = json!(channel);
+                }
+            }
+        }
+        other => return Err(anyhow!("不支持的通知规则类型: {}", other)),
+    }
+
+    Ok(body)
+}
+
+/// 解析通知规则响应
+pub fn parse_rule(rule: &Value) -> NotificationRuleInfo {
+    let status_rules = rule
+        .get("statusRules")
+        .and_then(|r| r.as_array())
+        .map(|rules| {
+            rules
+                .iter()
+                .map(|r| StatusRule {
+                    current_level: text(r, "currentLevel").unwrap_or_default(),
+                    previous_level: text(r, "previousLevel"),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let tag_rules = rule
+        .get("tagRules")
+        .and_then(|r| r.as_array())
+        .map(|rules| {
+            rules
+                .iter()
+                .map(|r| TagRule {
+                    key: text(r, "key").unwrap_or_default(),
+                    value: text(r, "value").unwrap_or_default(),
+                    operator: text(r, "operator").unwrap_or_else(|| "equal".to_string()),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    NotificationRuleInfo {
+        id: text(rule, "id").unwrap_or_default(),
+        name: text(rule, "name").unwrap_or_default(),
+        org_id: text(rule, "orgID").unwrap_or_default(),
+        rule_type: text(rule, "type").unwrap_or_default(),
+        endpoint_id: text(rule, "endpointID").unwrap_or_default(),
+        status: text(rule, "status").unwrap_or_else(|| "active".to_string()),
+        every: text(rule, "every"),
+        offset: text(rule, "offset"),
+        description: text(rule, "description"),
+        status_rules,
+        tag_rules,
+        message_template: text(rule, "messageTemplate"),
+        channel: text(rule, "channel"),
+        last_run_status: text(rule, "lastRunStatus"),
+        last_run_error: text(rule, "lastRunError"),
+        created_at: text(rule, "createdAt"),
+        updated_at: text(rule, "updatedAt"),
+    }
+}
+
+// ========== 检查状态 ==========
+
+/// 构建查询 _monitoring 存储桶中最近检查状态的 Flux 语句
+pub fn build_statuses_flux(check_id: Option<&str>, range: &str, limit: u32) -> Result<String> {
+    validate_duration(range)?;
+
+    let check_filter = match check_id.filter(|id| !id.is_empty()) {
+        Some(id) => {
+            if !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err(anyhow!("无效的检查 ID: {}", id));
+            }
+            format!("\n  |> filter(fn: (r) => r._check_id == \"{}\")", id)
+        }
+        None => String::new(),
+    };
+
+    Ok(format!(
+        "from(bucket: \"_monitoring\")\n  |> range(start: -{})\n  |> filter(fn: (r) => r._measurement == \"statuses\" and r._field == \"_message\"){}\n  |> group()\n  |> sort(columns: [\"_time\"], desc: true)\n  |> limit(n: {})\n  |> keep(columns: [\"_time\", \"_check_id\", \"_check_name\", \"_level\", \"_value\"])",
+        range,
+        check_filter,
+        limit.clamp(1, 1000)
+    ))
+}
+
+/// 解析检查状态查询返回的 Flux CSV
+pub fn parse_statuses_csv(csv_data: &str) -> Result<Vec<CheckStatusEntry>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(csv_data.as_bytes());
+
+    let mut header: Option<HashMap<String, usize>> = None;
+    let mut statuses = Vec::new();
+
+    for record in reader.records() {
+        let record = record.map_err(|e| anyhow!("解析状态数据失败: {}", e))?;
+        if record.iter().all(|field| field.trim().is_empty()) {
+            continue;
+        }
+
+        // 每个表都以表头开始
+        if record.iter().any(|field| field == "_time") {
+            header = Some(
+                record
+                    .iter()
+                    .enumerate()
+                    .map(|(i, name)| (name.to_string(), i))
+                    .collect(),
+            );
+            continue;
+        }
+
+        let Some(columns) = &header else {
+            continue;
+        };
+
+        let field = |name: &str| {
+            columns
+                .get(name)
+                .and_then(|&i| record.get(i))
+                .unwrap_or("")
+                .to_string()
+        };
+
+        statuses.push(CheckStatusEntry {
+            time: field("_time"),
+            check_id: field("_check_id"),
+            check_name: field("_check_name"),
+            level: field("_level").to_uppercase(),
+            message: field("_value"),
+        });
+    }
+
+    statuses.sort_by(|a, b| b.time.cmp(&a.time));
+    Ok(statuses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn threshold_check() -> SaveCheckRequest {
+        SaveCheckRequest {
+            name: "cpu high".to_string(),
+            org_id: None,
+            check_type: "threshold".to_string(),
+            query: "from(bucket: \"telegraf\") |> range(start: -1m)".to_string(),
+            every: "1m".to_string(),
+            offset: Some("10s".to_string()),
+            status_message_template: None,
+            description: None,
+            active: None,
+            thresholds: vec![CheckThreshold {
+                threshold_type: "greater".to_string(),
+                level: "crit".to_string(),
+                value: Some(90.0),
+                min: None,
+                max: None,
+                within: None,
+                all_values: false,
+            }],
+            deadman: None,
+        }
+    }
+
+    #[test]
+    fn test_build_threshold_check() {
+        let body = build_check_body(&threshold_check(), "org1").unwrap();
+        assert_eq!(body["type"], "threshold");
+        assert_eq!(body["orgID"], "org1");
+        assert_eq!(body["status"], "active");
+        assert_eq!(body["offset"], "10s");
+        assert_eq!(body["thresholds"][0]["level"], "CRIT");
+        assert_eq!(body["thresholds"][0]["value"], 90.0);
+        assert_eq!(body["statusMessageTemplate"], DEFAULT_STATUS_MESSAGE);
+
+        let parsed = parse_check(&body);
+        assert_eq!(parsed.check_type, "threshold");
+        assert_eq!(parsed.thresholds.len(), 1);
+        assert!(parsed.deadman.is_none());
+        assert!(parsed.query.starts_with("from(bucket"));
+    }
+
+    #[test]
+    fn test_build_check_validation() {
+        let mut request = threshold_check();
+        request.thresholds.clear();
+        assert!(build_check_body(&request, "org1").is_err());
+
+        let mut request = threshold_check();
+        request.thresholds[0].threshold_type = "range".to_string();
+        request.thresholds[0].min = Some(10.0);
+        assert!(build_check_body(&request, "org1").is_err());
+        request.thresholds[0].max = Some(5.0);
+        assert!(build_check_body(&request, "org1").is_err());
+        request.thresholds[0].max = Some(50.0);
+        assert!(build_check_body(&request, "org1").is_ok());
+
+        let mut request = threshold_check();
+        request.check_type = "deadman".to_string();
+        assert!(build_check_body(&request, "org1").is_err());
+        request.deadman = Some(DeadmanConfig {
+            time_since: "90s".to_string(),
+            stale_time: "10m".to_string(),
+            level: "warn".to_string(),
+            report_zero: false,
+        });
+        let body = build_check_body(&request, "org1").unwrap();
+        assert_eq!(body["timeSince"], "90s");
+        assert_eq!(body["level"], "WARN");
+        assert_eq!(parse_check(&body).deadman.unwrap().stale_time, "10m");
+    }
+
+    fn endpoint(endpoint_type: &str) -> SaveNotificationEndpointRequest {
+        SaveNotificationEndpointRequest {
+            name: "ops".to_string(),
+            org_id: None,
+            endpoint_type: endpoint_type.to_string(),
+            description: None,
+            active: Some(false),
+            url: None,
+            method: None,
+            auth_method: None,
+            username: None,
+            password: None,
+            token: None,
+            headers: HashMap::new(),
+            client_url: None,
+            routing_key: None,
+        }
+    }
+
+    #[test]
+    fn test_build_endpoint_body() {
+        let mut http = endpoint("http");
+        assert!(build_endpoint_body(&http, "org1").is_err());
+        http.url = Some("http://hook.local/alert".to_string());
+        http.auth_method = Some("bearer".to_string());
+        assert!(build_endpoint_body(&http, "org1").is_err());
+        http.token = Some("secret".to_string());
+        let body = build_endpoint_body(&http, "org1").unwrap();
+        assert_eq!(body["method"], "POST");
+        assert_eq!(body["status"], "inactive");
+        assert_eq!(body["token"], "secret");
+
+        let mut slack = endpoint("slack");
+        assert!(build_endpoint_body(&slack, "org1").is_err());
+        slack.url = Some("https://hooks.slack.com/x".to_string());
+        assert!(build_endpoint_body(&slack, "org1").is_ok());
+
+        let mut pagerduty = endpoint("pagerduty");
+        assert!(build_endpoint_body(&pagerduty, "org1").is_err());
+        pagerduty.routing_key = Some("key".to_string());
+        assert_eq!(build_endpoint_body(&pagerduty, "org1").unwrap()["routingKey"], "key");
+
+        assert!(build_endpoint_body(&endpoint("email"), "org1").is_err());
+    }
+
+    #[test]
+    fn test_parse_endpoint_hides_secrets() {
+        let value = json!({
+            "id": "e1", "name": "ops", "orgID": "org1", "type": "http",
+            "url": "http://hook", "authMethod": "basic",
+            "username": "secret: e1-username", "password": "secret: e1-password",
+            "headers": {"X-Team": "ops"}
+        });
+        let parsed = parse_endpoint(&value);
+        assert_eq!(parsed.username, None);
+        assert_eq!(parsed.headers.get("X-Team").map(|s| s.as_str()), Some("ops"));
+    }
+
+    #[test]
+    fn test_build_rule_body() {
+        let mut request = SaveNotificationRuleRequest {
+            name: "crit to slack".to_string(),
+            org_id: None,
+            endpoint_id: "e1".to_string(),
+            every: "1m".to_string(),
+            offset: None,
+            description: None,
+            active: None,
+            status_rules: vec![StatusRule {
+                current_level: "crit".to_string(),
+                previous_level: Some("ok".to_string()),
+            }],
+            tag_rules: vec![TagRule {
+                key: "host".to_string(),
+                value: "web-.*".to_string(),
+                operator: "equalregex".to_string(),
+            }],
+            message_template: None,
+            channel: Some("#ops".to_string()),
+        };
+
+        assert!(build_rule_body(&request, "org1", "slack").is_err());
+        assert!(build_rule_body(&request, "org1", "http").is_ok());
+
+        request.message_template = Some("${ r._message }".to_string());
+        let body = build_rule_body(&request, "org1", "slack").unwrap();
+        assert_eq!(body["statusRules"][0]["currentLevel"], "CRIT");
+        assert_eq!(body["statusRules"][0]["previousLevel"], "OK");
+        assert_eq!(body["channel"], "#ops");
+
+        let parsed = parse_rule(&body);
+        assert_eq!(parsed.rule_type, "slack");
+        assert_eq!(parsed.tag_rules[0].operator, "equalregex");
+
+        request.tag_rules[0].operator = "like".to_string();
+        assert!(build_rule_body(&request, "org1", "slack").is_err());
+    }
+
+    #[test]
+    fn test_statuses_flux_and_csv() {
+        let flux = build_statuses_flux(Some("0a1b2c"), "24h", 50).unwrap();
+        assert!(flux.contains("range(start: -24h)"));
+        assert!(flux.contains("r._check_id == \"0a1b2c\""));
+        assert!(flux.contains("limit(n: 50)"));
+        assert!(build_statuses_flux(Some("x\" or true"), "24h", 50).is_err());
+        assert!(build_statuses_flux(None, "yesterday", 50).is_err());
+
+        let csv_data = ",result,table,_time,_check_id,_check_name,_level,_value\r\n\
+,_result,0,2024-01-01T00:00:00Z,c1,cpu,crit,\"Check: cpu is: crit, value 95\"\r\n\
+,_result,0,2024-01-01T00:01:00Z,c1,cpu,ok,Check: cpu is: ok\r\n\
+\r\n";
+        let statuses = parse_statuses_csv(csv_data).unwrap();
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses[0].level, "OK");
+        assert_eq!(statuses[1].message, "Check: cpu is: crit, value 95");
+        assert_eq!(statuses[1].check_name, "cpu");
+    }
+}
@@ -0,0 +1,404 @@
+/**
+ * InfluxDB 3.x 处理引擎(Processing Engine)辅助函数
+ *
+ * 负责触发器规格与请求体构建、插件文件校验与测试请求构建，
+ * 以及 system.processing_engine_triggers / system.processing_engine_logs 系统表解析
+ */
+
+use crate::commands::influxdb3::{
+    CreateProcessingEngineTriggerRequest, PluginTestResult, ProcessingEngineLogEntry,
+    ProcessingEngineTriggerInfo, TestProcessingEnginePluginRequest,
+};
+use crate::database::influxdb::DataSet;
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// 触发器管理接口
+pub const TRIGGER_PATH: &str = "/api/v3/configure/processing_engine_trigger";
+
+/// 插件文件上传接口
+pub const PLUGIN_FILES_PATH: &str = "/api/v3/plugins/files";
+
+/// 默认读取的日志条数
+pub const DEFAULT_LOG_LIMIT: u32 = 200;
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(|v| v.trim()).filter(|v| !v.is_empty())
+}
+
+/// 根据触发器类型构建 trigger_specification
+///
+/// - wal: `table:<表名>`，未指定表时为 `all_tables`
+/// - schedule: `every:<间隔>` 或 `cron:<表达式>`（二选一）
+/// - request: `request:<路径>`，对应 /api/v3/engine/<路径>
+pub fn build_trigger_specification(request: &CreateProcessingEngineTriggerRequest) -> Result<String> {
+    match request.trigger_type.trim().to_lowercase().as_str() {
+        "wal" => Ok(match non_empty(&request.table) {
+            Some(table) => format!("table:{}", table),
+            None => "all_tables".to_string(),
+        }),
+        "schedule" => match (non_empty(&request.every), non_empty(&request.cron)) {
+            (Some(every), None) => Ok(format!("every:{}", every)),
+            (None, Some(cron)) => Ok(format!("cron:{}", cron)),
+            (Some(_), Some(_)) => Err(anyhow!("every 和 cron 不能同时设置")),
+            (None, None) => Err(anyhow!("定时触发器必须设置 every 或 cron")),
+        },
+        "request" => {
+            let path = non_empty(&request.path)
+                .map(|p| p.trim_start_matches('/'))
+                .filter(|p| !p.is_empty())
+                .ok_or_else(|| anyhow!("HTTP 请求触发器必须设置路径"))?;
+            Ok(format!("request:{}", path))
+        }
+        other => Err(anyhow!("无效的触发器类型: {}，有效值: wal, schedule, request", other)),
+    }
+}
+
+/// 由 trigger_specification 推断触发器类型
+pub fn trigger_type_of(specification: &str) -> &'static str {
+    if specification == "all_tables" || specification.starts_with("table:") {
+        "wal"
+    } else if specification.starts_with("every:") || specification.starts_with("cron:") {
+        "schedule"
+    } else if specification.starts_with("request:") {
+        "request"
+    } else {
+        "unknown"
+    }
+}
+
+/// 校验插件文件名：必须是 .py 文件，且不能包含上级目录或绝对路径
+pub fn validate_plugin_filename(filename: &str) -> Result<()> {
+    let filename = filename.trim();
+    if filename.is_empty() {
+        return Err(anyhow!("插件文件名不能为空"));
+    }
+    if filename.starts_with('/') || filename.starts_with('\\') || filename.split(['/', '\\']).any(|part| part == "..") {
+        return Err(anyhow!("插件文件名不能包含绝对路径或上级目录: {}", filename));
+    }
+    // gh: 前缀表示从插件仓库加载，不要求本地扩展名
+    if !filename.starts_with("gh:") && !filename.ends_with(".py") {
+        return Err(anyhow!("插件文件必须是 Python 文件(.py): {}", filename));
+    }
+    Ok(())
+}
+
+/// 错误处理方式在接口中的取值
+fn error_behavior(value: Option<&str>) -> Result<&'static str> {
+    match value.map(|v| v.trim().to_lowercase()).as_deref() {
+        None | Some("") | Some("log") => Ok("Log"),
+        Some("retry") => Ok("Retry"),
+        Some("disable") => Ok("Disable"),
+        Some(other) => Err(anyhow!("无效的错误处理方式: {}，有效值: log, retry, disable", other)),
+    }
+}
+
+/// 构建创建触发器的请求体
+pub fn build_create_trigger_body(request: &CreateProcessingEngineTriggerRequest) -> Result<Value> {
+    if request.database.trim().is_empty() {
+        return Err(anyhow!("数据库名称不能为空"));
+    }
+    if request.trigger_name.trim().is_empty() {
+        return Err(anyhow!("触发器名称不能为空"));
+    }
+    validate_plugin_filename(&request.plugin_filename)?;
+
+    Ok(json!({
+        "db": request.database,
+        "trigger_name": request.trigger_name.trim(),
+        "plugin_filename": request.plugin_filename.trim(),
+        "trigger_specification": build_trigger_specification(request)?,
+        "trigger_arguments": if request.arguments.is_empty() { Value::Null } else { json!(request.arguments) },
+        "trigger_settings": {
+            "run_async": request.run_async,
+            "error_behavior": error_behavior(request.error_behavior.as_deref())?,
+        },
+        "disabled": request.disabled,
+    }))
+}
+
+/// 构建指定触发器的查询参数
+pub fn build_trigger_query(database: &str, trigger_name: &str, force: bool) -> Result<String> {
+    if database.trim().is_empty() || trigger_name.trim().is_empty() {
+        return Err(anyhow!("数据库名称和触发器名称不能为空"));
+    }
+    let mut query = format!(
+        "?db={}&trigger_name={}",
+        urlencoding::encode(database),
+        urlencoding::encode(trigger_name)
+    );
+    if force {
+        query.push_str("&force=true");
+    }
+    Ok(query)
+}
+
+/// 构建插件测试请求，返回 (接口路径, 请求体)
+pub fn build_plugin_test(request: &TestProcessingEnginePluginRequest) -> Result<(&'static str, Value)> {
+    validate_plugin_filename(&request.filename)?;
+    let arguments = if request.arguments.is_empty() { Value::Null } else { json!(request.arguments) };
+
+    match request.trigger_type.trim().to_lowercase().as_str() {
+        "wal" => {
+            let input_lp = non_empty(&request.input_lp).ok_or_else(|| anyhow!("WAL 插件测试需要提供行协议输入数据"))?;
+            Ok((
+                "/api/v3/plugin_test/wal",
+                json!({
+                    "filename": request.filename.trim(),
+                    "database": request.database,
+                    "input_lp": input_lp,
+                    "input_arguments": arguments,
+                }),
+            ))
+        }
+        "schedule" => {
+            let schedule = non_empty(&request.schedule).ok_or_else(|| anyhow!("定时插件测试需要提供 cron 表达式"))?;
+            Ok((
+                "/api/v3/plugin_test/schedule",
+                json!({
+                    "filename": request.filename.trim(),
+                    "database": request.database,
+                    "schedule": schedule,
+                    "input_arguments": arguments,
+                }),
+            ))
+        }
+        other => Err(anyhow!("不支持测试该类型的插件: {}，有效值: wal, schedule", other)),
+    }
+}
+
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .map(|v| v.as_str().map(|s| s.to_string()).unwrap_or_else(|| v.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 解析插件测试响应
+pub fn parse_plugin_test_response(response: &Value) -> PluginTestResult {
+    let database_writes: HashMap<String, Vec<String>> = response
+        .get("database_writes")
+        .and_then(|w| w.as_object())
+        .map(|writes| {
+            writes
+                .iter()
+                .map(|(database, lines)| (database.clone(), string_list(Some(lines))))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    PluginTestResult {
+        log_lines: string_list(response.get("log_lines")),
+        database_writes,
+        errors: string_list(response.get("errors")),
+        trigger_time: response
+            .get("trigger_time")
+            .and_then(|t| t.as_str())
+            .map(|t| t.to_string()),
+    }
+}
+
+/// 构建读取处理引擎日志的 SQL，按时间倒序
+pub fn build_logs_query(trigger_name: Option<&str>, limit: u32) -> String {
+    let filter = match trigger_name.filter(|t| !t.is_empty()) {
+        Some(name) => format!(" WHERE trigger_name = '{}'", name.replace('\'', "''")),
+        None => String::new(),
+    };
+    format!(
+        "SELECT event_time, trigger_name, log_level, log_text FROM system.processing_engine_logs{} ORDER BY event_time DESC LIMIT {}",
+        filter,
+        limit.max(1)
+    )
+}
+
+fn cell<'a>(dataset: &DataSet, row: &'a [Value], column: &str) -> Option<&'a Value> {
+    let index = dataset.columns.iter().position(|c| c == column)?;
+    row.get(index).filter(|v| !v.is_null())
+}
+
+fn cell_string(dataset: &DataSet, row: &[Value], column: &str) -> Option<String> {
+    cell(dataset, row, column).map(|v| match v {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    })
+}
+
+/// 解析 system.processing_engine_triggers 查询结果
+pub fn parse_triggers(database: &str, dataset: &DataSet) -> Vec<ProcessingEngineTriggerInfo> {
+    let mut triggers: Vec<ProcessingEngineTriggerInfo> = dataset
+        .rows
+        .iter()
+        .filter_map(|row| {
+            let trigger_specification = cell_string(dataset, row, "trigger_specification").unwrap_or_default();
+            Some(ProcessingEngineTriggerInfo {
+                database: database.to_string(),
+                trigger_name: cell_string(dataset, row, "trigger_name")?,
+                plugin_filename: cell_string(dataset, row, "plugin_filename").unwrap_or_default(),
+                trigger_type: trigger_type_of(&trigger_specification).to_string(),
+                trigger_specification,
+                disabled: cell(dataset, row, "disabled")
+                    .map(|v| v.as_bool().unwrap_or_else(|| v.as_str() == Some("true")))
+                    .unwrap_or(false),
+            })
+        })
+        .collect();
+
+    triggers.sort_by(|a, b| a.trigger_name.cmp(&b.trigger_name));
+    triggers
+}
+
+/// 解析 system.processing_engine_logs 查询结果
+pub fn parse_trigger_logs(dataset: &DataSet) -> Vec<ProcessingEngineLogEntry> {
+    dataset
+        .rows
+        .iter()
+        .map(|row| ProcessingEngineLogEntry {
+            event_time: cell_string(dataset, row, "event_time").unwrap_or_default(),
+            trigger_name: cell_string(dataset, row, "trigger_name").unwrap_or_default(),
+            log_level: cell_string(dataset, row, "log_level").unwrap_or_default(),
+            log_text: cell_string(dataset, row, "log_text").unwrap_or_default(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_request(trigger_type: &str) -> CreateProcessingEngineTriggerRequest {
+        CreateProcessingEngineTriggerRequest {
+            database: "metrics".to_string(),
+            trigger_name: "downsample".to_string(),
+            plugin_filename: "downsample.py".to_string(),
+            trigger_type: trigger_type.to_string(),
+            table: None,
+            every: None,
+            cron: None,
+            path: None,
+            arguments: HashMap::new(),
+            disabled: false,
+            run_async: false,
+            error_behavior: None,
+        }
+    }
+
+    #[test]
+    fn test_build_trigger_specification() {
+        let mut request = create_request("wal");
+        assert_eq!(build_trigger_specification(&request).unwrap(), "all_tables");
+        request.table = Some("cpu".to_string());
+        assert_eq!(build_trigger_specification(&request).unwrap(), "table:cpu");
+
+        let mut request = create_request("schedule");
+        assert!(build_trigger_specification(&request).is_err());
+        request.every = Some("1m".to_string());
+        assert_eq!(build_trigger_specification(&request).unwrap(), "every:1m");
+        request.cron = Some("0 0 * * * *".to_string());
+        assert!(build_trigger_specification(&request).is_err());
+        request.every = None;
+        assert_eq!(build_trigger_specification(&request).unwrap(), "cron:0 0 * * * *");
+
+        let mut request = create_request("request");
+        request.path = Some("/webhook".to_string());
+        assert_eq!(build_trigger_specification(&request).unwrap(), "request:webhook");
+
+        assert!(build_trigger_specification(&create_request("http")).is_err());
+        assert_eq!(trigger_type_of("table:cpu"), "wal");
+        assert_eq!(trigger_type_of("cron:0 * * * * *"), "schedule");
+        assert_eq!(trigger_type_of("request:hook"), "request");
+    }
+
+    #[test]
+    fn test_build_create_trigger_body() {
+        let mut request = create_request("schedule");
+        request.every = Some("10s".to_string());
+        request.arguments.insert("window".to_string(), "1h".to_string());
+        request.error_behavior = Some("retry".to_string());
+
+        let body = build_create_trigger_body(&request).unwrap();
+        assert_eq!(body["trigger_specification"], "every:10s");
+        assert_eq!(body["trigger_arguments"]["window"], "1h");
+        assert_eq!(body["trigger_settings"]["error_behavior"], "Retry");
+        assert_eq!(body["disabled"], false);
+
+        request.error_behavior = Some("ignore".to_string());
+        assert!(build_create_trigger_body(&request).is_err());
+        request.error_behavior = None;
+        request.plugin_filename = "../etc/passwd.py".to_string();
+        assert!(build_create_trigger_body(&request).is_err());
+        request.plugin_filename = "plugin.txt".to_string();
+        assert!(build_create_trigger_body(&request).is_err());
+        request.plugin_filename = "gh:examples/schedule/system_metrics".to_string();
+        assert!(build_create_trigger_body(&request).is_ok());
+    }
+
+    #[test]
+    fn test_trigger_query_and_plugin_test() {
+        assert_eq!(
+            build_trigger_query("metrics", "my trigger", true).unwrap(),
+            "?db=metrics&trigger_name=my%20trigger&force=true"
+        );
+        assert!(build_trigger_query("metrics", "", false).is_err());
+
+        let mut request = TestProcessingEnginePluginRequest {
+            database: "metrics".to_string(),
+            filename: "alert.py".to_string(),
+            trigger_type: "wal".to_string(),
+            input_lp: Some("cpu,host=a usage=1".to_string()),
+            schedule: None,
+            arguments: HashMap::new(),
+        };
+        let (path, body) = build_plugin_test(&request).unwrap();
+        assert_eq!(path, "/api/v3/plugin_test/wal");
+        assert_eq!(body["input_lp"], "cpu,host=a usage=1");
+        assert_eq!(body["input_arguments"], Value::Null);
+
+        request.trigger_type = "schedule".to_string();
+        assert!(build_plugin_test(&request).is_err());
+        request.schedule = Some("*/5 * * * * *".to_string());
+        assert_eq!(build_plugin_test(&request).unwrap().0, "/api/v3/plugin_test/schedule");
+
+        let result = parse_plugin_test_response(&json!({
+            "log_lines": ["INFO: processed 1 row"],
+            "database_writes": {"metrics": ["alerts,host=a level=1"]},
+            "errors": []
+        }));
+        assert_eq!(result.log_lines.len(), 1);
+        assert_eq!(result.database_writes["metrics"], vec!["alerts,host=a level=1"]);
+        assert!(result.errors.is_empty());
+        assert_eq!(result.trigger_time, None);
+    }
+
+    #[test]
+    fn test_parse_triggers_and_logs() {
+        let triggers = DataSet::new(
+            ["trigger_name", "plugin_filename", "trigger_specification", "disabled"].iter().map(|c| c.to_string()).collect(),
+            vec![
+                vec![json!("webhook"), json!("hook.py"), json!("request:hook"), json!(true)],
+                vec![json!("downsample"), json!("downsample.py"), json!("every:1m"), json!(false)],
+            ],
+        );
+        let triggers = parse_triggers("metrics", &triggers);
+        assert_eq!(triggers.len(), 2);
+        assert_eq!(triggers[0].trigger_name, "downsample");
+        assert_eq!(triggers[0].trigger_type, "schedule");
+        assert!(triggers[1].disabled);
+
+        assert_eq!(
+            build_logs_query(Some("downsample"), 50),
+            "SELECT event_time, trigger_name, log_level, log_text FROM system.processing_engine_logs WHERE trigger_name = 'downsample' ORDER BY event_time DESC LIMIT 50"
+        );
+        let logs = parse_trigger_logs(&DataSet::new(
+            ["event_time", "trigger_name", "log_level", "log_text"].iter().map(|c| c.to_string()).collect(),
+            vec![vec![json!("2024-01-01T00:00:00"), json!("downsample"), json!("INFO"), json!("done")]],
+        ));
+        assert_eq!(logs[0].log_level, "INFO");
+        assert_eq!(logs[0].log_text, "done");
+    }
+}
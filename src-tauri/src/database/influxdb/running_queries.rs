@@ -0,0 +1,145 @@
+/**
+ * InfluxDB 1.x 运行中查询辅助函数
+ *
+ * 负责 SHOW QUERIES 结果解析和 KILL QUERY 语句构建
+ */
+
+use crate::commands::influxdb1::RunningQueryInfo;
+use crate::database::influxdb::influxql::{quote_identifier, InfluxQLSeries};
+
+/// 将 Go 风格的时长字符串（如 `1m2.5s`、`350ms`、`12µs`）换算为毫秒
+pub fn parse_go_duration_ms(duration: &str) -> Option<f64> {
+    let mut rest = duration.trim();
+    if rest.is_empty() {
+        return None;
+    }
+
+    let mut total = 0.0;
+    while !rest.is_empty() {
+        let number_len = rest
+            .char_indices()
+            .find(|(_, c)| !(c.is_ascii_digit() || *c == '.'))
+            .map(|(i, _)| i)
+            .unwrap_or(rest.len());
+        if number_len == 0 {
+            return None;
+        }
+        let amount: f64 = rest[..number_len].parse().ok()?;
+        rest = &rest[number_len..];
+
+        let units: [(&str, f64); 8] = [
+            ("ns", 1e-6),
+            ("us", 1e-3),
+            ("µs", 1e-3),
+            ("μs", 1e-3),
+            ("ms", 1.0),
+            ("s", 1_000.0),
+            ("m", 60_000.0),
+            ("h", 3_600_000.0),
+        ];
+        let (unit, factor) = units.iter().find(|(unit, _)| rest.starts_with(unit))?;
+        total += amount * factor;
+        rest = &rest[unit.len()..];
+    }
+    Some(total)
+}
+
+/// 解析 SHOW QUERIES 结果，按运行时长降序排列
+pub fn parse_running_queries(series: &[InfluxQLSeries]) -> Vec<RunningQueryInfo> {
+    let mut queries: Vec<RunningQueryInfo> = series
+        .iter()
+        .flat_map(|s| {
+            s.values.iter().filter_map(move |row| {
+                let qid = s.integer(row, "qid").and_then(|v| u64::try_from(v).ok())?;
+                let duration = s.string(row, "duration").unwrap_or_default();
+                Some(RunningQueryInfo {
+                    qid,
+                    query: s.string(row, "query").unwrap_or_default(),
+                    database: s.string(row, "database").filter(|d| !d.is_empty()),
+                    duration_ms: parse_go_duration_ms(&duration),
+                    duration,
+                    status: s.string(row, "status").unwrap_or_else(|| "running".to_string()),
+                    host: s.string(row, "host").filter(|h| !h.is_empty()),
+                })
+            })
+        })
+        .collect();
+
+    queries.sort_by(|a, b| {
+        b.duration_ms
+            .unwrap_or(0.0)
+            .total_cmp(&a.duration_ms.unwrap_or(0.0))
+            .then(a.qid.cmp(&b.qid))
+    });
+    queries
+}
+
+/// 去掉轮询自身发出的 SHOW QUERIES（同名语句中最新的一条）
+pub fn exclude_own_show_queries(mut queries: Vec<RunningQueryInfo>) -> Vec<RunningQueryInfo> {
+    let own_qid = queries
+        .iter()
+        .filter(|q| q.query.trim().trim_end_matches(';').eq_ignore_ascii_case("SHOW QUERIES"))
+        .map(|q| q.qid)
+        .max();
+    if let Some(own_qid) = own_qid {
+        queries.retain(|q| q.qid != own_qid);
+    }
+    queries
+}
+
+/// 构建 KILL QUERY 语句，集群版可指定节点
+pub fn build_kill_query(qid: u64, host: Option<&str>) -> String {
+    match host.filter(|h| !h.is_empty()) {
+        Some(host) => format!("KILL QUERY {} ON {}", qid, quote_identifier(host)),
+        None => format!("KILL QUERY {}", qid),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_go_duration_ms() {
+        assert_eq!(parse_go_duration_ms("350ms"), Some(350.0));
+        assert_eq!(parse_go_duration_ms("1m2.5s"), Some(62_500.0));
+        assert_eq!(parse_go_duration_ms("1h"), Some(3_600_000.0));
+        assert_eq!(parse_go_duration_ms("12µs"), Some(0.012));
+        assert_eq!(parse_go_duration_ms(""), None);
+        assert_eq!(parse_go_duration_ms("5x"), None);
+    }
+
+    #[test]
+    fn test_parse_running_queries() {
+        let series = vec![InfluxQLSeries {
+            columns: ["qid", "query", "database", "duration", "status"]
+                .iter()
+                .map(|c| c.to_string())
+                .collect(),
+            values: vec![
+                vec![json!(36), json!("SHOW QUERIES"), json!(""), json!("43µs"), json!("running")],
+                vec![json!(34), json!("SELECT mean(v) FROM cpu"), json!("telegraf"), json!("12.3s"), json!("running")],
+            ],
+            ..Default::default()
+        }];
+
+        let queries = parse_running_queries(&series);
+        assert_eq!(queries.len(), 2);
+        assert_eq!(queries[0].qid, 34);
+        assert_eq!(queries[0].database.as_deref(), Some("telegraf"));
+        assert_eq!(queries[0].duration_ms, Some(12_300.0));
+        assert_eq!(queries[1].database, None);
+        assert_eq!(queries[1].host, None);
+
+        let queries = exclude_own_show_queries(queries);
+        assert_eq!(queries.len(), 1);
+        assert_eq!(queries[0].qid, 34);
+    }
+
+    #[test]
+    fn test_build_kill_query() {
+        assert_eq!(build_kill_query(36, None), "KILL QUERY 36");
+        assert_eq!(build_kill_query(36, Some("node-1:8088")), "KILL QUERY 36 ON \"node-1:8088\"");
+    }
+}
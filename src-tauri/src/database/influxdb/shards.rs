@@ -0,0 +1,221 @@
+/**
+ * InfluxDB 1.x 分片(Shard)与分片组(Shard Group)辅助函数
+ *
+ * 负责 SHOW SHARDS / SHOW SHARD GROUPS 结果解析、_internal 分片大小统计，
+ * 以及按保留策略汇总的分片时间线
+ */
+
+use crate::commands::influxdb1::{RetentionPolicyTimeline, ShardGroupInfo, ShardInfo, TimeRange1x};
+use crate::database::influxdb::influxql::InfluxQLSeries;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+
+/// 从 _internal 数据库读取各分片最新磁盘占用的查询
+pub const SHARD_SIZE_QUERY: &str =
+    "SELECT last(\"diskBytes\") AS \"size\" FROM \"_internal\".\"monitor\".\"shard\" WHERE time > now() - 10m GROUP BY \"id\"";
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value).ok().map(|t| t.with_timezone(&Utc))
+}
+
+fn unsigned(series: &InfluxQLSeries, row: &[serde_json::Value], column: &str) -> Option<u64> {
+    series.integer(row, column).and_then(|v| u64::try_from(v).ok())
+}
+
+/// 解析 _internal 分片大小查询结果，返回 分片 ID → 字节数
+pub fn parse_shard_sizes(series: &[InfluxQLSeries]) -> HashMap<u64, i64> {
+    series
+        .iter()
+        .filter_map(|s| {
+            let id = s.tags.get("id")?.parse::<u64>().ok()?;
+            let size = s.values.last().and_then(|row| s.integer(row, "size"))?;
+            Some((id, size))
+        })
+        .collect()
+}
+
+/// 解析 SHOW SHARDS 结果（每个数据库一个 series）
+pub fn parse_shards(series: &[InfluxQLSeries], sizes: &HashMap<u64, i64>, now: DateTime<Utc>) -> Vec<ShardInfo> {
+    let mut shards: Vec<ShardInfo> = series
+        .iter()
+        .flat_map(|s| {
+            s.values.iter().filter_map(move |row| {
+                let id = unsigned(s, row, "id")?;
+                let expiry_time = s.string(row, "expiry_time").unwrap_or_default();
+                Some(ShardInfo {
+                    id,
+                    database: s
+                        .string(row, "database")
+                        .or_else(|| s.name.clone())
+                        .unwrap_or_default(),
+                    retention_policy: s.string(row, "retention_policy").unwrap_or_default(),
+                    shard_group: unsigned(s, row, "shard_group").unwrap_or_default(),
+                    start_time: s.string(row, "start_time").unwrap_or_default(),
+                    end_time: s.string(row, "end_time").unwrap_or_default(),
+                    expired: parse_time(&expiry_time).is_some_and(|t| t <= now),
+                    expiry_time,
+                    owners: s.string(row, "owners").filter(|o| !o.is_empty()),
+                    size_bytes: sizes.get(&id).copied(),
+                })
+            })
+        })
+        .collect();
+
+    shards.sort_by(|a, b| (&a.database, &a.retention_policy, &a.start_time, a.id).cmp(&(&b.database, &b.retention_policy, &b.start_time, b.id)));
+    shards
+}
+
+/// 解析 SHOW SHARD GROUPS 结果，并按分片汇总分片数量与大小
+pub fn parse_shard_groups(series: &[InfluxQLSeries], shards: &[ShardInfo]) -> Vec<ShardGroupInfo> {
+    let mut groups: Vec<ShardGroupInfo> = series
+        .iter()
+        .flat_map(|s| {
+            s.values.iter().filter_map(move |row| {
+                let id = unsigned(s, row, "id")?;
+                let members: Vec<&ShardInfo> = shards.iter().filter(|shard| shard.shard_group == id).collect();
+                let sizes: Vec<i64> = members.iter().filter_map(|shard| shard.size_bytes).collect();
+
+                Some(ShardGroupInfo {
+                    id,
+                    database: s.string(row, "database").unwrap_or_default(),
+                    retention_policy: s.string(row, "retention_policy").unwrap_or_default(),
+                    start_time: s.string(row, "start_time").unwrap_or_default(),
+                    end_time: s.string(row, "end_time").unwrap_or_default(),
+                    expiry_time: s.string(row, "expiry_time").unwrap_or_default(),
+                    shard_ids: members.iter().map(|shard| shard.id).collect(),
+                    size_bytes: if sizes.is_empty() { None } else { Some(sizes.iter().sum()) },
+                })
+            })
+        })
+        .collect();
+
+    groups.sort_by(|a, b| (&a.database, &a.retention_policy, &a.start_time, a.id).cmp(&(&b.database, &b.retention_policy, &b.start_time, b.id)));
+    groups
+}
+
+/// 按数据库和保留策略汇总分片组时间线，并标出覆盖范围中的空档
+pub fn build_timelines(groups: &[ShardGroupInfo]) -> Vec<RetentionPolicyTimeline> {
+    let mut by_policy: BTreeMap<(String, String), Vec<ShardGroupInfo>> = BTreeMap::new();
+    for group in groups {
+        by_policy
+            .entry((group.database.clone(), group.retention_policy.clone()))
+            .or_default()
+            .push(group.clone());
+    }
+
+    by_policy
+        .into_iter()
+        .map(|((database, retention_policy), mut groups)| {
+            groups.sort_by_key(|g| parse_time(&g.start_time));
+
+            let mut gaps = Vec::new();
+            for pair in groups.windows(2) {
+                if let (Some(prev_end), Some(next_start)) = (parse_time(&pair[0].end_time), parse_time(&pair[1].start_time)) {
+                    if next_start > prev_end {
+                        gaps.push(TimeRange1x {
+                            start: pair[0].end_time.clone(),
+                            end: pair[1].start_time.clone(),
+                        });
+                    }
+                }
+            }
+
+            let sizes: Vec<i64> = groups.iter().filter_map(|g| g.size_bytes).collect();
+            RetentionPolicyTimeline {
+                database,
+                retention_policy,
+                start_time: groups.first().map(|g| g.start_time.clone()),
+                end_time: groups.last().map(|g| g.end_time.clone()),
+                total_size_bytes: if sizes.is_empty() { None } else { Some(sizes.iter().sum()) },
+                gaps,
+                groups,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn shard_series() -> Vec<InfluxQLSeries> {
+        let columns: Vec<String> = ["id", "database", "retention_policy", "shard_group", "start_time", "end_time", "expiry_time", "owners"]
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        vec![
+            InfluxQLSeries {
+                name: Some("telegraf".to_string()),
+                columns: columns.clone(),
+                values: vec![
+                    vec![json!(3), json!("telegraf"), json!("autogen"), json!(3), json!("2024-01-08T00:00:00Z"), json!("2024-01-15T00:00:00Z"), json!("2024-01-15T00:00:00Z"), json!("")],
+                    vec![json!(1), json!("telegraf"), json!("autogen"), json!(1), json!("2024-01-01T00:00:00Z"), json!("2024-01-08T00:00:00Z"), json!("2024-01-08T00:00:00Z"), json!("")],
+                    vec![json!(5), json!("telegraf"), json!("autogen"), json!(5), json!("2024-01-22T00:00:00Z"), json!("2024-01-29T00:00:00Z"), json!("2024-01-29T00:00:00Z"), json!("")],
+                ],
+                ..Default::default()
+            },
+            InfluxQLSeries {
+                name: Some("_internal".to_string()),
+                columns,
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_parse_shards() {
+        let sizes = parse_shard_sizes(&[InfluxQLSeries {
+            name: Some("shard".to_string()),
+            tags: HashMap::from([("id".to_string(), "1".to_string())]),
+            columns: vec!["time".to_string(), "size".to_string()],
+            values: vec![vec![json!("2024-02-01T00:00:00Z"), json!(4096)]],
+        }]);
+        assert_eq!(sizes.get(&1), Some(&4096));
+
+        let now = Utc.with_ymd_and_hms(2024, 1, 10, 0, 0, 0).unwrap();
+        let shards = parse_shards(&shard_series(), &sizes, now);
+        assert_eq!(shards.len(), 3);
+        assert_eq!(shards[0].id, 1);
+        assert!(shards[0].expired);
+        assert!(!shards[1].expired);
+        assert_eq!(shards[0].size_bytes, Some(4096));
+        assert_eq!(shards[1].size_bytes, None);
+        assert_eq!(shards[0].owners, None);
+    }
+
+    #[test]
+    fn test_groups_and_timeline() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 10, 0, 0, 0).unwrap();
+        let sizes = HashMap::from([(1, 100), (3, 200)]);
+        let shards = parse_shards(&shard_series(), &sizes, now);
+
+        let group_series = vec![InfluxQLSeries {
+            name: Some("shard groups".to_string()),
+            columns: ["id", "database", "retention_policy", "start_time", "end_time", "expiry_time"]
+                .iter()
+                .map(|c| c.to_string())
+                .collect(),
+            values: shards
+                .iter()
+                .map(|s| vec![json!(s.shard_group), json!(s.database), json!(s.retention_policy), json!(s.start_time), json!(s.end_time), json!(s.expiry_time)])
+                .collect(),
+            ..Default::default()
+        }];
+        let groups = parse_shard_groups(&group_series, &shards);
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0].shard_ids, vec![1]);
+        assert_eq!(groups[2].size_bytes, None);
+
+        let timelines = build_timelines(&groups);
+        assert_eq!(timelines.len(), 1);
+        let timeline = &timelines[0];
+        assert_eq!(timeline.start_time.as_deref(), Some("2024-01-01T00:00:00Z"));
+        assert_eq!(timeline.end_time.as_deref(), Some("2024-01-29T00:00:00Z"));
+        assert_eq!(timeline.total_size_bytes, Some(300));
+        assert_eq!(timeline.gaps.len(), 1);
+        assert_eq!(timeline.gaps[0].start, "2024-01-15T00:00:00Z");
+        assert_eq!(timeline.gaps[0].end, "2024-01-22T00:00:00Z");
+    }
+}
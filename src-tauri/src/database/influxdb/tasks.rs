@@ -0,0 +1,237 @@
+/**
+ * InfluxDB 2.x 任务（Task）辅助函数
+ *
+ * 负责 Flux 任务脚本中 `option task = {...}` 头部的生成、剥离和调度参数校验
+ */
+
+use anyhow::{anyhow, Result};
+
+/// 任务调度参数，`every` 与 `cron` 二选一
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskSchedule {
+    pub every: Option<String>,
+    pub cron: Option<String>,
+    pub offset: Option<String>,
+}
+
+impl TaskSchedule {
+    /// 构造调度参数，空字符串视为未设置
+    pub fn new(every: Option<&str>, cron: Option<&str>, offset: Option<&str>) -> Self {
+        let normalize = |value: Option<&str>| {
+            value
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty())
+                .map(|v| v.to_string())
+        };
+
+        Self {
+            every: normalize(every),
+            cron: normalize(cron),
+            offset: normalize(offset),
+        }
+    }
+
+    /// 校验调度参数
+    pub fn validate(&self) -> Result<()> {
+        match (&self.every, &self.cron) {
+            (Some(_), Some(_)) => return Err(anyhow!("every 与 cron 不能同时设置")),
+            (None, None) => return Err(anyhow!("必须设置 every 或 cron 之一")),
+            (Some(every), None) => validate_duration(every)?,
+            (None, Some(cron)) => validate_cron(cron)?,
+        }
+
+        if let Some(offset) = &self.offset {
+            validate_duration(offset)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// 校验 Flux 时长字面量，例如 `10s`、`1h30m`、`1mo`
+pub fn validate_duration(duration: &str) -> Result<()> {
+    const UNITS: [&str; 11] = ["ns", "us", "µs", "ms", "mo", "s", "m", "h", "d", "w", "y"];
+
+    let mut rest = duration.trim();
+    if rest.is_empty() {
+        return Err(anyhow!("时长不能为空"));
+    }
+
+    while !rest.is_empty() {
+        let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+        if digits == 0 {
+            return Err(anyhow!("无效的时长: {}", duration));
+        }
+        rest = &rest[digits..];
+
+        let unit = UNITS
+            .iter()
+            .find(|unit| rest.starts_with(*unit))
+            .ok_or_else(|| anyhow!("无效的时长单位: {}", duration))?;
+        rest = &rest[unit.len()..];
+    }
+
+    Ok(())
+}
+
+/// 校验 cron 表达式的字段数（5 段或带秒的 6 段）
+pub fn validate_cron(cron: &str) -> Result<()> {
+    let fields = cron.split_whitespace().count();
+    if fields == 5 || fields == 6 {
+        Ok(())
+    } else {
+        Err(anyhow!("无效的 cron 表达式: {}", cron))
+    }
+}
+
+/// 移除 Flux 脚本中已有的 `option task = {...}` 声明
+pub fn strip_task_option(flux: &str) -> String {
+    let Some(start) = find_task_option(flux) else {
+        return flux.trim().to_string();
+    };
+
+    let Some(open) = flux[start..].find('{').map(|i| start + i) else {
+        return flux.trim().to_string();
+    };
+
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut end = None;
+
+    for (i, c) in flux[open..].char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_string = true,
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    end = Some(open + i + 1);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    match end {
+        Some(end) => format!("{}{}", &flux[..start], &flux[end..]).trim().to_string(),
+        None => flux.trim().to_string(),
+    }
+}
+
+/// 查找 `option task` 声明的起始位置
+fn find_task_option(flux: &str) -> Option<usize> {
+    let mut offset = 0;
+    for line in flux.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if let Some(rest) = trimmed.strip_prefix("option") {
+            if rest.trim_start().starts_with("task") && rest.starts_with(char::is_whitespace) {
+                return Some(offset + (line.len() - trimmed.len()));
+            }
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// 生成带 `option task` 头部的完整 Flux 任务脚本
+pub fn build_task_flux(name: &str, schedule: &TaskSchedule, flux: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(anyhow!("任务名称不能为空"));
+    }
+    schedule.validate()?;
+
+    let body = strip_task_option(flux);
+    if body.is_empty() {
+        return Err(anyhow!("任务脚本不能为空"));
+    }
+
+    let mut options = vec![format!("name: \"{}\"", escape_flux_string(name))];
+    if let Some(every) = &schedule.every {
+        options.push(format!("every: {}", every));
+    }
+    if let Some(cron) = &schedule.cron {
+        options.push(format!("cron: \"{}\"", escape_flux_string(cron)));
+    }
+    if let Some(offset) = &schedule.offset {
+        options.push(format!("offset: {}", offset));
+    }
+
+    Ok(format!("option task = {{{}}}\n\n{}\n", options.join(", "), body))
+}
+
+fn escape_flux_string(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_duration() {
+        assert!(validate_duration("10s").is_ok());
+        assert!(validate_duration("1h30m").is_ok());
+        assert!(validate_duration("1mo").is_ok());
+        assert!(validate_duration("500ms").is_ok());
+        assert!(validate_duration("").is_err());
+        assert!(validate_duration("h").is_err());
+        assert!(validate_duration("10x").is_err());
+    }
+
+    #[test]
+    fn test_schedule_validation() {
+        assert!(TaskSchedule::new(Some("1h"), None, Some("5m")).validate().is_ok());
+        assert!(TaskSchedule::new(None, Some("0 * * * *"), None).validate().is_ok());
+        assert!(TaskSchedule::new(Some("1h"), Some("0 * * * *"), None).validate().is_err());
+        assert!(TaskSchedule::new(Some(" "), None, None).validate().is_err());
+        assert!(TaskSchedule::new(None, Some("* *"), None).validate().is_err());
+    }
+
+    #[test]
+    fn test_strip_task_option() {
+        let flux = "option task = {name: \"a}b\", every: 1h}\n\nfrom(bucket: \"x\")\n  |> range(start: -1h)";
+        assert_eq!(strip_task_option(flux), "from(bucket: \"x\")\n  |> range(start: -1h)");
+
+        let plain = "from(bucket: \"x\")";
+        assert_eq!(strip_task_option(plain), plain);
+
+        let other = "option now = () => 2024-01-01T00:00:00Z\nfrom(bucket: \"x\")";
+        assert_eq!(strip_task_option(other), other);
+    }
+
+    #[test]
+    fn test_build_task_flux() {
+        let schedule = TaskSchedule::new(Some("1h"), None, Some("10m"));
+        let flux = build_task_flux(
+            "downsample \"cpu\"",
+            &schedule,
+            "option task = {name: \"old\", every: 5m}\nfrom(bucket: \"x\")",
+        )
+        .unwrap();
+
+        assert_eq!(
+            flux,
+            "option task = {name: \"downsample \\\"cpu\\\"\", every: 1h, offset: 10m}\n\nfrom(bucket: \"x\")\n"
+        );
+
+        let cron = TaskSchedule::new(None, Some("0 2 * * *"), None);
+        let flux = build_task_flux("nightly", &cron, "from(bucket: \"x\")").unwrap();
+        assert!(flux.starts_with("option task = {name: \"nightly\", cron: \"0 2 * * *\"}"));
+
+        assert!(build_task_flux("", &cron, "from(bucket: \"x\")").is_err());
+        assert!(build_task_flux("t", &cron, "option task = {name: \"t\", every: 1h}").is_err());
+    }
+}
@@ -0,0 +1,269 @@
+/**
+ * InfluxDB 2.x Telegraf 配置与抓取器(Scraper)辅助函数
+ *
+ * 负责 Telegraf TOML 配置校验、入门配置生成以及相关响应解析
+ */
+
+use crate::commands::influxdb2::{ScraperInfo, TelegrafConfigInfo, TelegrafValidation};
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+/// Telegraf 配置允许的顶层表
+const TOP_LEVEL_TABLES: [&str; 7] = [
+    "agent",
+    "global_tags",
+    "inputs",
+    "outputs",
+    "processors",
+    "aggregators",
+    "secretstores",
+];
+
+fn text(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+/// 收集某类插件（inputs / outputs ...）的名称，同名插件多次出现时按实例数展开
+fn collect_plugins(table: &toml::Table, kind: &str, errors: &mut Vec<String>) -> Vec<String> {
+    let Some(section) = table.get(kind) else {
+        return Vec::new();
+    };
+
+    let Some(plugins) = section.as_table() else {
+        errors.push(format!("[{}] 必须是表", kind));
+        return Vec::new();
+    };
+
+    let mut names = Vec::new();
+    for (name, value) in plugins {
+        match value {
+            toml::Value::Array(instances) => {
+                for instance in instances {
+                    if instance.is_table() {
+                        names.push(name.clone());
+                    } else {
+                        errors.push(format!("{}.{} 必须使用 [[{}.{}]] 声明", kind, name, kind, name));
+                    }
+                }
+            }
+            toml::Value::Table(_) => names.push(name.clone()),
+            _ => errors.push(format!("{}.{} 必须使用 [[{}.{}]] 声明", kind, name, kind, name)),
+        }
+    }
+    names
+}
+
+/// 校验 Telegraf TOML 配置
+pub fn validate_telegraf_toml(config: &str) -> TelegrafValidation {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    let table = match config.parse::<toml::Table>() {
+        Ok(table) => table,
+        Err(e) => {
+            return TelegrafValidation {
+                valid: false,
+                errors: vec![format!("TOML 语法错误: {}", e.message())],
+                warnings,
+                inputs: Vec::new(),
+                outputs: Vec::new(),
+            };
+        }
+    };
+
+    for key in table.keys() {
+        if !TOP_LEVEL_TABLES.contains(&key.as_str()) {
+            warnings.push(format!("未知的顶层配置项: {}", key));
+        }
+    }
+
+    let inputs = collect_plugins(&table, "inputs", &mut errors);
+    let outputs = collect_plugins(&table, "outputs", &mut errors);
+
+    if outputs.is_empty() {
+        errors.push("至少需要一个 outputs 插件".to_string());
+    }
+    if inputs.is_empty() {
+        warnings.push("未配置任何 inputs 插件".to_string());
+    }
+
+    // influxdb_v2 输出缺少关键字段时给出提示
+    if let Some(instances) = table
+        .get("outputs")
+        .and_then(|o| o.get("influxdb_v2"))
+        .and_then(|o| o.as_array())
+    {
+        for instance in instances {
+            for field in ["urls", "token", "bucket"] {
+                if instance.get(field).is_none() {
+                    warnings.push(format!("outputs.influxdb_v2 缺少 {} 配置", field));
+                }
+            }
+        }
+    }
+
+    if table.get("agent").is_none() {
+        warnings.push("未配置 [agent]，将使用 Telegraf 默认值".to_string());
+    }
+
+    TelegrafValidation {
+        valid: errors.is_empty(),
+        errors,
+        warnings,
+        inputs,
+        outputs,
+    }
+}
+
+fn escape_toml_string(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// 生成写入指定存储桶的 Telegraf 入门配置
+pub fn build_starter_config(url: &str, org: &str, bucket: &str, token: &str) -> String {
+    format!(
+        r#"# 由 InfloWave 生成的 Telegraf 入门配置
+[agent]
+  interval = "10s"
+  round_interval = true
+  metric_batch_size = 1000
+  metric_buffer_limit = 10000
+  flush_interval = "10s"
+
+[[outputs.influxdb_v2]]
+  urls = ["{}"]
+  token = "{}"
+  organization = "{}"
+  bucket = "{}"
+
+[[inputs.cpu]]
+  percpu = true
+  totalcpu = true
+  collect_cpu_time = false
+  report_active = false
+
+[[inputs.mem]]
+
+[[inputs.disk]]
+  ignore_fs = ["tmpfs", "devtmpfs", "devfs", "iso9660", "overlay", "aufs", "squashfs"]
+
+[[inputs.system]]
+"#,
+        escape_toml_string(url),
+        escape_toml_string(token),
+        escape_toml_string(org),
+        escape_toml_string(bucket)
+    )
+}
+
+/// 解析 Telegraf 配置响应
+pub fn parse_telegraf(telegraf: &Value) -> TelegrafConfigInfo {
+    let buckets = telegraf
+        .get("metadata")
+        .and_then(|m| m.get("buckets"))
+        .and_then(|b| b.as_array())
+        .map(|buckets| {
+            buckets
+                .iter()
+                .filter_map(|b| b.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    TelegrafConfigInfo {
+        id: text(telegraf, "id").unwrap_or_default(),
+        org_id: text(telegraf, "orgID").unwrap_or_default(),
+        name: text(telegraf, "name").unwrap_or_default(),
+        description: text(telegraf, "description"),
+        config: text(telegraf, "config").unwrap_or_default(),
+        buckets,
+    }
+}
+
+/// 校验抓取目标 URL
+pub fn validate_scraper_url(url: &str) -> Result<()> {
+    let parsed = url::Url::parse(url).map_err(|e| anyhow!("无效的抓取地址 {}: {}", url, e))?;
+    match parsed.scheme() {
+        "http" | "https" => Ok(()),
+        scheme => Err(anyhow!("抓取地址仅支持 http/https，当前为 {}", scheme)),
+    }
+}
+
+/// 解析抓取器响应
+pub fn parse_scraper(scraper: &Value) -> ScraperInfo {
+    ScraperInfo {
+        id: text(scraper, "id").unwrap_or_default(),
+        name: text(scraper, "name").unwrap_or_default(),
+        scraper_type: text(scraper, "type").unwrap_or_else(|| "prometheus".to_string()),
+        url: text(scraper, "url").unwrap_or_default(),
+        org_id: text(scraper, "orgID").unwrap_or_default(),
+        org_name: text(scraper, "org"),
+        bucket_id: text(scraper, "bucketID").unwrap_or_default(),
+        bucket_name: text(scraper, "bucket"),
+        allow_insecure: scraper.get("allowInsecure").and_then(|v| v.as_bool()).unwrap_or(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validate_valid_config() {
+        let config = build_starter_config("http://localhost:8086", "my-org", "telegraf", "t0k3n");
+        let result = validate_telegraf_toml(&config);
+        assert!(result.valid, "{:?}", result.errors);
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+        assert_eq!(result.outputs, vec!["influxdb_v2"]);
+        assert_eq!(result.inputs.len(), 4);
+    }
+
+    #[test]
+    fn test_validate_invalid_config() {
+        let syntax = validate_telegraf_toml("[[inputs.cpu]\npercpu = true");
+        assert!(!syntax.valid);
+        assert!(syntax.errors[0].starts_with("TOML 语法错误"));
+
+        let no_output = validate_telegraf_toml("[[inputs.cpu]]\n[foo]\n");
+        assert!(!no_output.valid);
+        assert!(no_output.warnings.iter().any(|w| w.contains("foo")));
+
+        let missing_token = validate_telegraf_toml(
+            "[agent]\n[[outputs.influxdb_v2]]\nurls = [\"http://x\"]\nbucket = \"b\"\n",
+        );
+        assert!(missing_token.valid);
+        assert!(missing_token.warnings.iter().any(|w| w.contains("token")));
+
+        let bad_plugin = validate_telegraf_toml("inputs = 1\n[[outputs.file]]\n");
+        assert!(!bad_plugin.valid);
+    }
+
+    #[test]
+    fn test_starter_config_escaping() {
+        let config = build_starter_config("http://localhost:8086", "org \"a\"", "b", "t");
+        assert!(config.contains("organization = \"org \\\"a\\\"\""));
+        assert!(validate_telegraf_toml(&config).valid);
+    }
+
+    #[test]
+    fn test_parse_responses() {
+        let telegraf = parse_telegraf(&json!({
+            "id": "t1", "orgID": "o1", "name": "host metrics",
+            "config": "[agent]", "metadata": {"buckets": ["telegraf"]}
+        }));
+        assert_eq!(telegraf.buckets, vec!["telegraf"]);
+        assert_eq!(telegraf.description, None);
+
+        let scraper = parse_scraper(&json!({
+            "id": "s1", "name": "node", "url": "http://node:9100/metrics",
+            "orgID": "o1", "bucketID": "b1", "allowInsecure": true
+        }));
+        assert_eq!(scraper.scraper_type, "prometheus");
+        assert!(scraper.allow_insecure);
+
+        assert!(validate_scraper_url("http://node:9100/metrics").is_ok());
+        assert!(validate_scraper_url("ftp://node/metrics").is_err());
+        assert!(validate_scraper_url("not a url").is_err());
+    }
+}
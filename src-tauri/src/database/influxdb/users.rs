@@ -0,0 +1,311 @@
+/**
+ * InfluxDB 1.x 用户与权限辅助函数
+ *
+ * 负责 CREATE USER / SET PASSWORD / GRANT / REVOKE 语句构建、
+ * SHOW USERS 与 SHOW GRANTS 结果解析以及按数据库汇总的权限矩阵
+ */
+
+use crate::commands::influxdb1::{DatabaseGrant1x, InfluxUser1xInfo, PrivilegeMatrix, PrivilegeMatrixRow};
+use crate::database::influxdb::influxql::{quote_identifier, quote_string, InfluxQLSeries};
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+
+/// 校验用户名
+pub fn validate_username(name: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        Err(anyhow!("用户名不能为空"))
+    } else {
+        Ok(())
+    }
+}
+
+/// 校验密码
+pub fn validate_password(password: &str) -> Result<()> {
+    if password.is_empty() {
+        Err(anyhow!("密码不能为空"))
+    } else {
+        Ok(())
+    }
+}
+
+/// 将权限名称（READ / WRITE / ALL [PRIVILEGES] / NO PRIVILEGES）解析为读写标记
+pub fn parse_privilege(privilege: &str) -> Result<(bool, bool)> {
+    match privilege.trim().to_uppercase().as_str() {
+        "READ" => Ok((true, false)),
+        "WRITE" => Ok((false, true)),
+        "ALL" | "ALL PRIVILEGES" => Ok((true, true)),
+        "NO PRIVILEGES" | "NONE" | "" => Ok((false, false)),
+        other => Err(anyhow!("无效的权限: {}，有效值: READ, WRITE, ALL", other)),
+    }
+}
+
+/// 读写标记对应的 GRANT 权限关键字，无权限时返回 None
+pub fn privilege_keyword(read: bool, write: bool) -> Option<&'static str> {
+    match (read, write) {
+        (true, true) => Some("ALL"),
+        (true, false) => Some("READ"),
+        (false, true) => Some("WRITE"),
+        (false, false) => None,
+    }
+}
+
+/// 构建 CREATE USER 语句
+pub fn build_create_user(name: &str, password: &str, admin: bool) -> Result<String> {
+    validate_username(name)?;
+    validate_password(password)?;
+
+    let mut statement = format!(
+        "CREATE USER {} WITH PASSWORD {}",
+        quote_identifier(name),
+        quote_string(password)
+    );
+    if admin {
+        statement.push_str(" WITH ALL PRIVILEGES");
+    }
+    Ok(statement)
+}
+
+/// 构建 SET PASSWORD 语句
+pub fn build_set_password(name: &str, password: &str) -> Result<String> {
+    validate_username(name)?;
+    validate_password(password)?;
+    Ok(format!("SET PASSWORD FOR {} = {}", quote_identifier(name), quote_string(password)))
+}
+
+/// 构建授予/收回管理员权限的语句
+pub fn build_set_admin(name: &str, admin: bool) -> Result<String> {
+    validate_username(name)?;
+    Ok(if admin {
+        format!("GRANT ALL PRIVILEGES TO {}", quote_identifier(name))
+    } else {
+        format!("REVOKE ALL PRIVILEGES FROM {}", quote_identifier(name))
+    })
+}
+
+/// 构建 GRANT 语句
+pub fn build_grant(name: &str, database: &str, privilege: &str) -> Result<String> {
+    validate_username(name)?;
+    let (read, write) = parse_privilege(privilege)?;
+    let keyword = privilege_keyword(read, write).ok_or_else(|| anyhow!("请指定要授予的权限"))?;
+    Ok(format!(
+        "GRANT {} ON {} TO {}",
+        keyword,
+        quote_identifier(database),
+        quote_identifier(name)
+    ))
+}
+
+/// 构建 REVOKE 语句
+pub fn build_revoke(name: &str, database: &str, privilege: &str) -> Result<String> {
+    validate_username(name)?;
+    let (read, write) = parse_privilege(privilege)?;
+    let keyword = privilege_keyword(read, write).ok_or_else(|| anyhow!("请指定要收回的权限"))?;
+    Ok(format!(
+        "REVOKE {} ON {} FROM {}",
+        keyword,
+        quote_identifier(database),
+        quote_identifier(name)
+    ))
+}
+
+/// 根据当前权限构建将用户在某数据库上的权限调整为目标读写组合的单条语句，无需变更时返回 None
+///
+/// GRANT 会直接覆盖该数据库上的权限，REVOKE 只清除指定的位，
+/// 因此任何变更都只需一条语句，不会出现先收回再授予之间失败导致权限丢失的情况
+pub fn build_set_privileges(
+    name: &str,
+    database: &str,
+    current: (bool, bool),
+    target: (bool, bool),
+) -> Result<Option<String>> {
+    let (read, write) = target;
+    let (current_read, current_write) = current;
+    if current == target {
+        return Ok(None);
+    }
+
+    let added = (read && !current_read) || (write && !current_write);
+    let statement = match privilege_keyword(read, write) {
+        None => build_revoke(name, database, "ALL")?,
+        Some(keyword) if added => build_grant(name, database, keyword)?,
+        // 只收回部分权限时使用 REVOKE，保留其余部分
+        Some(_) => {
+            let removed = privilege_keyword(current_read && !read, current_write && !write).unwrap_or_default();
+            build_revoke(name, database, removed)?
+        }
+    };
+    Ok(Some(statement))
+}
+
+/// 解析 SHOW USERS 结果
+pub fn parse_users(series: &[InfluxQLSeries]) -> Vec<InfluxUser1xInfo> {
+    series
+        .iter()
+        .flat_map(|s| {
+            s.values.iter().filter_map(move |row| {
+                Some(InfluxUser1xInfo {
+                    name: s.string(row, "user")?,
+                    admin: s.boolean(row, "admin").unwrap_or(false),
+                })
+            })
+        })
+        .collect()
+}
+
+/// 解析 SHOW GRANTS FOR 结果，忽略 NO PRIVILEGES 记录
+pub fn parse_grants(series: &[InfluxQLSeries]) -> Vec<DatabaseGrant1x> {
+    series
+        .iter()
+        .flat_map(|s| {
+            s.values.iter().filter_map(move |row| {
+                let database = s.string(row, "database")?;
+                let privilege = s.string(row, "privilege").unwrap_or_default();
+                let (read, write) = parse_privilege(&privilege).ok()?;
+                if !read && !write {
+                    return None;
+                }
+                Some(DatabaseGrant1x {
+                    database,
+                    privilege: privilege_keyword(read, write).unwrap_or_default().to_string(),
+                    read,
+                    write,
+                })
+            })
+        })
+        .collect()
+}
+
+/// 构建按数据库展开的权限矩阵；管理员拥有全部数据库的读写权限
+pub fn build_privilege_matrix(
+    users: &[InfluxUser1xInfo],
+    databases: &[String],
+    grants: &HashMap<String, Vec<DatabaseGrant1x>>,
+) -> PrivilegeMatrix {
+    let rows = users
+        .iter()
+        .map(|user| {
+            let user_grants = grants.get(&user.name).map(|g| g.as_slice()).unwrap_or_default();
+            let privileges = databases
+                .iter()
+                .map(|database| {
+                    let grant = user_grants.iter().find(|g| &g.database == database);
+                    let read = user.admin || grant.is_some_and(|g| g.read);
+                    let write = user.admin || grant.is_some_and(|g| g.write);
+                    DatabaseGrant1x {
+                        database: database.clone(),
+                        privilege: privilege_keyword(read, write).unwrap_or("NONE").to_string(),
+                        read,
+                        write,
+                    }
+                })
+                .collect();
+
+            PrivilegeMatrixRow {
+                user: user.name.clone(),
+                admin: user.admin,
+                privileges,
+            }
+        })
+        .collect();
+
+    PrivilegeMatrix {
+        databases: databases.to_vec(),
+        rows,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_build_statements() {
+        assert_eq!(
+            build_create_user("ops", "pa'ss", true).unwrap(),
+            "CREATE USER \"ops\" WITH PASSWORD 'pa\\'ss' WITH ALL PRIVILEGES"
+        );
+        assert_eq!(
+            build_create_user("reader", "secret", false).unwrap(),
+            "CREATE USER \"reader\" WITH PASSWORD 'secret'"
+        );
+        assert!(build_create_user("", "secret", false).is_err());
+        assert!(build_create_user("ops", "", false).is_err());
+
+        assert_eq!(build_set_password("ops", "new").unwrap(), "SET PASSWORD FOR \"ops\" = 'new'");
+        assert_eq!(build_set_admin("ops", false).unwrap(), "REVOKE ALL PRIVILEGES FROM \"ops\"");
+        assert_eq!(build_grant("ops", "telegraf", "read").unwrap(), "GRANT READ ON \"telegraf\" TO \"ops\"");
+        assert_eq!(
+            build_revoke("ops", "telegraf", "ALL PRIVILEGES").unwrap(),
+            "REVOKE ALL ON \"telegraf\" FROM \"ops\""
+        );
+        assert!(build_grant("ops", "telegraf", "admin").is_err());
+        assert!(build_grant("ops", "telegraf", "NONE").is_err());
+
+        assert_eq!(
+            build_set_privileges("ops", "telegraf", (true, false), (false, true)).unwrap().as_deref(),
+            Some("GRANT WRITE ON \"telegraf\" TO \"ops\"")
+        );
+        assert_eq!(
+            build_set_privileges("ops", "telegraf", (true, true), (true, false)).unwrap().as_deref(),
+            Some("REVOKE WRITE ON \"telegraf\" FROM \"ops\"")
+        );
+        assert_eq!(
+            build_set_privileges("ops", "telegraf", (false, true), (false, false)).unwrap().as_deref(),
+            Some("REVOKE ALL ON \"telegraf\" FROM \"ops\"")
+        );
+        assert_eq!(build_set_privileges("ops", "telegraf", (true, false), (true, false)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_users_and_grants() {
+        let users = parse_users(&[InfluxQLSeries {
+            columns: vec!["user".to_string(), "admin".to_string()],
+            values: vec![vec![json!("admin"), json!(true)], vec![json!("reader"), json!(false)]],
+            ..Default::default()
+        }]);
+        assert_eq!(users.len(), 2);
+        assert!(users[0].admin);
+        assert!(!users[1].admin);
+
+        let grants = parse_grants(&[InfluxQLSeries {
+            columns: vec!["database".to_string(), "privilege".to_string()],
+            values: vec![
+                vec![json!("telegraf"), json!("READ")],
+                vec![json!("app"), json!("ALL PRIVILEGES")],
+                vec![json!("old"), json!("NO PRIVILEGES")],
+            ],
+            ..Default::default()
+        }]);
+        assert_eq!(grants.len(), 2);
+        assert_eq!(grants[0].privilege, "READ");
+        assert!(grants[1].read && grants[1].write);
+        assert_eq!(grants[1].privilege, "ALL");
+    }
+
+    #[test]
+    fn test_build_privilege_matrix() {
+        let users = vec![
+            InfluxUser1xInfo { name: "admin".to_string(), admin: true },
+            InfluxUser1xInfo { name: "reader".to_string(), admin: false },
+        ];
+        let databases = vec!["app".to_string(), "telegraf".to_string()];
+        let mut grants = HashMap::new();
+        grants.insert(
+            "reader".to_string(),
+            vec![DatabaseGrant1x {
+                database: "telegraf".to_string(),
+                privilege: "READ".to_string(),
+                read: true,
+                write: false,
+            }],
+        );
+
+        let matrix = build_privilege_matrix(&users, &databases, &grants);
+        assert_eq!(matrix.databases, databases);
+        assert_eq!(matrix.rows.len(), 2);
+        assert!(matrix.rows[0].privileges.iter().all(|p| p.privilege == "ALL"));
+        assert_eq!(matrix.rows[1].privileges[0].privilege, "NONE");
+        assert_eq!(matrix.rows[1].privileges[1].privilege, "READ");
+    }
+}
@@ -14,6 +14,7 @@ use super::{
     detector::InfluxDetector,
 };
 use crate::models::ConnectionConfig;
+use crate::utils::query_params;
 use anyhow::Result;
 use async_trait::async_trait;
 use log::{debug, info};
@@ -42,7 +43,7 @@ impl V1HttpDriver {
         
         let client = Client::builder()
             .timeout(Duration::from_secs(config.timeout as u64))
-            .danger_accept_invalid_certs(!config.ssl)
+            .danger_accept_invalid_certs(config.skip_ssl_verification)
             .build()?;
         
         // 创建默认的 1.x 能力描述
@@ -199,7 +200,12 @@ impl InfluxDriver for V1HttpDriver {
         let start_time = Instant::now();
         let url = self.build_query_url(&query.text, query.database.as_deref());
         
-        let request = self.build_authenticated_request(&url);
+        let mut request = self.build_authenticated_request(&url);
+        if !query.params.is_empty() {
+            // InfluxQL 原生支持 $name 绑定参数
+            let params = query_params::to_influxql_params(&query.text, &query.params)?;
+            request = request.query(&[("params", params)]);
+        }
         let response = request.send().await
             .map_err(|e| anyhow::anyhow!("查询请求失败: {}", e))?;
         
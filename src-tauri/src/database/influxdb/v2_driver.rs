@@ -14,6 +14,7 @@ use super::{
     detector::InfluxDetector,
 };
 use crate::models::ConnectionConfig;
+use crate::utils::query_params::{self, ParamDialect};
 use anyhow::Result;
 use async_trait::async_trait;
 use log::{debug, info, warn};
@@ -47,7 +48,7 @@ impl V2HttpDriver {
         
         let client = Client::builder()
             .timeout(Duration::from_secs(config.timeout as u64))
-            .danger_accept_invalid_certs(!config.ssl)
+            .danger_accept_invalid_certs(config.skip_ssl_verification)
             .build()?;
         
         // 创建默认的 2.x 能力描述
@@ -233,10 +234,20 @@ impl InfluxDriver for V2HttpDriver {
         
         let dataset = match query.language {
             QueryLanguage::Flux => {
-                let request_body = json!({
-                    "query": query.text,
-                    "type": "flux"
-                });
+                let request_body = if query.params.is_empty() {
+                    json!({
+                        "query": query.text,
+                        "type": "flux"
+                    })
+                } else {
+                    // $name 改写为 params.name，参数随请求体一起提交
+                    let (text, params) = query_params::to_flux_params(&query.text, &query.params)?;
+                    json!({
+                        "query": text,
+                        "type": "flux",
+                        "params": params
+                    })
+                };
                 
                 let request = self.build_authenticated_request(reqwest::Method::POST, &url)
                     .header("Content-Type", "application/json")
@@ -260,8 +271,14 @@ impl InfluxDriver for V2HttpDriver {
             }
             
             QueryLanguage::InfluxQL => {
+                // v1 兼容接口不保证支持 params，参数转义后内联
+                let text = if query.params.is_empty() {
+                    query.text.clone()
+                } else {
+                    query_params::bind_inline(&query.text, &query.params, ParamDialect::InfluxQL)?
+                };
                 let request = self.build_authenticated_request(reqwest::Method::GET, &url)
-                    .query(&[("q", &query.text)])
+                    .query(&[("q", &text)])
                     .query(&[("db", query.database.as_deref().unwrap_or(""))]);
                 
                 let response = request.send().await
@@ -12,8 +12,10 @@ use super::{
         RetentionPolicyInfo, RetentionPolicyConfig,
     },
     detector::InfluxDetector,
+    arrow_result::ArrowResult,
 };
-use crate::models::ConnectionConfig;
+use crate::models::{ConnectionConfig, QueryParamValue, QueryParams};
+use crate::utils::query_params::{self, ParamDialect};
 use anyhow::Result;
 use async_trait::async_trait;
 use log::{debug, info, warn};
@@ -82,20 +84,72 @@ impl FlightSqlDriver {
         Ok(client_guard.as_ref().unwrap().clone())
     }
     
-    /// 执行 SQL 查询
-    async fn execute_sql_query(&self, sql: &str) -> Result<DataSet> {
+    /// 执行 SQL 查询并保留 Arrow 列式结果，InfluxDB 3.x 通过 database 请求头指定目标数据库；
+    /// 带参数时使用预编译语句绑定
+    async fn execute_arrow_query(&self, sql: &str, database: Option<&str>, params: &QueryParams) -> Result<ArrowResult> {
         debug!("执行 SQL 查询: {}", sql);
 
+        let start_time = Instant::now();
         let mut client = self.get_client().await?;
+        if !self.token.is_empty() {
+            client.set_token(self.token.clone());
+        }
+        if let Some(database) = database.or(self.config.database.as_deref()) {
+            client.set_header("database", database);
+        }
+
+        let (text, values) = if params.is_empty() {
+            (sql.to_string(), Vec::new())
+        } else {
+            query_params::to_positional_params(sql, params, ParamDialect::Sql)?
+        };
+        let mut prepared = None;
+        let flight_info = if values.is_empty() {
+            // 使用 FlightSqlServiceClient 的 execute 方法
+            client.execute(text, None).await
+                .map_err(|e| anyhow::anyhow!("执行 SQL 查询失败: {}", e))
+        } else {
+            let mut statement = client.prepare(text, None).await
+                .map_err(|e| anyhow::anyhow!("创建预编译语句失败: {}", e))?;
+            let executed = match Self::build_parameter_batch(&values) {
+                Ok(batch) => match statement.set_parameters(batch) {
+                    Ok(()) => statement.execute().await
+                        .map_err(|e| anyhow::anyhow!("执行预编译语句失败: {}", e)),
+                    Err(e) => Err(anyhow::anyhow!("绑定查询参数失败: {}", e)),
+                },
+                Err(e) => Err(e),
+            };
+            prepared = Some(statement);
+            executed
+        };
+
+        // 先取完所有端点的数据再关闭预编译语句，执行或取数失败时同样关闭
+        let fetched = match flight_info {
+            Ok(flight_info) => Self::fetch_batches(&mut client, flight_info).await,
+            Err(e) => Err(e),
+        };
+        if let Some(statement) = prepared {
+            if let Err(e) = statement.close().await {
+                warn!("关闭预编译语句失败: {}", e);
+            }
+        }
+        let (schema, batches) = fetched?;
+
+        let mut result = ArrowResult::new(schema, batches);
+        result.execution_time = Some(start_time.elapsed().as_millis() as u64);
+        Ok(result)
+    }
 
-        // 使用 FlightSqlServiceClient 的 execute 方法
-        let flight_info = client.execute(sql.to_string(), None).await
-            .map_err(|e| anyhow::anyhow!("执行 SQL 查询失败: {}", e))?;
+    /// 依次从各端点拉取数据批次
+    async fn fetch_batches(
+        client: &mut FlightSqlServiceClient<Channel>,
+        flight_info: arrow_flight::FlightInfo,
+    ) -> Result<(Option<arrow::datatypes::SchemaRef>, Vec<arrow::record_batch::RecordBatch>)> {
+        use futures_util::TryStreamExt;
 
-        // 执行查询并获取结果
-        let mut all_rows = Vec::new();
-        let mut columns = Vec::new();
+        let schema = flight_info.clone().try_decode_schema().ok().map(std::sync::Arc::new);
 
+        let mut batches = Vec::new();
         for endpoint in flight_info.endpoint {
             if let Some(ticket) = endpoint.ticket {
                 // 使用 do_get 获取数据流
@@ -103,104 +157,61 @@ impl FlightSqlDriver {
                     .map_err(|e| anyhow::anyhow!("获取数据流失败: {}", e))?;
 
                 // 使用 try_collect 收集所有 RecordBatch
-                use futures_util::TryStreamExt;
-                let batches: Vec<arrow::record_batch::RecordBatch> = stream.try_collect().await
+                let mut endpoint_batches: Vec<arrow::record_batch::RecordBatch> = stream.try_collect().await
                     .map_err(|e| anyhow::anyhow!("收集数据批次失败: {}", e))?;
+                batches.append(&mut endpoint_batches);
+            }
+        }
+        Ok((schema, batches))
+    }
 
-                for record_batch in batches {
-                    // 提取列名（只在第一次）
-                    if columns.is_empty() {
-                        columns = record_batch.schema()
-                            .fields()
-                            .iter()
-                            .map(|field| field.name().clone())
-                            .collect();
-                    }
-
-                    // 转换数据行
-                    for row_idx in 0..record_batch.num_rows() {
-                        let mut row = Vec::new();
-                        for col_idx in 0..record_batch.num_columns() {
-                            let column = record_batch.column(col_idx);
-                            let value = self.arrow_value_to_json(column, row_idx)?;
-                            row.push(value);
-                        }
-                        all_rows.push(row);
-                    }
+    /// 构建预编译语句的参数批次：单行，第 N 列对应 $N
+    fn build_parameter_batch(values: &[(String, QueryParamValue)]) -> Result<arrow::record_batch::RecordBatch> {
+        use arrow::array::{ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray, TimestampNanosecondArray};
+        use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+        use std::sync::Arc;
+
+        let mut fields = Vec::with_capacity(values.len());
+        let mut columns: Vec<ArrayRef> = Vec::with_capacity(values.len());
+        for (index, (name, value)) in values.iter().enumerate() {
+            let (data_type, column): (DataType, ArrayRef) = match value {
+                QueryParamValue::String(text) => (DataType::Utf8, Arc::new(StringArray::from(vec![text.as_str()]))),
+                QueryParamValue::Integer(number) => (DataType::Int64, Arc::new(Int64Array::from(vec![*number]))),
+                QueryParamValue::Float(number) => (DataType::Float64, Arc::new(Float64Array::from(vec![*number]))),
+                QueryParamValue::Boolean(flag) => (DataType::Boolean, Arc::new(BooleanArray::from(vec![*flag]))),
+                QueryParamValue::Time(time) => {
+                    let nanos = query_params::parse_time(time)?
+                        .timestamp_nanos_opt()
+                        .ok_or_else(|| anyhow::anyhow!("参数 ${} 超出纳秒时间戳范围", name))?;
+                    (
+                        DataType::Timestamp(TimeUnit::Nanosecond, None),
+                        Arc::new(TimestampNanosecondArray::from(vec![nanos])),
+                    )
                 }
-            }
+                QueryParamValue::Duration(_) => {
+                    return Err(anyhow::anyhow!("参数 ${} 为时长类型，不能作为预编译语句参数", name));
+                }
+            };
+            fields.push(Field::new(format!("${}", index + 1), data_type, false));
+            columns.push(column);
         }
 
-        Ok(DataSet::new(columns, all_rows))
+        arrow::record_batch::RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+            .map_err(|e| anyhow::anyhow!("构建参数批次失败: {}", e))
     }
-    
-    /// 将 Arrow 值转换为 JSON 值
-    fn arrow_value_to_json(&self, column: &dyn arrow::array::Array, row_idx: usize) -> Result<serde_json::Value> {
-        use arrow::array::*;
-        use arrow::datatypes::DataType;
-        
-        if column.is_null(row_idx) {
-            return Ok(serde_json::Value::Null);
-        }
-        
-        match column.data_type() {
-            DataType::Boolean => {
-                let array = column.as_any().downcast_ref::<BooleanArray>()
-                    .ok_or_else(|| anyhow::anyhow!("类型转换失败: Boolean"))?;
-                Ok(serde_json::Value::Bool(array.value(row_idx)))
-            }
-            DataType::Int32 => {
-                let array = column.as_any().downcast_ref::<Int32Array>()
-                    .ok_or_else(|| anyhow::anyhow!("类型转换失败: Int32"))?;
-                Ok(serde_json::Value::Number(serde_json::Number::from(array.value(row_idx))))
-            }
-            DataType::Int64 => {
-                let array = column.as_any().downcast_ref::<Int64Array>()
-                    .ok_or_else(|| anyhow::anyhow!("类型转换失败: Int64"))?;
-                Ok(serde_json::Value::Number(serde_json::Number::from(array.value(row_idx))))
-            }
-            DataType::Float32 => {
-                let array = column.as_any().downcast_ref::<Float32Array>()
-                    .ok_or_else(|| anyhow::anyhow!("类型转换失败: Float32"))?;
-                let value = array.value(row_idx);
-                Ok(serde_json::Value::Number(
-                    serde_json::Number::from_f64(value as f64)
-                        .unwrap_or_else(|| serde_json::Number::from(0))
-                ))
-            }
-            DataType::Float64 => {
-                let array = column.as_any().downcast_ref::<Float64Array>()
-                    .ok_or_else(|| anyhow::anyhow!("类型转换失败: Float64"))?;
-                let value = array.value(row_idx);
-                Ok(serde_json::Value::Number(
-                    serde_json::Number::from_f64(value)
-                        .unwrap_or_else(|| serde_json::Number::from(0))
-                ))
-            }
-            DataType::Utf8 => {
-                let array = column.as_any().downcast_ref::<StringArray>()
-                    .ok_or_else(|| anyhow::anyhow!("类型转换失败: Utf8"))?;
-                Ok(serde_json::Value::String(array.value(row_idx).to_string()))
-            }
-            DataType::Timestamp(_, _) => {
-                let array = column.as_any().downcast_ref::<TimestampNanosecondArray>()
-                    .ok_or_else(|| anyhow::anyhow!("类型转换失败: Timestamp"))?;
-                Ok(serde_json::Value::Number(serde_json::Number::from(array.value(row_idx))))
-            }
-            _ => {
-                warn!("不支持的 Arrow 数据类型: {:?}", column.data_type());
-                Ok(serde_json::Value::String(format!("unsupported_type_{}", row_idx)))
-            }
-        }
+
+    /// 执行 SQL 查询并转换为 DataSet
+    async fn execute_sql_query(&self, query: &Query) -> Result<DataSet> {
+        self.execute_arrow_query(&query.text, query.database.as_deref(), &query.params).await?.to_dataset()
     }
-    
+
     /// 执行写入操作（通过 HTTP API）
     async fn write_via_http(&self, line_protocol: &str, bucket: &BucketInfo) -> Result<()> {
         debug!("通过 HTTP API 写入数据");
         
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(self.config.timeout as u64))
-            .danger_accept_invalid_certs(!self.config.ssl)
+            .danger_accept_invalid_certs(self.config.skip_ssl_verification)
             .build()?;
         
         let write_url = format!("{}/api/v3/write", self.endpoint_url);
@@ -241,13 +252,13 @@ impl InfluxDriver for FlightSqlDriver {
         
         let dataset = match query.language {
             QueryLanguage::Sql => {
-                self.execute_sql_query(&query.text).await?
+                self.execute_sql_query(query).await?
             }
             QueryLanguage::InfluxQL => {
                 // 通过 FlightSQL ticket 指定语言为 InfluxQL
                 // 这里需要根据具体的 InfluxDB 3.x 实现来调整
                 warn!("InfluxQL 通过 FlightSQL 的支持可能因版本而异");
-                self.execute_sql_query(&query.text).await?
+                self.execute_sql_query(query).await?
             }
             QueryLanguage::Flux => {
                 return Err(anyhow::anyhow!("InfluxDB 3.x 不支持 Flux 查询语言"));
@@ -261,6 +272,26 @@ impl InfluxDriver for FlightSqlDriver {
         info!("查询执行完成，耗时: {}ms，返回 {} 行", execution_time, result.row_count);
         Ok(result)
     }
+
+    async fn query_arrow(&self, query: &Query) -> Result<ArrowResult> {
+        debug!("执行 Arrow 查询: {}", query.text);
+
+        if query.language == QueryLanguage::Flux {
+            return Err(anyhow::anyhow!("InfluxDB 3.x 不支持 Flux 查询语言"));
+        }
+
+        let result = self.execute_arrow_query(&query.text, query.database.as_deref(), &query.params).await?;
+        info!("Arrow 查询执行完成，返回 {} 行", result.row_count());
+        Ok(result)
+    }
+
+    async fn query_raw(&self, _query: &Query, format: super::v3_http_driver::V3ResponseFormat) -> Result<Vec<u8>> {
+        // 原始格式由 HTTP 查询接口 (/api/v3/query_sql) 生成，FlightSQL 只返回 Arrow 数据
+        Err(anyhow::anyhow!(
+            "FlightSQL 连接不支持以 {} 格式导出，请改用 HTTP 传输的连接，或导出 Arrow 结果",
+            format.as_str()
+        ))
+    }
     
     async fn health(&self) -> Result<Health> {
         InfluxDetector::get_health(&self.config, &self.capability).await
@@ -0,0 +1,509 @@
+/**
+ * InfluxDB 3.x HTTP 驱动实现
+ *
+ * 在 FlightSQL 不可用（如仅开放 HTTP 的代理之后）时，通过 /api/v3 HTTP 接口
+ * 与 InfluxDB 3 Core/Enterprise 交互，支持 SQL 和 InfluxQL
+ */
+
+#[cfg(feature = "influxdb-v3")]
+use super::{
+    capability::{Capability, Query, DataSet, BucketInfo, Health, QueryLanguage},
+    driver::{
+        InfluxDriver, MeasurementSchema, FieldSchema, TagSchema, FieldType,
+        RetentionPolicyInfo, RetentionPolicyConfig,
+    },
+    detector::InfluxDetector,
+};
+use crate::models::ConnectionConfig;
+use crate::utils::query_params::{self, ParamDialect};
+use anyhow::Result;
+use async_trait::async_trait;
+use log::{debug, info, warn};
+use reqwest::Client;
+use serde::de::{Deserializer, MapAccess, Visitor};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// /api/v3/query_* 接口支持的响应格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum V3ResponseFormat {
+    Json,
+    Jsonl,
+    Csv,
+    Parquet,
+    Pretty,
+}
+
+impl V3ResponseFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            V3ResponseFormat::Json => "json",
+            V3ResponseFormat::Jsonl => "jsonl",
+            V3ResponseFormat::Csv => "csv",
+            V3ResponseFormat::Parquet => "parquet",
+            V3ResponseFormat::Pretty => "pretty",
+        }
+    }
+
+    pub fn parse(format: &str) -> Option<Self> {
+        match format.to_lowercase().as_str() {
+            "json" => Some(V3ResponseFormat::Json),
+            "jsonl" | "ndjson" => Some(V3ResponseFormat::Jsonl),
+            "csv" => Some(V3ResponseFormat::Csv),
+            "parquet" => Some(V3ResponseFormat::Parquet),
+            "pretty" => Some(V3ResponseFormat::Pretty),
+            _ => None,
+        }
+    }
+}
+
+/// 保留字段顺序的 JSON 对象行（serde_json::Map 默认按键排序，会打乱列顺序）
+struct OrderedRow(Vec<(String, Value)>);
+
+impl<'de> Deserialize<'de> for OrderedRow {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct RowVisitor;
+
+        impl<'de> Visitor<'de> for RowVisitor {
+            type Value = OrderedRow;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("JSON 对象")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> std::result::Result<OrderedRow, A::Error> {
+                let mut entries = Vec::new();
+                while let Some((key, value)) = map.next_entry::<String, Value>()? {
+                    entries.push((key, value));
+                }
+                Ok(OrderedRow(entries))
+            }
+        }
+
+        deserializer.deserialize_map(RowVisitor)
+    }
+}
+
+/// 将对象行转换为 DataSet，列按首次出现的顺序排列，缺失（null）的列补 Null
+fn rows_to_dataset(rows: Vec<OrderedRow>) -> DataSet {
+    let mut columns: Vec<String> = Vec::new();
+    for row in &rows {
+        for (key, _) in &row.0 {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+    }
+
+    let data = rows
+        .into_iter()
+        .map(|row| {
+            let mut values = vec![Value::Null; columns.len()];
+            for (key, value) in row.0 {
+                if let Some(index) = columns.iter().position(|c| c == &key) {
+                    values[index] = value;
+                }
+            }
+            values
+        })
+        .collect();
+
+    DataSet::new(columns, data)
+}
+
+/// 解析 JSON Lines 格式的查询结果
+pub fn parse_jsonl_result(body: &str) -> Result<DataSet> {
+    let rows = body
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(serde_json::from_str::<OrderedRow>)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!("解析查询结果失败: {}", e))?;
+    Ok(rows_to_dataset(rows))
+}
+
+/// 解析 JSON 数组格式的查询结果
+pub fn parse_json_result(body: &str) -> Result<DataSet> {
+    if body.trim().is_empty() {
+        return Ok(DataSet::empty());
+    }
+    let rows: Vec<OrderedRow> = serde_json::from_str(body)
+        .map_err(|e| anyhow::anyhow!("解析查询结果失败: {}", e))?;
+    Ok(rows_to_dataset(rows))
+}
+
+/// 从错误响应中提取错误信息
+fn error_message(body: &str) -> String {
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|v| {
+            v.get("error")
+                .or_else(|| v.get("message"))
+                .and_then(|m| m.as_str())
+                .map(|m| m.to_string())
+        })
+        .unwrap_or_else(|| body.to_string())
+}
+
+/// Arrow 数据类型名称对应的字段类型
+fn arrow_field_type(data_type: &str) -> FieldType {
+    match data_type {
+        "Float64" | "Float32" => FieldType::Float,
+        "Int64" | "Int32" | "UInt64" | "UInt32" => FieldType::Integer,
+        "Utf8" | "LargeUtf8" | "Utf8View" => FieldType::String,
+        "Boolean" => FieldType::Boolean,
+        _ => FieldType::Unknown,
+    }
+}
+
+/// InfluxDB 3.x HTTP 驱动
+#[cfg(feature = "influxdb-v3")]
+pub struct V3HttpDriver {
+    client: Client,
+    config: ConnectionConfig,
+    capability: Capability,
+    base_url: String,
+    token: Option<String>,
+}
+
+#[cfg(feature = "influxdb-v3")]
+impl V3HttpDriver {
+    /// 创建新的 V3HttpDriver 实例
+    pub fn new(config: ConnectionConfig, version: String) -> Result<Self> {
+        let base_url = if config.ssl {
+            format!("https://{}:{}", config.host, config.port)
+        } else {
+            format!("http://{}:{}", config.host, config.port)
+        };
+
+        // 未启用认证的 3.x 服务可以不提供令牌
+        let token = config
+            .v2_config
+            .as_ref()
+            .map(|c| c.api_token.clone())
+            .filter(|t| !t.is_empty());
+
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout as u64))
+            .danger_accept_invalid_certs(config.skip_ssl_verification)
+            .build()?;
+
+        // HTTP 接口原生支持 InfluxQL，无需 FlightSQL
+        let mut capability = Capability::v3x(version, false);
+        capability.supports_influxql = true;
+
+        info!("创建 InfluxDB 3.x HTTP 驱动: {}", base_url);
+
+        Ok(Self {
+            client,
+            config,
+            capability,
+            base_url,
+            token,
+        })
+    }
+
+    /// 构建认证请求
+    fn build_authenticated_request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let request = self.client.request(method, format!("{}{}", self.base_url, path));
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    /// 未指定数据库时使用连接配置中的数据库
+    fn resolve_database(&self, database: Option<&str>) -> Result<String> {
+        database
+            .map(|d| d.to_string())
+            .or_else(|| self.config.database.clone())
+            .or_else(|| self.config.v2_config.as_ref().and_then(|c| c.bucket.clone()))
+            .filter(|d| !d.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("InfluxDB 3.x 查询需要指定数据库"))
+    }
+
+    /// 发送请求并返回响应体，非 2xx 状态时返回服务器错误信息
+    async fn send(&self, request: reqwest::RequestBuilder, action: &str) -> Result<bytes::Bytes> {
+        let response = request
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("{}请求失败: {}", action, e))?;
+
+        let status = response.status();
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| anyhow::anyhow!("读取响应失败: {}", e))?;
+
+        if !status.is_success() {
+            let text = String::from_utf8_lossy(&body);
+            return Err(anyhow::anyhow!("{}失败 ({}): {}", action, status, error_message(&text)));
+        }
+        Ok(body)
+    }
+
+    /// 构建 /api/v3/query_* 请求，查询参数转换为服务器端参数
+    fn build_query_request(&self, query: &Query, format: V3ResponseFormat) -> Result<reqwest::RequestBuilder> {
+        let path = match query.language {
+            QueryLanguage::Sql => "/api/v3/query_sql",
+            QueryLanguage::InfluxQL => "/api/v3/query_influxql",
+            QueryLanguage::Flux => return Err(anyhow::anyhow!("InfluxDB 3.x 不支持 Flux 查询语言")),
+        };
+
+        let mut body = json!({
+            "db": self.resolve_database(query.database.as_deref())?,
+            "q": query.text,
+            "format": format.as_str(),
+        });
+        if !query.params.is_empty() {
+            let dialect = match query.language {
+                QueryLanguage::Sql => ParamDialect::Sql,
+                _ => ParamDialect::InfluxQL,
+            };
+            let (text, params) = query_params::to_v3_params(&query.text, &query.params, dialect)?;
+            body["q"] = json!(text);
+            body["params"] = params;
+        }
+
+        let mut request = self
+            .build_authenticated_request(reqwest::Method::POST, path)
+            .json(&body);
+        if let Some(timeout) = query.timeout {
+            request = request.timeout(Duration::from_secs(timeout));
+        }
+        Ok(request)
+    }
+
+    /// 执行 SQL 查询
+    async fn sql(&self, database: &str, sql: String) -> Result<DataSet> {
+        let query = Query::new(QueryLanguage::Sql, sql).with_database(database.to_string());
+        self.query(&query).await
+    }
+}
+
+#[cfg(feature = "influxdb-v3")]
+#[async_trait]
+impl InfluxDriver for V3HttpDriver {
+    async fn write(&self, line_protocol: &str, bucket: &BucketInfo) -> Result<()> {
+        debug!("写入数据到 InfluxDB 3.x: {}", bucket.name);
+
+        let request = self
+            .build_authenticated_request(reqwest::Method::POST, "/api/v3/write_lp")
+            .header("Content-Type", "text/plain; charset=utf-8")
+            .query(&[("db", bucket.name.as_str()), ("precision", "auto")])
+            .body(line_protocol.to_string());
+
+        self.send(request, "写入").await?;
+
+        info!("数据写入成功");
+        Ok(())
+    }
+
+    async fn query(&self, query: &Query) -> Result<DataSet> {
+        debug!("执行查询: {}", query.text);
+
+        let start_time = Instant::now();
+
+        let request = self.build_query_request(query, V3ResponseFormat::Jsonl)?;
+        let response = self.send(request, "查询").await?;
+        let mut result = parse_jsonl_result(&String::from_utf8_lossy(&response))?;
+
+        let execution_time = start_time.elapsed().as_millis() as u64;
+        result.execution_time = Some(execution_time);
+
+        info!("查询执行完成，耗时: {}ms，返回 {} 行", execution_time, result.row_count);
+        Ok(result)
+    }
+
+    async fn query_raw(&self, query: &Query, format: V3ResponseFormat) -> Result<Vec<u8>> {
+        debug!("执行原始格式查询 ({}): {}", format.as_str(), query.text);
+
+        let request = self.build_query_request(query, format)?;
+        let response = self.send(request, "查询").await?;
+        Ok(response.to_vec())
+    }
+
+    async fn health(&self) -> Result<Health> {
+        InfluxDetector::get_health(&self.config, &self.capability).await
+    }
+
+    fn capabilities(&self) -> &Capability {
+        &self.capability
+    }
+
+    async fn test_connection(&self) -> Result<u64> {
+        let start_time = Instant::now();
+
+        // 列出数据库同时验证连通性和令牌权限
+        self.list_databases().await?;
+
+        let latency = start_time.elapsed().as_millis() as u64;
+        info!("连接测试成功，延迟: {}ms", latency);
+        Ok(latency)
+    }
+
+    async fn close(&self) -> Result<()> {
+        info!("InfluxDB 3.x HTTP 驱动已关闭");
+        Ok(())
+    }
+
+    async fn list_databases(&self) -> Result<Vec<String>> {
+        debug!("获取数据库列表");
+
+        let request = self
+            .build_authenticated_request(reqwest::Method::GET, "/api/v3/configure/database")
+            .query(&[("format", "json")]);
+        let response = self.send(request, "获取数据库列表").await?;
+        let dataset = parse_json_result(&String::from_utf8_lossy(&response))?;
+
+        let index = dataset
+            .columns
+            .iter()
+            .position(|c| c == "iox::database")
+            .unwrap_or(0);
+        Ok(dataset
+            .rows
+            .iter()
+            .filter_map(|row| row.get(index)?.as_str().map(|s| s.to_string()))
+            .collect())
+    }
+
+    async fn list_measurements(&self, database: &str) -> Result<Vec<String>> {
+        debug!("获取表列表: {}", database);
+
+        let dataset = self
+            .sql(
+                database,
+                "SELECT table_name FROM information_schema.tables WHERE table_schema = 'iox' ORDER BY table_name".to_string(),
+            )
+            .await?;
+
+        Ok(dataset
+            .rows
+            .iter()
+            .filter_map(|row| row.first()?.as_str().map(|s| s.to_string()))
+            .collect())
+    }
+
+    async fn describe_measurement(&self, database: &str, measurement: &str) -> Result<MeasurementSchema> {
+        debug!("描述表: {}.{}", database, measurement);
+
+        let dataset = self
+            .sql(
+                database,
+                format!(
+                    "SELECT column_name, data_type FROM information_schema.columns WHERE table_schema = 'iox' AND table_name = '{}' ORDER BY ordinal_position",
+                    measurement.replace('\'', "''")
+                ),
+            )
+            .await?;
+
+        let mut fields = Vec::new();
+        let mut tags = Vec::new();
+        for row in &dataset.rows {
+            let Some(name) = row.first().and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let data_type = row.get(1).and_then(|v| v.as_str()).unwrap_or("");
+
+            if name == "time" {
+                continue;
+            }
+            // 3.x 中标签列以字典编码的字符串存储
+            if data_type.starts_with("Dictionary") {
+                tags.push(TagSchema {
+                    name: name.to_string(),
+                    values: Vec::new(),
+                });
+            } else {
+                fields.push(FieldSchema {
+                    name: name.to_string(),
+                    field_type: arrow_field_type(data_type),
+                });
+            }
+        }
+
+        Ok(MeasurementSchema {
+            name: measurement.to_string(),
+            fields,
+            tags,
+        })
+    }
+
+    async fn create_database(&self, name: &str) -> Result<()> {
+        debug!("创建数据库: {}", name);
+
+        let request = self
+            .build_authenticated_request(reqwest::Method::POST, "/api/v3/configure/database")
+            .json(&json!({ "db": name }));
+        self.send(request, "创建数据库").await?;
+
+        info!("数据库创建成功: {}", name);
+        Ok(())
+    }
+
+    async fn drop_database(&self, name: &str) -> Result<()> {
+        debug!("删除数据库: {}", name);
+
+        let request = self
+            .build_authenticated_request(reqwest::Method::DELETE, "/api/v3/configure/database")
+            .query(&[("db", name)]);
+        self.send(request, "删除数据库").await?;
+
+        info!("数据库删除成功: {}", name);
+        Ok(())
+    }
+
+    async fn list_retention_policies(&self, _database: &str) -> Result<Vec<RetentionPolicyInfo>> {
+        // InfluxDB 3.x 不再使用传统的保留策略概念
+        warn!("InfluxDB 3.x 不支持传统的保留策略");
+        Ok(vec![])
+    }
+
+    async fn create_retention_policy(&self, _database: &str, _policy: &RetentionPolicyConfig) -> Result<()> {
+        Err(anyhow::anyhow!("InfluxDB 3.x 不支持传统的保留策略"))
+    }
+
+    async fn drop_retention_policy(&self, _database: &str, _policy_name: &str) -> Result<()> {
+        Err(anyhow::anyhow!("InfluxDB 3.x 不支持传统的保留策略"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_jsonl_preserves_column_order() {
+        let body = "{\"time\":\"2024-01-01T00:00:00\",\"host\":\"a\",\"usage\":1.5}\n\
+                    {\"time\":\"2024-01-01T00:01:00\",\"usage\":2.0,\"zone\":\"eu\"}\n";
+        let dataset = parse_jsonl_result(body).unwrap();
+        assert_eq!(dataset.columns, vec!["time", "host", "usage", "zone"]);
+        assert_eq!(dataset.row_count, 2);
+        assert_eq!(dataset.rows[1][1], Value::Null);
+        assert_eq!(dataset.rows[1][3], json!("eu"));
+        assert_eq!(parse_jsonl_result("").unwrap().row_count, 0);
+        assert!(parse_jsonl_result("not json").is_err());
+    }
+
+    #[test]
+    fn test_parse_json_result() {
+        let dataset = parse_json_result("[{\"iox::database\":\"metrics\"},{\"iox::database\":\"_internal\"}]").unwrap();
+        assert_eq!(dataset.columns, vec!["iox::database"]);
+        assert_eq!(dataset.rows[1][0], json!("_internal"));
+        assert_eq!(parse_json_result("").unwrap().row_count, 0);
+    }
+
+    #[test]
+    fn test_helpers() {
+        assert_eq!(error_message("{\"error\":\"database not found\"}"), "database not found");
+        assert_eq!(error_message("bad gateway"), "bad gateway");
+        assert!(matches!(arrow_field_type("Float64"), FieldType::Float));
+        assert!(matches!(arrow_field_type("Dictionary(Int32, Utf8)"), FieldType::Unknown));
+        assert_eq!(V3ResponseFormat::parse("Parquet"), Some(V3ResponseFormat::Parquet));
+        assert_eq!(V3ResponseFormat::Jsonl.as_str(), "jsonl");
+        assert_eq!(V3ResponseFormat::parse("xml"), None);
+    }
+}
@@ -5,7 +5,9 @@
  */
 
 use crate::database::influxdb::{InfluxDriver, InfluxDriverFactory};
-use crate::models::{ConnectionConfig, QueryResult, QueryRequest};
+use crate::database::influxdb::{admin, authorizations, caches, continuous_queries, influxql, monitoring, processing_engine, running_queries, shards, telegraf, users};
+use crate::database::influxdb::tasks::{build_task_flux, TaskSchedule};
+use crate::models::{ConnectionConfig, QueryParams, QueryResult, QueryRequest};
 use anyhow::Result;
 use log::{debug, info, warn};
 use std::sync::Arc;
@@ -15,6 +17,8 @@ use std::time::Instant;
 pub struct InfluxDBClient {
     driver: Arc<dyn InfluxDriver>,
     config: ConnectionConfig,
+    /// 管理接口请求复用的 HTTP 客户端（超时、TLS 与代理来自连接配置）
+    http_client: reqwest::Client,
 }
 
 impl InfluxDBClient {
@@ -23,10 +27,12 @@ impl InfluxDBClient {
         info!("创建 InfluxDB 客户端: {}:{}", config.host, config.port);
         
         let driver = InfluxDriverFactory::create_driver(&config).await?;
+        let http_client = crate::utils::http_client::build_http_client(&config)?;
         
         Ok(Self {
             driver,
             config,
+            http_client,
         })
     }
     
@@ -53,6 +59,10 @@ impl InfluxDBClient {
         if let Some(timeout) = request.timeout {
             query = query.with_timeout(timeout);
         }
+
+        if !request.params.is_empty() {
+            query = query.with_params(request.params.clone());
+        }
         
         // 执行查询
         let dataset = self.driver.query(&query).await?;
@@ -77,10 +87,24 @@ impl InfluxDBClient {
             query: query.to_string(),
             database: database.map(|s| s.to_string()),
             timeout: Some(self.config.query_timeout as u64),
+            params: QueryParams::new(),
         };
         
         self.execute_query(&request).await
     }
+
+    /// 执行参数化查询，参数由驱动按协议绑定
+    pub async fn execute_query_with_params(&self, query: &str, database: Option<&str>, params: &QueryParams) -> Result<QueryResult> {
+        let request = QueryRequest {
+            connection_id: self.config.id.clone(),
+            query: query.to_string(),
+            database: database.map(|s| s.to_string()),
+            timeout: Some(self.config.query_timeout as u64),
+            params: params.clone(),
+        };
+
+        self.execute_query(&request).await
+    }
     
     /// 写入 Line Protocol 数据
     pub async fn write_line_protocol(&self, database: &str, line_protocol: &str) -> Result<()> {
@@ -281,6 +305,37 @@ impl InfluxDBClient {
                         let measurements = self.list_measurements(database).await?;
                         info!("获取到 {} 个表", measurements.len());
 
+                        // InfluxDB 3.x：返回表节点（展开后包含列以及最新值/去重值缓存）和处理引擎触发器
+                        if self.driver.capabilities().major == 3 {
+                            let mut nodes: Vec<crate::models::TreeNode> = measurements
+                                .into_iter()
+                                .map(|table_name| {
+                                    crate::models::TreeNodeFactory::create_table(table_name.clone(), parent_node_id.to_string())
+                                        .with_metadata("database".to_string(), serde_json::Value::String(database.to_string()))
+                                        .with_metadata("measurement".to_string(), serde_json::Value::String(table_name.clone()))
+                                        .with_metadata("databaseName".to_string(), serde_json::Value::String(database.to_string()))
+                                        .with_metadata("tableName".to_string(), serde_json::Value::String(table_name))
+                                })
+                                .collect();
+
+                            match self.get_influxdb3_triggers(database).await {
+                                Ok(triggers) => {
+                                    for trigger in triggers {
+                                        let node = crate::models::TreeNodeFactory::create_trigger3x(trigger.trigger_name, parent_node_id.to_string(), trigger.disabled)
+                                            .with_metadata("database".to_string(), serde_json::Value::String(database.to_string()))
+                                            .with_metadata("triggerType".to_string(), serde_json::Value::String(trigger.trigger_type))
+                                            .with_metadata("triggerSpecification".to_string(), serde_json::Value::String(trigger.trigger_specification))
+                                            .with_metadata("pluginFilename".to_string(), serde_json::Value::String(trigger.plugin_filename));
+                                        nodes.push(node);
+                                    }
+                                }
+                                Err(e) => {
+                                    warn!("获取处理引擎触发器失败: {}", e);
+                                }
+                            }
+                            return Ok(nodes);
+                        }
+
                         // 🔧 修复：包含连接 ID 以确保节点 ID 唯一
                         let connection_id = &self.config.id;
 
@@ -394,6 +449,93 @@ impl InfluxDBClient {
                     Ok(vec![])
                 }
             }
+            "table" => {
+                // InfluxDB 3.x 表节点：返回标签列、字段列以及表上的缓存
+                let metadata_value = |key: &str, fallback: &str| {
+                    _metadata
+                        .and_then(|m| m.get(key).or_else(|| m.get(fallback)))
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("")
+                        .to_string()
+                };
+                let database = metadata_value("database", "databaseName");
+                let table = metadata_value("tableName", "measurement");
+                if database.is_empty() || table.is_empty() {
+                    warn!("表节点缺少必要的元数据: {}", parent_node_id);
+                    return Ok(vec![]);
+                }
+
+                let mut children = Vec::new();
+                let with_table = |node: crate::models::TreeNode| {
+                    node.with_metadata("database".to_string(), serde_json::Value::String(database.clone()))
+                        .with_metadata("measurement".to_string(), serde_json::Value::String(table.clone()))
+                        .with_metadata("databaseName".to_string(), serde_json::Value::String(database.clone()))
+                        .with_metadata("tableName".to_string(), serde_json::Value::String(table.clone()))
+                };
+
+                match self.driver.describe_measurement(&database, &table).await {
+                    Ok(schema) => {
+                        for tag in schema.tags {
+                            let node = crate::models::TreeNodeFactory::create_tag(tag.name.clone(), parent_node_id.to_string())
+                                .with_metadata("tagName".to_string(), serde_json::Value::String(tag.name));
+                            children.push(with_table(node));
+                        }
+                        for field in schema.fields {
+                            let field_type_str = match field.field_type {
+                                crate::database::influxdb::FieldType::Float => "float",
+                                crate::database::influxdb::FieldType::Integer => "integer",
+                                crate::database::influxdb::FieldType::String => "string",
+                                crate::database::influxdb::FieldType::Boolean => "boolean",
+                                crate::database::influxdb::FieldType::Unknown => "unknown",
+                            };
+                            let node = crate::models::TreeNodeFactory::create_field(
+                                field.name.clone(),
+                                parent_node_id.to_string(),
+                                field_type_str.to_string(),
+                            )
+                            .with_metadata("fieldName".to_string(), serde_json::Value::String(field.name));
+                            children.push(with_table(node));
+                        }
+                    }
+                    Err(e) => {
+                        warn!("获取表结构失败: {}", e);
+                    }
+                }
+
+                match self.get_influxdb3_last_caches(&database, Some(&table)).await {
+                    Ok(caches) => {
+                        for cache in caches {
+                            let node = crate::models::TreeNodeFactory::create_cache("last", cache.name, parent_node_id.to_string())
+                                .with_metadata("keyColumns".to_string(), serde_json::json!(cache.key_columns))
+                                .with_metadata("valueColumns".to_string(), serde_json::json!(cache.value_columns))
+                                .with_metadata("count".to_string(), serde_json::json!(cache.count))
+                                .with_metadata("ttlSeconds".to_string(), serde_json::json!(cache.ttl_seconds));
+                            children.push(with_table(node));
+                        }
+                    }
+                    Err(e) => {
+                        warn!("获取最新值缓存失败: {}", e);
+                    }
+                }
+
+                match self.get_influxdb3_distinct_caches(&database, Some(&table)).await {
+                    Ok(caches) => {
+                        for cache in caches {
+                            let node = crate::models::TreeNodeFactory::create_cache("distinct", cache.name, parent_node_id.to_string())
+                                .with_metadata("columns".to_string(), serde_json::json!(cache.columns))
+                                .with_metadata("maxCardinality".to_string(), serde_json::json!(cache.max_cardinality))
+                                .with_metadata("maxAgeSeconds".to_string(), serde_json::json!(cache.max_age_seconds));
+                            children.push(with_table(node));
+                        }
+                    }
+                    Err(e) => {
+                        warn!("获取去重值缓存失败: {}", e);
+                    }
+                }
+
+                info!("为表节点创建了 {} 个子节点", children.len());
+                Ok(children)
+            }
             "tag_group" => {
                 // Tags 分组节点：返回所有标签
                 info!("为 Tags 分组节点获取标签列表");
@@ -889,6 +1031,1667 @@ impl InfluxDBClient {
 
         Err(anyhow::anyhow!("缺少 InfluxDB 2.x 配置"))
     }
+
+    /// InfluxDB 2.x 服务地址
+    fn v2_base_url(&self) -> String {
+        if self.config.ssl {
+            format!("https://{}:{}", self.config.host, self.config.port)
+        } else {
+            format!("http://{}:{}", self.config.host, self.config.port)
+        }
+    }
+
+    /// 发送 InfluxDB 2.x REST API 请求，返回原始响应文本
+    async fn send_v2_raw(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+        accept: &str,
+    ) -> Result<String> {
+        let v2_config = self
+            .config
+            .v2_config
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("缺少 InfluxDB 2.x 配置"))?;
+
+        let url = format!("{}{}", self.v2_base_url(), path);
+
+        let mut request = reqwest::Client::new()
+            .request(method, &url)
+            .header("Authorization", format!("Token {}", v2_config.api_token))
+            .header("Accept", accept);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("请求 {} 失败: {}", path, e))?;
+
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            let message = serde_json::from_str::<serde_json::Value>(&text)
+                .ok()
+                .and_then(|v| v.get("message").and_then(|m| m.as_str()).map(|m| m.to_string()))
+                .unwrap_or(text);
+            return Err(anyhow::anyhow!("({}) {}", status, message));
+        }
+
+        Ok(text)
+    }
+
+    /// 发送 InfluxDB 2.x REST API 请求，返回解析后的 JSON（空响应返回 Null）
+    async fn send_v2_request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value> {
+        let text = self.send_v2_raw(method, path, body, "application/json").await?;
+        if text.trim().is_empty() {
+            return Ok(serde_json::Value::Null);
+        }
+        serde_json::from_str(&text).map_err(|e| anyhow::anyhow!("解析响应失败: {}", e))
+    }
+
+    /// 连接配置中的默认组织
+    fn default_v2_org(&self) -> Option<String> {
+        self.config
+            .v2_config
+            .as_ref()
+            .map(|c| c.organization.clone())
+            .filter(|org| !org.is_empty())
+    }
+
+    /// 解析组织 ID：优先使用传入的 ID，否则按组织名称（默认为连接配置中的组织）查询
+    async fn resolve_v2_org_id(&self, org_id: Option<&str>, org_name: Option<&str>) -> Result<String> {
+        if let Some(org_id) = org_id.filter(|id| !id.is_empty()) {
+            return Ok(org_id.to_string());
+        }
+
+        let org_name = org_name
+            .map(|o| o.to_string())
+            .or_else(|| self.default_v2_org())
+            .ok_or_else(|| anyhow::anyhow!("未指定组织"))?;
+        let org = self.get_influxdb2_organization_info(&org_name).await?;
+        Ok(org.id)
+    }
+
+    /// 获取任务列表
+    pub async fn get_influxdb2_tasks(&self, org_name: Option<&str>) -> Result<Vec<crate::commands::influxdb2::TaskInfo>> {
+        debug!("获取 InfluxDB 2.x 任务列表: {:?}", org_name);
+
+        let mut path = "/api/v2/tasks?limit=500".to_string();
+        if let Some(org) = org_name.map(|o| o.to_string()).or_else(|| self.default_v2_org()) {
+            path.push_str(&format!("&org={}", urlencoding::encode(&org)));
+        }
+
+        let response = self.send_v2_request(reqwest::Method::GET, &path, None).await?;
+        let tasks = response
+            .get("tasks")
+            .and_then(|t| t.as_array())
+            .map(|tasks| tasks.iter().map(Self::parse_task).collect())
+            .unwrap_or_default();
+
+        Ok(tasks)
+    }
+
+    /// 获取单个任务
+    pub async fn get_influxdb2_task(&self, task_id: &str) -> Result<crate::commands::influxdb2::TaskInfo> {
+        debug!("获取任务信息: {}", task_id);
+
+        let path = format!("/api/v2/tasks/{}", urlencoding::encode(task_id));
+        let response = self.send_v2_request(reqwest::Method::GET, &path, None).await?;
+        Ok(Self::parse_task(&response))
+    }
+
+    /// 创建任务
+    pub async fn create_influxdb2_task(&self, request: &crate::commands::influxdb2::CreateTaskRequest) -> Result<crate::commands::influxdb2::TaskInfo> {
+        debug!("创建任务: {}", request.name);
+
+        let schedule = TaskSchedule::new(
+            request.every.as_deref(),
+            request.cron.as_deref(),
+            request.offset.as_deref(),
+        );
+        let flux = build_task_flux(&request.name, &schedule, &request.flux)?;
+
+        let mut body = serde_json::json!({
+            "flux": flux,
+            "status": if request.active.unwrap_or(true) { "active" } else { "inactive" },
+        });
+
+        match request.org_id.as_deref().filter(|id| !id.is_empty()) {
+            Some(org_id) => body["orgID"] = serde_json::json!(org_id),
+            None => {
+                let org = self
+                    .default_v2_org()
+                    .ok_or_else(|| anyhow::anyhow!("未指定组织"))?;
+                body["org"] = serde_json::json!(org);
+            }
+        }
+
+        if let Some(desc) = &request.description {
+            body["description"] = serde_json::json!(desc);
+        }
+
+        let response = self.send_v2_request(reqwest::Method::POST, "/api/v2/tasks", Some(body)).await?;
+        let task = Self::parse_task(&response);
+        info!("任务 '{}' 创建成功: {}", task.name, task.id);
+        Ok(task)
+    }
+
+    /// 更新任务，名称或调度变化时重新生成 option task 声明
+    pub async fn update_influxdb2_task(&self, task_id: &str, request: &crate::commands::influxdb2::UpdateTaskRequest) -> Result<crate::commands::influxdb2::TaskInfo> {
+        debug!("更新任务: {}", task_id);
+
+        let current = self.get_influxdb2_task(task_id).await?;
+        let mut body = serde_json::json!({});
+
+        let schedule_changed = request.every.is_some() || request.cron.is_some() || request.offset.is_some();
+        if request.name.is_some() || request.flux.is_some() || schedule_changed {
+            let (every, cron) = match (&request.every, &request.cron) {
+                (None, None) => (current.every.as_deref(), current.cron.as_deref()),
+                (every, cron) => (every.as_deref(), cron.as_deref()),
+            };
+            let offset = request.offset.as_deref().or(current.offset.as_deref());
+            let schedule = TaskSchedule::new(every, cron, offset);
+
+            let name = request.name.as_deref().unwrap_or(&current.name);
+            let flux = request.flux.as_deref().unwrap_or(&current.flux);
+            body["flux"] = serde_json::json!(build_task_flux(name, &schedule, flux)?);
+        }
+
+        if let Some(active) = request.active {
+            body["status"] = serde_json::json!(if active { "active" } else { "inactive" });
+        }
+
+        if let Some(desc) = &request.description {
+            body["description"] = serde_json::json!(desc);
+        }
+
+        let path = format!("/api/v2/tasks/{}", urlencoding::encode(task_id));
+        let response = self.send_v2_request(reqwest::Method::PATCH, &path, Some(body)).await?;
+        let task = Self::parse_task(&response);
+        info!("任务 '{}' 更新成功", task.name);
+        Ok(task)
+    }
+
+    /// 删除任务
+    pub async fn delete_influxdb2_task(&self, task_id: &str) -> Result<()> {
+        debug!("删除任务: {}", task_id);
+
+        let path = format!("/api/v2/tasks/{}", urlencoding::encode(task_id));
+        self.send_v2_request(reqwest::Method::DELETE, &path, None).await?;
+        info!("任务 '{}' 删除成功", task_id);
+        Ok(())
+    }
+
+    /// 手动触发任务运行
+    pub async fn run_influxdb2_task(&self, task_id: &str) -> Result<crate::commands::influxdb2::TaskRunInfo> {
+        debug!("手动运行任务: {}", task_id);
+
+        let path = format!("/api/v2/tasks/{}/runs", urlencoding::encode(task_id));
+        let response = self
+            .send_v2_request(reqwest::Method::POST, &path, Some(serde_json::json!({})))
+            .await?;
+        Ok(Self::parse_task_run(&response, task_id))
+    }
+
+    /// 获取任务运行历史（按计划时间倒序）
+    pub async fn get_influxdb2_task_runs(&self, task_id: &str, limit: u32) -> Result<Vec<crate::commands::influxdb2::TaskRunInfo>> {
+        debug!("获取任务运行历史: {}", task_id);
+
+        let path = format!("/api/v2/tasks/{}/runs?limit={}", urlencoding::encode(task_id), limit.clamp(1, 500));
+        let response = self.send_v2_request(reqwest::Method::GET, &path, None).await?;
+
+        let mut runs: Vec<_> = response
+            .get("runs")
+            .and_then(|r| r.as_array())
+            .map(|runs| runs.iter().map(|run| Self::parse_task_run(run, task_id)).collect())
+            .unwrap_or_default();
+        runs.sort_by(|a, b| b.scheduled_for.cmp(&a.scheduled_for));
+
+        Ok(runs)
+    }
+
+    /// 获取任务日志，指定 run_id 时只返回该次运行的日志
+    pub async fn get_influxdb2_task_logs(&self, task_id: &str, run_id: Option<&str>) -> Result<Vec<crate::commands::influxdb2::TaskLogEntry>> {
+        debug!("获取任务日志: {} - {:?}", task_id, run_id);
+
+        let path = match run_id {
+            Some(run_id) => format!(
+                "/api/v2/tasks/{}/runs/{}/logs",
+                urlencoding::encode(task_id),
+                urlencoding::encode(run_id)
+            ),
+            None => format!("/api/v2/tasks/{}/logs", urlencoding::encode(task_id)),
+        };
+        let response = self.send_v2_request(reqwest::Method::GET, &path, None).await?;
+
+        let logs = response
+            .get("events")
+            .and_then(|e| e.as_array())
+            .map(|events| {
+                events
+                    .iter()
+                    .map(|event| crate::commands::influxdb2::TaskLogEntry {
+                        run_id: event.get("runID").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                        time: event.get("time").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                        message: event.get("message").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(logs)
+    }
+
+    /// 获取检查列表
+    pub async fn get_influxdb2_checks(&self, org_name: Option<&str>) -> Result<Vec<crate::commands::influxdb2::CheckInfo>> {
+        debug!("获取检查列表: {:?}", org_name);
+
+        let org_id = self.resolve_v2_org_id(None, org_name).await?;
+        let path = format!("/api/v2/checks?orgID={}&limit=100", urlencoding::encode(&org_id));
+        let response = self.send_v2_request(reqwest::Method::GET, &path, None).await?;
+
+        Ok(response
+            .get("checks")
+            .and_then(|c| c.as_array())
+            .map(|checks| checks.iter().map(monitoring::parse_check).collect())
+            .unwrap_or_default())
+    }
+
+    /// 创建检查
+    pub async fn create_influxdb2_check(&self, request: &crate::commands::influxdb2::SaveCheckRequest) -> Result<crate::commands::influxdb2::CheckInfo> {
+        debug!("创建检查: {}", request.name);
+
+        let org_id = self.resolve_v2_org_id(request.org_id.as_deref(), None).await?;
+        let body = monitoring::build_check_body(request, &org_id)?;
+        let response = self.send_v2_request(reqwest::Method::POST, "/api/v2/checks", Some(body)).await?;
+
+        info!("检查 '{}' 创建成功", request.name);
+        Ok(monitoring::parse_check(&response))
+    }
+
+    /// 更新检查（整体替换）
+    pub async fn update_influxdb2_check(&self, check_id: &str, request: &crate::commands::influxdb2::SaveCheckRequest) -> Result<crate::commands::influxdb2::CheckInfo> {
+        debug!("更新检查: {}", check_id);
+
+        let org_id = self.resolve_v2_org_id(request.org_id.as_deref(), None).await?;
+        let body = monitoring::build_check_body(request, &org_id)?;
+        let path = format!("/api/v2/checks/{}", urlencoding::encode(check_id));
+        let response = self.send_v2_request(reqwest::Method::PUT, &path, Some(body)).await?;
+
+        info!("检查 '{}' 更新成功", request.name);
+        Ok(monitoring::parse_check(&response))
+    }
+
+    /// 删除检查
+    pub async fn delete_influxdb2_check(&self, check_id: &str) -> Result<()> {
+        debug!("删除检查: {}", check_id);
+
+        let path = format!("/api/v2/checks/{}", urlencoding::encode(check_id));
+        self.send_v2_request(reqwest::Method::DELETE, &path, None).await?;
+        info!("检查 '{}' 删除成功", check_id);
+        Ok(())
+    }
+
+    /// 获取通知端点列表
+    pub async fn get_influxdb2_notification_endpoints(&self, org_name: Option<&str>) -> Result<Vec<crate::commands::influxdb2::NotificationEndpointInfo>> {
+        debug!("获取通知端点列表: {:?}", org_name);
+
+        let org_id = self.resolve_v2_org_id(None, org_name).await?;
+        let path = format!("/api/v2/notificationEndpoints?orgID={}&limit=100", urlencoding::encode(&org_id));
+        let response = self.send_v2_request(reqwest::Method::GET, &path, None).await?;
+
+        Ok(response
+            .get("notificationEndpoints")
+            .and_then(|e| e.as_array())
+            .map(|endpoints| endpoints.iter().map(monitoring::parse_endpoint).collect())
+            .unwrap_or_default())
+    }
+
+    /// 创建通知端点
+    pub async fn create_influxdb2_notification_endpoint(&self, request: &crate::commands::influxdb2::SaveNotificationEndpointRequest) -> Result<crate::commands::influxdb2::NotificationEndpointInfo> {
+        debug!("创建通知端点: {}", request.name);
+
+        let org_id = self.resolve_v2_org_id(request.org_id.as_deref(), None).await?;
+        let body = monitoring::build_endpoint_body(request, &org_id)?;
+        let response = self
+            .send_v2_request(reqwest::Method::POST, "/api/v2/notificationEndpoints", Some(body))
+            .await?;
+
+        info!("通知端点 '{}' 创建成功", request.name);
+        Ok(monitoring::parse_endpoint(&response))
+    }
+
+    /// 更新通知端点（整体替换）
+    pub async fn update_influxdb2_notification_endpoint(&self, endpoint_id: &str, request: &crate::commands::influxdb2::SaveNotificationEndpointRequest) -> Result<crate::commands::influxdb2::NotificationEndpointInfo> {
+        debug!("更新通知端点: {}", endpoint_id);
+
+        let org_id = self.resolve_v2_org_id(request.org_id.as_deref(), None).await?;
+        let body = monitoring::build_endpoint_body(request, &org_id)?;
+        let path = format!("/api/v2/notificationEndpoints/{}", urlencoding::encode(endpoint_id));
+        let response = self.send_v2_request(reqwest::Method::PUT, &path, Some(body)).await?;
+
+        info!("通知端点 '{}' 更新成功", request.name);
+        Ok(monitoring::parse_endpoint(&response))
+    }
+
+    /// 删除通知端点
+    pub async fn delete_influxdb2_notification_endpoint(&self, endpoint_id: &str) -> Result<()> {
+        debug!("删除通知端点: {}", endpoint_id);
+
+        let path = format!("/api/v2/notificationEndpoints/{}", urlencoding::encode(endpoint_id));
+        self.send_v2_request(reqwest::Method::DELETE, &path, None).await?;
+        info!("通知端点 '{}' 删除成功", endpoint_id);
+        Ok(())
+    }
+
+    /// 获取通知规则列表
+    pub async fn get_influxdb2_notification_rules(&self, org_name: Option<&str>) -> Result<Vec<crate::commands::influxdb2::NotificationRuleInfo>> {
+        debug!("获取通知规则列表: {:?}", org_name);
+
+        let org_id = self.resolve_v2_org_id(None, org_name).await?;
+        let path = format!("/api/v2/notificationRules?orgID={}&limit=100", urlencoding::encode(&org_id));
+        let response = self.send_v2_request(reqwest::Method::GET, &path, None).await?;
+
+        Ok(response
+            .get("notificationRules")
+            .and_then(|r| r.as_array())
+            .map(|rules| rules.iter().map(monitoring::parse_rule).collect())
+            .unwrap_or_default())
+    }
+
+    /// 构建通知规则请求体，规则类型取自目标端点
+    async fn notification_rule_body(&self, request: &crate::commands::influxdb2::SaveNotificationRuleRequest) -> Result<serde_json::Value> {
+        let org_id = self.resolve_v2_org_id(request.org_id.as_deref(), None).await?;
+
+        let path = format!("/api/v2/notificationEndpoints/{}", urlencoding::encode(&request.endpoint_id));
+        let endpoint = self.send_v2_request(reqwest::Method::GET, &path, None).await?;
+        let rule_type = endpoint
+            .get("type")
+            .and_then(|t| t.as_str())
+            .ok_or_else(|| anyhow::anyhow!("无法确定通知端点 {} 的类型", request.endpoint_id))?;
+
+        monitoring::build_rule_body(request, &org_id, rule_type)
+    }
+
+    /// 创建通知规则
+    pub async fn create_influxdb2_notification_rule(&self, request: &crate::commands::influxdb2::SaveNotificationRuleRequest) -> Result<crate::commands::influxdb2::NotificationRuleInfo> {
+        debug!("创建通知规则: {}", request.name);
+
+        let body = self.notification_rule_body(request).await?;
+        let response = self
+            .send_v2_request(reqwest::Method::POST, "/api/v2/notificationRules", Some(body))
+            .await?;
+
+        info!("通知规则 '{}' 创建成功", request.name);
+        Ok(monitoring::parse_rule(&response))
+    }
+
+    /// 更新通知规则（整体替换）
+    pub async fn update_influxdb2_notification_rule(&self, rule_id: &str, request: &crate::commands::influxdb2::SaveNotificationRuleRequest) -> Result<crate::commands::influxdb2::NotificationRuleInfo> {
+        debug!("更新通知规则: {}", rule_id);
+
+        let body = self.notification_rule_body(request).await?;
+        let path = format!("/api/v2/notificationRules/{}", urlencoding::encode(rule_id));
+        let response = self.send_v2_request(reqwest::Method::PUT, &path, Some(body)).await?;
+
+        info!("通知规则 '{}' 更新成功", request.name);
+        Ok(monitoring::parse_rule(&response))
+    }
+
+    /// 删除通知规则
+    pub async fn delete_influxdb2_notification_rule(&self, rule_id: &str) -> Result<()> {
+        debug!("删除通知规则: {}", rule_id);
+
+        let path = format!("/api/v2/notificationRules/{}", urlencoding::encode(rule_id));
+        self.send_v2_request(reqwest::Method::DELETE, &path, None).await?;
+        info!("通知规则 '{}' 删除成功", rule_id);
+        Ok(())
+    }
+
+    /// 查询 _monitoring 存储桶中最近的检查状态
+    pub async fn get_influxdb2_check_statuses(
+        &self,
+        org_name: Option<&str>,
+        check_id: Option<&str>,
+        range: &str,
+        limit: u32,
+    ) -> Result<Vec<crate::commands::influxdb2::CheckStatusEntry>> {
+        debug!("查询检查状态: {:?} - {:?} - {}", org_name, check_id, range);
+
+        let org_id = self.resolve_v2_org_id(None, org_name).await?;
+        let flux = monitoring::build_statuses_flux(check_id, range, limit)?;
+        let body = serde_json::json!({
+            "query": flux,
+            "type": "flux",
+            "dialect": {"header": true, "annotations": []},
+        });
+
+        let path = format!("/api/v2/query?orgID={}", urlencoding::encode(&org_id));
+        let csv_data = self
+            .send_v2_raw(reqwest::Method::POST, &path, Some(body), "application/csv")
+            .await?;
+        monitoring::parse_statuses_csv(&csv_data)
+    }
+
+    /// 当前连接使用的令牌
+    fn current_v2_token(&self) -> Option<&str> {
+        self.config.v2_config.as_ref().map(|c| c.api_token.as_str())
+    }
+
+    /// 获取令牌列表
+    pub async fn get_influxdb2_authorizations(&self, org_name: Option<&str>) -> Result<Vec<crate::commands::influxdb2::AuthorizationInfo>> {
+        debug!("获取令牌列表: {:?}", org_name);
+
+        let org_id = self.resolve_v2_org_id(None, org_name).await?;
+        let path = format!("/api/v2/authorizations?orgID={}", urlencoding::encode(&org_id));
+        let response = self.send_v2_request(reqwest::Method::GET, &path, None).await?;
+
+        let current_token = self.current_v2_token();
+        Ok(response
+            .get("authorizations")
+            .and_then(|a| a.as_array())
+            .map(|authorizations| {
+                authorizations
+                    .iter()
+                    .map(|a| authorizations::parse_authorization(a, current_token, false))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// 创建存储桶范围的读写令牌，返回完整令牌
+    pub async fn create_influxdb2_authorization(&self, request: &crate::commands::influxdb2::CreateAuthorizationRequest) -> Result<crate::commands::influxdb2::AuthorizationInfo> {
+        debug!("创建令牌: {:?}", request.bucket_names);
+
+        let mut org_id: Option<String> = None;
+        let mut bucket_ids = Vec::with_capacity(request.bucket_names.len());
+        for bucket_name in &request.bucket_names {
+            let bucket = self.get_influxdb2_bucket_info(bucket_name).await?;
+            match &org_id {
+                Some(existing) if *existing != bucket.org_id => {
+                    return Err(anyhow::anyhow!("所选存储桶不属于同一个组织"));
+                }
+                Some(_) => {}
+                None => org_id = Some(bucket.org_id.clone()),
+            }
+            bucket_ids.push(bucket.id);
+        }
+
+        let org_id = org_id.ok_or_else(|| anyhow::anyhow!("至少需要选择一个存储桶"))?;
+        let permissions = authorizations::build_bucket_permissions(&org_id, &bucket_ids, request.read, request.write)?;
+
+        let mut body = serde_json::json!({
+            "orgID": org_id,
+            "status": "active",
+            "permissions": permissions,
+        });
+        if let Some(desc) = &request.description {
+            body["description"] = serde_json::json!(desc);
+        }
+
+        let response = self
+            .send_v2_request(reqwest::Method::POST, "/api/v2/authorizations", Some(body))
+            .await?;
+        let authorization = authorizations::parse_authorization(&response, self.current_v2_token(), true);
+
+        info!("令牌 '{}' 创建成功", authorization.id);
+        Ok(authorization)
+    }
+
+    /// 启用或停用令牌
+    pub async fn set_influxdb2_authorization_active(&self, authorization_id: &str, active: bool) -> Result<crate::commands::influxdb2::AuthorizationInfo> {
+        debug!("设置令牌状态: {} - {}", authorization_id, active);
+
+        let path = format!("/api/v2/authorizations/{}", urlencoding::encode(authorization_id));
+        let body = serde_json::json!({"status": if active { "active" } else { "inactive" }});
+        let response = self.send_v2_request(reqwest::Method::PATCH, &path, Some(body)).await?;
+
+        Ok(authorizations::parse_authorization(&response, self.current_v2_token(), false))
+    }
+
+    /// 删除令牌
+    pub async fn delete_influxdb2_authorization(&self, authorization_id: &str) -> Result<()> {
+        debug!("删除令牌: {}", authorization_id);
+
+        let path = format!("/api/v2/authorizations/{}", urlencoding::encode(authorization_id));
+        self.send_v2_request(reqwest::Method::DELETE, &path, None).await?;
+        info!("令牌 '{}' 删除成功", authorization_id);
+        Ok(())
+    }
+
+    /// 获取当前连接使用的令牌，服务端不返回令牌值时返回 None
+    pub async fn get_influxdb2_current_authorization(&self) -> Result<Option<crate::commands::influxdb2::AuthorizationInfo>> {
+        debug!("获取当前连接令牌");
+
+        let response = self
+            .send_v2_request(reqwest::Method::GET, "/api/v2/authorizations", None)
+            .await?;
+
+        let current_token = self.current_v2_token();
+        Ok(response
+            .get("authorizations")
+            .and_then(|a| a.as_array())
+            .and_then(|authorizations| {
+                authorizations
+                    .iter()
+                    .map(|a| authorizations::parse_authorization(a, current_token, false))
+                    .find(|a| a.is_current)
+            }))
+    }
+
+    /// 创建组织
+    pub async fn create_influxdb2_organization(&self, name: &str, description: Option<&str>) -> Result<crate::commands::influxdb2::OrganizationInfo> {
+        debug!("创建组织: {}", name);
+        admin::validate_name(name, "组织")?;
+
+        let mut body = serde_json::json!({"name": name.trim()});
+        if let Some(desc) = description {
+            body["description"] = serde_json::json!(desc);
+        }
+
+        let response = self.send_v2_request(reqwest::Method::POST, "/api/v2/orgs", Some(body)).await?;
+        info!("组织 '{}' 创建成功", name);
+        Ok(admin::parse_organization(&response))
+    }
+
+    /// 重命名组织或更新描述
+    pub async fn update_influxdb2_organization(&self, org_name: &str, new_name: Option<&str>, description: Option<&str>) -> Result<crate::commands::influxdb2::OrganizationInfo> {
+        debug!("更新组织: {}", org_name);
+
+        let org_id = self.resolve_v2_org_id(None, Some(org_name)).await?;
+        let mut body = serde_json::json!({});
+        if let Some(new_name) = new_name {
+            admin::validate_name(new_name, "组织")?;
+            body["name"] = serde_json::json!(new_name.trim());
+        }
+        if let Some(desc) = description {
+            body["description"] = serde_json::json!(desc);
+        }
+
+        let path = format!("/api/v2/orgs/{}", urlencoding::encode(&org_id));
+        let response = self.send_v2_request(reqwest::Method::PATCH, &path, Some(body)).await?;
+        info!("组织 '{}' 更新成功", org_name);
+        Ok(admin::parse_organization(&response))
+    }
+
+    /// 删除组织（会同时删除组织下的存储桶、任务等资源）
+    pub async fn delete_influxdb2_organization(&self, org_name: &str) -> Result<()> {
+        debug!("删除组织: {}", org_name);
+
+        let org_id = self.resolve_v2_org_id(None, Some(org_name)).await?;
+        let path = format!("/api/v2/orgs/{}", urlencoding::encode(&org_id));
+        self.send_v2_request(reqwest::Method::DELETE, &path, None).await?;
+        info!("组织 '{}' 删除成功", org_name);
+        Ok(())
+    }
+
+    /// 获取组织成员，所有者排在前面
+    pub async fn get_influxdb2_org_members(&self, org_name: &str) -> Result<Vec<crate::commands::influxdb2::OrgMemberInfo>> {
+        debug!("获取组织成员: {}", org_name);
+
+        let org_id = self.resolve_v2_org_id(None, Some(org_name)).await?;
+        let mut members: Vec<crate::commands::influxdb2::OrgMemberInfo> = Vec::new();
+        for owner in [true, false] {
+            let path = format!("/api/v2/orgs/{}/{}", urlencoding::encode(&org_id), admin::member_segment(owner));
+            let response = self.send_v2_request(reqwest::Method::GET, &path, None).await?;
+            for mut member in admin::parse_list(&response, "users", admin::parse_member) {
+                if owner {
+                    member.role = "owner".to_string();
+                }
+                if !members.iter().any(|m| m.id == member.id) {
+                    members.push(member);
+                }
+            }
+        }
+
+        Ok(members)
+    }
+
+    /// 添加组织成员或所有者
+    pub async fn add_influxdb2_org_member(&self, org_name: &str, user_id: &str, owner: bool) -> Result<()> {
+        debug!("添加组织成员: {} - {} - {}", org_name, user_id, owner);
+
+        let org_id = self.resolve_v2_org_id(None, Some(org_name)).await?;
+        let path = format!("/api/v2/orgs/{}/{}", urlencoding::encode(&org_id), admin::member_segment(owner));
+        self.send_v2_request(reqwest::Method::POST, &path, Some(serde_json::json!({"id": user_id})))
+            .await?;
+        Ok(())
+    }
+
+    /// 移除组织成员或所有者
+    pub async fn remove_influxdb2_org_member(&self, org_name: &str, user_id: &str, owner: bool) -> Result<()> {
+        debug!("移除组织成员: {} - {} - {}", org_name, user_id, owner);
+
+        let org_id = self.resolve_v2_org_id(None, Some(org_name)).await?;
+        let path = format!(
+            "/api/v2/orgs/{}/{}/{}",
+            urlencoding::encode(&org_id),
+            admin::member_segment(owner),
+            urlencoding::encode(user_id)
+        );
+        self.send_v2_request(reqwest::Method::DELETE, &path, None).await?;
+        Ok(())
+    }
+
+    /// 获取用户列表
+    pub async fn get_influxdb2_users(&self) -> Result<Vec<crate::commands::influxdb2::User2xInfo>> {
+        debug!("获取 InfluxDB 2.x 用户列表");
+
+        let response = self.send_v2_request(reqwest::Method::GET, "/api/v2/users", None).await?;
+        Ok(admin::parse_list(&response, "users", admin::parse_user))
+    }
+
+    /// 创建用户，可选设置密码并加入组织
+    pub async fn create_influxdb2_user(&self, name: &str, password: Option<&str>, org_name: Option<&str>, owner: bool) -> Result<crate::commands::influxdb2::User2xInfo> {
+        debug!("创建用户: {}", name);
+        admin::validate_name(name, "用户")?;
+        if let Some(password) = password {
+            admin::validate_password(password)?;
+        }
+
+        let body = serde_json::json!({"name": name.trim(), "status": "active"});
+        let response = self.send_v2_request(reqwest::Method::POST, "/api/v2/users", Some(body)).await?;
+        let user = admin::parse_user(&response);
+
+        if let Some(password) = password {
+            self.reset_influxdb2_user_password(&user.id, password).await?;
+        }
+        if let Some(org_name) = org_name {
+            self.add_influxdb2_org_member(org_name, &user.id, owner).await?;
+        }
+
+        info!("用户 '{}' 创建成功", user.name);
+        Ok(user)
+    }
+
+    /// 重置用户密码
+    pub async fn reset_influxdb2_user_password(&self, user_id: &str, password: &str) -> Result<()> {
+        debug!("重置用户密码: {}", user_id);
+        admin::validate_password(password)?;
+
+        let path = format!("/api/v2/users/{}/password", urlencoding::encode(user_id));
+        self.send_v2_request(reqwest::Method::POST, &path, Some(serde_json::json!({"password": password})))
+            .await?;
+        Ok(())
+    }
+
+    /// 删除用户
+    pub async fn delete_influxdb2_user(&self, user_id: &str) -> Result<()> {
+        debug!("删除用户: {}", user_id);
+
+        let path = format!("/api/v2/users/{}", urlencoding::encode(user_id));
+        self.send_v2_request(reqwest::Method::DELETE, &path, None).await?;
+        info!("用户 '{}' 删除成功", user_id);
+        Ok(())
+    }
+
+    /// 获取标签列表
+    pub async fn get_influxdb2_labels(&self, org_name: Option<&str>) -> Result<Vec<crate::commands::influxdb2::LabelInfo>> {
+        debug!("获取标签列表: {:?}", org_name);
+
+        let org_id = self.resolve_v2_org_id(None, org_name).await?;
+        let path = format!("/api/v2/labels?orgID={}", urlencoding::encode(&org_id));
+        let response = self.send_v2_request(reqwest::Method::GET, &path, None).await?;
+        Ok(admin::parse_list(&response, "labels", admin::parse_label))
+    }
+
+    /// 创建标签
+    pub async fn create_influxdb2_label(&self, request: &crate::commands::influxdb2::SaveLabelRequest) -> Result<crate::commands::influxdb2::LabelInfo> {
+        debug!("创建标签: {}", request.name);
+        admin::validate_name(&request.name, "标签")?;
+
+        let org_id = self.resolve_v2_org_id(None, request.org_name.as_deref()).await?;
+        let body = serde_json::json!({
+            "orgID": org_id,
+            "name": request.name.trim(),
+            "properties": admin::build_label_properties(request.color.as_deref(), request.description.as_deref())?,
+        });
+
+        let response = self.send_v2_request(reqwest::Method::POST, "/api/v2/labels", Some(body)).await?;
+        info!("标签 '{}' 创建成功", request.name);
+        Ok(admin::parse_label(response.get("label").unwrap_or(&response)))
+    }
+
+    /// 更新标签
+    pub async fn update_influxdb2_label(&self, label_id: &str, request: &crate::commands::influxdb2::SaveLabelRequest) -> Result<crate::commands::influxdb2::LabelInfo> {
+        debug!("更新标签: {}", label_id);
+        admin::validate_name(&request.name, "标签")?;
+
+        let body = serde_json::json!({
+            "name": request.name.trim(),
+            "properties": admin::build_label_properties(request.color.as_deref(), request.description.as_deref())?,
+        });
+
+        let path = format!("/api/v2/labels/{}", urlencoding::encode(label_id));
+        let response = self.send_v2_request(reqwest::Method::PATCH, &path, Some(body)).await?;
+        Ok(admin::parse_label(response.get("label").unwrap_or(&response)))
+    }
+
+    /// 删除标签
+    pub async fn delete_influxdb2_label(&self, label_id: &str) -> Result<()> {
+        debug!("删除标签: {}", label_id);
+
+        let path = format!("/api/v2/labels/{}", urlencoding::encode(label_id));
+        self.send_v2_request(reqwest::Method::DELETE, &path, None).await?;
+        info!("标签 '{}' 删除成功", label_id);
+        Ok(())
+    }
+
+    /// 获取资源（存储桶、任务、仪表板）上的标签
+    pub async fn get_influxdb2_resource_labels(&self, resource_type: &str, resource_id: &str) -> Result<Vec<crate::commands::influxdb2::LabelInfo>> {
+        debug!("获取资源标签: {} - {}", resource_type, resource_id);
+
+        let segment = admin::label_resource_segment(resource_type)?;
+        let path = format!("/api/v2/{}/{}/labels", segment, urlencoding::encode(resource_id));
+        let response = self.send_v2_request(reqwest::Method::GET, &path, None).await?;
+        Ok(admin::parse_list(&response, "labels", admin::parse_label))
+    }
+
+    /// 为资源分配标签
+    pub async fn add_influxdb2_resource_label(&self, resource_type: &str, resource_id: &str, label_id: &str) -> Result<()> {
+        debug!("分配标签: {} - {} - {}", resource_type, resource_id, label_id);
+
+        let segment = admin::label_resource_segment(resource_type)?;
+        let path = format!("/api/v2/{}/{}/labels", segment, urlencoding::encode(resource_id));
+        self.send_v2_request(reqwest::Method::POST, &path, Some(serde_json::json!({"labelID": label_id})))
+            .await?;
+        Ok(())
+    }
+
+    /// 移除资源上的标签
+    pub async fn remove_influxdb2_resource_label(&self, resource_type: &str, resource_id: &str, label_id: &str) -> Result<()> {
+        debug!("移除标签: {} - {} - {}", resource_type, resource_id, label_id);
+
+        let segment = admin::label_resource_segment(resource_type)?;
+        let path = format!(
+            "/api/v2/{}/{}/labels/{}",
+            segment,
+            urlencoding::encode(resource_id),
+            urlencoding::encode(label_id)
+        );
+        self.send_v2_request(reqwest::Method::DELETE, &path, None).await?;
+        Ok(())
+    }
+
+    /// 获取 Telegraf 配置列表
+    pub async fn get_influxdb2_telegrafs(&self, org_name: Option<&str>) -> Result<Vec<crate::commands::influxdb2::TelegrafConfigInfo>> {
+        debug!("获取 Telegraf 配置列表: {:?}", org_name);
+
+        let org_id = self.resolve_v2_org_id(None, org_name).await?;
+        let path = format!("/api/v2/telegrafs?orgID={}", urlencoding::encode(&org_id));
+        let response = self.send_v2_request(reqwest::Method::GET, &path, None).await?;
+        Ok(admin::parse_list(&response, "configurations", telegraf::parse_telegraf))
+    }
+
+    /// 获取单个 Telegraf 配置
+    pub async fn get_influxdb2_telegraf(&self, telegraf_id: &str) -> Result<crate::commands::influxdb2::TelegrafConfigInfo> {
+        debug!("获取 Telegraf 配置: {}", telegraf_id);
+
+        let path = format!("/api/v2/telegrafs/{}", urlencoding::encode(telegraf_id));
+        let response = self.send_v2_request(reqwest::Method::GET, &path, None).await?;
+        Ok(telegraf::parse_telegraf(&response))
+    }
+
+    /// 构建 Telegraf 配置请求体，配置未通过校验时返回错误
+    async fn telegraf_body(&self, request: &crate::commands::influxdb2::SaveTelegrafRequest) -> Result<serde_json::Value> {
+        admin::validate_name(&request.name, "Telegraf 配置")?;
+
+        let validation = telegraf::validate_telegraf_toml(&request.config);
+        if !validation.valid {
+            return Err(anyhow::anyhow!("Telegraf 配置校验失败: {}", validation.errors.join("; ")));
+        }
+
+        let org_id = self.resolve_v2_org_id(request.org_id.as_deref(), None).await?;
+        let mut body = serde_json::json!({
+            "name": request.name.trim(),
+            "orgID": org_id,
+            "config": request.config,
+            "metadata": {"buckets": request.buckets},
+        });
+        if let Some(desc) = &request.description {
+            body["description"] = serde_json::json!(desc);
+        }
+
+        Ok(body)
+    }
+
+    /// 创建 Telegraf 配置
+    pub async fn create_influxdb2_telegraf(&self, request: &crate::commands::influxdb2::SaveTelegrafRequest) -> Result<crate::commands::influxdb2::TelegrafConfigInfo> {
+        debug!("创建 Telegraf 配置: {}", request.name);
+
+        let body = self.telegraf_body(request).await?;
+        let response = self.send_v2_request(reqwest::Method::POST, "/api/v2/telegrafs", Some(body)).await?;
+        info!("Telegraf 配置 '{}' 创建成功", request.name);
+        Ok(telegraf::parse_telegraf(&response))
+    }
+
+    /// 更新 Telegraf 配置（整体替换）
+    pub async fn update_influxdb2_telegraf(&self, telegraf_id: &str, request: &crate::commands::influxdb2::SaveTelegrafRequest) -> Result<crate::commands::influxdb2::TelegrafConfigInfo> {
+        debug!("更新 Telegraf 配置: {}", telegraf_id);
+
+        let body = self.telegraf_body(request).await?;
+        let path = format!("/api/v2/telegrafs/{}", urlencoding::encode(telegraf_id));
+        let response = self.send_v2_request(reqwest::Method::PUT, &path, Some(body)).await?;
+        info!("Telegraf 配置 '{}' 更新成功", request.name);
+        Ok(telegraf::parse_telegraf(&response))
+    }
+
+    /// 删除 Telegraf 配置
+    pub async fn delete_influxdb2_telegraf(&self, telegraf_id: &str) -> Result<()> {
+        debug!("删除 Telegraf 配置: {}", telegraf_id);
+
+        let path = format!("/api/v2/telegrafs/{}", urlencoding::encode(telegraf_id));
+        self.send_v2_request(reqwest::Method::DELETE, &path, None).await?;
+        info!("Telegraf 配置 '{}' 删除成功", telegraf_id);
+        Ok(())
+    }
+
+    /// 为存储桶生成 Telegraf 入门配置；`create_token` 为 true 时创建只写令牌并写入配置
+    pub async fn generate_influxdb2_telegraf_config(&self, bucket_name: &str, create_token: bool) -> Result<crate::commands::influxdb2::TelegrafStarterConfig> {
+        debug!("生成 Telegraf 入门配置: {}", bucket_name);
+
+        let bucket = self.get_influxdb2_bucket_info(bucket_name).await?;
+        let org_name = if bucket.org_name.is_empty() {
+            self.default_v2_org().unwrap_or_default()
+        } else {
+            bucket.org_name.clone()
+        };
+
+        let (token, token_id) = if create_token {
+            let request = crate::commands::influxdb2::CreateAuthorizationRequest {
+                description: Some(format!("Telegraf 写入 {}", bucket_name)),
+                bucket_names: vec![bucket_name.to_string()],
+                read: false,
+                write: true,
+            };
+            let authorization = self.create_influxdb2_authorization(&request).await?;
+            (authorization.token.unwrap_or_default(), Some(authorization.id))
+        } else {
+            ("${INFLUX_TOKEN}".to_string(), None)
+        };
+
+        let config = telegraf::build_starter_config(&self.v2_base_url(), &org_name, bucket_name, &token);
+        Ok(crate::commands::influxdb2::TelegrafStarterConfig {
+            config,
+            bucket_name: bucket_name.to_string(),
+            org_name,
+            token_id,
+        })
+    }
+
+    /// 获取抓取器列表
+    pub async fn get_influxdb2_scrapers(&self, org_name: Option<&str>) -> Result<Vec<crate::commands::influxdb2::ScraperInfo>> {
+        debug!("获取抓取器列表: {:?}", org_name);
+
+        let org_id = self.resolve_v2_org_id(None, org_name).await?;
+        let path = format!("/api/v2/scrapers?orgID={}", urlencoding::encode(&org_id));
+        let response = self.send_v2_request(reqwest::Method::GET, &path, None).await?;
+        Ok(admin::parse_list(&response, "configurations", telegraf::parse_scraper))
+    }
+
+    /// 创建抓取器
+    pub async fn create_influxdb2_scraper(&self, request: &crate::commands::influxdb2::SaveScraperRequest) -> Result<crate::commands::influxdb2::ScraperInfo> {
+        debug!("创建抓取器: {}", request.name);
+        admin::validate_name(&request.name, "抓取器")?;
+        telegraf::validate_scraper_url(&request.url)?;
+
+        let bucket = self.get_influxdb2_bucket_info(&request.bucket_name).await?;
+        let body = serde_json::json!({
+            "name": request.name.trim(),
+            "type": "prometheus",
+            "url": request.url,
+            "orgID": bucket.org_id,
+            "bucketID": bucket.id,
+            "allowInsecure": request.allow_insecure,
+        });
+
+        let response = self.send_v2_request(reqwest::Method::POST, "/api/v2/scrapers", Some(body)).await?;
+        info!("抓取器 '{}' 创建成功", request.name);
+        Ok(telegraf::parse_scraper(&response))
+    }
+
+    /// 更新抓取器
+    pub async fn update_influxdb2_scraper(&self, scraper_id: &str, request: &crate::commands::influxdb2::SaveScraperRequest) -> Result<crate::commands::influxdb2::ScraperInfo> {
+        debug!("更新抓取器: {}", scraper_id);
+        admin::validate_name(&request.name, "抓取器")?;
+        telegraf::validate_scraper_url(&request.url)?;
+
+        let bucket = self.get_influxdb2_bucket_info(&request.bucket_name).await?;
+        let body = serde_json::json!({
+            "name": request.name.trim(),
+            "url": request.url,
+            "bucketID": bucket.id,
+            "allowInsecure": request.allow_insecure,
+        });
+
+        let path = format!("/api/v2/scrapers/{}", urlencoding::encode(scraper_id));
+        let response = self.send_v2_request(reqwest::Method::PATCH, &path, Some(body)).await?;
+        info!("抓取器 '{}' 更新成功", request.name);
+        Ok(telegraf::parse_scraper(&response))
+    }
+
+    /// 删除抓取器
+    pub async fn delete_influxdb2_scraper(&self, scraper_id: &str) -> Result<()> {
+        debug!("删除抓取器: {}", scraper_id);
+
+        let path = format!("/api/v2/scrapers/{}", urlencoding::encode(scraper_id));
+        self.send_v2_request(reqwest::Method::DELETE, &path, None).await?;
+        info!("抓取器 '{}' 删除成功", scraper_id);
+        Ok(())
+    }
+
+    /// 发送 InfluxDB 1.x /query 请求并返回全部 series
+    ///
+    /// 非 SHOW/SELECT 语句（以及 SELECT ... INTO）必须使用 POST，参数放在表单请求体中，
+    /// 避免 CREATE USER / SET PASSWORD 中的密码出现在 URL 和访问日志里
+    async fn send_v1_query(&self, query: &str, database: Option<&str>) -> Result<Vec<influxql::InfluxQLSeries>> {
+        if self.driver.capabilities().major != 1 {
+            return Err(anyhow::anyhow!("此操作仅支持 InfluxDB 1.x"));
+        }
+
+        let url = format!("{}/query", self.v2_base_url());
+        let mut params = vec![("q", query)];
+        if let Some(database) = database {
+            params.push(("db", database));
+        }
+
+        let upper = query.trim_start().to_uppercase();
+        let read_only = upper.starts_with("SHOW")
+            || (upper.starts_with("SELECT") && influxql::find_keyword(query, "INTO").is_none());
+
+        let mut request = if read_only {
+            self.http_client.get(&url).query(&params)
+        } else {
+            self.http_client.post(&url).form(&params)
+        };
+        if let (Some(username), Some(password)) = (&self.config.username, &self.config.password) {
+            request = request.basic_auth(username, Some(password));
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("执行 InfluxQL 失败: {}", e))?;
+
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        let json: serde_json::Value = match serde_json::from_str(&text) {
+            Ok(json) => json,
+            Err(_) if !status.is_success() => return Err(anyhow::anyhow!("({}) {}", status, text)),
+            Err(e) => return Err(anyhow::anyhow!("解析响应失败: {}", e)),
+        };
+        influxql::parse_influxql_response(&json)
+    }
+
+    /// 获取全部数据库的连续查询
+    pub async fn get_influxdb1_continuous_queries(&self) -> Result<Vec<crate::commands::influxdb1::InfluxContinuousQueryInfo>> {
+        debug!("获取连续查询列表");
+
+        let series = self.send_v1_query("SHOW CONTINUOUS QUERIES", None).await?;
+        Ok(continuous_queries::parse_continuous_queries(&series))
+    }
+
+    /// 获取指定连续查询
+    async fn get_influxdb1_continuous_query(&self, database: &str, name: &str) -> Result<crate::commands::influxdb1::InfluxContinuousQueryInfo> {
+        self.get_influxdb1_continuous_queries()
+            .await?
+            .into_iter()
+            .find(|cq| cq.database == database && cq.name == name)
+            .ok_or_else(|| anyhow::anyhow!("连续查询 '{}' 不存在于数据库 '{}'", name, database))
+    }
+
+    /// 创建连续查询
+    pub async fn create_influxdb1_continuous_query(
+        &self,
+        request: &crate::commands::influxdb1::CreateInfluxContinuousQueryRequest,
+    ) -> Result<crate::commands::influxdb1::InfluxContinuousQueryInfo> {
+        debug!("创建连续查询: {}.{}", request.database, request.name);
+
+        let statement = continuous_queries::build_create_statement(request)?;
+        self.send_v1_query(&statement, Some(&request.database)).await?;
+        info!("连续查询 '{}' 创建成功", request.name);
+
+        self.get_influxdb1_continuous_query(&request.database, &request.name).await
+    }
+
+    /// 删除连续查询
+    pub async fn drop_influxdb1_continuous_query(&self, database: &str, name: &str) -> Result<()> {
+        debug!("删除连续查询: {}.{}", database, name);
+
+        let statement = continuous_queries::build_drop_statement(name, database);
+        self.send_v1_query(&statement, Some(database)).await?;
+        info!("连续查询 '{}' 删除成功", name);
+        Ok(())
+    }
+
+    /// 按时间窗口分段执行连续查询的 SELECT ... INTO，回填历史数据
+    pub async fn backfill_influxdb1_continuous_query<F>(
+        &self,
+        request: &crate::commands::influxdb1::BackfillContinuousQueryRequest,
+        on_progress: F,
+    ) -> Result<crate::commands::influxdb1::ContinuousQueryBackfillResult>
+    where
+        F: Fn(&crate::commands::influxdb1::ContinuousQueryBackfillProgress),
+    {
+        debug!("回填连续查询: {}.{} 从 {} 到 {:?}", request.database, request.name, request.start, request.end);
+
+        let start_time = Instant::now();
+        let parse_time = |value: &str| {
+            chrono::DateTime::parse_from_rfc3339(value)
+                .map(|t| t.with_timezone(&chrono::Utc))
+                .map_err(|e| anyhow::anyhow!("无效的时间 {}: {}", value, e))
+        };
+        let start = parse_time(&request.start)?;
+        let end = match request.end.as_deref().filter(|e| !e.is_empty()) {
+            Some(end) => parse_time(end)?,
+            None => chrono::Utc::now(),
+        };
+
+        let cq = self.get_influxdb1_continuous_query(&request.database, &request.name).await?;
+        let interval = cq
+            .group_by_interval
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("连续查询缺少 GROUP BY time() 间隔"))?;
+        let interval_secs = influxql::parse_duration_secs(interval)?;
+        let chunk = request
+            .chunk
+            .as_deref()
+            .filter(|c| !c.is_empty())
+            .unwrap_or(continuous_queries::DEFAULT_BACKFILL_CHUNK);
+        let chunk_secs = influxql::parse_duration_secs(chunk)?;
+
+        let windows = continuous_queries::plan_backfill_windows(start, end, interval_secs, chunk_secs)?;
+        let total = windows.len();
+        let mut points_written = 0;
+
+        for (index, (window_start, window_end)) in windows.iter().enumerate() {
+            let statement = continuous_queries::build_backfill_statement(&cq.select, *window_start, *window_end)?;
+            let series = self
+                .send_v1_query(&statement, Some(&cq.database))
+                .await
+                .map_err(|e| anyhow::anyhow!("回填窗口 {} ~ {} 失败: {}", window_start.to_rfc3339(), window_end.to_rfc3339(), e))?;
+            points_written += continuous_queries::points_written(&series);
+
+            on_progress(&crate::commands::influxdb1::ContinuousQueryBackfillProgress {
+                connection_id: self.config.id.clone(),
+                database: cq.database.clone(),
+                name: cq.name.clone(),
+                completed: index + 1,
+                total,
+                window_start: window_start.to_rfc3339(),
+                window_end: window_end.to_rfc3339(),
+                points_written,
+            });
+        }
+
+        info!("连续查询 '{}' 回填完成: {} 个窗口，写入 {} 个点", cq.name, total, points_written);
+        Ok(crate::commands::influxdb1::ContinuousQueryBackfillResult {
+            windows: total,
+            points_written,
+            execution_time: start_time.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// 获取 InfluxDB 1.x 用户列表
+    pub async fn get_influxdb1_users(&self) -> Result<Vec<crate::commands::influxdb1::InfluxUser1xInfo>> {
+        debug!("获取用户列表");
+
+        let series = self.send_v1_query("SHOW USERS", None).await?;
+        Ok(users::parse_users(&series))
+    }
+
+    /// 创建 InfluxDB 1.x 用户
+    pub async fn create_influxdb1_user(&self, request: &crate::commands::influxdb1::CreateInfluxUser1xRequest) -> Result<()> {
+        debug!("创建用户: {} (admin: {})", request.name, request.admin);
+
+        let statement = users::build_create_user(&request.name, &request.password, request.admin)?;
+        self.send_v1_query(&statement, None).await?;
+        info!("用户 '{}' 创建成功", request.name);
+        Ok(())
+    }
+
+    /// 修改 InfluxDB 1.x 用户密码
+    pub async fn set_influxdb1_user_password(&self, username: &str, password: &str) -> Result<()> {
+        debug!("修改用户密码: {}", username);
+
+        let statement = users::build_set_password(username, password)?;
+        self.send_v1_query(&statement, None).await?;
+        Ok(())
+    }
+
+    /// 授予或收回 InfluxDB 1.x 管理员权限
+    pub async fn set_influxdb1_user_admin(&self, username: &str, admin: bool) -> Result<()> {
+        debug!("设置管理员权限: {} - {}", username, admin);
+
+        let statement = users::build_set_admin(username, admin)?;
+        self.send_v1_query(&statement, None).await?;
+        Ok(())
+    }
+
+    /// 删除 InfluxDB 1.x 用户
+    pub async fn drop_influxdb1_user(&self, username: &str) -> Result<()> {
+        debug!("删除用户: {}", username);
+
+        users::validate_username(username)?;
+        let statement = format!("DROP USER {}", influxql::quote_identifier(username));
+        self.send_v1_query(&statement, None).await?;
+        info!("用户 '{}' 删除成功", username);
+        Ok(())
+    }
+
+    /// 获取 InfluxDB 1.x 用户在各数据库上的权限
+    pub async fn get_influxdb1_user_grants(&self, username: &str) -> Result<Vec<crate::commands::influxdb1::DatabaseGrant1x>> {
+        debug!("获取用户权限: {}", username);
+
+        users::validate_username(username)?;
+        let statement = format!("SHOW GRANTS FOR {}", influxql::quote_identifier(username));
+        let series = self.send_v1_query(&statement, None).await?;
+        Ok(users::parse_grants(&series))
+    }
+
+    /// 授予 InfluxDB 1.x 数据库权限
+    pub async fn grant_influxdb1_privilege(&self, username: &str, database: &str, privilege: &str) -> Result<()> {
+        debug!("授予权限: {} {} ON {}", username, privilege, database);
+
+        let statement = users::build_grant(username, database, privilege)?;
+        self.send_v1_query(&statement, None).await?;
+        Ok(())
+    }
+
+    /// 收回 InfluxDB 1.x 数据库权限
+    pub async fn revoke_influxdb1_privilege(&self, username: &str, database: &str, privilege: &str) -> Result<()> {
+        debug!("收回权限: {} {} ON {}", username, privilege, database);
+
+        let statement = users::build_revoke(username, database, privilege)?;
+        self.send_v1_query(&statement, None).await?;
+        Ok(())
+    }
+
+    /// 将用户在某数据库上的权限设置为指定的读写组合，返回设置后的权限
+    pub async fn set_influxdb1_privileges(
+        &self,
+        username: &str,
+        database: &str,
+        read: bool,
+        write: bool,
+    ) -> Result<crate::commands::influxdb1::DatabaseGrant1x> {
+        debug!("设置权限: {} ON {} (read: {}, write: {})", username, database, read, write);
+
+        let current = self.get_influxdb1_database_grant(username, database).await?;
+        if let Some(statement) = users::build_set_privileges(username, database, (current.read, current.write), (read, write))? {
+            self.send_v1_query(&statement, None).await?;
+        }
+
+        let updated = self.get_influxdb1_database_grant(username, database).await?;
+        if (updated.read, updated.write) != (read, write) {
+            return Err(anyhow::anyhow!(
+                "权限设置未生效，当前权限为 {}",
+                users::privilege_keyword(updated.read, updated.write).unwrap_or("NONE")
+            ));
+        }
+        Ok(updated)
+    }
+
+    /// 获取用户在指定数据库上的权限，没有记录时返回无权限
+    async fn get_influxdb1_database_grant(&self, username: &str, database: &str) -> Result<crate::commands::influxdb1::DatabaseGrant1x> {
+        Ok(self
+            .get_influxdb1_user_grants(username)
+            .await?
+            .into_iter()
+            .find(|g| g.database == database)
+            .unwrap_or_else(|| crate::commands::influxdb1::DatabaseGrant1x {
+                database: database.to_string(),
+                privilege: "NONE".to_string(),
+                read: false,
+                write: false,
+            }))
+    }
+
+    /// 获取 InfluxDB 1.x 用户 × 数据库权限矩阵
+    pub async fn get_influxdb1_privilege_matrix(&self) -> Result<crate::commands::influxdb1::PrivilegeMatrix> {
+        debug!("获取权限矩阵");
+
+        let user_list = self.get_influxdb1_users().await?;
+        let mut databases = self.list_databases().await?;
+        databases.sort();
+
+        // 管理员对所有数据库拥有全部权限，无需逐个查询
+        let mut grants = std::collections::HashMap::new();
+        for user in user_list.iter().filter(|u| !u.admin) {
+            grants.insert(user.name.clone(), self.get_influxdb1_user_grants(&user.name).await?);
+        }
+
+        Ok(users::build_privilege_matrix(&user_list, &databases, &grants))
+    }
+
+    /// 读取 _internal 中的分片大小，未启用监控时返回空表
+    async fn get_influxdb1_shard_sizes(&self) -> std::collections::HashMap<u64, i64> {
+        match self.send_v1_query(shards::SHARD_SIZE_QUERY, None).await {
+            Ok(series) => shards::parse_shard_sizes(&series),
+            Err(e) => {
+                warn!("读取 _internal 分片大小失败: {}", e);
+                std::collections::HashMap::new()
+            }
+        }
+    }
+
+    /// 获取 InfluxDB 1.x 分片列表
+    pub async fn get_influxdb1_shards(&self, database: Option<&str>) -> Result<Vec<crate::commands::influxdb1::ShardInfo>> {
+        debug!("获取分片列表: {:?}", database);
+
+        let series = self.send_v1_query("SHOW SHARDS", None).await?;
+        let sizes = self.get_influxdb1_shard_sizes().await;
+        Ok(shards::parse_shards(&series, &sizes, chrono::Utc::now())
+            .into_iter()
+            .filter(|shard| database.is_none_or(|db| shard.database == db))
+            .collect())
+    }
+
+    /// 获取 InfluxDB 1.x 分片组列表
+    pub async fn get_influxdb1_shard_groups(&self, database: Option<&str>) -> Result<Vec<crate::commands::influxdb1::ShardGroupInfo>> {
+        debug!("获取分片组列表: {:?}", database);
+
+        let shard_list = self.get_influxdb1_shards(database).await?;
+        let series = self.send_v1_query("SHOW SHARD GROUPS", None).await?;
+        Ok(shards::parse_shard_groups(&series, &shard_list)
+            .into_iter()
+            .filter(|group| database.is_none_or(|db| group.database == db))
+            .collect())
+    }
+
+    /// 获取 InfluxDB 1.x 按保留策略汇总的分片时间线
+    pub async fn get_influxdb1_shard_timeline(&self, database: Option<&str>) -> Result<Vec<crate::commands::influxdb1::RetentionPolicyTimeline>> {
+        debug!("获取分片时间线: {:?}", database);
+
+        let groups = self.get_influxdb1_shard_groups(database).await?;
+        Ok(shards::build_timelines(&groups))
+    }
+
+    /// 删除 InfluxDB 1.x 分片，返回被删除的分片信息
+    pub async fn drop_influxdb1_shard(&self, shard_id: u64) -> Result<crate::commands::influxdb1::ShardInfo> {
+        debug!("删除分片: {}", shard_id);
+
+        let shard = self
+            .get_influxdb1_shards(None)
+            .await?
+            .into_iter()
+            .find(|shard| shard.id == shard_id)
+            .ok_or_else(|| anyhow::anyhow!("分片 {} 不存在", shard_id))?;
+
+        self.send_v1_query(&format!("DROP SHARD {}", shard_id), None).await?;
+        info!("分片 {} 删除成功", shard_id);
+        Ok(shard)
+    }
+
+    /// 获取 InfluxDB 1.x 服务器上正在执行的查询
+    pub async fn get_influxdb1_running_queries(&self) -> Result<Vec<crate::commands::influxdb1::RunningQueryInfo>> {
+        debug!("获取运行中查询");
+
+        let series = self.send_v1_query("SHOW QUERIES", None).await?;
+        Ok(running_queries::parse_running_queries(&series))
+    }
+
+    /// 终止 InfluxDB 1.x 服务器上正在执行的查询
+    pub async fn kill_influxdb1_query(&self, qid: u64, host: Option<&str>) -> Result<()> {
+        debug!("终止查询: {} {:?}", qid, host);
+
+        self.send_v1_query(&running_queries::build_kill_query(qid, host), None).await?;
+        info!("查询 {} 已终止", qid);
+        Ok(())
+    }
+
+    /// 发送 InfluxDB 3.x 管理接口请求，返回解析后的 JSON（空响应或非 JSON 响应返回 Null）
+    async fn send_v3_request(&self, method: reqwest::Method, path: &str, body: Option<serde_json::Value>) -> Result<serde_json::Value> {
+        if self.driver.capabilities().major != 3 {
+            return Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x"));
+        }
+
+        let url = format!("{}{}", self.v2_base_url(), path);
+        let mut request = reqwest::Client::new().request(method, &url);
+        if let Some(token) = self.current_v2_token().filter(|t| !t.is_empty()) {
+            request = request.bearer_auth(token);
+        }
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("请求 {} 失败: {}", path, e))?;
+
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            let message = serde_json::from_str::<serde_json::Value>(&text)
+                .ok()
+                .and_then(|v| {
+                    v.get("error")
+                        .or_else(|| v.get("message"))
+                        .and_then(|m| m.as_str())
+                        .map(|m| m.to_string())
+                })
+                .unwrap_or(text);
+            return Err(anyhow::anyhow!("({}) {}", status, message));
+        }
+        Ok(serde_json::from_str(&text).unwrap_or(serde_json::Value::Null))
+    }
+
+    /// 在 InfluxDB 3.x 数据库上执行 SQL（有 FlightSQL 时经由 FlightSqlDriver）
+    async fn query_v3_sql(&self, database: &str, sql: String) -> Result<crate::database::influxdb::DataSet> {
+        use crate::database::influxdb::{Query, QueryLanguage};
+
+        if self.driver.capabilities().major != 3 {
+            return Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x"));
+        }
+
+        let query = Query::new(QueryLanguage::Sql, sql).with_database(database.to_string());
+        self.driver.query(&query).await
+    }
+
+    /// 获取 InfluxDB 3.x 最新值缓存列表
+    pub async fn get_influxdb3_last_caches(&self, database: &str, table: Option<&str>) -> Result<Vec<crate::commands::influxdb3::LastCacheInfo>> {
+        debug!("获取最新值缓存列表: {} {:?}", database, table);
+
+        let dataset = self.query_v3_sql(database, caches::build_list_query("last_caches", table)).await?;
+        Ok(caches::parse_last_caches(database, &dataset))
+    }
+
+    /// 创建 InfluxDB 3.x 最新值缓存，返回服务器上的缓存定义
+    pub async fn create_influxdb3_last_cache(&self, request: &crate::commands::influxdb3::CreateLastCacheRequest) -> Result<crate::commands::influxdb3::LastCacheInfo> {
+        debug!("创建最新值缓存: {}.{}", request.database, request.table);
+
+        let body = caches::build_last_cache_body(request)?;
+        let before: Vec<String> = self
+            .get_influxdb3_last_caches(&request.database, Some(&request.table))
+            .await?
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+
+        self.send_v3_request(reqwest::Method::POST, caches::LAST_CACHE_PATH, Some(body)).await?;
+
+        // 未指定名称时由服务器生成，取新出现的那个
+        self.get_influxdb3_last_caches(&request.database, Some(&request.table))
+            .await?
+            .into_iter()
+            .find(|c| match request.name.as_deref().filter(|n| !n.is_empty()) {
+                Some(name) => c.name == name,
+                None => !before.contains(&c.name),
+            })
+            .ok_or_else(|| anyhow::anyhow!("最新值缓存已创建，但未能读取其定义"))
+    }
+
+    /// 删除 InfluxDB 3.x 最新值缓存
+    pub async fn delete_influxdb3_last_cache(&self, database: &str, table: &str, name: &str) -> Result<()> {
+        debug!("删除最新值缓存: {}.{}/{}", database, table, name);
+
+        let path = format!("{}{}", caches::LAST_CACHE_PATH, caches::build_delete_query(database, table, name)?);
+        self.send_v3_request(reqwest::Method::DELETE, &path, None).await?;
+        info!("最新值缓存 {} 删除成功", name);
+        Ok(())
+    }
+
+    /// 通过 last_cache() 读取 InfluxDB 3.x 最新值缓存内容
+    pub async fn query_influxdb3_last_cache(&self, database: &str, table: &str, name: Option<&str>) -> Result<QueryResult> {
+        debug!("读取最新值缓存: {}.{} {:?}", database, table, name);
+
+        let start_time = Instant::now();
+        let dataset = self.query_v3_sql(database, caches::build_last_cache_query(table, name)).await?;
+        Ok(QueryResult::new(
+            dataset.columns,
+            dataset.rows,
+            dataset.execution_time.unwrap_or(start_time.elapsed().as_millis() as u64),
+        ))
+    }
+
+    /// 获取 InfluxDB 3.x 去重值缓存列表
+    pub async fn get_influxdb3_distinct_caches(&self, database: &str, table: Option<&str>) -> Result<Vec<crate::commands::influxdb3::DistinctCacheInfo>> {
+        debug!("获取去重值缓存列表: {} {:?}", database, table);
+
+        let dataset = self.query_v3_sql(database, caches::build_list_query("distinct_caches", table)).await?;
+        Ok(caches::parse_distinct_caches(database, &dataset))
+    }
+
+    /// 创建 InfluxDB 3.x 去重值缓存，返回服务器上的缓存定义
+    pub async fn create_influxdb3_distinct_cache(&self, request: &crate::commands::influxdb3::CreateDistinctCacheRequest) -> Result<crate::commands::influxdb3::DistinctCacheInfo> {
+        debug!("创建去重值缓存: {}.{}", request.database, request.table);
+
+        let body = caches::build_distinct_cache_body(request)?;
+        let before: Vec<String> = self
+            .get_influxdb3_distinct_caches(&request.database, Some(&request.table))
+            .await?
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+
+        self.send_v3_request(reqwest::Method::POST, caches::DISTINCT_CACHE_PATH, Some(body)).await?;
+
+        self.get_influxdb3_distinct_caches(&request.database, Some(&request.table))
+            .await?
+            .into_iter()
+            .find(|c| match request.name.as_deref().filter(|n| !n.is_empty()) {
+                Some(name) => c.name == name,
+                None => !before.contains(&c.name),
+            })
+            .ok_or_else(|| anyhow::anyhow!("去重值缓存已创建，但未能读取其定义"))
+    }
+
+    /// 删除 InfluxDB 3.x 去重值缓存
+    pub async fn delete_influxdb3_distinct_cache(&self, database: &str, table: &str, name: &str) -> Result<()> {
+        debug!("删除去重值缓存: {}.{}/{}", database, table, name);
+
+        let path = format!("{}{}", caches::DISTINCT_CACHE_PATH, caches::build_delete_query(database, table, name)?);
+        self.send_v3_request(reqwest::Method::DELETE, &path, None).await?;
+        info!("去重值缓存 {} 删除成功", name);
+        Ok(())
+    }
+
+    /// 通过 distinct_cache() 读取 InfluxDB 3.x 去重值缓存内容
+    pub async fn query_influxdb3_distinct_cache(&self, database: &str, table: &str, name: Option<&str>) -> Result<QueryResult> {
+        debug!("读取去重值缓存: {}.{} {:?}", database, table, name);
+
+        let start_time = Instant::now();
+        let dataset = self.query_v3_sql(database, caches::build_distinct_cache_query(table, name)).await?;
+        Ok(QueryResult::new(
+            dataset.columns,
+            dataset.rows,
+            dataset.execution_time.unwrap_or(start_time.elapsed().as_millis() as u64),
+        ))
+    }
+
+    /// 获取 InfluxDB 3.x 处理引擎触发器
+    pub async fn get_influxdb3_triggers(&self, database: &str) -> Result<Vec<crate::commands::influxdb3::ProcessingEngineTriggerInfo>> {
+        debug!("获取触发器列表: {}", database);
+
+        let dataset = self
+            .query_v3_sql(database, "SELECT * FROM system.processing_engine_triggers".to_string())
+            .await?;
+        Ok(processing_engine::parse_triggers(database, &dataset))
+    }
+
+    /// 创建 InfluxDB 3.x 处理引擎触发器
+    pub async fn create_influxdb3_trigger(&self, request: &crate::commands::influxdb3::CreateProcessingEngineTriggerRequest) -> Result<crate::commands::influxdb3::ProcessingEngineTriggerInfo> {
+        debug!("创建触发器: {}/{}", request.database, request.trigger_name);
+
+        let body = processing_engine::build_create_trigger_body(request)?;
+        self.send_v3_request(reqwest::Method::POST, processing_engine::TRIGGER_PATH, Some(body)).await?;
+
+        let trigger_name = request.trigger_name.trim();
+        self.get_influxdb3_triggers(&request.database)
+            .await?
+            .into_iter()
+            .find(|t| t.trigger_name == trigger_name)
+            .ok_or_else(|| anyhow::anyhow!("触发器已创建，但未能读取其定义"))
+    }
+
+    /// 启用或禁用 InfluxDB 3.x 处理引擎触发器
+    pub async fn set_influxdb3_trigger_enabled(&self, database: &str, trigger_name: &str, enabled: bool) -> Result<()> {
+        debug!("{}触发器: {}/{}", if enabled { "启用" } else { "禁用" }, database, trigger_name);
+
+        let path = format!(
+            "{}/{}{}",
+            processing_engine::TRIGGER_PATH,
+            if enabled { "enable" } else { "disable" },
+            processing_engine::build_trigger_query(database, trigger_name, false)?
+        );
+        self.send_v3_request(reqwest::Method::POST, &path, None).await?;
+        Ok(())
+    }
+
+    /// 删除 InfluxDB 3.x 处理引擎触发器
+    pub async fn delete_influxdb3_trigger(&self, database: &str, trigger_name: &str, force: bool) -> Result<()> {
+        debug!("删除触发器: {}/{} (force: {})", database, trigger_name, force);
+
+        let path = format!(
+            "{}{}",
+            processing_engine::TRIGGER_PATH,
+            processing_engine::build_trigger_query(database, trigger_name, force)?
+        );
+        self.send_v3_request(reqwest::Method::DELETE, &path, None).await?;
+        info!("触发器 {} 删除成功", trigger_name);
+        Ok(())
+    }
+
+    /// 上传 InfluxDB 3.x 处理引擎插件文件（需要服务器启用插件目录）
+    pub async fn upload_influxdb3_plugin(&self, request: &crate::commands::influxdb3::UploadProcessingEnginePluginRequest) -> Result<()> {
+        debug!("上传插件: {}", request.filename);
+
+        processing_engine::validate_plugin_filename(&request.filename)?;
+        if request.content.trim().is_empty() {
+            return Err(anyhow::anyhow!("插件内容不能为空"));
+        }
+
+        let body = serde_json::json!({
+            "plugin_name": request.filename.trim(),
+            "content": request.content,
+        });
+        self.send_v3_request(reqwest::Method::PUT, processing_engine::PLUGIN_FILES_PATH, Some(body)).await?;
+        Ok(())
+    }
+
+    /// 使用示例输入测试 InfluxDB 3.x 处理引擎插件
+    pub async fn test_influxdb3_plugin(&self, request: &crate::commands::influxdb3::TestProcessingEnginePluginRequest) -> Result<crate::commands::influxdb3::PluginTestResult> {
+        debug!("测试插件: {} ({})", request.filename, request.trigger_type);
+
+        let (path, body) = processing_engine::build_plugin_test(request)?;
+        let response = self.send_v3_request(reqwest::Method::POST, path, Some(body)).await?;
+        Ok(processing_engine::parse_plugin_test_response(&response))
+    }
+
+    /// 获取 InfluxDB 3.x 处理引擎日志
+    pub async fn get_influxdb3_trigger_logs(&self, database: &str, trigger_name: Option<&str>, limit: Option<u32>) -> Result<Vec<crate::commands::influxdb3::ProcessingEngineLogEntry>> {
+        debug!("获取触发器日志: {} {:?}", database, trigger_name);
+
+        let sql = processing_engine::build_logs_query(trigger_name, limit.unwrap_or(processing_engine::DEFAULT_LOG_LIMIT));
+        let dataset = self.query_v3_sql(database, sql).await?;
+        Ok(processing_engine::parse_trigger_logs(&dataset))
+    }
+
+    /// 执行 EXPLAIN / EXPLAIN ANALYZE 并解析执行计划
+    ///
+    /// 3.x 由 DataFusion 输出计划，Flux 查询通过 profiler 获取各算子耗时，其余走 InfluxQL EXPLAIN
+    pub async fn explain_query(&self, query: &str, database: Option<&str>, analyze: bool) -> Result<QueryResult> {
+        use crate::database::explain::{self, PlanFormat};
+        use crate::database::influxdb::{Query, QueryLanguage};
+
+        let language = self.detect_query_language(query);
+        let format = match language {
+            QueryLanguage::Flux => PlanFormat::FluxProfiler,
+            _ if self.driver.capabilities().major == 3 => PlanFormat::DataFusion,
+            _ => PlanFormat::InfluxQL,
+        };
+
+        let text = explain::explain_statement(format, query, analyze);
+        debug!("执行计划查询: {}", text);
+        let mut explain_query = Query::new(language, text).with_timeout(self.config.query_timeout as u64);
+        if let Some(database) = database {
+            explain_query = explain_query.with_database(database.to_string());
+        }
+
+        let start_time = Instant::now();
+        let dataset = self.driver.query(&explain_query).await?;
+        let mut result = QueryResult::new(
+            dataset.columns,
+            dataset.rows,
+            dataset.execution_time.unwrap_or(start_time.elapsed().as_millis() as u64),
+        );
+        match explain::parse_plan(format, &result) {
+            Some(plan) => result.set_execution_plan(plan),
+            None => warn!("未能从返回结果中解析出执行计划，返回原始结果"),
+        }
+        Ok(result)
+    }
+
+    /// 在 InfluxDB 3.x 上执行查询并保留 Arrow 列式结果（需要 FlightSQL）
+    pub async fn query_arrow(&self, query: &str, database: &str, language: crate::database::influxdb::QueryLanguage) -> Result<crate::database::influxdb::ArrowResult> {
+        use crate::database::influxdb::Query;
+
+        if self.driver.capabilities().major != 3 {
+            return Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x"));
+        }
+
+        let query = Query::new(language, query.to_string()).with_database(database.to_string());
+        self.driver.query_arrow(&query).await
+    }
+
+    /// 在 InfluxDB 3.x 上以 CSV / Parquet 等原生格式执行查询（需要 HTTP API）
+    pub async fn query_raw(&self, query: &str, database: &str, language: crate::database::influxdb::QueryLanguage, format: crate::database::influxdb::V3ResponseFormat) -> Result<Vec<u8>> {
+        use crate::database::influxdb::Query;
+
+        if self.driver.capabilities().major != 3 {
+            return Err(anyhow::anyhow!("此操作仅支持 InfluxDB 3.x"));
+        }
+
+        let query = Query::new(language, query.to_string())
+            .with_database(database.to_string())
+            .with_timeout(self.config.query_timeout as u64);
+        self.driver.query_raw(&query, format).await
+    }
+
+    fn parse_task(task: &serde_json::Value) -> crate::commands::influxdb2::TaskInfo {
+        let text = |key: &str| task.get(key).and_then(|v| v.as_str()).map(|s| s.to_string());
+
+        crate::commands::influxdb2::TaskInfo {
+            id: text("id").unwrap_or_default(),
+            name: text("name").unwrap_or_default(),
+            org_id: text("orgID").unwrap_or_default(),
+            org_name: text("org").unwrap_or_default(),
+            status: text("status").unwrap_or_else(|| "active".to_string()),
+            every: text("every"),
+            cron: text("cron"),
+            offset: text("offset"),
+            flux: text("flux").unwrap_or_default(),
+            description: text("description"),
+            latest_completed: text("latestCompleted"),
+            last_run_status: text("lastRunStatus"),
+            last_run_error: text("lastRunError"),
+            created_at: text("createdAt"),
+            updated_at: text("updatedAt"),
+        }
+    }
+
+    fn parse_task_run(run: &serde_json::Value, task_id: &str) -> crate::commands::influxdb2::TaskRunInfo {
+        let text = |key: &str| run.get(key).and_then(|v| v.as_str()).map(|s| s.to_string());
+
+        crate::commands::influxdb2::TaskRunInfo {
+            id: text("id").unwrap_or_default(),
+            task_id: text("taskID").unwrap_or_else(|| task_id.to_string()),
+            status: text("status").unwrap_or_default(),
+            scheduled_for: text("scheduledFor"),
+            started_at: text("startedAt"),
+            finished_at: text("finishedAt"),
+            requested_at: text("requestedAt"),
+        }
+    }
 }
 
 impl std::fmt::Debug for InfluxDBClient {
@@ -905,6 +2708,7 @@ impl Clone for InfluxDBClient {
         Self {
             driver: self.driver.clone(),
             config: self.config.clone(),
+            http_client: self.http_client.clone(),
         }
     }
 }
@@ -0,0 +1,376 @@
+/**
+ * IoTDB 连续查询与 TTL 管理辅助函数
+ *
+ * 负责 SHOW CONTINUOUS QUERIES / SHOW ALL TTL 结果解析、CREATE CONTINUOUS QUERY 语句构建
+ * 以及生效 TTL 的计算，命令层和树节点加载共用同一套解析逻辑
+ */
+
+use crate::commands::iotdb::{validate_identifier, ContinuousQueryInfo, CreateContinuousQueryRequest, TtlInfo};
+use anyhow::{anyhow, Result};
+use log::warn;
+use regex::Regex;
+use serde_json::Value;
+
+/// 在结果列中按名称查找列索引（忽略大小写）
+pub fn find_column_index(columns: &[String], candidates: &[&str]) -> Option<usize> {
+    columns.iter().position(|c| {
+        let lower = c.to_lowercase();
+        candidates.iter().any(|candidate| lower == *candidate)
+    })
+}
+
+/// 单元格转为字符串，空值返回 None
+pub fn cell_string(row: &[Value], index: usize) -> Option<String> {
+    match row.get(index)? {
+        Value::String(s) => Some(s.clone()),
+        Value::Null => None,
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// 从 SHOW CONTINUOUS QUERIES 返回的完整语句中解析重采样参数
+pub fn parse_continuous_query(cq_id: String, query: String, state: String) -> ContinuousQueryInfo {
+    let upper = query.to_ascii_uppercase();
+    let begin_pos = upper.find("BEGIN");
+    let header = begin_pos.map(|pos| &query[..pos]).unwrap_or(&query);
+
+    let capture = |pattern: &str| -> Option<String> {
+        Regex::new(pattern)
+            .ok()
+            .and_then(|re| re.captures(header))
+            .and_then(|caps| caps.get(1))
+            .map(|m| m.as_str().to_string())
+    };
+
+    let body = begin_pos.map(|pos| {
+        let rest = &query[pos + "BEGIN".len()..];
+        let end = rest.to_ascii_uppercase().rfind("END").unwrap_or(rest.len());
+        rest[..end].trim().to_string()
+    });
+
+    ContinuousQueryInfo {
+        every_interval: capture(r"(?i)\bEVERY\s+([^\s,]+)"),
+        boundary_time: capture(r"(?i)\bBOUNDARY\s+([^\s,]+)"),
+        range_start: capture(r"(?i)\bRANGE\s+([^\s,]+)"),
+        range_end: capture(r"(?i)\bRANGE\s+[^\s,]+\s*,\s*([^\s,]+)"),
+        timeout_policy: capture(r"(?i)\bTIMEOUT\s+POLICY\s+(\w+)").map(|p| p.to_uppercase()),
+        body,
+        cq_id,
+        query,
+        state,
+    }
+}
+
+/// 解析 SHOW CONTINUOUS QUERIES 的结果行
+pub fn parse_continuous_queries(columns: &[String], rows: &[Vec<Value>]) -> Vec<ContinuousQueryInfo> {
+    let id_idx = find_column_index(columns, &["cq_id", "cqid"]).unwrap_or(0);
+    let query_idx = find_column_index(columns, &["query"]).unwrap_or(id_idx + 1);
+    let state_idx = find_column_index(columns, &["state"]).unwrap_or(id_idx + 2);
+
+    rows.iter()
+        .filter_map(|row| match cell_string(row, id_idx) {
+            Some(cq_id) => Some(parse_continuous_query(
+                cq_id,
+                cell_string(row, query_idx).unwrap_or_default(),
+                cell_string(row, state_idx).unwrap_or_else(|| "UNKNOWN".to_string()),
+            )),
+            None => {
+                warn!("IoTDB 连续查询数据缺少 cq_id 列: {:?}", row);
+                None
+            }
+        })
+        .collect()
+}
+
+/// 构建 CREATE CONTINUOUS QUERY 语句
+pub fn build_create_statement(request: &CreateContinuousQueryRequest) -> Result<String> {
+    validate_identifier(&request.cq_id, "连续查询 ID").map_err(|e| anyhow!(e))?;
+
+    let body = request.body.trim().trim_end_matches(';').trim();
+    let body_upper = body.to_uppercase();
+    if !body_upper.starts_with("SELECT") || !body_upper.contains(" INTO ") {
+        return Err(anyhow!("连续查询体必须是 SELECT ... INTO ... 语句"));
+    }
+
+    let mut query = format!("CREATE CONTINUOUS QUERY {}", request.cq_id);
+
+    let has_resample = request.every_interval.is_some()
+        || request.boundary_time.is_some()
+        || request.range_start.is_some();
+    if has_resample {
+        query.push_str(" RESAMPLE");
+        if let Some(every) = &request.every_interval {
+            query.push_str(&format!(" EVERY {}", every));
+        }
+        if let Some(boundary) = &request.boundary_time {
+            query.push_str(&format!(" BOUNDARY {}", boundary));
+        }
+        if let Some(start) = &request.range_start {
+            query.push_str(&format!(" RANGE {}", start));
+            if let Some(end) = &request.range_end {
+                query.push_str(&format!(", {}", end));
+            }
+        }
+    } else if request.range_end.is_some() {
+        return Err(anyhow!("设置 RANGE 结束偏移时必须同时设置起始偏移"));
+    }
+
+    if let Some(policy) = &request.timeout_policy {
+        let policy_upper = policy.to_uppercase();
+        if policy_upper != "BLOCKED" && policy_upper != "DISCARD" {
+            return Err(anyhow!("无效的超时策略 '{}'. 有效值: BLOCKED, DISCARD", policy));
+        }
+        query.push_str(&format!(" TIMEOUT POLICY {}", policy_upper));
+    }
+
+    query.push_str(&format!(" BEGIN {} END", body));
+    Ok(query)
+}
+
+/// 解析 TTL 值，INF/空值表示未设置
+fn parse_ttl_value(row: &[Value], index: usize) -> Option<i64> {
+    let ttl = match row.get(index)? {
+        Value::Number(n) => n.as_i64(),
+        Value::String(s) => s.trim().parse::<i64>().ok(),
+        _ => None,
+    };
+    ttl.filter(|ttl| *ttl > 0 && *ttl < i64::MAX)
+}
+
+/// 解析 SHOW ALL TTL 的结果行
+///
+/// 不同版本列名不同：Database/TTL（1.0-1.2）、Device/TTL(ms)（1.3+）
+pub fn parse_ttl_rules(columns: &[String], rows: &[Vec<Value>]) -> Vec<(String, Option<i64>)> {
+    let path_idx = find_column_index(columns, &["database", "storage group", "device", "path"]).unwrap_or(0);
+    let ttl_idx = columns
+        .iter()
+        .position(|c| c.to_lowercase().starts_with("ttl"))
+        .unwrap_or(path_idx + 1);
+
+    rows.iter()
+        .filter_map(|row| cell_string(row, path_idx).map(|path| (path, parse_ttl_value(row, ttl_idx))))
+        .collect()
+}
+
+/// 判断 TTL 规则路径是否覆盖目标路径（支持 * 和 ** 通配）
+pub fn ttl_rule_covers(rule_path: &str, target: &str) -> bool {
+    let rule: Vec<&str> = rule_path.split('.').collect();
+    let path: Vec<&str> = target.split('.').collect();
+
+    fn matches(rule: &[&str], path: &[&str]) -> bool {
+        match (rule.first(), path.first()) {
+            (None, _) => true,
+            (Some(&"**"), _) => true,
+            (Some(_), None) => false,
+            (Some(&"*"), Some(_)) => matches(&rule[1..], &path[1..]),
+            (Some(r), Some(p)) => r == p && matches(&rule[1..], &path[1..]),
+        }
+    }
+
+    // 规则的前缀覆盖其所有子路径
+    matches(&rule, &path)
+}
+
+/// 计算每个数据库及规则路径的生效 TTL
+pub fn resolve_effective_ttl(databases: &[String], rules: &[(String, Option<i64>)]) -> Vec<TtlInfo> {
+    let mut infos = Vec::new();
+
+    for database in databases {
+        let direct = rules.iter().find(|(path, _)| path == database);
+        if let Some((_, ttl)) = direct {
+            infos.push(TtlInfo {
+                path: database.clone(),
+                ttl_ms: *ttl,
+                is_set: ttl.is_some(),
+                inherited_from: None,
+                is_database: true,
+            });
+            continue;
+        }
+
+        // 查找最具体的（路径最长的）覆盖规则
+        let inherited = rules
+            .iter()
+            .filter(|(path, ttl)| ttl.is_some() && ttl_rule_covers(path, database))
+            .max_by_key(|(path, _)| path.len());
+
+        infos.push(TtlInfo {
+            path: database.clone(),
+            ttl_ms: inherited.and_then(|(_, ttl)| *ttl),
+            is_set: false,
+            inherited_from: inherited.map(|(path, _)| path.clone()),
+            is_database: true,
+        });
+    }
+
+    // 非数据库级别的路径规则（IoTDB 1.3+ 支持）
+    for (path, ttl) in rules {
+        if ttl.is_some() && !databases.contains(path) {
+            infos.push(TtlInfo {
+                path: path.clone(),
+                ttl_ms: *ttl,
+                is_set: true,
+                inherited_from: None,
+                is_database: false,
+            });
+        }
+    }
+
+    infos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request(cq_id: &str, body: &str) -> CreateContinuousQueryRequest {
+        CreateContinuousQueryRequest {
+            cq_id: cq_id.to_string(),
+            body: body.to_string(),
+            every_interval: None,
+            boundary_time: None,
+            range_start: None,
+            range_end: None,
+            timeout_policy: None,
+        }
+    }
+
+    const BODY: &str = "SELECT max_value(temperature) INTO root.ln.wf02.wt02(temperature_max) FROM root.ln.*.* GROUP BY(10m)";
+
+    #[test]
+    fn test_parse_continuous_query() {
+        let statement = format!(
+            "CREATE CONTINUOUS QUERY cq1 RESAMPLE EVERY 20s BOUNDARY 1000 RANGE 40s, 20s TIMEOUT POLICY discard BEGIN {} END",
+            BODY
+        );
+        let info = parse_continuous_query("cq1".to_string(), statement, "ACTIVE".to_string());
+        assert_eq!(info.every_interval.as_deref(), Some("20s"));
+        assert_eq!(info.boundary_time.as_deref(), Some("1000"));
+        assert_eq!(info.range_start.as_deref(), Some("40s"));
+        assert_eq!(info.range_end.as_deref(), Some("20s"));
+        assert_eq!(info.timeout_policy.as_deref(), Some("DISCARD"));
+        assert_eq!(info.body.as_deref(), Some(BODY));
+
+        // 查询体中的 EVERY/RANGE 不影响头部解析
+        let info = parse_continuous_query(
+            "cq2".to_string(),
+            "CREATE CONTINUOUS QUERY cq2 BEGIN SELECT count(s1) INTO root.sg.d(c) FROM root.sg.d GROUP BY(1h) END".to_string(),
+            "INACTIVE".to_string(),
+        );
+        assert!(info.every_interval.is_none() && info.range_start.is_none());
+        assert_eq!(info.body.as_deref(), Some("SELECT count(s1) INTO root.sg.d(c) FROM root.sg.d GROUP BY(1h)"));
+    }
+
+    #[test]
+    fn test_parse_continuous_queries_rows() {
+        let columns = vec!["State".to_string(), "CQId".to_string(), "Query".to_string()];
+        let rows = vec![
+            vec![json!("ACTIVE"), json!("cq1"), json!("CREATE CONTINUOUS QUERY cq1 RESAMPLE EVERY 1m BEGIN SELECT 1 END")],
+            vec![json!("ACTIVE"), Value::Null, json!("")],
+            vec![Value::Null, json!("cq3")],
+        ];
+        let queries = parse_continuous_queries(&columns, &rows);
+        assert_eq!(queries.len(), 2);
+        assert_eq!(queries[0].cq_id, "cq1");
+        assert_eq!(queries[0].every_interval.as_deref(), Some("1m"));
+        assert_eq!(queries[1].state, "UNKNOWN");
+        assert_eq!(queries[1].query, "");
+    }
+
+    #[test]
+    fn test_build_create_statement() {
+        let mut req = request("cq_max", &format!("  {};", BODY));
+        assert_eq!(
+            build_create_statement(&req).unwrap(),
+            format!("CREATE CONTINUOUS QUERY cq_max BEGIN {} END", BODY)
+        );
+
+        req.every_interval = Some("20s".to_string());
+        req.range_start = Some("40s".to_string());
+        req.range_end = Some("20s".to_string());
+        req.timeout_policy = Some("blocked".to_string());
+        assert_eq!(
+            build_create_statement(&req).unwrap(),
+            format!("CREATE CONTINUOUS QUERY cq_max RESAMPLE EVERY 20s RANGE 40s, 20s TIMEOUT POLICY BLOCKED BEGIN {} END", BODY)
+        );
+
+        req.timeout_policy = Some("retry".to_string());
+        assert!(build_create_statement(&req).is_err());
+
+        let mut req = request("cq", BODY);
+        req.range_end = Some("20s".to_string());
+        assert!(build_create_statement(&req).is_err());
+
+        assert!(build_create_statement(&request("cq-1", BODY)).is_err());
+        assert!(build_create_statement(&request("", BODY)).is_err());
+        assert!(build_create_statement(&request("cq", "SELECT s1 FROM root.sg.d")).is_err());
+        assert!(build_create_statement(&request("cq", "DELETE FROM root.sg.d INTO x")).is_err());
+    }
+
+    #[test]
+    fn test_parse_ttl_rules() {
+        let columns = vec!["Device".to_string(), "TTL(ms)".to_string()];
+        let rows = vec![
+            vec![json!("root.ln"), json!(3600000)],
+            vec![json!("root.sg.**"), json!("86400000")],
+            vec![json!("root.tmp"), json!("INF")],
+            vec![json!("root.max"), json!(i64::MAX)],
+            vec![Value::Null, json!(1000)],
+        ];
+        assert_eq!(
+            parse_ttl_rules(&columns, &rows),
+            vec![
+                ("root.ln".to_string(), Some(3600000)),
+                ("root.sg.**".to_string(), Some(86400000)),
+                ("root.tmp".to_string(), None),
+                ("root.max".to_string(), None),
+            ]
+        );
+
+        // 1.0-1.2 的列名
+        let columns = vec!["Database".to_string(), "TTL".to_string()];
+        assert_eq!(parse_ttl_rules(&columns, &[vec![json!("root.a"), Value::Null]]), vec![("root.a".to_string(), None)]);
+    }
+
+    #[test]
+    fn test_ttl_rule_covers() {
+        assert!(ttl_rule_covers("root.sg", "root.sg"));
+        assert!(ttl_rule_covers("root.sg", "root.sg.d1"));
+        assert!(ttl_rule_covers("root.**", "root.sg.d1"));
+        assert!(ttl_rule_covers("root.*.d1", "root.sg.d1"));
+        assert!(!ttl_rule_covers("root.*.d1", "root.sg.d2"));
+        assert!(!ttl_rule_covers("root.sg.d1", "root.sg"));
+        assert!(!ttl_rule_covers("root.sg1", "root.sg"));
+    }
+
+    #[test]
+    fn test_resolve_effective_ttl() {
+        let databases = vec!["root.ln".to_string(), "root.sg".to_string(), "root.other".to_string()];
+        let rules = vec![
+            ("root.ln".to_string(), None),
+            ("root.**".to_string(), Some(1000)),
+            ("root.sg.**".to_string(), Some(2000)),
+            ("root.unused".to_string(), None),
+        ];
+        let infos = resolve_effective_ttl(&databases, &rules);
+        assert_eq!(infos.len(), 5);
+
+        // 直接列出但未设置的数据库不继承
+        assert_eq!(infos[0].path, "root.ln");
+        assert!(infos[0].ttl_ms.is_none() && !infos[0].is_set && infos[0].inherited_from.is_none());
+
+        // 取最具体的覆盖规则
+        assert_eq!(infos[1].ttl_ms, Some(2000));
+        assert_eq!(infos[1].inherited_from.as_deref(), Some("root.sg.**"));
+        assert_eq!(infos[2].ttl_ms, Some(1000));
+        assert_eq!(infos[2].inherited_from.as_deref(), Some("root.**"));
+
+        // 有值的路径规则单独列出
+        assert!(infos[3..].iter().all(|info| info.is_set && !info.is_database));
+        assert_eq!(infos[3].path, "root.**");
+        assert_eq!(infos[4].path, "root.sg.**");
+    }
+}
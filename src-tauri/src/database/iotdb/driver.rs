@@ -102,32 +102,45 @@ pub struct DriverFactory;
 impl DriverFactory {
     /// 创建最佳驱动
     pub async fn create_best_driver(
-        _config: DriverConfig,
+        config: DriverConfig,
         capability: &Capability,
     ) -> Result<Box<dyn IoTDBDriver>> {
         // 根据服务器能力选择最佳驱动
-        let _protocols = &capability.server.supported_protocols;
-        
-        // 驱动工厂已简化，直接返回错误，使用简化的客户端架构
-        // 实际的IoTDB连接通过IoTDBOfficialClient处理
-        
-        // REST V2 驱动已移除，现在只使用官方Thrift客户端
-        
+        // Thrift 连接通过 IoTDBOfficialClient 处理，这里只提供 REST 备用驱动
+        let protocols = &capability.server.supported_protocols;
+
+        if protocols.iter().any(|p| p == "rest") || capability.server.rest_v2 {
+            return Self::create_driver("rest_v2", config, capability).await;
+        }
+
         Err(anyhow::anyhow!("没有找到合适的驱动实现"))
     }
     
     /// 创建指定类型的驱动
     pub async fn create_driver(
         driver_type: &str,
-        _config: DriverConfig,
-        _capability: &Capability,
+        config: DriverConfig,
+        capability: &Capability,
     ) -> Result<Box<dyn IoTDBDriver>> {
         match driver_type {
             "thrift" => {
                 Err(anyhow::anyhow!("Thrift 驱动已移除，请使用IoTDBOfficialClient"))
             }
+            #[cfg(feature = "iotdb-rest")]
+            "rest" | "rest_v2" => {
+                let mut config = config;
+                // 优先使用探测到的 REST 端口
+                if let Some(port) = capability.connection_info.available_ports.get("rest") {
+                    config.port = *port;
+                }
+                let mut driver = super::drivers::rest_v2::RestV2Driver::new(config, capability.clone())?;
+                driver.connect().await?;
+                Ok(Box::new(driver))
+            }
+            #[cfg(not(feature = "iotdb-rest"))]
             "rest" | "rest_v2" => {
-                Err(anyhow::anyhow!("REST 驱动已移除，现在只使用官方Thrift客户端"))
+                let _ = (config, capability);
+                Err(anyhow::anyhow!("REST 驱动未启用，请开启 iotdb-rest 特性"))
             }
             _ => Err(anyhow::anyhow!("不支持的驱动类型: {}", driver_type)),
         }
@@ -140,7 +153,8 @@ impl DriverFactory {
         #[cfg(any(feature = "iotdb-v1", feature = "iotdb-v2"))]
         drivers.push("thrift");
 
-        // REST驱动已移除，现在只使用官方Thrift客户端
+        #[cfg(feature = "iotdb-rest")]
+        drivers.push("rest_v2");
 
         drivers
     }
@@ -13,10 +13,11 @@ pub mod official_thrift;
 
 // Thrift驱动已移除，只使用官方客户端
 
-// REST V2 驱动已移除，现在只使用官方Thrift客户端
+// REST V2 驱动（Thrift 不可达时的备用传输）
+#[cfg(feature = "iotdb-rest")]
+pub mod rest_v2;
 
 // 重新导出驱动实现（内部使用，不对外导出以避免未使用警告）
 // #[cfg(any(feature = "iotdb-v1", feature = "iotdb-v2"))]
 // pub use thrift::ThriftDriver;
 
-// REST V2 驱动已移除
@@ -14,9 +14,35 @@ use thrift::transport::{TFramedReadTransport, TFramedWriteTransport, TIoChannel,
 
 // 导入官方生成的Thrift接口
 use super::client::{IClientRPCServiceSyncClient, TIClientRPCServiceSyncClient};
-use super::client::{TSOpenSessionReq, TSOpenSessionResp, TSCloseSessionReq, TSExecuteStatementReq, TSExecuteStatementResp, TSProtocolVersion};
+use super::client::{TSOpenSessionReq, TSOpenSessionResp, TSCloseSessionReq, TSExecuteStatementReq, TSExecuteStatementResp, TSProtocolVersion, TSSetTimeZoneReq};
 use super::common::TSStatus;
 
+/// 执行成功
+pub const SUCCESS_STATUS: i32 = 200;
+/// 重定向建议（请求已执行成功，建议后续请求发往 redirect_node）
+pub const REDIRECTION_RECOMMEND: i32 = 400;
+
+/// 服务端返回的非成功状态
+#[derive(Debug, thiserror::Error)]
+#[error("{context}: {message}")]
+pub struct IoTDBStatusError {
+    pub context: &'static str,
+    pub code: i32,
+    pub message: String,
+    pub need_retry: bool,
+}
+
+impl IoTDBStatusError {
+    fn from_status(context: &'static str, status: &TSStatus) -> Self {
+        Self {
+            context,
+            code: status.code,
+            message: status.message.clone().unwrap_or_else(|| "未知错误".to_string()),
+            need_retry: status.need_retry.unwrap_or(false),
+        }
+    }
+}
+
 /// IoTDB 官方 Thrift 客户端
 pub struct OfficialThriftClient {
     /// Thrift 服务客户端
@@ -32,6 +58,12 @@ pub struct OfficialThriftClient {
     connected: bool,
     /// Statement ID 计数器
     statement_id_counter: AtomicI64,
+    /// 是否请求服务端返回重定向建议
+    enable_redirection: bool,
+    /// 会话时区
+    time_zone: String,
+    /// 打开会话时服务端返回的配置
+    server_configuration: std::collections::BTreeMap<String, String>,
 }
 
 impl OfficialThriftClient {
@@ -46,9 +78,39 @@ impl OfficialThriftClient {
             password,
             connected: false,
             statement_id_counter: AtomicI64::new(1), // 从1开始，避免使用0
+            enable_redirection: false,
+            time_zone: "UTC+08:00".to_string(), // 默认时区
+            server_configuration: std::collections::BTreeMap::new(),
         }
     }
 
+    /// 设置会话时区
+    pub fn with_time_zone(mut self, time_zone: impl Into<String>) -> Self {
+        self.time_zone = time_zone.into();
+        self
+    }
+
+    /// 获取会话时区
+    pub fn time_zone(&self) -> &str {
+        &self.time_zone
+    }
+
+    /// 打开会话时服务端返回的配置项
+    pub fn server_configuration(&self) -> &std::collections::BTreeMap<String, String> {
+        &self.server_configuration
+    }
+
+    /// 设置是否启用重定向
+    pub fn with_redirection(mut self, enable: bool) -> Self {
+        self.enable_redirection = enable;
+        self
+    }
+
+    /// 获取连接地址
+    pub fn endpoint(&self) -> (&str, u16) {
+        (&self.host, self.port)
+    }
+
     /// 连接到IoTDB服务器
     pub async fn connect(&mut self) -> Result<()> {
         let address = format!("{}:{}", self.host, self.port);
@@ -95,7 +157,7 @@ impl OfficialThriftClient {
         // 构建打开会话请求
         let request = TSOpenSessionReq::new(
             TSProtocolVersion::IotdbServiceProtocolV3,
-            "UTC+08:00".to_string(), // 默认时区
+            self.time_zone.clone(),
             self.username.clone(),
             Some(self.password.clone()),
             None, // configuration
@@ -115,8 +177,15 @@ impl OfficialThriftClient {
             .ok_or_else(|| anyhow::anyhow!("服务器未返回会话ID"))?;
 
         self.session_id = Some(session_id);
+        self.server_configuration = response.configuration.unwrap_or_default();
         info!("会话打开成功，会话ID: {}", session_id);
 
+        // 部分版本忽略 open 请求中的时区，显式设置一次
+        let time_zone = self.time_zone.clone();
+        if let Err(e) = self.set_time_zone(&time_zone).await {
+            warn!("设置会话时区失败: {}", e);
+        }
+
         // 验证会话是否真正可用（通过请求一个StatementId）
         match self.verify_session(session_id).await {
             Ok(_) => {
@@ -167,6 +236,25 @@ impl OfficialThriftClient {
         Ok(())
     }
 
+    /// 设置会话时区
+    pub async fn set_time_zone(&mut self, time_zone: &str) -> Result<()> {
+        let session_id = self.session_id
+            .ok_or_else(|| anyhow::anyhow!("未打开会话"))?;
+        let client = self.client.as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Thrift客户端未初始化"))?;
+
+        let status = client.set_time_zone(TSSetTimeZoneReq::new(session_id, time_zone.to_string()))
+            .map_err(|e| anyhow::anyhow!("Thrift RPC调用失败: {}", e))?;
+
+        if status.code != SUCCESS_STATUS {
+            return Err(IoTDBStatusError::from_status("设置时区失败", &status).into());
+        }
+
+        debug!("会话 {} 时区已设置为 {}", session_id, time_zone);
+        self.time_zone = time_zone.to_string();
+        Ok(())
+    }
+
     /// 执行SQL语句
     pub async fn execute_statement(&mut self, sql: &str) -> Result<TSExecuteStatementResp> {
         debug!("执行SQL语句: {}", sql);
@@ -221,7 +309,7 @@ impl OfficialThriftClient {
             statement_id, // 使用请求到的statement_id
             Some(1000), // fetch_size
             Some(60000), // timeout (60秒)
-            Some(false), // enable_redirect_query
+            Some(self.enable_redirection), // enable_redirect_query
             Some(false), // jdbc_query
         );
 
@@ -229,9 +317,8 @@ impl OfficialThriftClient {
         let response = self.send_query_statement_request(request).await?;
 
         // 检查响应状态
-        if response.status.code != 200 {
-            let error_msg = response.status.message.unwrap_or_else(|| "未知错误".to_string());
-            return Err(anyhow::anyhow!("执行查询失败: {}", error_msg));
+        if response.status.code != SUCCESS_STATUS && response.status.code != REDIRECTION_RECOMMEND {
+            return Err(IoTDBStatusError::from_status("执行查询失败", &response.status).into());
         }
 
         debug!("查询执行成功");
@@ -311,7 +398,7 @@ impl OfficialThriftClient {
             statement_id, // 使用请求到的statement_id
             Some(1000), // fetch_size
             Some(60000), // timeout (60秒)
-            Some(false), // enable_redirect_query
+            Some(self.enable_redirection), // enable_redirect_query
             Some(false), // jdbc_query
         );
 
@@ -319,9 +406,8 @@ impl OfficialThriftClient {
         let response = self.send_update_statement_request(request).await?;
 
         // 检查响应状态
-        if response.status.code != 200 {
-            let error_msg = response.status.message.unwrap_or_else(|| "未知错误".to_string());
-            return Err(anyhow::anyhow!("执行更新失败: {}", error_msg));
+        if response.status.code != SUCCESS_STATUS && response.status.code != REDIRECTION_RECOMMEND {
+            return Err(IoTDBStatusError::from_status("执行更新失败", &response.status).into());
         }
 
         debug!("更新执行成功");
@@ -0,0 +1,497 @@
+/**
+ * IoTDB REST API v2 驱动
+ *
+ * 当 Thrift 端口（6667）不可达时，通过 REST 服务（默认 18080）访问 IoTDB
+ * 对应端点：/rest/v2/query、/rest/v2/nonQuery、/rest/v2/insertTablet
+ */
+
+use anyhow::Result;
+use async_trait::async_trait;
+use base64::{Engine as _, engine::general_purpose};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use crate::database::iotdb::capability::Capability;
+use crate::database::iotdb::driver::{
+    ColumnInfo, DriverConfig, IoTDBDriver, QueryRequest, QueryResponse, Tablet,
+};
+use crate::database::iotdb::types::{DataValue, IoTDBDataType};
+
+/// REST 服务默认端口
+pub const DEFAULT_REST_PORT: u16 = 18080;
+
+/// 成功状态码
+const SUCCESS_CODE: i64 = 200;
+
+/// /rest/v2/query 请求体
+#[derive(Debug, Serialize)]
+struct RestV2QueryRequest<'a> {
+    sql: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    row_limit: Option<usize>,
+}
+
+/// /rest/v2/nonQuery 请求体
+#[derive(Debug, Serialize)]
+struct RestV2NonQueryRequest<'a> {
+    sql: &'a str,
+}
+
+/// /rest/v2/insertTablet 请求体（values 按列存储）
+#[derive(Debug, Serialize)]
+struct RestV2InsertTabletRequest<'a> {
+    device: &'a str,
+    is_aligned: bool,
+    timestamps: &'a [i64],
+    measurements: &'a [String],
+    data_types: Vec<&'static str>,
+    values: Vec<Vec<serde_json::Value>>,
+}
+
+/// /rest/v2/query 响应体
+///
+/// values 按列存储：values[列][行]
+#[derive(Debug, Default, Deserialize)]
+struct RestV2QueryResponse {
+    #[serde(default)]
+    expressions: Option<Vec<String>>,
+    #[serde(default)]
+    column_names: Option<Vec<String>>,
+    #[serde(default)]
+    timestamps: Option<Vec<i64>>,
+    #[serde(default)]
+    values: Vec<Vec<serde_json::Value>>,
+}
+
+/// 通用状态响应体
+#[derive(Debug, Deserialize)]
+struct RestV2Status {
+    code: i64,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+/// IoTDB REST v2 驱动
+#[derive(Debug)]
+pub struct RestV2Driver {
+    config: DriverConfig,
+    client: reqwest::Client,
+    base_url: String,
+    auth_header: String,
+    capability: Capability,
+    connected: bool,
+}
+
+impl RestV2Driver {
+    /// 创建 REST v2 驱动
+    pub fn new(config: DriverConfig, capability: Capability) -> Result<Self> {
+        let client = crate::utils::http_client::build_http_client_with_timeout(
+            None,
+            config.timeout,
+            config.ssl,
+        )?;
+
+        let scheme = if config.ssl { "https" } else { "http" };
+        let base_url = format!("{}://{}:{}", scheme, config.host, config.port);
+
+        let credentials = format!(
+            "{}:{}",
+            config.username.as_deref().unwrap_or("root"),
+            config.password.as_deref().unwrap_or("root"),
+        );
+        let auth_header = format!("Basic {}", general_purpose::STANDARD.encode(credentials));
+
+        Ok(Self {
+            config,
+            client,
+            base_url,
+            auth_header,
+            capability,
+            connected: false,
+        })
+    }
+
+    /// 发送 POST 请求并返回响应文本
+    async fn post<T: Serialize>(&self, endpoint: &str, body: &T) -> Result<String> {
+        let url = format!("{}{}", self.base_url, endpoint);
+        debug!("REST v2 请求: {}", url);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", &self.auth_header)
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("REST 请求失败: {}", e))?;
+
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+
+        if !status.is_success() {
+            let message = serde_json::from_str::<RestV2Status>(&text)
+                .ok()
+                .and_then(|s| s.message)
+                .unwrap_or(text);
+            return Err(anyhow::anyhow!("REST 请求失败 ({}): {}", status, message));
+        }
+
+        // 部分错误以 200 + 非成功 code 返回
+        if let Ok(status) = serde_json::from_str::<RestV2Status>(&text) {
+            if status.code != SUCCESS_CODE {
+                return Err(anyhow::anyhow!(
+                    "IoTDB 返回错误 ({}): {}",
+                    status.code,
+                    status.message.unwrap_or_default()
+                ));
+            }
+        }
+
+        Ok(text)
+    }
+
+    /// 将 JSON 值转换为 DataValue
+    fn json_to_data_value(value: &serde_json::Value) -> DataValue {
+        match value {
+            serde_json::Value::Null => DataValue::Null,
+            serde_json::Value::Bool(b) => DataValue::Boolean(*b),
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(i) => DataValue::Int64(i),
+                None => DataValue::Double(n.as_f64().unwrap_or_default()),
+            },
+            serde_json::Value::String(s) => DataValue::Text(s.clone()),
+            other => DataValue::Text(other.to_string()),
+        }
+    }
+
+    /// 将 DataValue 转换为 JSON 值
+    fn data_value_to_json(value: Option<&DataValue>) -> serde_json::Value {
+        match value {
+            None | Some(DataValue::Null) => serde_json::Value::Null,
+            Some(DataValue::Boolean(b)) => serde_json::Value::Bool(*b),
+            Some(DataValue::Int32(i)) => serde_json::Value::from(*i),
+            Some(DataValue::Int64(i)) | Some(DataValue::Timestamp(i)) => serde_json::Value::from(*i),
+            Some(DataValue::Float(f)) => serde_json::Value::from(*f),
+            Some(DataValue::Double(d)) => serde_json::Value::from(*d),
+            Some(DataValue::Text(s)) => serde_json::Value::String(s.clone()),
+            Some(DataValue::Blob(b)) => serde_json::Value::String(hex::encode(b)),
+        }
+    }
+
+    /// 将列式响应转换为行式 QueryResponse
+    fn convert_response(response: RestV2QueryResponse, execution_time: Duration) -> QueryResponse {
+        let mut names = Vec::new();
+        let has_time = response.timestamps.is_some();
+        if has_time {
+            names.push("Time".to_string());
+        }
+        names.extend(
+            response
+                .expressions
+                .or(response.column_names)
+                .unwrap_or_default(),
+        );
+
+        let row_count = response
+            .timestamps
+            .as_ref()
+            .map(|t| t.len())
+            .or_else(|| response.values.first().map(|c| c.len()))
+            .unwrap_or(0);
+
+        let mut rows = Vec::with_capacity(row_count);
+        for row_index in 0..row_count {
+            let mut row = Vec::with_capacity(names.len());
+            if let Some(timestamps) = &response.timestamps {
+                row.push(DataValue::Timestamp(timestamps[row_index]));
+            }
+            for column in &response.values {
+                row.push(
+                    column
+                        .get(row_index)
+                        .map(Self::json_to_data_value)
+                        .unwrap_or(DataValue::Null),
+                );
+            }
+            rows.push(row);
+        }
+
+        // 根据首个非空值推断列类型
+        let columns = names
+            .into_iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let data_type = rows
+                    .iter()
+                    .filter_map(|row| row.get(index))
+                    .find(|v| !matches!(v, DataValue::Null))
+                    .map(|v| v.data_type())
+                    .unwrap_or(IoTDBDataType::Text);
+                ColumnInfo { name, data_type, nullable: true }
+            })
+            .collect();
+
+        QueryResponse {
+            columns,
+            rows,
+            execution_time,
+            affected_rows: None,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl IoTDBDriver for RestV2Driver {
+    async fn connect(&mut self) -> Result<()> {
+        let url = format!("{}/ping", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("无法连接 IoTDB REST 服务 {}: {}", self.base_url, e))?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("IoTDB REST 服务不可用: {}", response.status()));
+        }
+
+        self.connected = true;
+        info!("已连接 IoTDB REST v2 服务: {}", self.base_url);
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        // REST 为无状态协议，无需关闭会话
+        self.connected = false;
+        Ok(())
+    }
+
+    async fn query(&mut self, request: QueryRequest) -> Result<QueryResponse> {
+        let start = Instant::now();
+        let body = RestV2QueryRequest {
+            sql: &request.sql,
+            row_limit: request.fetch_size,
+        };
+
+        let text = self.post("/rest/v2/query", &body).await?;
+        let response: RestV2QueryResponse = serde_json::from_str(&text)
+            .map_err(|e| anyhow::anyhow!("解析 REST 查询响应失败: {}", e))?;
+
+        Ok(Self::convert_response(response, start.elapsed()))
+    }
+
+    async fn execute(&mut self, sql: &str) -> Result<usize> {
+        self.post("/rest/v2/nonQuery", &RestV2NonQueryRequest { sql }).await?;
+        // REST 接口不返回影响行数
+        Ok(0)
+    }
+
+    async fn write_tablet(&mut self, tablet: &Tablet) -> Result<()> {
+        if tablet.measurements.len() != tablet.data_types.len() {
+            return Err(anyhow::anyhow!("测点数量与数据类型数量不一致"));
+        }
+
+        // Tablet.values 按行存储，REST 接口需要按列存储
+        let values = (0..tablet.measurements.len())
+            .map(|column| {
+                tablet
+                    .values
+                    .iter()
+                    .map(|row| Self::data_value_to_json(row.get(column).and_then(|v| v.as_ref())))
+                    .collect()
+            })
+            .collect();
+
+        let body = RestV2InsertTabletRequest {
+            device: &tablet.device_id,
+            is_aligned: tablet.is_aligned,
+            timestamps: &tablet.timestamps,
+            measurements: &tablet.measurements,
+            data_types: tablet.data_types.iter().map(|t| t.as_str()).collect(),
+            values,
+        };
+
+        self.post("/rest/v2/insertTablet", &body).await?;
+        debug!("REST 写入 Tablet 成功: {} ({} 行)", tablet.device_id, tablet.timestamps.len());
+        Ok(())
+    }
+
+    async fn test_connection(&mut self) -> Result<Duration> {
+        let start = Instant::now();
+        self.connect().await?;
+
+        // ping 不需要认证，再执行一次查询以验证凭据
+        if let Err(e) = self.post("/rest/v2/query", &RestV2QueryRequest { sql: "SHOW VERSION", row_limit: Some(1) }).await {
+            warn!("IoTDB REST 认证检查失败: {}", e);
+            self.connected = false;
+            return Err(e);
+        }
+
+        Ok(start.elapsed())
+    }
+
+    fn capabilities(&self) -> &Capability {
+        &self.capability
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    fn driver_type(&self) -> &str {
+        "rest_v2"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::iotdb::capability::{ConnectionInfo, ServerCapability, VersionInfo};
+    use axum::{routing::{get, post}, Json, Router};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    fn test_capability() -> Capability {
+        Capability {
+            server: ServerCapability {
+                version: VersionInfo::parse("1.3.2").unwrap(),
+                tree_model: true,
+                table_model: false,
+                rest_v2: true,
+                new_types: true,
+                ssl: false,
+                supported_protocols: vec!["rest".to_string()],
+                extra_properties: HashMap::new(),
+            },
+            connection_info: ConnectionInfo {
+                host: "127.0.0.1".to_string(),
+                port: DEFAULT_REST_PORT,
+                ssl: false,
+                available_ports: HashMap::new(),
+            },
+        }
+    }
+
+    /// 启动模拟 REST 服务，返回端口和收到的 insertTablet 请求
+    async fn start_mock_server() -> (u16, Arc<Mutex<Vec<serde_json::Value>>>) {
+        let tablets = Arc::new(Mutex::new(Vec::new()));
+        let recorded = tablets.clone();
+
+        let app = Router::new()
+            .route("/ping", get(|| async { Json(serde_json::json!({"code": 200, "message": "SUCCESS_STATUS"})) }))
+            .route("/rest/v2/query", post(|Json(body): Json<serde_json::Value>| async move {
+                if body["sql"].as_str().unwrap_or("").starts_with("SHOW") {
+                    Json(serde_json::json!({
+                        "expressions": null,
+                        "column_names": ["database", "ttl"],
+                        "timestamps": null,
+                        "values": [["root.ln", "root.sg"], [null, 3600000]]
+                    }))
+                } else {
+                    Json(serde_json::json!({
+                        "expressions": ["root.ln.d1.s1", "root.ln.d1.s2"],
+                        "column_names": null,
+                        "timestamps": [1, 2],
+                        "values": [[1.5, 2.5], [true, null]]
+                    }))
+                }
+            }))
+            .route("/rest/v2/nonQuery", post(|Json(body): Json<serde_json::Value>| async move {
+                if body["sql"].as_str().unwrap_or("").contains("bad") {
+                    Json(serde_json::json!({"code": 700, "message": "syntax error"}))
+                } else {
+                    Json(serde_json::json!({"code": 200, "message": "SUCCESS_STATUS"}))
+                }
+            }))
+            .route("/rest/v2/insertTablet", post(move |Json(body): Json<serde_json::Value>| {
+                let recorded = recorded.clone();
+                async move {
+                    recorded.lock().unwrap().push(body);
+                    Json(serde_json::json!({"code": 200, "message": "SUCCESS_STATUS"}))
+                }
+            }));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        (port, tablets)
+    }
+
+    fn test_driver(port: u16) -> RestV2Driver {
+        let config = DriverConfig {
+            host: "127.0.0.1".to_string(),
+            port,
+            username: Some("root".to_string()),
+            password: Some("root".to_string()),
+            ssl: false,
+            timeout: Duration::from_secs(5),
+            extra_params: HashMap::new(),
+        };
+        RestV2Driver::new(config, test_capability()).unwrap()
+    }
+
+    fn query_request(sql: &str) -> QueryRequest {
+        QueryRequest {
+            sql: sql.to_string(),
+            database: None,
+            session_id: None,
+            fetch_size: None,
+            timeout: None,
+            parameters: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rest_v2_query_and_non_query() {
+        let (port, _) = start_mock_server().await;
+        let mut driver = test_driver(port);
+
+        driver.test_connection().await.unwrap();
+        assert!(driver.is_connected());
+
+        let response = driver.query(query_request("SELECT s1, s2 FROM root.ln.d1")).await.unwrap();
+        let names: Vec<_> = response.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Time", "root.ln.d1.s1", "root.ln.d1.s2"]);
+        assert_eq!(response.rows.len(), 2);
+        assert_eq!(response.rows[0][0], DataValue::Timestamp(1));
+        assert_eq!(response.rows[0][1], DataValue::Double(1.5));
+        assert_eq!(response.rows[1][2], DataValue::Null);
+
+        let response = driver.query(query_request("SHOW ALL TTL")).await.unwrap();
+        assert_eq!(response.columns.len(), 2);
+        assert_eq!(response.rows[1][0], DataValue::Text("root.sg".to_string()));
+        assert_eq!(response.rows[1][1], DataValue::Int64(3600000));
+
+        driver.execute("CREATE DATABASE root.ln").await.unwrap();
+        assert!(driver.execute("bad statement").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rest_v2_insert_tablet_is_column_major() {
+        let (port, tablets) = start_mock_server().await;
+        let mut driver = test_driver(port);
+
+        let tablet = Tablet {
+            device_id: "root.ln.d1".to_string(),
+            measurements: vec!["s1".to_string(), "s2".to_string()],
+            data_types: vec![IoTDBDataType::Int32, IoTDBDataType::Boolean],
+            timestamps: vec![1, 2],
+            values: vec![
+                vec![Some(DataValue::Int32(10)), Some(DataValue::Boolean(true))],
+                vec![Some(DataValue::Int32(20)), None],
+            ],
+            is_aligned: false,
+        };
+        driver.write_tablet(&tablet).await.unwrap();
+
+        let recorded = tablets.lock().unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0]["data_types"], serde_json::json!(["INT32", "BOOLEAN"]));
+        assert_eq!(recorded[0]["values"], serde_json::json!([[10, 20], [true, null]]));
+    }
+}
@@ -4,10 +4,13 @@
  * 支持 IoTDB 0.13 → 1.x → 2.x 全谱系
  * 运行时探测 + 编译期特性 + 可插拔驱动
  */
+pub mod admin;
 pub mod capability;
 pub mod dialect;
 pub mod driver;
 pub mod drivers;
+pub mod session_pool;
+pub mod template;
 pub mod types;
 
 // 重新导出核心类型
@@ -22,8 +25,8 @@ pub use driver::{DriverConfig, DriverFactory, IoTDBDriver};
 // #[cfg(any(feature = "iotdb-v1", feature = "iotdb-v2"))]
 // pub use drivers::thrift::ThriftDriver;
 
-// #[cfg(feature = "iotdb-rest")]
-// pub use drivers::rest_v2::RestV2Driver;
+#[cfg(feature = "iotdb-rest")]
+pub use drivers::rest_v2::RestV2Driver;
 
 use anyhow::Result;
 use std::collections::HashMap;
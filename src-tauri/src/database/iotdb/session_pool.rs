@@ -0,0 +1,723 @@
+/**
+ * IoTDB 会话池
+ *
+ * 按 IoTDBConfig 维护多个 Thrift 会话，支持并发查询、
+ * 按设备跟随服务端重定向到对应 DataNode，以及瞬时故障重试
+ */
+
+use anyhow::{Context, Result};
+use log::{debug, info, warn};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, Semaphore};
+
+use super::drivers::client::TSExecuteStatementResp;
+use super::drivers::official_thrift::{IoTDBStatusError, OfficialThriftClient};
+use super::types::TimeConverter;
+use crate::models::{ConnectionConfig, IoTDBConfig};
+use crate::utils::query_parser::lexer::tokenize;
+use crate::utils::query_parser::QueryDialect;
+
+/// 会话空闲超时，超过后丢弃
+const IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// 按设备记录的重定向条目上限，超过后清空重新记录
+const MAX_REDIRECT_ENTRIES: usize = 1024;
+
+/// 可重复执行的语句前缀，这类语句在网络故障后重试不会产生副作用（SELECT ... INTO 除外）
+const IDEMPOTENT_PREFIXES: &[&str] = &["SELECT", "SHOW", "COUNT", "LIST", "DESCRIBE", "DESC ", "EXPLAIN"];
+
+/// 服务端地址
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    pub host: String,
+    pub port: u16,
+}
+
+impl std::fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.host, self.port)
+    }
+}
+
+/// 会话池配置
+#[derive(Debug, Clone)]
+pub struct SessionPoolConfig {
+    pub endpoint: Endpoint,
+    pub username: String,
+    pub password: String,
+    pub pool_size: usize,
+    pub enable_redirection: bool,
+    pub max_retry_count: u32,
+    pub retry_interval: Duration,
+    pub time_zone: String,
+}
+
+impl SessionPoolConfig {
+    /// 从连接配置构建，未配置 IoTDBConfig 时使用默认值
+    pub fn from_connection_config(config: &ConnectionConfig) -> Self {
+        let iotdb = config
+            .driver_config
+            .as_ref()
+            .and_then(|c| c.iotdb.clone())
+            .unwrap_or_default();
+
+        Self::new(config, &iotdb)
+    }
+
+    pub fn new(config: &ConnectionConfig, iotdb: &IoTDBConfig) -> Self {
+        Self {
+            endpoint: Endpoint {
+                host: config.host.clone(),
+                port: config.port,
+            },
+            username: config.username.clone().unwrap_or_else(|| "root".to_string()),
+            password: config.password.clone().unwrap_or_default(),
+            pool_size: iotdb.session_pool_size.max(1) as usize,
+            enable_redirection: iotdb.enable_redirection,
+            max_retry_count: iotdb.max_retry_count,
+            retry_interval: Duration::from_millis(iotdb.retry_interval_ms),
+            time_zone: iotdb.time_zone.clone(),
+        }
+    }
+}
+
+/// 会话池统计信息
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionPoolStats {
+    pub max_sessions: usize,
+    pub total_sessions: usize,
+    pub active_sessions: usize,
+    pub idle_sessions: usize,
+    pub available_permits: usize,
+    pub current_endpoint: String,
+    pub time_zone: String,
+    pub redirection_enabled: bool,
+    pub created_sessions: u64,
+    pub redirect_count: u64,
+    pub retry_count: u64,
+    pub failure_count: u64,
+}
+
+/// 池中的会话
+struct PooledSession {
+    client: OfficialThriftClient,
+    endpoint: Endpoint,
+    last_used: Instant,
+}
+
+/// IoTDB 会话池
+pub struct IoTDBSessionPool {
+    config: SessionPoolConfig,
+    idle: Mutex<Vec<PooledSession>>,
+    semaphore: Arc<Semaphore>,
+    /// 设备路径到重定向地址的映射
+    device_endpoints: Mutex<HashMap<String, Endpoint>>,
+    /// 当前会话时区，可在运行时修改
+    time_zone: RwLock<String>,
+    /// 服务端在打开会话时返回的配置
+    server_configuration: Mutex<BTreeMap<String, String>>,
+    active: AtomicUsize,
+    created: AtomicU64,
+    redirects: AtomicU64,
+    retries: AtomicU64,
+    failures: AtomicU64,
+}
+
+impl std::fmt::Debug for IoTDBSessionPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IoTDBSessionPool")
+            .field("endpoint", &self.config.endpoint)
+            .field("pool_size", &self.config.pool_size)
+            .field("active", &self.active.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+impl IoTDBSessionPool {
+    /// 创建会话池（不会立即建立连接）
+    pub fn new(config: SessionPoolConfig) -> Self {
+        info!(
+            "创建IoTDB会话池: {}，大小: {}，重定向: {}",
+            config.endpoint, config.pool_size, config.enable_redirection
+        );
+
+        Self {
+            semaphore: Arc::new(Semaphore::new(config.pool_size)),
+            time_zone: RwLock::new(config.time_zone.clone()),
+            config,
+            server_configuration: Mutex::new(BTreeMap::new()),
+            idle: Mutex::new(Vec::new()),
+            device_endpoints: Mutex::new(HashMap::new()),
+            active: AtomicUsize::new(0),
+            created: AtomicU64::new(0),
+            redirects: AtomicU64::new(0),
+            retries: AtomicU64::new(0),
+            failures: AtomicU64::new(0),
+        }
+    }
+
+    /// 确保至少有一个可用会话
+    pub async fn ensure_session(&self) -> Result<()> {
+        let _permit = self.semaphore.acquire().await.context("获取会话池许可失败")?;
+        let session = self.checkout(&self.config.endpoint).await?;
+        self.checkin(session).await;
+        Ok(())
+    }
+
+    /// 执行语句，自动处理重定向与重试
+    pub async fn execute(&self, sql: &str) -> Result<TSExecuteStatementResp> {
+        self.with_retry(sql, || self.execute_once(sql)).await
+    }
+
+    /// 按重试策略反复执行一次请求
+    async fn with_retry<T, F, Fut>(&self, sql: &str, mut attempt_once: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 0;
+
+        loop {
+            match attempt_once().await {
+                Ok(response) => return Ok(response),
+                Err(e) if attempt < self.config.max_retry_count && Self::is_retryable(&e, sql) => {
+                    attempt += 1;
+                    self.retries.fetch_add(1, Ordering::Relaxed);
+                    warn!(
+                        "IoTDB请求失败，{}ms 后重试 ({}/{}): {}",
+                        self.config.retry_interval.as_millis(),
+                        attempt,
+                        self.config.max_retry_count,
+                        e
+                    );
+                    tokio::time::sleep(self.config.retry_interval).await;
+                }
+                Err(e) => {
+                    self.failures.fetch_add(1, Ordering::Relaxed);
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    /// 执行一次请求
+    async fn execute_once(&self, sql: &str) -> Result<TSExecuteStatementResp> {
+        let device = statement_device(sql);
+        let target = self.target_endpoint(device.as_deref()).await;
+
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .context("获取会话池许可失败")
+            .map_err(RequestNotSent::wrap)?;
+        let mut session = match self.checkout(&target).await {
+            Ok(session) => session,
+            Err(e) if target != self.config.endpoint => {
+                // 重定向的节点不可达时回到配置的地址
+                warn!("连接重定向节点 {} 失败，回退到 {}: {}", target, self.config.endpoint, e);
+                if let Some(device) = &device {
+                    self.device_endpoints.lock().await.remove(device);
+                }
+                self.checkout(&self.config.endpoint).await.map_err(RequestNotSent::wrap)?
+            }
+            Err(e) => return Err(RequestNotSent::wrap(e)),
+        };
+
+        self.active.fetch_add(1, Ordering::Relaxed);
+        let result = session.client.execute_statement(sql).await;
+        self.active.fetch_sub(1, Ordering::Relaxed);
+
+        match result {
+            Ok(response) => {
+                if self.config.enable_redirection {
+                    if let Some(node) = &response.status.redirect_node {
+                        match &device {
+                            Some(device) => {
+                                self.follow_redirect(device, &session.endpoint, &node.ip, node.port).await
+                            }
+                            None => debug!("语句未指向单个设备，忽略重定向建议: {}:{}", node.ip, node.port),
+                        }
+                    }
+                }
+                self.checkin(session).await;
+                Ok(response)
+            }
+            Err(e) => {
+                // 服务端业务错误时会话仍可用，其余错误丢弃会话
+                if e.downcast_ref::<IoTDBStatusError>().is_some() && session.client.is_connected() {
+                    self.checkin(session).await;
+                } else {
+                    let _ = session.client.disconnect().await;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// 判断错误是否可重试：服务端明确要求重试、请求尚未发出，或语句本身可重复执行
+    fn is_retryable(error: &anyhow::Error, sql: &str) -> bool {
+        if let Some(status) = error.downcast_ref::<IoTDBStatusError>() {
+            return status.need_retry;
+        }
+        error.downcast_ref::<RequestNotSent>().is_some() || is_idempotent(sql)
+    }
+
+    /// 记录设备的重定向目标，只影响之后发往该设备的语句
+    async fn follow_redirect(&self, device: &str, current: &Endpoint, ip: &str, port: i32) {
+        let Ok(port) = u16::try_from(port) else {
+            return;
+        };
+        let target = Endpoint { host: ip.to_string(), port };
+        if &target == current {
+            return;
+        }
+
+        let mut endpoints = self.device_endpoints.lock().await;
+        if endpoints.get(device) != Some(&target) {
+            debug!("IoTDB设备 {} 重定向: {} -> {}", device, current, target);
+            self.redirects.fetch_add(1, Ordering::Relaxed);
+            if endpoints.len() >= MAX_REDIRECT_ENTRIES && !endpoints.contains_key(device) {
+                endpoints.clear();
+            }
+            endpoints.insert(device.to_string(), target);
+        }
+    }
+
+    /// 语句的目标地址，设备没有重定向记录时使用配置的地址
+    async fn target_endpoint(&self, device: Option<&str>) -> Endpoint {
+        let Some(device) = device else {
+            return self.config.endpoint.clone();
+        };
+        self.device_endpoints
+            .lock()
+            .await
+            .get(device)
+            .cloned()
+            .unwrap_or_else(|| self.config.endpoint.clone())
+    }
+
+    /// 取出一个指向 target 的会话，没有可用会话时新建；网络操作均在释放空闲队列锁后进行
+    async fn checkout(&self, target: &Endpoint) -> Result<PooledSession> {
+        loop {
+            let (candidate, discarded) = {
+                let mut idle = self.idle.lock().await;
+                let mut discarded = Vec::new();
+                let mut index = 0;
+                while index < idle.len() {
+                    let session = &idle[index];
+                    if session.last_used.elapsed() > IDLE_TIMEOUT || !session.client.is_connected() {
+                        discarded.push(idle.swap_remove(index));
+                    } else {
+                        index += 1;
+                    }
+                }
+                let position = idle.iter().rposition(|session| &session.endpoint == target);
+                (position.map(|position| idle.swap_remove(position)), discarded)
+            };
+
+            for mut session in discarded {
+                debug!("丢弃过期IoTDB会话: {}", session.endpoint);
+                let _ = session.client.disconnect().await;
+            }
+
+            let Some(mut session) = candidate else {
+                return self.open_session(target).await;
+            };
+
+            let time_zone = self.time_zone();
+            if session.client.time_zone() == time_zone {
+                return Ok(session);
+            }
+            // 时区变更后复用前同步
+            match session.client.set_time_zone(&time_zone).await {
+                Ok(()) => return Ok(session),
+                Err(e) => {
+                    warn!("同步IoTDB会话时区失败: {}", e);
+                    let _ = session.client.disconnect().await;
+                }
+            }
+        }
+    }
+
+    /// 归还会话
+    async fn checkin(&self, mut session: PooledSession) {
+        session.last_used = Instant::now();
+        let mut idle = self.idle.lock().await;
+        if idle.len() < self.config.pool_size {
+            idle.push(session);
+        } else {
+            drop(idle);
+            let _ = session.client.disconnect().await;
+        }
+    }
+
+    /// 建立新会话
+    async fn open_session(&self, endpoint: &Endpoint) -> Result<PooledSession> {
+        let mut client = OfficialThriftClient::new(
+            endpoint.host.clone(),
+            endpoint.port,
+            self.config.username.clone(),
+            self.config.password.clone(),
+        )
+        .with_redirection(self.config.enable_redirection)
+        .with_time_zone(self.time_zone());
+
+        client
+            .connect()
+            .await
+            .map_err(|e| anyhow::anyhow!("连接IoTDB服务器失败: {}", e))?;
+        let session_id = client
+            .open_session()
+            .await
+            .map_err(|e| anyhow::anyhow!("打开IoTDB会话失败: {}", e))?;
+
+        self.created.fetch_add(1, Ordering::Relaxed);
+        {
+            let mut configuration = self.server_configuration.lock().await;
+            if configuration.is_empty() {
+                *configuration = client.server_configuration().clone();
+            }
+        }
+        info!("IoTDB会话建立成功: {}，会话ID: {}", endpoint, session_id);
+
+        Ok(PooledSession {
+            client,
+            endpoint: endpoint.clone(),
+            last_used: Instant::now(),
+        })
+    }
+
+    /// 当前会话时区
+    pub fn time_zone(&self) -> String {
+        self.time_zone
+            .read()
+            .map(|tz| tz.clone())
+            .unwrap_or_else(|_| self.config.time_zone.clone())
+    }
+
+    /// 修改会话时区，空闲会话立即同步，使用中的会话在下次取出时同步
+    pub async fn set_time_zone(&self, time_zone: &str) -> Result<()> {
+        // 没有空闲会话时无法由服务端验证，先在本地拒绝无法识别的时区
+        if TimeConverter::parse_time_zone(time_zone).is_none() {
+            return Err(anyhow::anyhow!("无效的时区: {}", time_zone));
+        }
+
+        // 取出空闲会话后再进行网络操作，避免阻塞其他请求取会话
+        let mut sessions: Vec<PooledSession> = self.idle.lock().await.drain(..).collect();
+
+        // 先在一个会话上验证时区是否被服务端接受
+        if let Some(session) = sessions.first_mut() {
+            if let Err(e) = session.client.set_time_zone(time_zone).await {
+                for session in sessions {
+                    self.checkin(session).await;
+                }
+                return Err(e);
+            }
+        }
+
+        if let Ok(mut current) = self.time_zone.write() {
+            *current = time_zone.to_string();
+        }
+
+        for mut session in sessions {
+            if session.client.time_zone() != time_zone {
+                if let Err(e) = session.client.set_time_zone(time_zone).await {
+                    warn!("同步IoTDB会话时区失败: {}", e);
+                }
+            }
+            self.checkin(session).await;
+        }
+
+        info!("IoTDB会话时区已设置为 {}", time_zone);
+        Ok(())
+    }
+
+    /// 服务端在打开会话时返回的配置
+    pub async fn server_configuration(&self) -> BTreeMap<String, String> {
+        self.server_configuration.lock().await.clone()
+    }
+
+    /// 获取统计信息
+    pub async fn stats(&self) -> SessionPoolStats {
+        let idle_sessions = self.idle.lock().await.len();
+        let active_sessions = self.active.load(Ordering::Relaxed);
+
+        SessionPoolStats {
+            max_sessions: self.config.pool_size,
+            total_sessions: idle_sessions + active_sessions,
+            active_sessions,
+            idle_sessions,
+            available_permits: self.semaphore.available_permits(),
+            current_endpoint: self.config.endpoint.to_string(),
+            time_zone: self.time_zone(),
+            redirection_enabled: self.config.enable_redirection,
+            created_sessions: self.created.load(Ordering::Relaxed),
+            redirect_count: self.redirects.load(Ordering::Relaxed),
+            retry_count: self.retries.load(Ordering::Relaxed),
+            failure_count: self.failures.load(Ordering::Relaxed),
+        }
+    }
+
+    /// 是否持有会话
+    pub async fn has_sessions(&self) -> bool {
+        !self.idle.lock().await.is_empty() || self.active.load(Ordering::Relaxed) > 0
+    }
+
+    /// 关闭所有空闲会话
+    pub async fn close(&self) {
+        let sessions: Vec<PooledSession> = self.idle.lock().await.drain(..).collect();
+        let count = sessions.len();
+        for mut session in sessions {
+            if let Err(e) = session.client.disconnect().await {
+                warn!("关闭IoTDB会话时出错: {}", e);
+            }
+        }
+        self.device_endpoints.lock().await.clear();
+        info!("IoTDB会话池已关闭，释放 {} 个会话", count);
+    }
+}
+
+/// 请求发出前的失败（取许可、建立会话），重试不会导致语句被重复执行
+#[derive(Debug)]
+struct RequestNotSent(anyhow::Error);
+
+impl RequestNotSent {
+    fn wrap(error: anyhow::Error) -> anyhow::Error {
+        anyhow::Error::new(Self(error))
+    }
+}
+
+impl std::fmt::Display for RequestNotSent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for RequestNotSent {}
+
+/// 语句是否可重复执行
+///
+/// SELECT ... INTO 会写入数据，不能重放；按词法单元查找 INTO 关键字，
+/// 字符串和引号标识符中的 into 不受影响，`::` 这类占位符也无需完整解析
+fn is_idempotent(sql: &str) -> bool {
+    let upper = sql.trim_start().to_uppercase();
+    IDEMPOTENT_PREFIXES.iter().any(|prefix| upper.starts_with(prefix))
+        && !tokenize(sql, QueryDialect::IoTDB).0.iter().any(|token| token.is_keyword("INTO"))
+}
+
+/// 语句指向的单个设备路径（FROM / INTO 之后），多个设备或通配路径时返回 None
+fn statement_device(sql: &str) -> Option<String> {
+    let tokens: Vec<&str> = sql.split_whitespace().collect();
+    let position = tokens
+        .iter()
+        .position(|token| token.eq_ignore_ascii_case("FROM") || token.eq_ignore_ascii_case("INTO"))?;
+    let target = tokens.get(position + 1)?;
+    let device = target.split('(').next()?.trim_end_matches(';');
+
+    let followed_by_list = !target.contains('(')
+        && tokens.get(position + 2).is_some_and(|next| next.starts_with(','));
+    if followed_by_list
+        || device.contains([',', '*'])
+        || !device.get(..5).is_some_and(|head| head.eq_ignore_ascii_case("root."))
+    {
+        return None;
+    }
+    Some(device.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_config_uses_iotdb_config() {
+        let config = ConnectionConfig::new_iotdb("test".to_string(), "10.0.0.1".to_string(), 6667, None);
+        let iotdb = IoTDBConfig {
+            session_pool_size: 8,
+            enable_redirection: true,
+            max_retry_count: 2,
+            retry_interval_ms: 250,
+            ..IoTDBConfig::default()
+        };
+
+        let pool_config = SessionPoolConfig::new(&config, &iotdb);
+        assert_eq!(pool_config.endpoint.to_string(), "10.0.0.1:6667");
+        assert_eq!(pool_config.pool_size, 8);
+        assert!(pool_config.enable_redirection);
+        assert_eq!(pool_config.max_retry_count, 2);
+        assert_eq!(pool_config.retry_interval, Duration::from_millis(250));
+    }
+
+    fn test_pool(max_retry_count: u32) -> IoTDBSessionPool {
+        let config = ConnectionConfig::new_iotdb("test".to_string(), "10.0.0.1".to_string(), 6667, None);
+        let mut pool_config = SessionPoolConfig::from_connection_config(&config);
+        pool_config.max_retry_count = max_retry_count;
+        pool_config.retry_interval = Duration::ZERO;
+        IoTDBSessionPool::new(pool_config)
+    }
+
+    fn status_error(need_retry: bool) -> anyhow::Error {
+        IoTDBStatusError {
+            context: "执行更新失败",
+            code: if need_retry { 606 } else { 700 },
+            message: "status error".to_string(),
+            need_retry,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_retryable_errors() {
+        let transport_error = || anyhow::anyhow!("Thrift查询RPC调用失败: broken pipe");
+        assert!(IoTDBSessionPool::is_retryable(&transport_error(), "SELECT * FROM root.sg.d1"));
+        assert!(IoTDBSessionPool::is_retryable(&transport_error(), "  show timeseries"));
+        // 写入可能已经执行，传输错误后不重试
+        assert!(!IoTDBSessionPool::is_retryable(&transport_error(), "INSERT INTO root.sg.d1(time, s1) VALUES (1, 1)"));
+        assert!(!IoTDBSessionPool::is_retryable(&transport_error(), "DELETE FROM root.sg.d1.s1"));
+        assert!(!IoTDBSessionPool::is_retryable(
+            &transport_error(),
+            "SELECT s1 INTO root.sg.d2(t1) FROM root.sg.d1"
+        ));
+        assert!(!IoTDBSessionPool::is_retryable(
+            &transport_error(),
+            "select s1, s2 into root.backup.::(::) from root.sg.d1"
+        ));
+        assert!(IoTDBSessionPool::is_retryable(&transport_error(), "SELECT s1 FROM root.sg.d1 WHERE s2 = 'into'"));
+
+        // 请求尚未发出时任何语句都可以重试
+        let not_sent = RequestNotSent::wrap(anyhow::anyhow!("连接IoTDB服务器失败: refused"));
+        assert!(IoTDBSessionPool::is_retryable(&not_sent, "DELETE FROM root.sg.d1.s1"));
+        assert_eq!(not_sent.to_string(), "连接IoTDB服务器失败: refused");
+
+        assert!(!IoTDBSessionPool::is_retryable(&status_error(false), "SELECT * FROM root.sg.d1"));
+        assert!(IoTDBSessionPool::is_retryable(&status_error(true), "INSERT INTO root.sg.d1(time, s1) VALUES (1, 1)"));
+    }
+
+    #[test]
+    fn test_statement_device() {
+        assert_eq!(statement_device("SELECT s1 FROM root.sg.d1 WHERE time > 0").as_deref(), Some("root.sg.d1"));
+        assert_eq!(
+            statement_device("insert into root.sg.d1(timestamp, s1) values (1, 2)").as_deref(),
+            Some("root.sg.d1")
+        );
+        assert_eq!(statement_device("SELECT * FROM root.sg.d1;").as_deref(), Some("root.sg.d1"));
+        assert_eq!(statement_device("SELECT * FROM root.sg.*"), None);
+        assert_eq!(statement_device("SELECT * FROM root.sg.d1, root.sg.d2"), None);
+        assert_eq!(statement_device("SELECT * FROM root.sg.d1 , root.sg.d2"), None);
+        assert_eq!(statement_device("SHOW TIMESERIES root.sg.**"), None);
+        assert_eq!(statement_device("SELECT * FROM cpu"), None);
+    }
+
+    #[tokio::test]
+    async fn test_redirect_is_scoped_to_device() {
+        let pool = test_pool(0);
+        let current = pool.target_endpoint(None).await;
+
+        pool.follow_redirect("root.sg.d1", &current, "10.0.0.2", 6668).await;
+        assert_eq!(pool.target_endpoint(Some("root.sg.d1")).await.to_string(), "10.0.0.2:6668");
+        // 其他设备和不指向单个设备的语句仍使用配置的地址
+        assert_eq!(pool.target_endpoint(Some("root.sg.d2")).await.to_string(), "10.0.0.1:6667");
+        assert_eq!(pool.target_endpoint(None).await.to_string(), "10.0.0.1:6667");
+        assert_eq!(pool.stats().await.current_endpoint, "10.0.0.1:6667");
+
+        // 重复的重定向只计数一次，指向当前地址的建议被忽略
+        pool.follow_redirect("root.sg.d1", &current, "10.0.0.2", 6668).await;
+        pool.follow_redirect("root.sg.d2", &current, "10.0.0.1", 6667).await;
+        pool.follow_redirect("root.sg.d2", &current, "10.0.0.3", -1).await;
+        assert_eq!(pool.stats().await.redirect_count, 1);
+
+        pool.close().await;
+        assert_eq!(pool.target_endpoint(Some("root.sg.d1")).await.to_string(), "10.0.0.1:6667");
+    }
+
+    #[tokio::test]
+    async fn test_retry_loop_retries_idempotent_statements() {
+        let pool = test_pool(2);
+        let mut calls = 0;
+        let result = pool
+            .with_retry("SELECT * FROM root.sg.d1", || {
+                calls += 1;
+                let attempt = calls;
+                async move {
+                    if attempt < 3 {
+                        Err(anyhow::anyhow!("broken pipe"))
+                    } else {
+                        Ok(attempt)
+                    }
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 3);
+        let stats = pool.stats().await;
+        assert_eq!(stats.retry_count, 2);
+        assert_eq!(stats.failure_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_retry_loop_does_not_replay_writes() {
+        let pool = test_pool(3);
+        let mut calls = 0;
+        let result: Result<()> = pool
+            .with_retry("INSERT INTO root.sg.d1(time, s1) VALUES (1, 1)", || {
+                calls += 1;
+                async { Err(anyhow::anyhow!("timed out")) }
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+        let stats = pool.stats().await;
+        assert_eq!(stats.retry_count, 0);
+        assert_eq!(stats.failure_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_loop_limits_and_status_errors() {
+        let pool = test_pool(2);
+
+        // 请求未发出的错误会重试，直到达到次数上限
+        let mut calls = 0;
+        let result: Result<()> = pool
+            .with_retry("DELETE FROM root.sg.d1.s1", || {
+                calls += 1;
+                async { Err(RequestNotSent::wrap(anyhow::anyhow!("connection refused"))) }
+            })
+            .await;
+        assert_eq!(result.unwrap_err().to_string(), "connection refused");
+        assert_eq!(calls, 3);
+
+        // 服务端不要求重试的业务错误立即返回
+        let mut calls = 0;
+        let result: Result<()> = pool
+            .with_retry("SELECT * FROM root.sg.d1", || {
+                calls += 1;
+                async { Err(status_error(false)) }
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+
+        let stats = pool.stats().await;
+        assert_eq!(stats.retry_count, 2);
+        assert_eq!(stats.failure_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_set_time_zone_validates_without_sessions() {
+        let pool = test_pool(0);
+        let original = pool.time_zone();
+
+        assert!(pool.set_time_zone("Mars/Olympus").await.is_err());
+        assert_eq!(pool.time_zone(), original);
+
+        pool.set_time_zone("+08:00").await.unwrap();
+        assert_eq!(pool.time_zone(), "+08:00");
+    }
+}
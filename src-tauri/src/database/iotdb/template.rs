@@ -0,0 +1,438 @@
+/**
+ * IoTDB 设备模板设计器
+ *
+ * 对比提议模板与现有模板，生成迁移语句以及模板激活/反激活语句。
+ * 与 SHOW SCHEMA TEMPLATES 等查询保持一致，统一使用 SCHEMA TEMPLATE 语法
+ */
+
+use serde::{Deserialize, Serialize};
+
+use super::types::IoTDBDataType;
+use crate::models::{TreeNode, TreeNodeFactory, TreeNodeType};
+use crate::utils::query_params::validate_iotdb_path;
+
+/// 默认编码
+const DEFAULT_ENCODING: &str = "PLAIN";
+/// 默认压缩
+const DEFAULT_COMPRESSION: &str = "SNAPPY";
+
+/// 模板中的测点定义
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateMeasurement {
+    pub name: String,
+    pub data_type: String,
+    pub encoding: Option<String>,
+    pub compression: Option<String>,
+}
+
+impl TemplateMeasurement {
+    /// 规范化：类型/编码/压缩统一为大写并补齐默认值
+    pub fn normalized(&self) -> Result<Self, String> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Err("测点名称不能为空".to_string());
+        }
+
+        let data_type = IoTDBDataType::from_str(self.data_type.trim())
+            .filter(|t| *t != IoTDBDataType::Null)
+            .ok_or_else(|| format!("测点 '{}' 的数据类型 '{}' 无效", name, self.data_type))?;
+
+        Ok(Self {
+            name: name.to_string(),
+            data_type: data_type.as_str().to_string(),
+            encoding: Some(
+                self.encoding
+                    .as_deref()
+                    .map(|e| e.trim().to_uppercase())
+                    .filter(|e| !e.is_empty())
+                    .unwrap_or_else(|| DEFAULT_ENCODING.to_string()),
+            ),
+            compression: Some(
+                self.compression
+                    .as_deref()
+                    .map(|c| c.trim().to_uppercase())
+                    .filter(|c| !c.is_empty())
+                    .unwrap_or_else(|| DEFAULT_COMPRESSION.to_string()),
+            ),
+        })
+    }
+
+    /// 生成模板定义中的测点片段
+    pub fn to_definition(&self) -> String {
+        format!(
+            "{} {} encoding={} compression={}",
+            self.name,
+            self.data_type,
+            self.encoding.as_deref().unwrap_or(DEFAULT_ENCODING),
+            self.compression.as_deref().unwrap_or(DEFAULT_COMPRESSION)
+        )
+    }
+}
+
+/// 提议的模板设计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateDesign {
+    pub name: String,
+    pub measurements: Vec<TemplateMeasurement>,
+    #[serde(default)]
+    pub aligned: bool,
+}
+
+/// 测点变更类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateChangeKind {
+    Added,
+    Removed,
+    DataTypeChanged,
+    EncodingChanged,
+    CompressionChanged,
+    Unchanged,
+}
+
+/// 单个测点的变更
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateMeasurementChange {
+    pub measurement: String,
+    pub kinds: Vec<TemplateChangeKind>,
+    pub current: Option<TemplateMeasurement>,
+    pub proposed: Option<TemplateMeasurement>,
+}
+
+/// 模板差异
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateDiff {
+    pub template_name: String,
+    pub exists: bool,
+    pub changes: Vec<TemplateMeasurementChange>,
+    /// 可自动执行的迁移语句
+    pub statements: Vec<String>,
+    /// 需要人工处理的变更（IoTDB 不支持修改/删除模板测点）
+    pub warnings: Vec<String>,
+    /// 提议模板的树形预览
+    pub preview: TreeNode,
+}
+
+impl TemplateDiff {
+    pub fn has_changes(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|c| c.kinds != [TemplateChangeKind::Unchanged])
+    }
+}
+
+/// 规范化模板设计，校验重名测点
+pub fn normalize_design(design: &TemplateDesign) -> Result<TemplateDesign, String> {
+    let name = design.name.trim();
+    if name.is_empty() {
+        return Err("模板名称不能为空".to_string());
+    }
+    if design.measurements.is_empty() {
+        return Err("模板必须至少包含一个测量点".to_string());
+    }
+
+    let mut measurements: Vec<TemplateMeasurement> = Vec::with_capacity(design.measurements.len());
+    for measurement in &design.measurements {
+        let normalized = measurement.normalized()?;
+        if measurements.iter().any(|m| m.name == normalized.name) {
+            return Err(format!("测点 '{}' 重复定义", normalized.name));
+        }
+        measurements.push(normalized);
+    }
+
+    Ok(TemplateDesign {
+        name: name.to_string(),
+        measurements,
+        aligned: design.aligned,
+    })
+}
+
+/// 对比现有模板与提议模板
+///
+/// `current` 为 None 表示模板尚不存在，将生成创建语句
+pub fn diff_template(current: Option<&[TemplateMeasurement]>, proposed: &TemplateDesign) -> TemplateDiff {
+    let mut changes = Vec::new();
+    let mut warnings = Vec::new();
+    let mut added = Vec::new();
+
+    let current_list = current.unwrap_or(&[]);
+
+    for measurement in &proposed.measurements {
+        match current_list.iter().find(|m| m.name == measurement.name) {
+            None => {
+                added.push(measurement.clone());
+                changes.push(TemplateMeasurementChange {
+                    measurement: measurement.name.clone(),
+                    kinds: vec![TemplateChangeKind::Added],
+                    current: None,
+                    proposed: Some(measurement.clone()),
+                });
+            }
+            Some(existing) => {
+                let mut kinds = Vec::new();
+                if existing.data_type != measurement.data_type {
+                    kinds.push(TemplateChangeKind::DataTypeChanged);
+                }
+                if existing.encoding != measurement.encoding {
+                    kinds.push(TemplateChangeKind::EncodingChanged);
+                }
+                if existing.compression != measurement.compression {
+                    kinds.push(TemplateChangeKind::CompressionChanged);
+                }
+                if kinds.is_empty() {
+                    kinds.push(TemplateChangeKind::Unchanged);
+                } else {
+                    warnings.push(format!(
+                        "测点 '{}' 的定义已变更（{} -> {}），IoTDB 不支持修改模板测点，需要重建模板",
+                        measurement.name,
+                        existing.to_definition(),
+                        measurement.to_definition()
+                    ));
+                }
+
+                changes.push(TemplateMeasurementChange {
+                    measurement: measurement.name.clone(),
+                    kinds,
+                    current: Some(existing.clone()),
+                    proposed: Some(measurement.clone()),
+                });
+            }
+        }
+    }
+
+    for existing in current_list {
+        if !proposed.measurements.iter().any(|m| m.name == existing.name) {
+            warnings.push(format!(
+                "测点 '{}' 在提议模板中被移除，IoTDB 不支持删除模板测点，需要重建模板",
+                existing.name
+            ));
+            changes.push(TemplateMeasurementChange {
+                measurement: existing.name.clone(),
+                kinds: vec![TemplateChangeKind::Removed],
+                current: Some(existing.clone()),
+                proposed: None,
+            });
+        }
+    }
+
+    let statements = if current.is_none() {
+        vec![build_create_statement(proposed)]
+    } else if added.is_empty() {
+        Vec::new()
+    } else {
+        vec![build_alter_add_statement(&proposed.name, &added)]
+    };
+
+    TemplateDiff {
+        template_name: proposed.name.clone(),
+        exists: current.is_some(),
+        changes,
+        statements,
+        warnings,
+        preview: build_preview(proposed),
+    }
+}
+
+/// 生成创建模板语句
+pub fn build_create_statement(design: &TemplateDesign) -> String {
+    let definitions: Vec<String> = design.measurements.iter().map(|m| m.to_definition()).collect();
+    let aligned = if design.aligned { " ALIGNED" } else { "" };
+    format!(
+        "CREATE SCHEMA TEMPLATE {}{} ({})",
+        design.name,
+        aligned,
+        definitions.join(", ")
+    )
+}
+
+/// 生成追加测点语句
+pub fn build_alter_add_statement(template_name: &str, measurements: &[TemplateMeasurement]) -> String {
+    let definitions: Vec<String> = measurements.iter().map(|m| m.to_definition()).collect();
+    format!("ALTER SCHEMA TEMPLATE {} ADD ({})", template_name, definitions.join(", "))
+}
+
+/// 模板激活操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateActivationAction {
+    Activate,
+    Deactivate,
+}
+
+/// 生成激活/反激活语句，路径必须是不含通配符的合法设备路径
+pub fn build_activation_statement(
+    action: TemplateActivationAction,
+    template_name: &str,
+    path: &str,
+) -> Result<String, String> {
+    validate_iotdb_path(path, false).map_err(|e| e.to_string())?;
+
+    Ok(match action {
+        TemplateActivationAction::Activate => format!("CREATE TIMESERIES USING SCHEMA TEMPLATE ON {}", path),
+        TemplateActivationAction::Deactivate => {
+            format!("DEACTIVATE SCHEMA TEMPLATE {} FROM {}", template_name, path)
+        }
+    })
+}
+
+/// 判断路径是否位于某个挂载路径之下（含自身）
+pub fn is_covered_by(path: &str, set_paths: &[String]) -> bool {
+    set_paths.iter().any(|set_path| {
+        path == set_path
+            || path
+                .strip_prefix(set_path.as_str())
+                .is_some_and(|rest| rest.starts_with('.'))
+    })
+}
+
+/// 构建模板树形预览，复用数据类型/编码/压缩节点
+fn build_preview(design: &TemplateDesign) -> TreeNode {
+    let mut root = TreeNodeFactory::create_schema_template(design.name.clone())
+        .with_metadata("aligned".to_string(), serde_json::Value::Bool(design.aligned));
+    let root_id = root.id.clone();
+
+    for measurement in &design.measurements {
+        let node_id = format!("{}/{}", root_id, measurement.name);
+        let mut node = TreeNode::new(node_id.clone(), measurement.name.clone(), TreeNodeType::Timeseries)
+            .with_parent(root_id.clone());
+
+        node.add_child(TreeNodeFactory::create_data_type_info(measurement.data_type.clone(), node_id.clone()));
+        if let Some(encoding) = &measurement.encoding {
+            node.add_child(TreeNodeFactory::create_encoding_info(encoding.clone(), node_id.clone()));
+        }
+        if let Some(compression) = &measurement.compression {
+            node.add_child(TreeNodeFactory::create_compression_info(compression.clone(), node_id.clone()));
+        }
+        root.add_child(node);
+    }
+
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(name: &str, data_type: &str, encoding: &str, compression: &str) -> TemplateMeasurement {
+        TemplateMeasurement {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            encoding: Some(encoding.to_string()),
+            compression: Some(compression.to_string()),
+        }
+    }
+
+    fn design(measurements: Vec<TemplateMeasurement>) -> TemplateDesign {
+        normalize_design(&TemplateDesign {
+            name: "t1".to_string(),
+            measurements,
+            aligned: false,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_normalize_design() {
+        let normalized = design(vec![TemplateMeasurement {
+            name: " temperature ".to_string(),
+            data_type: "float".to_string(),
+            encoding: None,
+            compression: Some("lz4".to_string()),
+        }]);
+        assert_eq!(
+            normalized.measurements[0],
+            measurement("temperature", "FLOAT", "PLAIN", "LZ4")
+        );
+
+        let invalid = TemplateDesign {
+            name: "t1".to_string(),
+            measurements: vec![measurement("s1", "DECIMAL", "PLAIN", "SNAPPY")],
+            aligned: false,
+        };
+        assert!(normalize_design(&invalid).is_err());
+
+        let duplicated = TemplateDesign {
+            name: "t1".to_string(),
+            measurements: vec![
+                measurement("s1", "INT32", "RLE", "SNAPPY"),
+                measurement("s1", "INT64", "RLE", "SNAPPY"),
+            ],
+            aligned: false,
+        };
+        assert!(normalize_design(&duplicated).is_err());
+    }
+
+    #[test]
+    fn test_diff_new_template_generates_create() {
+        let proposed = design(vec![measurement("s1", "INT32", "RLE", "SNAPPY")]);
+        let diff = diff_template(None, &proposed);
+
+        assert!(!diff.exists);
+        assert_eq!(
+            diff.statements,
+            vec!["CREATE SCHEMA TEMPLATE t1 (s1 INT32 encoding=RLE compression=SNAPPY)"]
+        );
+        assert_eq!(diff.preview.children.len(), 1);
+        assert_eq!(diff.preview.children[0].children.len(), 3);
+    }
+
+    #[test]
+    fn test_diff_existing_template() {
+        let current = vec![
+            measurement("s1", "INT32", "RLE", "SNAPPY"),
+            measurement("s2", "FLOAT", "GORILLA", "SNAPPY"),
+            measurement("s3", "TEXT", "PLAIN", "SNAPPY"),
+        ];
+        let proposed = design(vec![
+            measurement("s1", "INT32", "RLE", "SNAPPY"),
+            measurement("s2", "FLOAT", "GORILLA", "LZ4"),
+            measurement("s4", "DOUBLE", "GORILLA", "SNAPPY"),
+            measurement("s5", "BOOLEAN", "RLE", "SNAPPY"),
+        ]);
+
+        let diff = diff_template(Some(&current), &proposed);
+        assert!(diff.exists);
+        assert!(diff.has_changes());
+
+        let kinds = |name: &str| {
+            diff.changes
+                .iter()
+                .find(|c| c.measurement == name)
+                .map(|c| c.kinds.clone())
+                .unwrap()
+        };
+        assert_eq!(kinds("s1"), vec![TemplateChangeKind::Unchanged]);
+        assert_eq!(kinds("s2"), vec![TemplateChangeKind::CompressionChanged]);
+        assert_eq!(kinds("s3"), vec![TemplateChangeKind::Removed]);
+        assert_eq!(kinds("s4"), vec![TemplateChangeKind::Added]);
+
+        assert_eq!(
+            diff.statements,
+            vec![
+                "ALTER SCHEMA TEMPLATE t1 ADD (s4 DOUBLE encoding=GORILLA compression=SNAPPY, \
+                 s5 BOOLEAN encoding=RLE compression=SNAPPY)"
+            ]
+        );
+        assert_eq!(diff.warnings.len(), 2);
+    }
+
+    #[test]
+    fn test_activation_helpers() {
+        assert_eq!(
+            build_activation_statement(TemplateActivationAction::Activate, "t1", "root.sg.d1").unwrap(),
+            "CREATE TIMESERIES USING SCHEMA TEMPLATE ON root.sg.d1"
+        );
+        assert_eq!(
+            build_activation_statement(TemplateActivationAction::Deactivate, "t1", "root.sg.`d-1`").unwrap(),
+            "DEACTIVATE SCHEMA TEMPLATE t1 FROM root.sg.`d-1`"
+        );
+        for path in ["root.sg.d1; DROP DATABASE root.sg", "root.sg.*", "sg.d1", "root.sg..d1", "root.`d1"] {
+            assert!(build_activation_statement(TemplateActivationAction::Activate, "t1", path).is_err());
+        }
+
+        let set_paths = vec!["root.sg".to_string()];
+        assert!(is_covered_by("root.sg", &set_paths));
+        assert!(is_covered_by("root.sg.d1", &set_paths));
+        assert!(!is_covered_by("root.sg2.d1", &set_paths));
+    }
+}
@@ -4,6 +4,8 @@
  * 处理不同版本间的数据类型差异和自动降级
  */
 
+use chrono::{DateTime, FixedOffset, NaiveDate, Offset, SecondsFormat, TimeZone, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -269,6 +271,12 @@ impl TypeConverter {
                     .map(DataValue::Timestamp)
                     .map_err(|_| format!("无法将 '{}' 转换为 Timestamp", s))
             }
+            IoTDBDataType::Date => {
+                // DATE 在传输层以 INT32 (yyyyMMdd) 表示
+                NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                    .map(|d| DataValue::Int32(TimeConverter::date_to_int(d)))
+                    .map_err(|_| format!("无法将 '{}' 转换为 Date", s))
+            }
             _ => Err(format!("不支持的目标类型: {:?}", target_type)),
         }
     }
@@ -297,6 +305,157 @@ impl TypeConverter {
     }
 }
 
+/// 服务端时间戳精度（timestamp_precision）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampPrecision {
+    #[default]
+    Ms,
+    Us,
+    Ns,
+}
+
+impl TimestampPrecision {
+    /// 从配置值解析（ms/us/ns）
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "ms" => Some(Self::Ms),
+            "us" => Some(Self::Us),
+            "ns" => Some(Self::Ns),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ms => "ms",
+            Self::Us => "us",
+            Self::Ns => "ns",
+        }
+    }
+
+    /// 每秒的单位数
+    fn units_per_second(&self) -> i64 {
+        match self {
+            Self::Ms => 1_000,
+            Self::Us => 1_000_000,
+            Self::Ns => 1_000_000_000,
+        }
+    }
+
+    fn seconds_format(&self) -> SecondsFormat {
+        match self {
+            Self::Ms => SecondsFormat::Millis,
+            Self::Us => SecondsFormat::Micros,
+            Self::Ns => SecondsFormat::Nanos,
+        }
+    }
+}
+
+/// 会话时区：固定偏移或带夏令时规则的 IANA 时区
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SessionTimeZone {
+    Fixed(FixedOffset),
+    Named(Tz),
+}
+
+impl SessionTimeZone {
+    pub fn utc() -> Self {
+        SessionTimeZone::Fixed(FixedOffset::east_opt(0).expect("UTC offset"))
+    }
+
+    /// 指定时刻相对 UTC 的偏移
+    pub fn offset_at(&self, dt: &DateTime<Utc>) -> FixedOffset {
+        match self {
+            SessionTimeZone::Fixed(offset) => *offset,
+            SessionTimeZone::Named(tz) => tz.offset_from_utc_datetime(&dt.naive_utc()).fix(),
+        }
+    }
+}
+
+/// 时间转换工具
+pub struct TimeConverter;
+
+impl TimeConverter {
+    /// 按精度将原始时间戳转换为 UTC 时间
+    pub fn to_datetime(value: i64, precision: TimestampPrecision) -> Option<DateTime<Utc>> {
+        let units = precision.units_per_second();
+        let secs = value.div_euclid(units);
+        let nanos = value.rem_euclid(units) * (1_000_000_000 / units);
+        DateTime::from_timestamp(secs, nanos as u32)
+    }
+
+    /// 按精度将原始时间戳转换为毫秒
+    pub fn to_millis(value: i64, precision: TimestampPrecision) -> i64 {
+        match precision {
+            TimestampPrecision::Ms => value,
+            TimestampPrecision::Us => value.div_euclid(1_000),
+            TimestampPrecision::Ns => value.div_euclid(1_000_000),
+        }
+    }
+
+    /// 格式化为带时区偏移的 RFC3339 字符串，小数位与精度一致
+    pub fn format_timestamp(value: i64, precision: TimestampPrecision, zone: SessionTimeZone) -> String {
+        match Self::to_datetime(value, precision) {
+            Some(dt) => dt.with_timezone(&zone.offset_at(&dt)).to_rfc3339_opts(precision.seconds_format(), true),
+            None => value.to_string(),
+        }
+    }
+
+    /// 查询结果中的时间戳：默认为毫秒时间戳，指定时区时格式化为 RFC3339 字符串
+    pub fn timestamp_value(value: i64, precision: TimestampPrecision, format: Option<SessionTimeZone>) -> serde_json::Value {
+        match format {
+            Some(zone) => serde_json::Value::String(Self::format_timestamp(value, precision, zone)),
+            None => serde_json::Value::from(Self::to_millis(value, precision)),
+        }
+    }
+
+    /// 将 DATE 的 INT32 (yyyyMMdd) 表示转换为日期
+    pub fn int_to_date(value: i32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(value / 10_000, (value / 100 % 100) as u32, (value % 100) as u32)
+    }
+
+    /// 将日期转换为 DATE 的 INT32 (yyyyMMdd) 表示
+    pub fn date_to_int(date: NaiveDate) -> i32 {
+        use chrono::Datelike;
+        date.year() * 10_000 + date.month() as i32 * 100 + date.day() as i32
+    }
+
+    /// 解析时区配置
+    ///
+    /// 支持 IANA 时区（如 `Europe/Berlin`，按夏令时规则换算）以及 `+08:00`、`UTC+8`、`GMT+08:00` 这类固定偏移
+    pub fn parse_time_zone(zone: &str) -> Option<SessionTimeZone> {
+        let zone = zone.trim();
+        if zone == "Z" {
+            return Some(SessionTimeZone::utc());
+        }
+        if let Ok(tz) = zone.parse::<Tz>() {
+            return Some(SessionTimeZone::Named(tz));
+        }
+
+        let offset = zone
+            .strip_prefix("UTC")
+            .or_else(|| zone.strip_prefix("GMT"))
+            .unwrap_or(zone);
+        let (sign, rest) = match offset.chars().next()? {
+            '+' => (1, &offset[1..]),
+            '-' => (-1, &offset[1..]),
+            _ => return None,
+        };
+
+        let (hours, minutes) = match rest.split_once(':') {
+            Some((h, m)) => (h.parse::<i32>().ok()?, m.parse::<i32>().ok()?),
+            None if rest.len() == 4 => (rest[..2].parse::<i32>().ok()?, rest[2..].parse::<i32>().ok()?),
+            None => (rest.parse::<i32>().ok()?, 0),
+        };
+        if hours > 14 || minutes >= 60 {
+            return None;
+        }
+
+        FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).map(SessionTimeZone::Fixed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -351,4 +510,72 @@ mod tests {
             IoTDBDataType::Text
         );
     }
+
+    #[test]
+    fn test_timestamp_precision_formatting() {
+        let shanghai = TimeConverter::parse_time_zone("Asia/Shanghai").unwrap();
+        let utc = TimeConverter::parse_time_zone("UTC").unwrap();
+
+        // 2024-01-01T00:00:00Z 的三种精度表示
+        assert_eq!(
+            TimeConverter::format_timestamp(1_704_067_200_123, TimestampPrecision::Ms, utc),
+            "2024-01-01T00:00:00.123Z"
+        );
+        assert_eq!(
+            TimeConverter::format_timestamp(1_704_067_200_123_456, TimestampPrecision::Us, shanghai),
+            "2024-01-01T08:00:00.123456+08:00"
+        );
+        assert_eq!(
+            TimeConverter::format_timestamp(1_704_067_200_000_000_001, TimestampPrecision::Ns, utc),
+            "2024-01-01T00:00:00.000000001Z"
+        );
+        assert_eq!(TimeConverter::to_millis(1_704_067_200_123_456, TimestampPrecision::Us), 1_704_067_200_123);
+        assert_eq!(TimeConverter::to_millis(-1, TimestampPrecision::Us), -1);
+
+        // 默认保持数值时间戳（毫秒）
+        assert_eq!(
+            TimeConverter::timestamp_value(1_704_067_200_123_456, TimestampPrecision::Us, None),
+            serde_json::json!(1_704_067_200_123i64)
+        );
+        assert_eq!(
+            TimeConverter::timestamp_value(1_704_067_200_123, TimestampPrecision::Ms, Some(utc)),
+            serde_json::json!("2024-01-01T00:00:00.123Z")
+        );
+    }
+
+    #[test]
+    fn test_time_zone_parsing() {
+        let winter = DateTime::parse_from_rfc3339("2024-01-15T12:00:00Z").unwrap().with_timezone(&Utc);
+        let summer = DateTime::parse_from_rfc3339("2024-07-15T12:00:00Z").unwrap().with_timezone(&Utc);
+        let offset = |s: &str| TimeConverter::parse_time_zone(s).map(|z| z.offset_at(&winter).local_minus_utc());
+        assert_eq!(offset("UTC"), Some(0));
+        assert_eq!(offset("+08:00"), Some(8 * 3600));
+        assert_eq!(offset("UTC+8"), Some(8 * 3600));
+        assert_eq!(offset("GMT-05:30"), Some(-(5 * 3600 + 1800)));
+        assert_eq!(offset("+0530"), Some(5 * 3600 + 1800));
+        assert_eq!(offset("Asia/Shanghai"), Some(8 * 3600));
+        assert_eq!(offset("Europe/Berlin"), Some(3600));
+        assert_eq!(offset("Not/A_Zone"), None);
+
+        // 夏令时按时刻换算
+        let berlin = TimeConverter::parse_time_zone("Europe/Berlin").unwrap();
+        assert_eq!(berlin.offset_at(&summer).local_minus_utc(), 2 * 3600);
+        assert_eq!(
+            TimeConverter::format_timestamp(summer.timestamp_millis(), TimestampPrecision::Ms, berlin),
+            "2024-07-15T14:00:00.000+02:00"
+        );
+        assert_eq!(offset("+25:00"), None);
+    }
+
+    #[test]
+    fn test_date_conversion() {
+        let date = TimeConverter::int_to_date(20240229).unwrap();
+        assert_eq!(date.to_string(), "2024-02-29");
+        assert_eq!(TimeConverter::date_to_int(date), 20240229);
+        assert!(TimeConverter::int_to_date(20230229).is_none());
+        assert_eq!(
+            TypeConverter::parse_string_value("2024-02-29", &IoTDBDataType::Date).unwrap(),
+            DataValue::Int32(20240229)
+        );
+    }
 }
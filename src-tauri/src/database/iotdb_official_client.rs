@@ -5,13 +5,21 @@
  * 移除了所有自定义Thrift协议实现
  */
 
-use crate::models::{ConnectionConfig, QueryResult, DatabaseType, FieldInfo, FieldType};
-use crate::database::iotdb::drivers::official_thrift::OfficialThriftClient;
+use crate::models::{ConnectionConfig, QueryResult, DatabaseType, FieldInfo, FieldType, IoTDBTransport};
+use crate::database::iotdb::session_pool::{IoTDBSessionPool, SessionPoolConfig, SessionPoolStats};
+use crate::database::iotdb::admin;
+use crate::database::iotdb::types::{SessionTimeZone, TimeConverter, TimestampPrecision};
+#[cfg(feature = "iotdb-rest")]
+use crate::database::iotdb::drivers::rest_v2::RestV2Driver;
 use anyhow::Result;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 use log::{debug, info, trace, warn};
 
+/// Auto 模式下 Thrift 连接失败后直接使用 REST v2 的时长，到期后重新探测 Thrift
+const THRIFT_REPROBE_INTERVAL: Duration = Duration::from_secs(60);
+
 /// IoTDB 版本信息
 #[derive(Debug, Clone, Default)]
 pub struct IoTDBVersionInfo {
@@ -66,10 +74,18 @@ impl IoTDBVersionInfo {
 /// IoTDB 官方客户端包装器
 #[derive(Debug)]
 pub struct IoTDBOfficialClient {
-    client: Arc<Mutex<Option<OfficialThriftClient>>>,
+    /// Thrift 会话池
+    pool: Arc<IoTDBSessionPool>,
     config: ConnectionConfig,
     /// 缓存的版本信息
     version_info: Arc<Mutex<Option<IoTDBVersionInfo>>>,
+    /// 缓存的服务端时间戳精度
+    timestamp_precision: Arc<Mutex<Option<TimestampPrecision>>>,
+    /// REST v2 备用驱动（按需创建）
+    #[cfg(feature = "iotdb-rest")]
+    rest_driver: Arc<Mutex<Option<RestV2Driver>>>,
+    /// Auto 模式下回退到 REST v2 的截止时间，期间不再尝试 Thrift
+    rest_fallback_until: Arc<Mutex<Option<Instant>>>,
 }
 
 impl IoTDBOfficialClient {
@@ -77,13 +93,17 @@ impl IoTDBOfficialClient {
     pub async fn new(config: ConnectionConfig) -> Result<Self> {
         info!("创建IoTDB官方客户端: {}:{}", config.host, config.port);
 
-        let client = Arc::new(Mutex::new(None));
+        let pool = Arc::new(IoTDBSessionPool::new(SessionPoolConfig::from_connection_config(&config)));
         let version_info = Arc::new(Mutex::new(None));
 
         let instance = Self {
-            client,
+            pool,
             config,
             version_info,
+            timestamp_precision: Arc::new(Mutex::new(None)),
+            #[cfg(feature = "iotdb-rest")]
+            rest_driver: Arc::new(Mutex::new(None)),
+            rest_fallback_until: Arc::new(Mutex::new(None)),
         };
 
         info!("IoTDB官方客户端创建成功");
@@ -115,58 +135,21 @@ impl IoTDBOfficialClient {
 
     /// 连接到IoTDB服务器
     async fn connect(&self) -> Result<()> {
-        let mut client_guard = self.client.lock().await;
-
-        // 检查是否已经连接且会话有效
-        if let Some(client) = client_guard.as_ref() {
-            if client.is_connected() {
-                debug!("IoTDB连接已存在且会话有效，跳过重新连接");
-                return Ok(()); // 已经连接且会话有效
-            } else {
-                warn!("IoTDB会话已失效，需要重新连接");
-            }
-        }
-
         info!("连接到IoTDB服务器: {}:{}", self.config.host, self.config.port);
-
-        // 创建官方Thrift客户端
-        let mut thrift_client = OfficialThriftClient::new(
-            self.config.host.clone(),
-            self.config.port,
-            self.config.username.clone().unwrap_or_else(|| "root".to_string()),
-            self.config.password.clone().unwrap_or_default(),
-        );
-
-        // 连接并打开会话
-        thrift_client.connect().await
-            .map_err(|e| anyhow::anyhow!("连接IoTDB服务器失败: {}", e))?;
-
-        let session_id = thrift_client.open_session().await
-            .map_err(|e| anyhow::anyhow!("打开IoTDB会话失败: {}", e))?;
-
-        info!("IoTDB连接建立成功，会话ID: {}", session_id);
-
-        *client_guard = Some(thrift_client);
-
-        Ok(())
+        self.pool.ensure_session().await
     }
 
     /// 确保连接可用
     async fn ensure_connected(&self) -> Result<()> {
-        let client_guard = self.client.lock().await;
-
-        // 检查客户端是否存在以及会话是否有效
-        let needs_reconnect = if let Some(client) = client_guard.as_ref() {
-            !client.is_connected()
-        } else {
-            true
-        };
-
-        if needs_reconnect {
-            drop(client_guard);
-            self.connect().await?;
+        if self.pool.has_sessions().await {
+            return Ok(());
         }
-        Ok(())
+        self.connect().await
+    }
+
+    /// 获取会话池统计信息
+    pub async fn get_pool_stats(&self) -> SessionPoolStats {
+        self.pool.stats().await
     }
     
     /// 测试连接
@@ -175,37 +158,58 @@ impl IoTDBOfficialClient {
 
         let start_time = std::time::Instant::now();
 
-        // 尝试连接并执行简单查询
+        if self.transport() == IoTDBTransport::RestV2 {
+            self.execute_via_rest("SHOW VERSION").await?;
+            return Ok(start_time.elapsed().as_millis() as u64);
+        }
+
+        // 尝试连接并执行简单查询，测试连接时总是重新探测 Thrift
         match self.ensure_connected().await {
-            Ok(_) => info!("IoTDB连接建立成功"),
+            Ok(_) => {
+                *self.rest_fallback_until.lock().await = None;
+                info!("IoTDB连接建立成功")
+            }
+            Err(e) if self.transport() == IoTDBTransport::Auto => {
+                warn!("IoTDB Thrift 连接失败，尝试 REST v2: {}", e);
+                *self.rest_fallback_until.lock().await = Some(Instant::now() + THRIFT_REPROBE_INTERVAL);
+                self.execute_via_rest("SHOW VERSION").await?;
+                return Ok(start_time.elapsed().as_millis() as u64);
+            }
             Err(e) => {
                 warn!("IoTDB连接失败: {}", e);
                 return Err(e);
             }
         }
 
-        let mut client_guard = self.client.lock().await;
-        if let Some(client) = client_guard.as_mut() {
-            // 执行版本查询测试连接
-            match client.execute_statement("SHOW VERSION").await {
-                Ok(_result) => {
-                    let elapsed = start_time.elapsed().as_millis() as u64;
-                    info!("IoTDB连接测试成功，延迟: {}ms", elapsed);
-                    Ok(elapsed)
-                }
-                Err(e) => {
-                    warn!("IoTDB查询执行失败: {}", e);
-                    Err(anyhow::anyhow!("查询执行失败: {}", e))
-                }
+        // 执行版本查询测试连接
+        match self.pool.execute("SHOW VERSION").await {
+            Ok(_result) => {
+                let elapsed = start_time.elapsed().as_millis() as u64;
+                info!("IoTDB连接测试成功，延迟: {}ms", elapsed);
+                Ok(elapsed)
+            }
+            Err(e) => {
+                warn!("IoTDB查询执行失败: {}", e);
+                Err(anyhow::anyhow!("查询执行失败: {}", e))
             }
-        } else {
-            Err(anyhow::anyhow!("IoTDB客户端未连接"))
         }
     }
     
     /// 执行查询
     pub async fn execute_query(&self, sql: &str, database: Option<&str>) -> Result<QueryResult> {
+        let precision = self.get_timestamp_precision().await;
+        self.execute_query_with_precision(sql, database, precision).await
+    }
+
+    /// 按指定时间戳精度执行查询
+    async fn execute_query_with_precision(
+        &self,
+        sql: &str,
+        database: Option<&str>,
+        precision: TimestampPrecision,
+    ) -> Result<QueryResult> {
         debug!("执行IoTDB查询: {} (数据库: {:?})", sql, database);
+        let time_format = self.time_format();
 
         // 如果是SHOW VERSION查询，测试数据解析
         if sql.to_uppercase().contains("SHOW VERSION") {
@@ -215,13 +219,20 @@ impl IoTDBOfficialClient {
 
         let start_time = std::time::Instant::now();
 
-        // 确保连接可用
-        self.ensure_connected().await?;
+        // 确保连接可用，按配置回退到 REST v2
+        match self.transport() {
+            IoTDBTransport::RestV2 => return self.execute_via_rest(sql).await,
+            IoTDBTransport::Auto => {
+                if self.use_rest_fallback().await {
+                    return self.execute_via_rest(sql).await;
+                }
+            }
+            IoTDBTransport::Thrift => self.ensure_connected().await?,
+        }
 
-        let mut client_guard = self.client.lock().await;
-        if let Some(client) = client_guard.as_mut() {
+        {
             // 执行SQL查询
-            let response = client.execute_statement(sql).await?;
+            let response = self.pool.execute(sql).await?;
             let execution_time = start_time.elapsed().as_millis() as u64;
 
             // 转换响应为QueryResult格式
@@ -370,9 +381,9 @@ impl IoTDBOfficialClient {
                             // 如果有Time列（无论是原始的还是添加的），都需要添加时间戳数据
                             if has_time_column || !timestamps.is_empty() {
                                 if !timestamps.is_empty() && row_index < timestamps.len() {
-                                    row_values.push(serde_json::Value::Number(
-                                        serde_json::Number::from(timestamps[row_index])
-                                    ));
+                                    row_values.push(
+                                        TimeConverter::timestamp_value(timestamps[row_index], precision, time_format)
+                                    );
                                     trace!("第 {} 行，添加时间戳: {}", row_index, timestamps[row_index]);
                                 } else {
                                     row_values.push(serde_json::Value::Null);
@@ -397,6 +408,8 @@ impl IoTDBOfficialClient {
                                     bitmap
                                 )?;
 
+                                let value = Self::format_temporal_value(value, data_type, precision, time_format);
+
                                 trace!("第 {} 行，第 {} 列解析结果: {:?}", row_index, col_index, value);
                                 row_values.push(value);
                             }
@@ -464,11 +477,239 @@ impl IoTDBOfficialClient {
             info!("IoTDB查询执行成功: {} 行结果，耗时: {}ms",
                   result.row_count.unwrap_or(0), execution_time);
             Ok(result)
-        } else {
-            Err(anyhow::anyhow!("IoTDB客户端未连接"))
         }
     }
     
+    /// 时间戳的输出格式：默认保持毫秒时间戳，开启 formatTimestamps 时按会话时区格式化为字符串
+    fn time_format(&self) -> Option<SessionTimeZone> {
+        let enabled = self
+            .config
+            .driver_config
+            .as_ref()
+            .and_then(|c| c.iotdb.as_ref())
+            .is_some_and(|c| c.format_timestamps);
+        if !enabled {
+            return None;
+        }
+
+        let zone = self.pool.time_zone();
+        Some(TimeConverter::parse_time_zone(&zone).unwrap_or_else(|| {
+            warn!("无法解析时区 {}，按 UTC 格式化时间戳", zone);
+            SessionTimeZone::utc()
+        }))
+    }
+
+    /// 修改会话时区，对池中所有会话生效
+    pub async fn set_time_zone(&self, time_zone: &str) -> Result<()> {
+        self.pool.set_time_zone(time_zone).await
+    }
+
+    /// 获取服务端时间戳精度，只缓存成功的检测结果，检测失败时本次按毫秒处理
+    pub async fn get_timestamp_precision(&self) -> TimestampPrecision {
+        if let Some(precision) = *self.timestamp_precision.lock().await {
+            return precision;
+        }
+
+        match self.detect_timestamp_precision().await {
+            Ok(precision) => {
+                // 服务端未提供该配置时即为默认的毫秒精度
+                let precision = precision.unwrap_or_default();
+                info!("IoTDB时间戳精度: {}", precision.as_str());
+                *self.timestamp_precision.lock().await = Some(precision);
+                precision
+            }
+            Err(e) => {
+                debug!("检测时间戳精度失败，暂按毫秒处理: {}", e);
+                TimestampPrecision::Ms
+            }
+        }
+    }
+
+    /// 检测服务端 timestamp_precision，服务端未返回该配置时为 None
+    async fn detect_timestamp_precision(&self) -> Result<Option<TimestampPrecision>> {
+        // 优先使用打开会话时返回的配置
+        let configuration = self.pool.server_configuration().await;
+        if let Some(precision) = configuration
+            .iter()
+            .find(|(key, _)| key.to_lowercase().replace('_', "") == "timestampprecision")
+            .and_then(|(_, value)| TimestampPrecision::from_str(value))
+        {
+            return Ok(Some(precision));
+        }
+
+        // SHOW VARIABLES 返回 Variable/Value 两列
+        let result = self
+            .execute_query_with_precision("SHOW VARIABLES", None, TimestampPrecision::Ms)
+            .await?;
+        for row in result.data() {
+            let mut cells = row.iter().filter_map(|v| v.as_str());
+            while let Some(cell) = cells.next() {
+                if cell.to_lowercase().replace('_', "") == "timestampprecision" {
+                    if let Some(precision) = cells.next().and_then(TimestampPrecision::from_str) {
+                        return Ok(Some(precision));
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// TIMESTAMP 列统一为毫秒时间戳；开启时间格式化时 TIMESTAMP 和 DATE 列输出为可读时间
+    fn format_temporal_value(
+        value: serde_json::Value,
+        data_type: &str,
+        precision: TimestampPrecision,
+        time_format: Option<SessionTimeZone>,
+    ) -> serde_json::Value {
+        match data_type.to_uppercase().as_str() {
+            "TIMESTAMP" => match value.as_i64() {
+                Some(ts) => TimeConverter::timestamp_value(ts, precision, time_format),
+                None => value,
+            },
+            "DATE" if time_format.is_some() => match value.as_i64().and_then(|d| TimeConverter::int_to_date(d as i32)) {
+                Some(date) => serde_json::Value::String(date.to_string()),
+                None => value,
+            },
+            _ => value,
+        }
+    }
+
+    /// 获取配置的传输方式
+    fn transport(&self) -> IoTDBTransport {
+        self.config
+            .driver_config
+            .as_ref()
+            .and_then(|c| c.iotdb.as_ref())
+            .map(|c| c.transport)
+            .unwrap_or_default()
+    }
+
+    /// Auto 模式下判断本次是否改用 REST v2
+    ///
+    /// Thrift 最近失败过时直接使用 REST，避免每条语句都等待 Thrift 连接超时；
+    /// 超过 [`THRIFT_REPROBE_INTERVAL`] 后重新尝试 Thrift
+    async fn use_rest_fallback(&self) -> bool {
+        let mut fallback_until = self.rest_fallback_until.lock().await;
+        if fallback_until.is_some_and(|until| Instant::now() < until) {
+            return true;
+        }
+
+        match self.ensure_connected().await {
+            Ok(_) => {
+                *fallback_until = None;
+                false
+            }
+            Err(e) => {
+                warn!("IoTDB Thrift 连接不可用，{}s 内改用 REST v2: {}", THRIFT_REPROBE_INTERVAL.as_secs(), e);
+                *fallback_until = Some(Instant::now() + THRIFT_REPROBE_INTERVAL);
+                true
+            }
+        }
+    }
+
+    /// 判断语句是否返回结果集
+    fn is_query_statement(sql: &str) -> bool {
+        let upper = sql.trim_start().to_uppercase();
+        ["SELECT", "SHOW", "COUNT", "LIST", "DESCRIBE", "DESC ", "EXPLAIN"]
+            .iter()
+            .any(|prefix| upper.starts_with(prefix))
+    }
+
+    /// 通过 REST v2 执行语句
+    #[cfg(feature = "iotdb-rest")]
+    async fn execute_via_rest(&self, sql: &str) -> Result<QueryResult> {
+        use crate::database::iotdb::capability::{Capability, ConnectionInfo, ServerCapability, VersionInfo};
+        use crate::database::iotdb::driver::{DriverConfig, IoTDBDriver, QueryRequest};
+        use crate::database::iotdb::types::DataValue;
+
+        let start_time = std::time::Instant::now();
+        let mut driver_guard = self.rest_driver.lock().await;
+
+        if driver_guard.is_none() {
+            let rest_port = self.config.driver_config.as_ref()
+                .and_then(|c| c.iotdb.as_ref())
+                .map(|c| c.rest_port)
+                .unwrap_or(crate::database::iotdb::drivers::rest_v2::DEFAULT_REST_PORT);
+
+            let driver_config = DriverConfig {
+                host: self.config.host.clone(),
+                port: rest_port,
+                username: self.config.username.clone(),
+                password: self.config.password.clone(),
+                ssl: self.config.ssl,
+                timeout: std::time::Duration::from_secs(self.config.query_timeout.max(1)),
+                extra_params: std::collections::HashMap::new(),
+            };
+            let capability = Capability {
+                server: ServerCapability {
+                    version: VersionInfo::parse("1.0.0")?,
+                    tree_model: true,
+                    table_model: false,
+                    rest_v2: true,
+                    new_types: false,
+                    ssl: self.config.ssl,
+                    supported_protocols: vec!["rest".to_string()],
+                    extra_properties: std::collections::HashMap::new(),
+                },
+                connection_info: ConnectionInfo {
+                    host: self.config.host.clone(),
+                    port: rest_port,
+                    ssl: self.config.ssl,
+                    available_ports: std::collections::HashMap::from([("rest".to_string(), rest_port)]),
+                },
+            };
+
+            let mut driver = RestV2Driver::new(driver_config, capability)?;
+            driver.connect().await?;
+            info!("IoTDB REST v2 连接建立成功: {}:{}", self.config.host, rest_port);
+            *driver_guard = Some(driver);
+        }
+
+        let driver = driver_guard.as_mut().ok_or_else(|| anyhow::anyhow!("IoTDB REST 驱动未初始化"))?;
+
+        if !Self::is_query_statement(sql) {
+            driver.execute(sql).await?;
+            let mut result = QueryResult::empty();
+            result.execution_time = Some(start_time.elapsed().as_millis() as u64);
+            return Ok(result);
+        }
+
+        let response = driver.query(QueryRequest {
+            sql: sql.to_string(),
+            database: None,
+            session_id: None,
+            fetch_size: None,
+            timeout: None,
+            parameters: None,
+        }).await?;
+
+        let precision = self.timestamp_precision.lock().await.unwrap_or_default();
+        let time_format = self.time_format();
+        let columns = response.columns.into_iter().map(|c| c.name).collect();
+        let rows = response.rows.into_iter().map(|row| {
+            row.into_iter().map(|value| match value {
+                DataValue::Null => serde_json::Value::Null,
+                DataValue::Boolean(b) => serde_json::json!(b),
+                DataValue::Int32(i) => serde_json::json!(i),
+                DataValue::Int64(i) => serde_json::json!(i),
+                DataValue::Timestamp(t) => TimeConverter::timestamp_value(t, precision, time_format),
+                DataValue::Float(f) => serde_json::json!(f),
+                DataValue::Double(d) => serde_json::json!(d),
+                DataValue::Text(s) => serde_json::json!(s),
+                DataValue::Blob(b) => serde_json::json!(hex::encode(b)),
+            }).collect()
+        }).collect();
+
+        Ok(QueryResult::new(columns, rows, start_time.elapsed().as_millis() as u64))
+    }
+
+    /// 通过 REST v2 执行语句（未启用 iotdb-rest 特性）
+    #[cfg(not(feature = "iotdb-rest"))]
+    async fn execute_via_rest(&self, _sql: &str) -> Result<QueryResult> {
+        Err(anyhow::anyhow!("REST 驱动未启用，请开启 iotdb-rest 特性"))
+    }
+
     /// 获取数据库列表（存储组）
     pub async fn get_databases(&self) -> Result<Vec<String>> {
         debug!("获取IoTDB存储组列表");
@@ -777,10 +1018,13 @@ impl IoTDBOfficialClient {
     pub async fn close(&self) -> Result<()> {
         info!("关闭IoTDB官方客户端连接");
 
-        let mut client_guard = self.client.lock().await;
-        if let Some(mut client) = client_guard.take() {
-            if let Err(e) = client.disconnect().await {
-                warn!("关闭IoTDB连接时出错: {}", e);
+        self.pool.close().await;
+
+        #[cfg(feature = "iotdb-rest")]
+        {
+            use crate::database::iotdb::driver::IoTDBDriver;
+            if let Some(mut driver) = self.rest_driver.lock().await.take() {
+                let _ = driver.disconnect().await;
             }
         }
 
@@ -800,8 +1044,7 @@ impl IoTDBOfficialClient {
 
     /// 获取连接状态
     pub async fn get_connection_status(&self) -> String {
-        let client_guard = self.client.lock().await;
-        if client_guard.is_some() {
+        if self.pool.has_sessions().await {
             "Connected".to_string()
         } else {
             "Disconnected".to_string()
@@ -895,7 +1138,17 @@ impl IoTDBOfficialClient {
         .with_metadata("node_category".to_string(), serde_json::Value::String("management_container".to_string()));
         nodes.push(schema_template_node);
 
-        // 4. 获取存储组列表
+        // 4. 添加存储组管理节点（容器节点，包含连续查询和 TTL）
+        let storage_group_management_node = TreeNode::new(
+            "StorageGroupManagement".to_string(),
+            "Storage Group Management".to_string(),
+            TreeNodeType::StorageGroupManagement,
+        )
+        .with_metadata("is_container".to_string(), serde_json::Value::Bool(true))
+        .with_metadata("node_category".to_string(), serde_json::Value::String("management_container".to_string()));
+        nodes.push(storage_group_management_node);
+
+        // 5. 获取存储组列表
         let storage_groups = self.get_databases().await?;
         let storage_groups_empty = storage_groups.is_empty();
 
@@ -908,7 +1161,7 @@ impl IoTDBOfficialClient {
             nodes.push(node);
         }
 
-        // 5. 添加函数节点（容器节点，可展开）
+        // 6. 添加函数节点（容器节点，可展开）
         let functions_node = TreeNode::new(
             "Functions".to_string(),
             "Functions".to_string(),
@@ -918,7 +1171,7 @@ impl IoTDBOfficialClient {
         .with_metadata("node_category".to_string(), serde_json::Value::String("management_container".to_string()));
         nodes.push(functions_node);
 
-        // 6. 添加触发器节点（容器节点，可展开）
+        // 7. 添加触发器节点（容器节点，可展开）
         let triggers_node = TreeNode::new(
             "Triggers".to_string(),
             "Triggers".to_string(),
@@ -1081,6 +1334,35 @@ impl IoTDBOfficialClient {
         }
     }
 
+    /// 获取连续查询列表（ID 和状态）
+    async fn get_continuous_queries(&self) -> Result<Vec<(String, String)>> {
+        debug!("获取连续查询列表");
+
+        match self.execute_query("SHOW CONTINUOUS QUERIES", None).await {
+            Ok(result) => Ok(admin::parse_continuous_queries(&result.columns(), &result.rows())
+                .into_iter()
+                .map(|cq| (cq.cq_id, cq.state))
+                .collect()),
+            Err(e) => {
+                warn!("获取连续查询列表失败: {}", e);
+                Ok(vec![])
+            }
+        }
+    }
+
+    /// 获取 TTL 规则（路径和毫秒值，None 表示未设置）
+    async fn get_ttl_rules(&self) -> Result<Vec<(String, Option<i64>)>> {
+        debug!("获取 TTL 规则");
+
+        match self.execute_query("SHOW ALL TTL", None).await {
+            Ok(result) => Ok(admin::parse_ttl_rules(&result.columns(), &result.rows())),
+            Err(e) => {
+                warn!("获取 TTL 规则失败: {}", e);
+                Ok(vec![])
+            }
+        }
+    }
+
     /// 获取树子节点
     pub async fn get_tree_children(&self, parent_node_id: &str, node_type: &str, parent_metadata: Option<&serde_json::Value>) -> Result<Vec<crate::models::TreeNode>> {
         use crate::models::{TreeNode, TreeNodeType};
@@ -1188,6 +1470,70 @@ impl IoTDBOfficialClient {
                     children.push(child);
                 }
             }
+            "StorageGroupManagement" | "storage_group_management" => {
+                // 存储组管理节点的子节点：连续查询和 TTL 分组
+                let continuous_queries = TreeNode::new(
+                    format!("{}_ContinuousQueries", parent_node_id),
+                    "Continuous Queries".to_string(),
+                    TreeNodeType::ContinuousQuery,
+                )
+                .with_parent(parent_node_id.to_string())
+                .with_metadata("is_container".to_string(), serde_json::Value::Bool(true))
+                .with_metadata("node_category".to_string(), serde_json::Value::String("management_container".to_string()));
+                children.push(continuous_queries);
+
+                let ttl = TreeNode::new(
+                    format!("{}_TTL", parent_node_id),
+                    "TTL".to_string(),
+                    TreeNodeType::RetentionPolicy,
+                )
+                .with_parent(parent_node_id.to_string())
+                .with_metadata("is_container".to_string(), serde_json::Value::Bool(true))
+                .with_metadata("node_category".to_string(), serde_json::Value::String("management_container".to_string()));
+                children.push(ttl);
+            }
+            "ContinuousQuery" | "continuous_query" => {
+                // 连续查询分组的子节点
+                let queries = self.get_continuous_queries().await?;
+                for (cq_id, state) in queries {
+                    let child = TreeNode::new(
+                        format!("{}_{}", parent_node_id, cq_id),
+                        format!("{} ({})", cq_id, state),
+                        TreeNodeType::ContinuousQuery,
+                    )
+                    .with_parent(parent_node_id.to_string())
+                    .as_leaf()
+                    .with_metadata("is_container".to_string(), serde_json::Value::Bool(false))
+                    .with_metadata("node_category".to_string(), serde_json::Value::String("management_item".to_string()))
+                    .with_metadata("cqId".to_string(), serde_json::Value::String(cq_id))
+                    .with_metadata("state".to_string(), serde_json::Value::String(state));
+                    children.push(child);
+                }
+            }
+            "RetentionPolicy" | "retention_policy" => {
+                // TTL 分组的子节点：每个存储组的 TTL 设置
+                let rules = self.get_ttl_rules().await?;
+                for (path, ttl) in rules {
+                    let display = match ttl {
+                        Some(ms) => format!("{} (TTL: {}ms)", path, ms),
+                        None => format!("{} (TTL: INF)", path),
+                    };
+                    let mut child = TreeNode::new(
+                        format!("{}_{}", parent_node_id, path.replace(".", "_")),
+                        display,
+                        TreeNodeType::RetentionPolicy,
+                    )
+                    .with_parent(parent_node_id.to_string())
+                    .as_leaf()
+                    .with_metadata("is_container".to_string(), serde_json::Value::Bool(false))
+                    .with_metadata("node_category".to_string(), serde_json::Value::String("management_item".to_string()))
+                    .with_metadata("path".to_string(), serde_json::Value::String(path));
+                    if let Some(ms) = ttl {
+                        child = child.with_metadata("ttlMs".to_string(), serde_json::Value::from(ms));
+                    }
+                    children.push(child);
+                }
+            }
             "StorageGroup" | "storage_group" => {
                 // 存储组节点的子节点（设备）
                 let storage_group_name = parent_node_id.strip_prefix("sg_").unwrap_or(parent_node_id);
@@ -1372,8 +1718,8 @@ impl IoTDBOfficialClient {
         // 根据数据类型解析值
         match data_type.to_uppercase().as_str() {
             "BOOLEAN" => self.parse_boolean_value(column_data, row_index),
-            "INT32" => self.parse_int32_value(column_data, row_index),
-            "INT64" => self.parse_int64_value(column_data, row_index),
+            "INT32" | "DATE" => self.parse_int32_value(column_data, row_index),
+            "INT64" | "TIMESTAMP" => self.parse_int64_value(column_data, row_index),
             "FLOAT" => self.parse_float_value(column_data, row_index),
             "DOUBLE" => self.parse_double_value(column_data, row_index),
             "TEXT" | "STRING" => self.parse_text_value(column_data, row_index),
@@ -1711,8 +2057,8 @@ impl IoTDBOfficialClient {
 
         let count = match data_type.to_uppercase().as_str() {
             "BOOLEAN" => data.len(),
-            "INT32" | "FLOAT" => data.len() / 4,
-            "INT64" | "DOUBLE" => data.len() / 8,
+            "INT32" | "FLOAT" | "DATE" => data.len() / 4,
+            "INT64" | "DOUBLE" | "TIMESTAMP" => data.len() / 8,
             "TEXT" | "STRING" => {
                 // 对于文本类型，需要解析长度前缀来计算行数
                 let mut count = 0;
@@ -12,6 +12,9 @@ pub mod iotdb;
 pub mod influxdb;
 pub mod influxdb_client;
 
+// 执行计划解析
+pub mod explain;
+
 // pub use client::InfluxClient; // 旧的客户端，保留以兼容现有代码
 
 // S3/MinIO 支持
@@ -27,18 +27,33 @@ struct RestQueryRequest {
 /// HTTP REST API响应体
 #[derive(Debug, Deserialize)]
 struct RestQueryResponse {
+    /// v2 查询成功时不返回 code
+    #[serde(default = "default_success_code")]
     code: i32,
     message: Option<String>,
-    #[serde(default)]
+    #[serde(default, alias = "column_names", deserialize_with = "null_as_default")]
     columns: Vec<String>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "null_as_default")]
     values: Vec<Vec<serde_json::Value>>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "null_as_default")]
     timestamps: Vec<i64>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "null_as_default")]
     expressions: Vec<String>,
 }
 
+fn default_success_code() -> i32 {
+    200
+}
+
+/// 将 JSON null 视为默认值（v2 对未使用字段返回 null）
+fn null_as_default<'de, D, T>(deserializer: D) -> std::result::Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
 /// 服务器信息响应
 #[derive(Debug, Deserialize)]
 struct ServerInfoResponse {
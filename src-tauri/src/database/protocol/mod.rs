@@ -148,19 +148,24 @@ impl ProtocolFactory {
         }
     }
     
-    /// 自动检测最佳协议
+    /// 自动检测最佳协议，返回可用的协议及其实际连通的端口
     pub async fn detect_best_protocol(
         host: &str,
         port: u16,
         username: Option<String>,
         password: Option<String>,
-    ) -> Result<ProtocolType> {
-        let protocols = vec![
-            ProtocolType::IoTDBOfficial,  // IoTDB 官方客户端，唯一选择
-            ProtocolType::Http,           // REST API 备用协议
+    ) -> Result<(ProtocolType, u16)> {
+        // Thrift 优先，REST 作为备用：先尝试给定端口，再尝试 REST 默认端口
+        let rest_port = Self::get_default_port(&ProtocolType::Http);
+        let mut candidates = vec![
+            (ProtocolType::IoTDBOfficial, port),  // IoTDB 官方客户端
+            (ProtocolType::Http, port),           // REST API 备用协议
         ];
+        if rest_port != port {
+            candidates.push((ProtocolType::Http, rest_port));
+        }
 
-        for protocol in protocols {
+        for (protocol, port) in candidates {
             let config = ProtocolConfig {
                 protocol_type: protocol.clone(),
                 host: host.to_string(),
@@ -175,7 +180,10 @@ impl ProtocolFactory {
 
             if let Ok(mut client) = Self::create_client(config) {
                 if client.test_connection().await.is_ok() {
-                    return Ok(protocol);
+                    if protocol == ProtocolType::Http {
+                        log::info!("Thrift 不可用，回退到 REST API（端口 {}）", port);
+                    }
+                    return Ok((protocol, port));
                 }
             }
         }
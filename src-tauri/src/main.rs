@@ -31,7 +31,9 @@ use commands::port_manager::*;
 use commands::embedded_server::*;
 use commands::workspace::*;
 use commands::iotdb::*;
+use commands::influxdb1::*;
 use commands::influxdb2::*;
+use commands::influxdb3::*;
 use commands::database_detection::*;
 use commands::multi_source_performance::*;
 use commands::s3::*;
@@ -1155,6 +1157,7 @@ async fn main() {
             get_tag_keys,
             get_tag_values,
             get_table_schema,
+            search_schema,
             import_data,
 
             // New database operations
@@ -1169,6 +1172,7 @@ async fn main() {
             export_table_data,
             export_database_metadata,
             refresh_database_structure,
+            invalidate_schema_cache,
             create_measurement_template,
             show_measurements,
 
@@ -1196,6 +1200,18 @@ async fn main() {
             get_iotdb_device_info,
             get_iotdb_timeseries_info,
             get_iotdb_timeseries_statistics,
+            get_iotdb_continuous_queries,
+            get_iotdb_continuous_query_info,
+            create_iotdb_continuous_query,
+            drop_iotdb_continuous_query,
+            get_iotdb_ttl,
+            set_iotdb_ttl,
+            unset_iotdb_ttl,
+            set_iotdb_time_zone,
+            diff_iotdb_template,
+            apply_iotdb_template_design,
+            get_iotdb_template_paths,
+            batch_set_iotdb_template_activation,
 
             // InfluxDB 2.x specific operations
             get_influxdb2_organizations,
@@ -1205,6 +1221,109 @@ async fn main() {
             create_influxdb2_bucket,
             delete_influxdb2_bucket,
             update_bucket_retention,
+            get_influxdb2_tasks,
+            get_influxdb2_task,
+            create_influxdb2_task,
+            update_influxdb2_task,
+            set_influxdb2_task_active,
+            delete_influxdb2_task,
+            run_influxdb2_task,
+            get_influxdb2_task_runs,
+            get_influxdb2_task_logs,
+            get_influxdb2_checks,
+            create_influxdb2_check,
+            update_influxdb2_check,
+            delete_influxdb2_check,
+            get_influxdb2_notification_endpoints,
+            create_influxdb2_notification_endpoint,
+            update_influxdb2_notification_endpoint,
+            delete_influxdb2_notification_endpoint,
+            get_influxdb2_notification_rules,
+            create_influxdb2_notification_rule,
+            update_influxdb2_notification_rule,
+            delete_influxdb2_notification_rule,
+            get_influxdb2_check_statuses,
+            get_influxdb2_authorizations,
+            create_influxdb2_authorization,
+            create_influxdb2_bucket_token,
+            set_influxdb2_authorization_active,
+            delete_influxdb2_authorization,
+            get_influxdb2_current_authorization,
+            create_influxdb2_organization,
+            update_influxdb2_organization,
+            delete_influxdb2_organization,
+            get_influxdb2_org_members,
+            add_influxdb2_org_member,
+            remove_influxdb2_org_member,
+            get_influxdb2_users,
+            create_influxdb2_user,
+            reset_influxdb2_user_password,
+            delete_influxdb2_user,
+            get_influxdb2_labels,
+            create_influxdb2_label,
+            update_influxdb2_label,
+            delete_influxdb2_label,
+            get_influxdb2_resource_labels,
+            add_influxdb2_resource_label,
+            remove_influxdb2_resource_label,
+            get_influxdb2_telegrafs,
+            get_influxdb2_telegraf,
+            create_influxdb2_telegraf,
+            update_influxdb2_telegraf,
+            delete_influxdb2_telegraf,
+            validate_telegraf_config,
+            generate_influxdb2_telegraf_config,
+            get_influxdb2_scrapers,
+            create_influxdb2_scraper,
+            update_influxdb2_scraper,
+            delete_influxdb2_scraper,
+
+            // InfluxDB 1.x specific operations
+            get_influxdb1_continuous_queries,
+            create_influxdb1_continuous_query,
+            drop_influxdb1_continuous_query,
+            backfill_influxdb1_continuous_query,
+            get_influxdb1_users,
+            create_influxdb1_user,
+            set_influxdb1_user_password,
+            set_influxdb1_user_admin,
+            drop_influxdb1_user,
+            get_influxdb1_user_grants,
+            grant_influxdb1_privilege,
+            revoke_influxdb1_privilege,
+            set_influxdb1_privileges,
+            get_influxdb1_privilege_matrix,
+            get_influxdb1_shards,
+            get_influxdb1_shard_groups,
+            get_influxdb1_shard_timeline,
+            drop_influxdb1_shard,
+            get_server_queries,
+            kill_server_query,
+            start_server_query_watch,
+            stop_server_query_watch,
+
+            // InfluxDB 3.x specific operations
+            get_influxdb3_last_caches,
+            create_influxdb3_last_cache,
+            delete_influxdb3_last_cache,
+            query_influxdb3_last_cache,
+            get_influxdb3_distinct_caches,
+            create_influxdb3_distinct_cache,
+            delete_influxdb3_distinct_cache,
+            query_influxdb3_distinct_cache,
+            get_influxdb3_triggers,
+            create_influxdb3_trigger,
+            enable_influxdb3_trigger,
+            disable_influxdb3_trigger,
+            delete_influxdb3_trigger,
+            upload_influxdb3_plugin,
+            test_influxdb3_plugin,
+            get_influxdb3_trigger_logs,
+            execute_influxdb3_arrow_query,
+            get_influxdb3_arrow_page,
+            get_influxdb3_arrow_ipc,
+            release_influxdb3_arrow_result,
+            export_influxdb3_query,
 
             // Database version detection
             commands::database_detection::detect_database_version,
@@ -1224,6 +1343,7 @@ async fn main() {
             execute_batch_queries,
             validate_query,
             get_query_suggestions,
+            get_completions,
             format_query,
             explain_query,
 
@@ -1326,6 +1446,7 @@ async fn main() {
             get_chart_types,
             get_color_schemes,
             duplicate_dashboard,
+            resolve_dashboard_query_params,
 
             // Performance monitoring
             get_performance_metrics,
@@ -1587,6 +1708,10 @@ async fn main() {
                 }
             });
 
+            // Initialize schema metadata cache
+            app.manage(services::SchemaCacheService::new());
+            services::SchemaCacheService::start_background_refresh(app.handle().clone());
+
             // Initialize database version detector
             app.manage(commands::database_detection::init_detector());
 
@@ -104,6 +104,26 @@ pub struct IoTDBConfig {
     pub max_retry_count: u32,
     #[serde(rename = "retryIntervalMs", default = "default_retry_interval_ms")]
     pub retry_interval_ms: u64,
+    #[serde(default)]
+    pub transport: IoTDBTransport,
+    #[serde(rename = "restPort", default = "default_rest_port")]
+    pub rest_port: u16,
+    /// 查询结果中的时间戳按会话时区格式化为字符串（默认为毫秒时间戳）
+    #[serde(rename = "formatTimestamps", default)]
+    pub format_timestamps: bool,
+}
+
+/// IoTDB 传输方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IoTDBTransport {
+    /// 官方 Thrift 会话（默认）
+    #[default]
+    Thrift,
+    /// REST API v2
+    RestV2,
+    /// 优先 Thrift，不可达时回退到 REST v2
+    Auto,
 }
 
 /// S3/对象存储特有配置
@@ -212,6 +232,9 @@ pub struct ConnectionConfig {
     pub password: Option<String>,
     pub database: Option<String>,
     pub ssl: bool,
+    /// 跳过 TLS 证书校验，用于自签名证书，只在显式开启时生效
+    #[serde(default)]
+    pub skip_ssl_verification: bool,
     pub timeout: u64,
     #[serde(rename = "connectionTimeout", default = "default_connection_timeout")]
     pub connection_timeout: u64,
@@ -290,6 +313,10 @@ fn default_retry_interval_ms() -> u64 {
     1000
 }
 
+fn default_rest_port() -> u16 {
+    18080
+}
+
 impl Default for ProxyConfig {
     fn default() -> Self {
         Self {
@@ -324,6 +351,9 @@ impl Default for IoTDBConfig {
             enable_redirection: default_enable_redirection(),
             max_retry_count: default_max_retry_count(),
             retry_interval_ms: default_retry_interval_ms(),
+            transport: IoTDBTransport::default(),
+            rest_port: default_rest_port(),
+            format_timestamps: false,
         }
     }
 }
@@ -352,6 +382,7 @@ impl ConnectionConfig {
             password: None,
             database: None,
             ssl: false,
+            skip_ssl_verification: false,
             timeout: 30,
             connection_timeout: 30,
             query_timeout: 60,
@@ -403,6 +434,7 @@ impl ConnectionConfig {
             password: None,
             database: None,
             ssl: false,
+            skip_ssl_verification: false,
             timeout: 30,
             connection_timeout: 30,
             query_timeout: 60,
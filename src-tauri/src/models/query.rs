@@ -9,8 +9,25 @@ pub struct QueryRequest {
     pub database: Option<String>,
     pub query: String,
     pub timeout: Option<u64>,
+    #[serde(default)]
+    pub params: QueryParams, // 查询中以 $name 引用的参数
 }
 
+/// 查询参数值（带类型）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum QueryParamValue {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    Time(String),     // RFC3339 时间，如 2024-01-01T00:00:00Z
+    Duration(String), // 时长，如 1h、30m、1h30m
+}
+
+/// 命名查询参数：参数名（不含 $）→ 参数值
+pub type QueryParams = std::collections::HashMap<String, QueryParamValue>;
+
 /// 执行消息类型
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "lowercase")]
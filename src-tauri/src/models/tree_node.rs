@@ -393,6 +393,32 @@ impl TreeNodeFactory {
         .as_leaf()
     }
 
+    /// 创建缓存节点（kind 为 last 或 distinct，分别对应最新值缓存和去重值缓存）
+    pub fn create_cache(kind: &str, name: String, parent_id: String) -> TreeNode {
+        TreeNode::new(
+            format!("{}/{}_cache_{}", parent_id, kind, name),
+            name.clone(),
+            TreeNodeType::Index,
+        )
+        .with_parent(parent_id)
+        .with_metadata("cacheType".to_string(), serde_json::Value::String(kind.to_string()))
+        .with_metadata("cacheName".to_string(), serde_json::Value::String(name))
+        .as_leaf()
+    }
+
+    /// 创建 InfluxDB 3.x 处理引擎触发器节点
+    pub fn create_trigger3x(name: String, parent_id: String, disabled: bool) -> TreeNode {
+        TreeNode::new(
+            format!("{}/trigger3x_{}", parent_id, name),
+            name.clone(),
+            TreeNodeType::Trigger3x,
+        )
+        .with_parent(parent_id)
+        .with_metadata("triggerName".to_string(), serde_json::Value::String(name))
+        .with_metadata("disabled".to_string(), serde_json::Value::Bool(disabled))
+        .as_leaf()
+    }
+
     /// 创建视图节点
     pub fn create_view(name: String, parent_id: String) -> TreeNode {
         TreeNode::new(
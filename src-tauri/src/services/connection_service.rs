@@ -1200,6 +1200,19 @@ impl ConnectionService {
     pub async fn get_pool_stats(&self, connection_id: &str) -> Result<serde_json::Value> {
         debug!("获取连接池统计信息: {}", connection_id);
 
+        // IoTDB 连接使用自身的会话池
+        if let Ok(client) = self.manager.get_connection(connection_id).await {
+            if let Some(stats) = client.get_session_pool_stats().await {
+                let mut value = serde_json::to_value(&stats)?;
+                value["connection_id"] = serde_json::json!(connection_id);
+                value["total_connections"] = serde_json::json!(stats.total_sessions);
+                value["active_connections"] = serde_json::json!(stats.active_sessions);
+                value["idle_connections"] = serde_json::json!(stats.idle_sessions);
+                value["max_connections"] = serde_json::json!(stats.max_sessions);
+                return Ok(value);
+            }
+        }
+
         let pools = self.pools.read().await;
         if let Some(pool) = pools.get(connection_id) {
             let stats = pool.get_stats().await;
@@ -7,8 +7,10 @@ pub mod database_version_detector;
 pub mod performance_stats;
 pub mod performance_collector;
 pub mod video_server;
+pub mod schema_cache;
 
 pub use connection_service::ConnectionService;
 pub use performance_stats::PerformanceStatsService;
 pub use performance_collector::PerformanceCollector;
+pub use schema_cache::SchemaCacheService;
 pub use video_server::{start_video_server, get_video_server_port, cleanup_temp_video_files};
@@ -0,0 +1,1028 @@
+/**
+ * 结构信息缓存
+ *
+ * 按连接缓存数据库、测量、字段、标签、树节点和表结构等结构信息。
+ * 首次访问时从服务器加载并持久化到磁盘，重启后直接使用磁盘缓存；
+ * 后台任务定期刷新过期条目，发现变化时向前端发送 schema-changed 事件；
+ * 缓存内容同时作为跨连接结构搜索的索引来源
+ */
+
+use crate::database::client::DatabaseClient;
+use crate::models::{ConnectionState, TableSchema, TreeNode, TreeNodeType};
+use crate::services::ConnectionService;
+use crate::utils::query_params::split_iotdb_path;
+use crate::utils::schema_search::{Matcher, SchemaKind, SchemaSearchEntry, SchemaSearchHit};
+use anyhow::{Context, Result};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::{Mutex, RwLock};
+
+/// 缓存有效期，超过后由后台任务刷新
+const DEFAULT_TTL: Duration = Duration::from_secs(300);
+
+/// 后台刷新间隔
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 缓存目录名
+const CACHE_DIR: &str = "schema_cache";
+
+/// 还原树路径时的最大深度
+const MAX_TREE_DEPTH: usize = 32;
+
+/// 结构变化事件名
+pub const SCHEMA_CHANGED_EVENT: &str = "schema-changed";
+
+/// 缓存的结构信息
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum SchemaItem {
+    Databases,
+    Measurements { database: String },
+    Fields { database: String, measurement: String },
+    FieldKeys { database: String, measurement: Option<String> },
+    TagKeys { database: String, measurement: Option<String> },
+    TagValues { database: String, measurement: Option<String>, tag_key: String },
+    TableSchema { database: String, measurement: String },
+    TreeChildren { database: Option<String>, parent_node_id: String, node_type: String },
+}
+
+impl SchemaItem {
+    pub fn database(&self) -> Option<&str> {
+        match self {
+            SchemaItem::Databases => None,
+            SchemaItem::TreeChildren { database, .. } => database.as_deref(),
+            SchemaItem::Measurements { database }
+            | SchemaItem::Fields { database, .. }
+            | SchemaItem::FieldKeys { database, .. }
+            | SchemaItem::TagKeys { database, .. }
+            | SchemaItem::TagValues { database, .. }
+            | SchemaItem::TableSchema { database, .. } => Some(database),
+        }
+    }
+
+    /// 从树节点元数据中提取所属数据库
+    fn tree_children(parent_node_id: &str, node_type: &str, metadata: Option<&serde_json::Value>) -> Self {
+        let database = metadata.and_then(|metadata| {
+            ["database", "databaseName", "storageGroup", "bucket"]
+                .iter()
+                .find_map(|key| metadata.get(key).and_then(|v| v.as_str()))
+                .map(|s| s.to_string())
+        });
+        SchemaItem::TreeChildren {
+            database,
+            parent_node_id: parent_node_id.to_string(),
+            node_type: node_type.to_string(),
+        }
+    }
+}
+
+/// 可搜索的树节点类型
+fn node_kind(node_type: &TreeNodeType) -> Option<SchemaKind> {
+    match node_type {
+        TreeNodeType::Database | TreeNodeType::SystemDatabase | TreeNodeType::Database3x | TreeNodeType::StorageGroup => {
+            Some(SchemaKind::Database)
+        }
+        TreeNodeType::Bucket | TreeNodeType::SystemBucket | TreeNodeType::StorageBucket => Some(SchemaKind::Bucket),
+        TreeNodeType::Measurement | TreeNodeType::Table => Some(SchemaKind::Measurement),
+        TreeNodeType::Field | TreeNodeType::Column => Some(SchemaKind::Field),
+        TreeNodeType::Tag => Some(SchemaKind::TagKey),
+        TreeNodeType::Device => Some(SchemaKind::Device),
+        TreeNodeType::Timeseries | TreeNodeType::AlignedTimeseries => Some(SchemaKind::Timeseries),
+        _ => None,
+    }
+}
+
+/// 缓存数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "camelCase")]
+pub enum SchemaData {
+    Names(Vec<String>),
+    Nodes(Vec<TreeNode>),
+    Table(TableSchema),
+}
+
+impl SchemaData {
+    /// 用于比较变化的名称列表
+    fn names(&self) -> Vec<String> {
+        match self {
+            SchemaData::Names(names) => names.clone(),
+            SchemaData::Nodes(nodes) => nodes.iter().map(|node| node.name.clone()).collect(),
+            SchemaData::Table(schema) => schema
+                .tags
+                .iter()
+                .map(|tag| tag.name.clone())
+                .chain(schema.fields.iter().map(|field| field.name.clone()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    data: SchemaData,
+    /// 树节点的元数据，后台刷新时重新传给客户端
+    #[serde(default)]
+    metadata: Option<serde_json::Value>,
+    /// 加载时间（毫秒时间戳）
+    loaded_at: i64,
+}
+
+impl CacheEntry {
+    fn new(data: SchemaData, metadata: Option<serde_json::Value>) -> Self {
+        Self {
+            data,
+            metadata,
+            loaded_at: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    fn is_expired(&self, ttl: Duration) -> bool {
+        chrono::Utc::now().timestamp_millis() - self.loaded_at >= ttl.as_millis() as i64
+    }
+}
+
+/// IoTDB 时间序列路径拆分为设备路径和叶子名称
+fn split_series_path(name: &str) -> Option<(&str, String)> {
+    if !name.starts_with("root.") {
+        return None;
+    }
+    let nodes = split_iotdb_path(name).ok()?;
+    let leaf = nodes.last().filter(|_| nodes.len() > 1)?;
+    let device = &name[..name.len() - leaf.len() - 1];
+    let leaf = match leaf.strip_prefix('`').and_then(|rest| rest.strip_suffix('`')) {
+        Some(inner) => inner.replace("``", "`"),
+        None => leaf.to_string(),
+    };
+    Some((device, leaf))
+}
+
+/// 搜索结果去重键：（类型, 名称, 数据库, 所属测量/设备）
+type DedupKey<'a> = (SchemaKind, &'a str, Option<&'a str>, Option<String>);
+
+/// 搜索索引条目，路径不含连接名称
+#[derive(Debug, Clone)]
+struct IndexEntry {
+    /// None 表示只用于还原树路径、本身不参与搜索的树节点
+    kind: Option<SchemaKind>,
+    name: String,
+    path: Vec<String>,
+    node_id: Option<String>,
+    parent_id: Option<String>,
+}
+
+impl IndexEntry {
+    fn named(kind: SchemaKind, path: &[&str], name: &str) -> Self {
+        Self {
+            kind: Some(kind),
+            name: name.to_string(),
+            path: path.iter().map(|s| s.to_string()).collect(),
+            node_id: None,
+            parent_id: None,
+        }
+    }
+
+    /// 由缓存条目生成索引条目
+    fn from_cache(item: &SchemaItem, data: &SchemaData) -> Vec<Self> {
+        match (item, data) {
+            (SchemaItem::TreeChildren { .. }, SchemaData::Nodes(nodes)) => nodes
+                .iter()
+                .map(|node| Self {
+                    kind: node_kind(&node.node_type),
+                    name: node.name.clone(),
+                    path: Vec::new(),
+                    node_id: Some(node.id.clone()),
+                    parent_id: node.parent_id.clone(),
+                })
+                .collect(),
+            (SchemaItem::Databases, SchemaData::Names(names)) => {
+                names.iter().map(|name| Self::named(SchemaKind::Database, &[], name)).collect()
+            }
+            (SchemaItem::Measurements { database }, SchemaData::Names(names)) => names
+                .iter()
+                .map(|name| Self::named(SchemaKind::Measurement, &[database.as_str()], name))
+                .collect(),
+            (SchemaItem::Fields { database, measurement }, SchemaData::Names(names)) => names
+                .iter()
+                .map(|name| Self::named(SchemaKind::Field, &[database.as_str(), measurement.as_str()], name))
+                .collect(),
+            (SchemaItem::FieldKeys { database, measurement }, SchemaData::Names(names)) => names
+                .iter()
+                .map(|name| match split_series_path(name) {
+                    // IoTDB 返回完整的时间序列路径，按叶子名称索引，设备路径放在 path 中
+                    Some((device, leaf)) if device == database => {
+                        Self::named(SchemaKind::Timeseries, &[database.as_str()], &leaf)
+                    }
+                    Some((device, leaf)) => Self::named(SchemaKind::Timeseries, &[database.as_str(), device], &leaf),
+                    None => {
+                        let mut path = vec![database.as_str()];
+                        path.extend(measurement.as_deref());
+                        Self::named(SchemaKind::Field, &path, name)
+                    }
+                })
+                .collect(),
+            (SchemaItem::TagKeys { database, measurement }, SchemaData::Names(names)) => {
+                let mut path = vec![database.as_str()];
+                path.extend(measurement.as_deref());
+                names.iter().map(|name| Self::named(SchemaKind::TagKey, &path, name)).collect()
+            }
+            (SchemaItem::TableSchema { database, measurement }, SchemaData::Table(schema)) => {
+                let path = [database.as_str(), measurement.as_str()];
+                schema
+                    .tags
+                    .iter()
+                    .map(|tag| Self::named(SchemaKind::TagKey, &path, &tag.name))
+                    .chain(schema.fields.iter().map(|field| Self::named(SchemaKind::Field, &path, &field.name)))
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// 单个连接的搜索索引，随缓存条目的写入和清除增量更新
+#[derive(Debug, Default)]
+struct SearchIndex {
+    items: HashMap<SchemaItem, Vec<IndexEntry>>,
+    /// 树节点 ID -> (名称, 父节点 ID, 类型)，用于还原节点在树中的路径
+    tree: HashMap<String, (String, Option<String>, Option<SchemaKind>)>,
+}
+
+impl SearchIndex {
+    fn from_items(items: &HashMap<SchemaItem, CacheEntry>) -> Self {
+        let mut index = Self::default();
+        for (item, entry) in items {
+            index.insert(item, &entry.data);
+        }
+        index
+    }
+
+    fn insert(&mut self, item: &SchemaItem, data: &SchemaData) {
+        self.remove(item);
+        let entries = IndexEntry::from_cache(item, data);
+        if entries.is_empty() {
+            return;
+        }
+        for entry in &entries {
+            if let Some(node_id) = &entry.node_id {
+                self.tree.insert(node_id.clone(), (entry.name.clone(), entry.parent_id.clone(), entry.kind));
+            }
+        }
+        self.items.insert(item.clone(), entries);
+    }
+
+    fn remove(&mut self, item: &SchemaItem) {
+        for entry in self.items.remove(item).unwrap_or_default() {
+            if let Some(node_id) = entry.node_id {
+                self.tree.remove(&node_id);
+            }
+        }
+    }
+
+    fn retain(&mut self, keep: impl Fn(&SchemaItem) -> bool) {
+        let removed: Vec<SchemaItem> = self.items.keys().filter(|item| !keep(item)).cloned().collect();
+        for item in removed {
+            self.remove(&item);
+        }
+    }
+
+    fn tree_path(&self, parent_id: Option<&str>) -> Vec<String> {
+        let mut path = Vec::new();
+        let mut parent = parent_id;
+        while let Some((name, next, _)) = parent.and_then(|id| self.tree.get(id)) {
+            // 防止异常数据形成环
+            if path.len() >= MAX_TREE_DEPTH {
+                break;
+            }
+            path.push(name.clone());
+            parent = next.as_deref();
+        }
+        path.reverse();
+        path
+    }
+
+    /// 最近的测量或设备祖先节点名称
+    fn tree_owner(&self, parent_id: Option<&str>) -> Option<String> {
+        let mut parent = parent_id;
+        for _ in 0..MAX_TREE_DEPTH {
+            let (name, next, kind) = self.tree.get(parent?)?;
+            if matches!(kind, Some(SchemaKind::Measurement | SchemaKind::Device)) {
+                return Some(name.clone());
+            }
+            parent = next.as_deref();
+        }
+        None
+    }
+
+    /// 按名称匹配索引条目，只为命中的条目生成路径，路径以连接名称开头
+    ///
+    /// 同一对象可能同时来自树节点缓存和名称列表缓存（例如测量既在树中展开过、又被补全加载过），
+    /// 按（类型, 名称, 数据库, 所属测量/设备）去重，优先保留可以定位到树节点的条目
+    fn search(&self, connection_id: &str, connection_name: &str, matcher: &Matcher) -> Vec<SchemaSearchHit> {
+        let mut best: HashMap<DedupKey, (&IndexEntry, u32)> = HashMap::new();
+        for (item, entries) in &self.items {
+            for entry in entries {
+                let Some(kind) = entry.kind else {
+                    continue;
+                };
+                let Some(score) = matcher.score(&entry.name) else {
+                    continue;
+                };
+                let owner = match (&entry.node_id, kind) {
+                    (_, SchemaKind::Database | SchemaKind::Bucket | SchemaKind::Measurement | SchemaKind::Device) => None,
+                    (Some(_), _) => self.tree_owner(entry.parent_id.as_deref()),
+                    (None, _) => entry.path.get(1).cloned(),
+                };
+                match best.entry((kind, entry.name.as_str(), item.database(), owner)) {
+                    Entry::Vacant(vacant) => {
+                        vacant.insert((entry, score));
+                    }
+                    Entry::Occupied(mut occupied) => {
+                        if occupied.get().0.node_id.is_none() && entry.node_id.is_some() {
+                            occupied.insert((entry, score));
+                        }
+                    }
+                }
+            }
+        }
+
+        best.into_iter()
+            .map(|((kind, ..), (entry, score))| {
+                let path = match &entry.node_id {
+                    Some(_) => self.tree_path(entry.parent_id.as_deref()),
+                    None => entry.path.clone(),
+                };
+                SchemaSearchHit {
+                    entry: SchemaSearchEntry {
+                        connection_id: connection_id.to_string(),
+                        kind,
+                        name: entry.name.clone(),
+                        path: std::iter::once(connection_name.to_string()).chain(path).collect(),
+                        node_id: entry.node_id.clone(),
+                    },
+                    score,
+                }
+            })
+            .collect()
+    }
+}
+
+/// 结构变化事件
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaChangeEvent {
+    pub connection_id: String,
+    pub database: Option<String>,
+    pub item: SchemaItem,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// 结构信息缓存服务
+pub struct SchemaCacheService {
+    entries: RwLock<HashMap<String, HashMap<SchemaItem, CacheEntry>>>,
+    /// 各连接的搜索索引，与 entries 同步更新，加锁顺序为先 entries 后 index
+    index: RwLock<HashMap<String, SearchIndex>>,
+    /// 内存中有改动、尚未写回磁盘的连接
+    dirty: Mutex<HashSet<String>>,
+    storage_dir: Option<PathBuf>,
+    ttl: Duration,
+}
+
+impl SchemaCacheService {
+    pub fn new() -> Self {
+        let storage_dir = dirs::config_dir().map(|dir| dir.join("inflowave").join(CACHE_DIR));
+        if storage_dir.is_none() {
+            warn!("无法获取系统配置目录，结构信息缓存不会持久化");
+        }
+
+        Self::with_storage_dir(storage_dir)
+    }
+
+    /// 使用指定的缓存目录，None 时只缓存在内存中
+    pub fn with_storage_dir(storage_dir: Option<PathBuf>) -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            index: RwLock::new(HashMap::new()),
+            dirty: Mutex::new(HashSet::new()),
+            storage_dir,
+            ttl: DEFAULT_TTL,
+        }
+    }
+
+    /// 获取名称类结构信息
+    pub async fn get(&self, connection_id: &str, client: &DatabaseClient, item: SchemaItem) -> Result<Vec<String>> {
+        match self.fetch(connection_id, client, item, None).await? {
+            SchemaData::Names(names) => Ok(names),
+            other => Ok(other.names()),
+        }
+    }
+
+    /// 获取树节点的子节点
+    pub async fn get_tree_children(
+        &self,
+        connection_id: &str,
+        client: &DatabaseClient,
+        parent_node_id: &str,
+        node_type: &str,
+        metadata: Option<&serde_json::Value>,
+    ) -> Result<Vec<TreeNode>> {
+        let item = SchemaItem::tree_children(parent_node_id, node_type, metadata);
+        match self.fetch(connection_id, client, item, metadata.cloned()).await? {
+            SchemaData::Nodes(nodes) => Ok(nodes),
+            _ => Err(anyhow::anyhow!("缓存数据类型不匹配")),
+        }
+    }
+
+    /// 获取表结构
+    pub async fn get_table_schema(
+        &self,
+        connection_id: &str,
+        client: &DatabaseClient,
+        database: &str,
+        measurement: &str,
+    ) -> Result<TableSchema> {
+        let item = SchemaItem::TableSchema {
+            database: database.to_string(),
+            measurement: measurement.to_string(),
+        };
+        match self.fetch(connection_id, client, item, None).await? {
+            SchemaData::Table(schema) => Ok(schema),
+            _ => Err(anyhow::anyhow!("缓存数据类型不匹配")),
+        }
+    }
+
+    /// 读取缓存，过期条目照常返回并交给后台任务刷新，未命中时从服务器加载
+    async fn fetch(
+        &self,
+        connection_id: &str,
+        client: &DatabaseClient,
+        item: SchemaItem,
+        metadata: Option<serde_json::Value>,
+    ) -> Result<SchemaData> {
+        self.ensure_loaded(connection_id).await;
+
+        {
+            let entries = self.entries.read().await;
+            if let Some(entry) = entries.get(connection_id).and_then(|items| items.get(&item)) {
+                return Ok(entry.data.clone());
+            }
+        }
+
+        debug!("加载结构信息: {} - {:?}", connection_id, item);
+        let data = Self::load(client, &item, metadata.as_ref()).await?;
+        self.store(connection_id, item, CacheEntry::new(data.clone(), metadata)).await;
+        Ok(data)
+    }
+
+    /// 写入缓存条目并标记待写回磁盘
+    async fn store(&self, connection_id: &str, item: SchemaItem, entry: CacheEntry) {
+        {
+            let mut entries = self.entries.write().await;
+            self.index
+                .write()
+                .await
+                .entry(connection_id.to_string())
+                .or_default()
+                .insert(&item, &entry.data);
+            entries.entry(connection_id.to_string()).or_default().insert(item, entry);
+        }
+        self.dirty.lock().await.insert(connection_id.to_string());
+    }
+
+    async fn load(client: &DatabaseClient, item: &SchemaItem, metadata: Option<&serde_json::Value>) -> Result<SchemaData> {
+        let data = match item {
+            SchemaItem::Databases => SchemaData::Names(client.get_databases().await?),
+            SchemaItem::Measurements { database } => SchemaData::Names(client.get_measurements(database).await?),
+            SchemaItem::Fields { database, measurement } => {
+                SchemaData::Names(client.get_field_keys(database, measurement).await?)
+            }
+            SchemaItem::FieldKeys { database, measurement } => {
+                SchemaData::Names(client.list_field_keys(database, measurement.as_deref()).await?)
+            }
+            SchemaItem::TagKeys { database, measurement } => {
+                SchemaData::Names(client.get_tag_keys(database, measurement.as_deref()).await?)
+            }
+            SchemaItem::TagValues { database, measurement, tag_key } => {
+                SchemaData::Names(client.get_tag_values(database, tag_key, measurement.as_deref()).await?)
+            }
+            SchemaItem::TableSchema { database, measurement } => {
+                SchemaData::Table(client.get_table_schema(database, measurement).await?)
+            }
+            SchemaItem::TreeChildren { parent_node_id, node_type, .. } => {
+                SchemaData::Nodes(client.get_tree_children(parent_node_id, node_type, metadata).await?)
+            }
+        };
+        Ok(data)
+    }
+
+    /// 清除连接的缓存，指定数据库时只清除该数据库及不属于具体数据库的条目
+    pub async fn invalidate(&self, connection_id: &str, database: Option<&str>) {
+        self.ensure_loaded(connection_id).await;
+
+        let mut entries = self.entries.write().await;
+        let mut index = self.index.write().await;
+        match database {
+            Some(database) => {
+                let keep = |item: &SchemaItem| item.database().is_some_and(|d| d != database);
+                if let Some(items) = entries.get_mut(connection_id) {
+                    items.retain(|item, _| keep(item));
+                }
+                if let Some(index) = index.get_mut(connection_id) {
+                    index.retain(keep);
+                }
+                self.dirty.lock().await.insert(connection_id.to_string());
+            }
+            None => {
+                entries.remove(connection_id);
+                index.remove(connection_id);
+                self.dirty.lock().await.remove(connection_id);
+                if let Some(path) = self.cache_path(connection_id) {
+                    if let Err(e) = tokio::fs::remove_file(&path).await {
+                        if e.kind() != std::io::ErrorKind::NotFound {
+                            warn!("删除结构信息缓存文件失败: {:?} - {}", path, e);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// 在连接的搜索索引中按名称查找，路径以连接名称开头，结果未排序
+    pub async fn search(&self, connection_id: &str, connection_name: &str, matcher: &Matcher) -> Vec<SchemaSearchHit> {
+        self.ensure_loaded(connection_id).await;
+
+        self.index
+            .read()
+            .await
+            .get(connection_id)
+            .map(|index| index.search(connection_id, connection_name, matcher))
+            .unwrap_or_default()
+    }
+
+    fn cache_path(&self, connection_id: &str) -> Option<PathBuf> {
+        let file_name: String = connection_id
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        self.storage_dir.as_ref().map(|dir| dir.join(format!("{}.json", file_name)))
+    }
+
+    /// 首次访问连接时从磁盘载入缓存
+    async fn ensure_loaded(&self, connection_id: &str) {
+        if self.entries.read().await.contains_key(connection_id) {
+            return;
+        }
+
+        let items = match self.cache_path(connection_id) {
+            Some(path) if path.exists() => match Self::read_cache_file(&path).await {
+                Ok(items) => {
+                    debug!("从磁盘载入 {} 条结构信息缓存: {}", items.len(), connection_id);
+                    items
+                }
+                Err(e) => {
+                    warn!("读取结构信息缓存失败: {:?} - {}", path, e);
+                    HashMap::new()
+                }
+            },
+            _ => HashMap::new(),
+        };
+
+        let mut entries = self.entries.write().await;
+        if let Entry::Vacant(slot) = entries.entry(connection_id.to_string()) {
+            self.index
+                .write()
+                .await
+                .insert(connection_id.to_string(), SearchIndex::from_items(&items));
+            slot.insert(items);
+        }
+    }
+
+    async fn read_cache_file(path: &Path) -> Result<HashMap<SchemaItem, CacheEntry>> {
+        let content = tokio::fs::read_to_string(path).await.context("读取缓存文件失败")?;
+        let items: Vec<(SchemaItem, CacheEntry)> = serde_json::from_str(&content).context("解析缓存文件失败")?;
+        Ok(items.into_iter().collect())
+    }
+
+    /// 将有改动的连接缓存写回磁盘
+    pub async fn flush(&self) {
+        let Some(dir) = self.storage_dir.as_ref() else {
+            return;
+        };
+
+        let connection_ids: Vec<String> = self.dirty.lock().await.drain().collect();
+        if connection_ids.is_empty() {
+            return;
+        }
+
+        if let Err(e) = tokio::fs::create_dir_all(dir).await {
+            warn!("创建结构信息缓存目录失败: {:?} - {}", dir, e);
+            return;
+        }
+
+        for connection_id in connection_ids {
+            let content = {
+                let entries = self.entries.read().await;
+                let Some(items) = entries.get(&connection_id) else {
+                    continue;
+                };
+                let items: Vec<(&SchemaItem, &CacheEntry)> = items.iter().collect();
+                match serde_json::to_string(&items) {
+                    Ok(content) => content,
+                    Err(e) => {
+                        warn!("序列化结构信息缓存失败: {} - {}", connection_id, e);
+                        continue;
+                    }
+                }
+            };
+
+            let Some(path) = self.cache_path(&connection_id) else {
+                continue;
+            };
+            // 先写入临时文件再重命名，避免写入中断导致缓存文件损坏
+            let temp_path = path.with_extension("tmp");
+            let result = match tokio::fs::write(&temp_path, content).await {
+                Ok(()) => tokio::fs::rename(&temp_path, &path).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                warn!("写入结构信息缓存失败: {:?} - {}", path, e);
+            }
+        }
+    }
+
+    /// 刷新一个连接中的过期条目，返回发生变化的条目
+    async fn refresh_expired(&self, connection_id: &str, client: &DatabaseClient) -> Vec<SchemaChangeEvent> {
+        let expired: Vec<(SchemaItem, CacheEntry)> = {
+            let entries = self.entries.read().await;
+            let Some(items) = entries.get(connection_id) else {
+                return Vec::new();
+            };
+            items
+                .iter()
+                .filter(|(_, entry)| entry.is_expired(self.ttl))
+                .map(|(item, entry)| (item.clone(), entry.clone()))
+                .collect()
+        };
+
+        let mut events = Vec::new();
+        for (item, old) in expired {
+            let data = match Self::load(client, &item, old.metadata.as_ref()).await {
+                Ok(data) => data,
+                Err(e) => {
+                    debug!("刷新结构信息失败: {} - {:?} - {}", connection_id, item, e);
+                    continue;
+                }
+            };
+            events.extend(self.apply_refresh(connection_id, item, old, data).await);
+        }
+        events
+    }
+
+    /// 用新加载的数据替换条目，名称有增减时返回变化事件
+    async fn apply_refresh(
+        &self,
+        connection_id: &str,
+        item: SchemaItem,
+        old: CacheEntry,
+        data: SchemaData,
+    ) -> Option<SchemaChangeEvent> {
+        let old_names = old.data.names();
+        let new_names = data.names();
+        let old_set: HashSet<&String> = old_names.iter().collect();
+        let new_set: HashSet<&String> = new_names.iter().collect();
+        let added: Vec<String> = new_names.iter().filter(|name| !old_set.contains(name)).cloned().collect();
+        let removed: Vec<String> = old_names.iter().filter(|name| !new_set.contains(name)).cloned().collect();
+
+        {
+            let mut entries = self.entries.write().await;
+            // 刷新期间缓存可能已被清除，此时不再写回
+            let entry = entries.get_mut(connection_id).and_then(|items| items.get_mut(&item))?;
+            *entry = CacheEntry::new(data, old.metadata);
+            if let Some(index) = self.index.write().await.get_mut(connection_id) {
+                index.insert(&item, &entry.data);
+            }
+        }
+        self.dirty.lock().await.insert(connection_id.to_string());
+
+        if added.is_empty() && removed.is_empty() {
+            return None;
+        }
+        Some(SchemaChangeEvent {
+            connection_id: connection_id.to_string(),
+            database: item.database().map(|d| d.to_string()),
+            item,
+            added,
+            removed,
+        })
+    }
+
+    /// 启动后台刷新任务，只刷新已连接的连接
+    pub fn start_background_refresh(app: AppHandle) {
+        tokio::spawn(async move {
+            info!("结构信息缓存后台刷新已启动");
+            let mut interval_timer = tokio::time::interval(REFRESH_INTERVAL);
+
+            loop {
+                interval_timer.tick().await;
+
+                let (Some(cache), Some(connection_service)) =
+                    (app.try_state::<SchemaCacheService>(), app.try_state::<ConnectionService>())
+                else {
+                    continue;
+                };
+
+                let connection_ids: Vec<String> = cache.entries.read().await.keys().cloned().collect();
+                for connection_id in connection_ids {
+                    let connected = connection_service
+                        .get_connection_status(&connection_id)
+                        .await
+                        .is_some_and(|status| matches!(status.status, ConnectionState::Connected));
+                    if !connected {
+                        continue;
+                    }
+
+                    let client = match connection_service.get_manager().get_connection(&connection_id).await {
+                        Ok(client) => client,
+                        Err(e) => {
+                            debug!("后台刷新获取连接失败: {} - {}", connection_id, e);
+                            continue;
+                        }
+                    };
+
+                    for event in cache.refresh_expired(&connection_id, &client).await {
+                        debug!(
+                            "结构信息变化: {} - {:?} (+{} -{})",
+                            event.connection_id,
+                            event.item,
+                            event.added.len(),
+                            event.removed.len()
+                        );
+                        if let Err(e) = app.emit(SCHEMA_CHANGED_EVENT, &event) {
+                            warn!("发送结构变化事件失败: {}", e);
+                        }
+                    }
+                }
+
+                cache.flush().await;
+            }
+        });
+    }
+}
+
+impl Default for SchemaCacheService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::schema_search::SearchMode;
+    use tempfile::tempdir;
+
+    fn names(values: &[&str]) -> SchemaData {
+        SchemaData::Names(values.iter().map(|s| s.to_string()).collect())
+    }
+
+    fn measurements(database: &str) -> SchemaItem {
+        SchemaItem::Measurements { database: database.to_string() }
+    }
+
+    async fn cached_names(cache: &SchemaCacheService, connection_id: &str, item: &SchemaItem) -> Option<Vec<String>> {
+        let entries = cache.entries.read().await;
+        entries.get(connection_id)?.get(item).map(|entry| entry.data.names())
+    }
+
+    async fn indexed(cache: &SchemaCacheService, connection_id: &str) -> Vec<(SchemaKind, String, String)> {
+        let mut entries: Vec<_> = cache
+            .search(connection_id, "conn", &Matcher::new(".*", SearchMode::Regex).unwrap())
+            .await
+            .into_iter()
+            .map(|hit| (hit.entry.kind, hit.entry.path.join("/"), hit.entry.name))
+            .collect();
+        entries.sort_by(|a, b| (&a.1, &a.2).cmp(&(&b.1, &b.2)));
+        entries
+    }
+
+    #[test]
+    fn test_schema_item_serde() {
+        let item = SchemaItem::TagValues {
+            database: "telegraf".to_string(),
+            measurement: None,
+            tag_key: "host".to_string(),
+        };
+        let json = serde_json::to_value(&item).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "kind": "tagValues", "database": "telegraf", "measurement": null, "tagKey": "host" })
+        );
+        assert_eq!(serde_json::from_value::<SchemaItem>(json).unwrap(), item);
+
+        let item = SchemaItem::tree_children("db1/cpu", "measurement", Some(&serde_json::json!({ "databaseName": "db1" })));
+        let json = serde_json::to_string(&item).unwrap();
+        assert!(json.contains("\"parentNodeId\":\"db1/cpu\""));
+        assert_eq!(item.database(), Some("db1"));
+        assert_eq!(serde_json::from_str::<SchemaItem>(&json).unwrap(), item);
+    }
+
+    #[tokio::test]
+    async fn test_flush_and_reload() {
+        let dir = tempdir().unwrap();
+        let cache = SchemaCacheService::with_storage_dir(Some(dir.path().to_path_buf()));
+        cache.store("conn/1", SchemaItem::Databases, CacheEntry::new(names(&["db1", "db2"]), None)).await;
+        cache.store("conn/1", measurements("db1"), CacheEntry::new(names(&["cpu"]), None)).await;
+        cache.flush().await;
+
+        assert!(cache.dirty.lock().await.is_empty());
+        assert!(dir.path().join("conn_1.json").exists());
+        assert!(!dir.path().join("conn_1.tmp").exists());
+
+        let reloaded = SchemaCacheService::with_storage_dir(Some(dir.path().to_path_buf()));
+        reloaded.ensure_loaded("conn/1").await;
+        assert_eq!(
+            cached_names(&reloaded, "conn/1", &SchemaItem::Databases).await,
+            Some(vec!["db1".to_string(), "db2".to_string()])
+        );
+        assert_eq!(cached_names(&reloaded, "conn/1", &measurements("db1")).await, Some(vec!["cpu".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_cache_file_is_ignored() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("conn.json"), "{ not json").unwrap();
+
+        let cache = SchemaCacheService::with_storage_dir(Some(dir.path().to_path_buf()));
+        cache.ensure_loaded("conn").await;
+        assert!(cache.entries.read().await.get("conn").is_some_and(|items| items.is_empty()));
+
+        // 损坏的文件在下次写回时被覆盖
+        cache.store("conn", SchemaItem::Databases, CacheEntry::new(names(&["db1"]), None)).await;
+        cache.flush().await;
+        let items = SchemaCacheService::read_cache_file(&dir.path().join("conn.json")).await.unwrap();
+        assert_eq!(items.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_invalidate() {
+        let dir = tempdir().unwrap();
+        let cache = SchemaCacheService::with_storage_dir(Some(dir.path().to_path_buf()));
+        cache.store("conn", SchemaItem::Databases, CacheEntry::new(names(&["db1", "db2"]), None)).await;
+        cache.store("conn", measurements("db1"), CacheEntry::new(names(&["cpu"]), None)).await;
+        cache.store("conn", measurements("db2"), CacheEntry::new(names(&["mem"]), None)).await;
+        cache.flush().await;
+
+        // 只清除该数据库和数据库列表
+        cache.invalidate("conn", Some("db1")).await;
+        assert!(cached_names(&cache, "conn", &measurements("db1")).await.is_none());
+        assert!(cached_names(&cache, "conn", &SchemaItem::Databases).await.is_none());
+        assert!(cached_names(&cache, "conn", &measurements("db2")).await.is_some());
+        assert!(cache.dirty.lock().await.contains("conn"));
+
+        cache.invalidate("conn", None).await;
+        assert!(!cache.entries.read().await.contains_key("conn"));
+        assert!(!cache.dirty.lock().await.contains("conn"));
+        assert!(!dir.path().join("conn.json").exists());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_reports_changes() {
+        let cache = SchemaCacheService::with_storage_dir(None);
+        let item = measurements("db1");
+        let old = CacheEntry {
+            loaded_at: 0,
+            ..CacheEntry::new(names(&["cpu", "mem"]), None)
+        };
+        assert!(old.is_expired(cache.ttl));
+        cache.store("conn", item.clone(), old.clone()).await;
+
+        let event = cache
+            .apply_refresh("conn", item.clone(), old, names(&["cpu", "disk"]))
+            .await
+            .unwrap();
+        assert_eq!(event.database.as_deref(), Some("db1"));
+        assert_eq!(event.added, vec!["disk"]);
+        assert_eq!(event.removed, vec!["mem"]);
+        assert_eq!(cached_names(&cache, "conn", &item).await, Some(vec!["cpu".to_string(), "disk".to_string()]));
+
+        // 没有变化时只更新加载时间
+        let current = cache.entries.read().await["conn"][&item].clone();
+        assert!(!current.is_expired(cache.ttl));
+        assert!(cache.apply_refresh("conn", item.clone(), current.clone(), names(&["cpu", "disk"])).await.is_none());
+
+        // 刷新期间被清除的条目不再写回
+        cache.invalidate("conn", Some("db1")).await;
+        assert!(cache.apply_refresh("conn", item.clone(), current, names(&["cpu"])).await.is_none());
+        assert!(cached_names(&cache, "conn", &item).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_search_index_follows_cache_writes() {
+        let cache = SchemaCacheService::with_storage_dir(None);
+        let field_keys = SchemaItem::FieldKeys {
+            database: "root.sg".to_string(),
+            measurement: Some("root.sg.d1".to_string()),
+        };
+        cache.store("conn", SchemaItem::Databases, CacheEntry::new(names(&["root.sg"]), None)).await;
+        cache
+            .store(
+                "conn",
+                field_keys.clone(),
+                CacheEntry::new(names(&["root.sg.d1.cpu_temp", "root.sg.d1.`a.b`", "root.sg.status"]), None),
+            )
+            .await;
+        cache
+            .store(
+                "conn",
+                SchemaItem::tree_children("root.sg", "storage_group", None),
+                CacheEntry::new(
+                    SchemaData::Nodes(vec![
+                        TreeNode::new("sg/devices".to_string(), "Devices".to_string(), TreeNodeType::TimeseriesManagement)
+                            .with_parent("root.sg".to_string()),
+                        TreeNode::new("sg/d1".to_string(), "d1".to_string(), TreeNodeType::Device)
+                            .with_parent("sg/devices".to_string()),
+                    ]),
+                    None,
+                ),
+            )
+            .await;
+
+        // IoTDB 时间序列按叶子名称索引，设备路径在 path 中
+        assert_eq!(
+            indexed(&cache, "conn").await,
+            vec![
+                (SchemaKind::Database, "conn".to_string(), "root.sg".to_string()),
+                (SchemaKind::Device, "conn/Devices".to_string(), "d1".to_string()),
+                (SchemaKind::Timeseries, "conn/root.sg".to_string(), "status".to_string()),
+                (SchemaKind::Timeseries, "conn/root.sg/root.sg.d1".to_string(), "a.b".to_string()),
+                (SchemaKind::Timeseries, "conn/root.sg/root.sg.d1".to_string(), "cpu_temp".to_string()),
+            ]
+        );
+
+        // 刷新后替换该条目的索引
+        let old = cache.entries.read().await["conn"][&field_keys].clone();
+        cache.apply_refresh("conn", field_keys.clone(), old, names(&["root.sg.d1.humidity"])).await;
+        let indexed_names: Vec<String> = indexed(&cache, "conn").await.into_iter().map(|(_, _, name)| name).collect();
+        assert_eq!(indexed_names, vec!["root.sg", "d1", "humidity"]);
+
+        cache.invalidate("conn", Some("root.sg")).await;
+        assert!(indexed(&cache, "conn").await.is_empty());
+
+        cache.store("conn", SchemaItem::Databases, CacheEntry::new(names(&["db1"]), None)).await;
+        cache.invalidate("conn", None).await;
+        assert!(cache.index.read().await.get("conn").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_search_dedupes_tree_and_name_entries() {
+        let cache = SchemaCacheService::with_storage_dir(None);
+        cache.store("conn", measurements("db1"), CacheEntry::new(names(&["cpu", "mem"]), None)).await;
+        for measurement in ["cpu", "mem"] {
+            let item = SchemaItem::Fields {
+                database: "db1".to_string(),
+                measurement: measurement.to_string(),
+            };
+            cache.store("conn", item, CacheEntry::new(names(&["usage"]), None)).await;
+        }
+        let metadata = serde_json::json!({ "database": "db1" });
+        cache
+            .store(
+                "conn",
+                SchemaItem::tree_children("db1", "database", Some(&metadata)),
+                CacheEntry::new(
+                    SchemaData::Nodes(vec![TreeNode::new("db1/cpu".to_string(), "cpu".to_string(), TreeNodeType::Measurement)
+                        .with_parent("db1".to_string())]),
+                    None,
+                ),
+            )
+            .await;
+
+        // 测量同时来自树节点和名称列表时只保留可定位的树节点，不同测量下的同名字段都保留
+        let matcher = Matcher::new("cpu", SearchMode::Prefix).unwrap();
+        let hits = cache.search("conn", "conn", &matcher).await;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].entry.node_id.as_deref(), Some("db1/cpu"));
+
+        let matcher = Matcher::new("usage", SearchMode::Prefix).unwrap();
+        assert_eq!(cache.search("conn", "conn", &matcher).await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_search_index_loaded_from_disk() {
+        let dir = tempdir().unwrap();
+        let cache = SchemaCacheService::with_storage_dir(Some(dir.path().to_path_buf()));
+        cache.store("conn", measurements("db1"), CacheEntry::new(names(&["cpu"]), None)).await;
+        cache.flush().await;
+
+        let reloaded = SchemaCacheService::with_storage_dir(Some(dir.path().to_path_buf()));
+        assert_eq!(
+            indexed(&reloaded, "conn").await,
+            vec![(SchemaKind::Measurement, "conn/db1".to_string(), "cpu".to_string())]
+        );
+    }
+}
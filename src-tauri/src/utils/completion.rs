@@ -0,0 +1,642 @@
+/**
+ * 查询自动补全
+ *
+ * 根据光标前的词法单元判断所处的语法位置（FROM 之后、WHERE 条件、Flux filter 的 fn 中、
+ * IoTDB 路径前缀等），结构信息由调用方按位置查询后交给这里排序和生成插入文本
+ */
+
+use crate::utils::query_formatter::dialect_keywords;
+use crate::utils::query_parser::lexer::{self, Token, TokenKind};
+use crate::utils::query_parser::{flux, QueryDialect};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+const MAX_COMPLETIONS: usize = 200;
+
+const INFLUXQL_FUNCTIONS: &[&str] = &[
+    "COUNT", "DISTINCT", "INTEGRAL", "MEAN", "MEDIAN", "MODE", "SPREAD", "STDDEV", "SUM", "BOTTOM", "FIRST",
+    "LAST", "MAX", "MIN", "PERCENTILE", "SAMPLE", "TOP", "ABS", "ACOS", "ASIN", "ATAN", "ATAN2", "CEIL", "COS",
+    "CUMULATIVE_SUM", "DERIVATIVE", "DIFFERENCE", "ELAPSED", "EXP", "FLOOR", "HISTOGRAM", "LN", "LOG", "LOG2",
+    "LOG10", "MOVING_AVERAGE", "NON_NEGATIVE_DERIVATIVE", "NON_NEGATIVE_DIFFERENCE", "POW", "ROUND", "SIN",
+    "SQRT", "TAN", "HOLT_WINTERS", "EXPONENTIAL_MOVING_AVERAGE", "DOUBLE_EXPONENTIAL_MOVING_AVERAGE",
+    "TRIPLE_EXPONENTIAL_MOVING_AVERAGE", "RELATIVE_STRENGTH_INDEX", "TIME", "NOW",
+];
+
+const SQL_FUNCTIONS: &[&str] = &[
+    "avg", "count", "sum", "min", "max", "median", "stddev", "variance", "approx_distinct",
+    "approx_percentile_cont", "array_agg", "first_value", "last_value", "selector_first", "selector_last",
+    "selector_min", "selector_max", "date_bin", "date_bin_gapfill", "date_trunc", "date_part", "interpolate",
+    "locf", "now", "to_timestamp", "tz", "abs", "ceil", "floor", "round", "sqrt", "power", "ln", "log10",
+    "coalesce", "nullif", "concat", "lower", "upper", "length", "substr", "trim", "regexp_match", "regexp_replace",
+];
+
+const IOTDB_FUNCTIONS: &[&str] = &[
+    "COUNT", "SUM", "AVG", "EXTREME", "MAX_VALUE", "MIN_VALUE", "FIRST_VALUE", "LAST_VALUE", "MAX_TIME",
+    "MIN_TIME", "STDDEV", "VARIANCE", "MODE", "COUNT_IF", "TIME_DURATION", "MAX_BY", "MIN_BY", "ABS", "CEIL",
+    "FLOOR", "ROUND", "SIN", "COS", "TAN", "SQRT", "EXP", "LN", "LOG10", "DIFF", "TOP_K", "BOTTOM_K", "CAST",
+    "REPLACE", "SUBSTRING", "UPPER", "LOWER", "TRIM", "STRING_CONTAINS", "DERIVATIVE", "NON_NEGATIVE_DERIVATIVE",
+    "DIFFERENCE", "CHANGE_POINTS",
+];
+
+const FLUX_FUNCTIONS: &[&str] = &[
+    "from", "range", "filter", "map", "keep", "drop", "rename", "pivot", "group", "aggregateWindow", "window",
+    "mean", "median", "sum", "count", "min", "max", "first", "last", "sort", "limit", "tail", "top", "bottom",
+    "unique", "distinct", "derivative", "difference", "cumulativeSum", "movingAverage", "timedMovingAverage",
+    "integral", "spread", "stddev", "quantile", "fill", "yield", "to", "union", "join", "set", "duplicate",
+    "toFloat", "toInt", "toString", "timeShift", "truncateTimeColumn", "increase", "elapsed", "columns", "keys",
+    "keyValues", "reduce",
+];
+
+const FLUX_KEYWORDS: &[&str] = &["import", "option", "return", "if", "then", "else", "and", "or", "not", "exists"];
+
+/// Flux 表中除标签外的固定列
+const FLUX_COLUMNS: &[&str] = &["_measurement", "_field", "_value", "_time", "_start", "_stop"];
+
+/// 光标所处的语法位置
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompletionContext {
+    /// 语句开头或表达式之后，提示关键字
+    Keyword,
+    /// Flux 管道之后，提示函数
+    Function,
+    Database,
+    Bucket,
+    Measurement,
+    /// SELECT 列表，同时提示函数
+    Field,
+    /// 条件、GROUP BY 或 Flux 的 r. 之后，同时提示字段
+    TagKey,
+    TagValue { tag_key: String },
+    /// IoTDB 以 root 开头的路径，候选为存储组、设备和时间序列的完整路径
+    DevicePath,
+    /// 别名、数值等无需补全的位置
+    None,
+}
+
+/// 补全位置分析结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompletionAnalysis {
+    pub dialect: QueryDialect,
+    pub context: CompletionContext,
+    /// 光标前正在输入的部分
+    pub prefix: String,
+    /// 被替换的范围（字符偏移）
+    pub replace_start: usize,
+    pub replace_end: usize,
+    /// 光标是否位于引号内
+    pub quoted: bool,
+    /// 查询中指定的数据库（InfluxQL 的 db.rp.measurement 或 Flux 的 bucket）
+    pub database: Option<String>,
+    /// FROM 子句中的测量/设备，或 Flux 中 _measurement 的过滤值
+    pub measurement: Option<String>,
+}
+
+/// 补全候选类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CompletionKind {
+    Keyword,
+    Function,
+    Database,
+    Bucket,
+    Measurement,
+    Field,
+    TagKey,
+    TagValue,
+    Device,
+    Timeseries,
+}
+
+/// 补全候选
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionItem {
+    pub label: String,
+    pub kind: CompletionKind,
+    /// 插入编辑器的文本，必要时带引号
+    pub insert_text: String,
+}
+
+/// 补全结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionResult {
+    pub items: Vec<CompletionItem>,
+    pub replace_start: usize,
+    pub replace_end: usize,
+}
+
+/// 光标前正在输入的单词
+struct Word {
+    prefix: String,
+    start: usize,
+    quoted: bool,
+}
+
+/// 分析光标（字符偏移）所处的补全位置
+pub fn analyze(query: &str, cursor: usize, dialect: QueryDialect) -> CompletionAnalysis {
+    let cursor_byte = query.char_indices().nth(cursor).map_or(query.len(), |(i, _)| i);
+    let (context, word, database, measurement) = match dialect {
+        QueryDialect::Flux => analyze_flux(query, cursor_byte),
+        _ => analyze_sql(query, cursor_byte, dialect),
+    };
+    CompletionAnalysis {
+        dialect,
+        context,
+        prefix: word.prefix,
+        replace_start: query[..word.start].chars().count(),
+        replace_end: query[..cursor_byte].chars().count(),
+        quoted: word.quoted,
+        database,
+        measurement,
+    }
+}
+
+/// 取出光标前正在输入的单词，返回剩余的上下文词法单元
+///
+/// Flux 字符串的 text 含开头的引号，`quote_in_text` 为 true 时去掉
+fn current_word<'a>(tokens: &'a [Token], unclosed: &[usize], end: usize, quote_in_text: bool) -> (Word, &'a [Token]) {
+    if let Some((last, rest)) = tokens.split_last().filter(|(last, _)| last.span.end == end) {
+        if matches!(last.kind, TokenKind::String | TokenKind::QuotedIdent) && unclosed.contains(&last.span.start) {
+            let prefix = if quote_in_text { last.text.chars().skip(1).collect() } else { last.text.clone() };
+            return (Word { prefix, start: last.span.start + 1, quoted: true }, rest);
+        }
+        if last.kind == TokenKind::Ident {
+            return (Word { prefix: last.text.clone(), start: last.span.start, quoted: false }, rest);
+        }
+    }
+    (Word { prefix: String::new(), start: end, quoted: false }, tokens)
+}
+
+fn is_keyword(token: &Token, dialect: QueryDialect) -> bool {
+    token.kind == TokenKind::Ident && dialect_keywords(dialect).contains(&token.text.to_uppercase().as_str())
+}
+
+/// 前一个词法单元是否是完整的操作数，此时接下来应输入关键字或运算符
+fn is_operand(tokens: &[Token], dialect: QueryDialect) -> bool {
+    let Some(last) = tokens.last() else {
+        return false;
+    };
+    match last.kind {
+        TokenKind::Ident => !is_keyword(last, dialect),
+        TokenKind::QuotedIdent
+        | TokenKind::String
+        | TokenKind::Number
+        | TokenKind::Duration
+        | TokenKind::Time
+        | TokenKind::Regex
+        | TokenKind::Param => true,
+        TokenKind::Punct => last.text == ")",
+        // SELECT * 中的星号
+        TokenKind::Operator => {
+            last.text == "*" && tokens.len() >= 2 && (tokens[tokens.len() - 2].is_keyword("SELECT") || tokens[tokens.len() - 2].is_punct(","))
+        }
+        TokenKind::Comment => false,
+    }
+}
+
+/// 向前查找最近的子句关键字，确定所处的子句
+fn clause_context(tokens: &[Token], dialect: QueryDialect) -> CompletionContext {
+    let mut depth = 0usize;
+    for i in (0..tokens.len()).rev() {
+        let token = &tokens[i];
+        if token.is_punct(")") {
+            depth += 1;
+            continue;
+        }
+        if token.is_punct("(") {
+            if depth > 0 {
+                depth -= 1;
+                continue;
+            }
+            // 子查询或 IN 列表的开头；其余为函数参数或分组括号，继续向前查找
+            let opens_subquery = i == 0
+                || ["FROM", "JOIN", "IN", "EXISTS", "AS"].iter().any(|k| tokens[i - 1].is_keyword(k));
+            if opens_subquery {
+                return if i > 0 && tokens[i - 1].is_keyword("IN") { CompletionContext::None } else { CompletionContext::Keyword };
+            }
+            continue;
+        }
+        if depth > 0 || token.kind != TokenKind::Ident {
+            continue;
+        }
+        let previous = i.checked_sub(1).map(|p| &tokens[p]);
+        let context = match token.text.to_uppercase().as_str() {
+            "SELECT" => CompletionContext::Field,
+            "FROM" | "JOIN" | "INTO" | "MEASUREMENT" | "TABLE" => CompletionContext::Measurement,
+            "WHERE" | "AND" | "OR" | "NOT" | "HAVING" | "KEY" => CompletionContext::TagKey,
+            "ON" if dialect == QueryDialect::InfluxQL => CompletionContext::Database,
+            "ON" => CompletionContext::TagKey,
+            "USE" | "DATABASE" => CompletionContext::Database,
+            "BY" if previous.is_some_and(|p| p.is_keyword("GROUP")) => CompletionContext::TagKey,
+            "BY" if previous.is_some_and(|p| p.is_keyword("ORDER")) => CompletionContext::Field,
+            "LIMIT" | "OFFSET" | "SLIMIT" | "SOFFSET" | "AS" | "FILL" | "TZ" | "ALIGN" => CompletionContext::None,
+            _ => continue,
+        };
+        return context;
+    }
+    CompletionContext::Keyword
+}
+
+/// 读取以点号分隔的名称，`db..cpu` 中省略的部分记为空字符串
+fn name_chain(tokens: &[Token]) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut expect_name = true;
+    for token in tokens {
+        if token.is_punct(".") {
+            if expect_name {
+                segments.push(String::new());
+            }
+            expect_name = true;
+        } else if expect_name && (matches!(token.kind, TokenKind::Ident | TokenKind::QuotedIdent | TokenKind::Number) || token.is_operator("*") || token.is_operator("**")) {
+            segments.push(token.text.clone());
+            expect_name = false;
+        } else {
+            break;
+        }
+    }
+    segments
+}
+
+/// 语句 FROM 子句中的数据库和测量
+fn from_target(tokens: &[Token], dialect: QueryDialect) -> (Option<String>, Option<String>) {
+    for (i, token) in tokens.iter().enumerate() {
+        if !token.is_keyword("FROM") {
+            continue;
+        }
+        let segments = name_chain(&tokens[i + 1..]);
+        let Some(last) = segments.last().filter(|s| !s.is_empty()) else {
+            continue;
+        };
+        return match dialect {
+            QueryDialect::IoTDB => {
+                let path = segments.join(".");
+                (None, (!path.contains('*')).then_some(path))
+            }
+            // db.rp.measurement 或 db..measurement
+            QueryDialect::InfluxQL if segments.len() >= 3 => {
+                (Some(segments[0].clone()).filter(|s| !s.is_empty()), Some(last.clone()))
+            }
+            _ => (None, Some(last.clone())),
+        };
+    }
+    (None, None)
+}
+
+fn analyze_sql(query: &str, cursor: usize, dialect: QueryDialect) -> (CompletionContext, Word, Option<String>, Option<String>) {
+    let before = &query[..cursor];
+    let (tokens, errors) = lexer::tokenize(before, dialect);
+    let unclosed: Vec<usize> = errors.iter().map(|e| e.offset).collect();
+
+    // 只分析光标所在的语句
+    let statement_start = tokens.iter().rposition(|t| t.is_punct(";")).map_or(0, |i| i + 1);
+    let tokens = &tokens[statement_start..];
+
+    // IoTDB 路径：从光标向前连续的 root.a.b. 形式
+    if dialect == QueryDialect::IoTDB {
+        let mut start = tokens.len();
+        let mut end = before.len();
+        while start > 0 {
+            let token = &tokens[start - 1];
+            let path_like = matches!(token.kind, TokenKind::Ident | TokenKind::Number | TokenKind::QuotedIdent)
+                || token.is_punct(".")
+                || token.is_operator("*")
+                || token.is_operator("**");
+            if !path_like || token.span.end != end {
+                break;
+            }
+            end = token.span.start;
+            start -= 1;
+        }
+        if tokens.get(start).is_some_and(|t| t.is_keyword("root")) {
+            let word = Word { prefix: before[end..].to_string(), start: end, quoted: false };
+            return (CompletionContext::DevicePath, word, None, None);
+        }
+    }
+
+    let (word, context_tokens) = current_word(tokens, &unclosed, before.len(), false);
+    let mut context = clause_context(context_tokens, dialect);
+    let last = context_tokens.last();
+    let comparison = last.is_some_and(|t| {
+        t.kind == TokenKind::Operator && ["=", "!=", "<>", "=~", "!~", "<", "<=", ">", ">="].contains(&t.text.as_str())
+    });
+
+    context = match context {
+        CompletionContext::TagKey if comparison => {
+            let key = context_tokens.len().checked_sub(2).map(|i| &context_tokens[i]);
+            match key {
+                Some(key) if key.is_keyword("KEY") => CompletionContext::TagKey,
+                Some(key) if key.is_keyword("time") => CompletionContext::None,
+                // IoTDB 没有标签，比较值只能是字面量
+                Some(_) if dialect == QueryDialect::IoTDB => CompletionContext::None,
+                Some(key) if matches!(key.kind, TokenKind::Ident | TokenKind::QuotedIdent) => {
+                    CompletionContext::TagValue { tag_key: key.text.clone() }
+                }
+                _ => CompletionContext::None,
+            }
+        }
+        CompletionContext::TagKey | CompletionContext::Field | CompletionContext::Measurement | CompletionContext::Database
+            if !word.quoted && is_operand(context_tokens, dialect) =>
+        {
+            CompletionContext::Keyword
+        }
+        // 引号内的值只能是标识符，不提示关键字
+        CompletionContext::Keyword if word.quoted => CompletionContext::None,
+        other => other,
+    };
+
+    // FROM 子句可能在光标之后，需要分析整条语句
+    let (all_tokens, _) = lexer::tokenize(query, dialect);
+    let statement: Vec<Token> = all_tokens
+        .split(|t| t.is_punct(";"))
+        .find(|segment| segment.last().is_none_or(|t| t.span.end >= cursor))
+        .map(|segment| segment.to_vec())
+        .unwrap_or_default();
+    let (database, measurement) = from_target(&statement, dialect);
+
+    // InfluxQL 的 FROM db.rp. 之后补全测量，数据库取自前缀
+    let database = database.or_else(|| {
+        let chain_start = context_tokens.iter().rposition(|t| t.is_keyword("FROM"))?;
+        let segments = name_chain(&context_tokens[chain_start + 1..]);
+        (dialect == QueryDialect::InfluxQL && context == CompletionContext::Measurement && segments.len() >= 2)
+            .then(|| segments[0].clone())
+            .filter(|s| !s.is_empty())
+    });
+
+    (context, word, database, measurement)
+}
+
+/// 与列比较的 Flux 表达式：r.key == 或 r["key"] ==
+fn flux_compared_column(tokens: &[Token]) -> Option<String> {
+    let (op, rest) = tokens.split_last()?;
+    if !["==", "!=", "=~", "!~"].contains(&op.text.as_str()) || op.kind != TokenKind::Operator {
+        return None;
+    }
+    match rest {
+        [.., param, dot, key] if param.kind == TokenKind::Ident && dot.is_punct(".") && key.kind == TokenKind::Ident => {
+            Some(key.text.clone())
+        }
+        [.., param, open, key, close]
+            if param.kind == TokenKind::Ident && open.is_punct("[") && key.kind == TokenKind::String && close.is_punct("]") =>
+        {
+            Some(key.text.trim_matches('"').to_string())
+        }
+        _ => None,
+    }
+}
+
+fn analyze_flux(query: &str, cursor: usize) -> (CompletionContext, Word, Option<String>, Option<String>) {
+    let before = &query[..cursor];
+    let (tokens, errors) = flux::tokenize(before);
+    let unclosed: Vec<usize> = errors.iter().map(|e| e.offset).collect();
+    let (word, context_tokens) = current_word(&tokens, &unclosed, before.len(), true);
+
+    // fn: (r) => 中的参数名
+    let params: HashSet<&str> = context_tokens
+        .windows(4)
+        .filter(|w| w[0].is_punct("(") && w[1].kind == TokenKind::Ident && w[2].is_punct(")") && w[3].is_operator("=>"))
+        .map(|w| w[1].text.as_str())
+        .collect();
+
+    let context = if word.quoted {
+        match context_tokens {
+            [.., name, colon] if name.text == "bucket" && colon.is_punct(":") => CompletionContext::Bucket,
+            [.., param, open] if params.contains(param.text.as_str()) && open.is_punct("[") => CompletionContext::TagKey,
+            _ => match flux_compared_column(context_tokens).as_deref() {
+                Some("_measurement") => CompletionContext::Measurement,
+                Some("_field") => CompletionContext::Field,
+                Some(key) => CompletionContext::TagValue { tag_key: key.to_string() },
+                None => CompletionContext::None,
+            },
+        }
+    } else {
+        match context_tokens {
+            [] => CompletionContext::Keyword,
+            [.., param, dot] if dot.is_punct(".") => {
+                if params.contains(param.text.as_str()) { CompletionContext::TagKey } else { CompletionContext::None }
+            }
+            [.., last] if last.is_operator("|>") => CompletionContext::Function,
+            [.., last] if last.is_punct(")") || last.is_punct("}") => CompletionContext::Keyword,
+            [.., last] if last.kind == TokenKind::Operator || last.is_punct(":") => CompletionContext::Function,
+            _ => CompletionContext::None,
+        }
+    };
+
+    // bucket 和 _measurement 可能出现在光标之后
+    let (all_tokens, _) = flux::tokenize(query);
+    let unquote = |t: &Token| t.text.trim_matches('"').to_string();
+    let database = all_tokens.windows(3).find_map(|w| match w {
+        [key, colon, value] if key.text == "bucket" && colon.is_punct(":") && value.kind == TokenKind::String => Some(unquote(value)),
+        _ => None,
+    });
+    let measurement = (0..all_tokens.len()).find_map(|i| match &all_tokens[i..] {
+        [key, op, value, ..] if key.text == "_measurement" && op.is_operator("==") && value.kind == TokenKind::String => {
+            Some(unquote(value))
+        }
+        [key, close, op, value, ..]
+            if key.text == "\"_measurement\"" && close.is_punct("]") && op.is_operator("==") && value.kind == TokenKind::String =>
+        {
+            Some(unquote(value))
+        }
+        _ => None,
+    });
+
+    (context, word, database, measurement)
+}
+
+/// 当前位置不依赖结构信息的候选：关键字、函数和 Flux 固定列
+pub fn static_candidates(analysis: &CompletionAnalysis) -> Vec<(String, CompletionKind)> {
+    let functions = match analysis.dialect {
+        QueryDialect::InfluxQL => INFLUXQL_FUNCTIONS,
+        QueryDialect::Sql => SQL_FUNCTIONS,
+        QueryDialect::IoTDB => IOTDB_FUNCTIONS,
+        QueryDialect::Flux => FLUX_FUNCTIONS,
+    };
+    let keywords = match analysis.dialect {
+        QueryDialect::Flux => FLUX_KEYWORDS,
+        dialect => dialect_keywords(dialect),
+    };
+    let list = |names: &[&str], kind: CompletionKind| names.iter().map(|n| (n.to_string(), kind)).collect::<Vec<_>>();
+
+    match &analysis.context {
+        CompletionContext::Keyword => {
+            let mut candidates = list(keywords, CompletionKind::Keyword);
+            if analysis.dialect == QueryDialect::Flux {
+                candidates.extend(list(functions, CompletionKind::Function));
+            }
+            candidates
+        }
+        CompletionContext::Function => list(functions, CompletionKind::Function),
+        CompletionContext::Field if analysis.dialect != QueryDialect::Flux => list(functions, CompletionKind::Function),
+        CompletionContext::TagKey if analysis.dialect == QueryDialect::Flux => list(FLUX_COLUMNS, CompletionKind::Field),
+        _ => Vec::new(),
+    }
+}
+
+/// 标识符是否可以不加引号直接使用
+fn is_plain_identifier(name: &str, dialect: QueryDialect) -> bool {
+    let mut chars = name.chars();
+    let valid_start = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+    let valid_rest = chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    // InfluxDB 3.x SQL 会把未加引号的标识符转为小写
+    let case_ok = dialect != QueryDialect::Sql || !name.chars().any(|c| c.is_ascii_uppercase());
+    valid_start && valid_rest && case_ok && !dialect_keywords(dialect).contains(&name.to_uppercase().as_str())
+}
+
+fn insert_text(analysis: &CompletionAnalysis, label: &str, kind: CompletionKind) -> String {
+    if analysis.quoted {
+        return label.to_string();
+    }
+    match (analysis.dialect, kind) {
+        (QueryDialect::Flux, CompletionKind::Bucket | CompletionKind::Measurement | CompletionKind::TagValue) => {
+            format!("\"{}\"", label.replace('"', "\\\""))
+        }
+        (QueryDialect::Flux, _) => label.to_string(),
+        (_, CompletionKind::TagValue) => format!("'{}'", label.replace('\'', "\\'")),
+        (QueryDialect::IoTDB, _) => label.to_string(),
+        (dialect, CompletionKind::Database | CompletionKind::Measurement | CompletionKind::Field | CompletionKind::TagKey)
+            if !is_plain_identifier(label, dialect) =>
+        {
+            format!("\"{}\"", label.replace('"', "\\\""))
+        }
+        _ => label.to_string(),
+    }
+}
+
+/// 按输入前缀过滤并排序候选：前缀匹配优先，其次是包含匹配，同级保持传入顺序
+pub fn complete(analysis: &CompletionAnalysis, candidates: Vec<(String, CompletionKind)>) -> CompletionResult {
+    let prefix = analysis.prefix.to_lowercase();
+    let mut seen = HashSet::new();
+    let mut scored: Vec<(u8, String, CompletionKind)> = candidates
+        .into_iter()
+        .filter(|(label, kind)| seen.insert((label.clone(), *kind)))
+        .filter_map(|(label, kind)| {
+            let lower = label.to_lowercase();
+            let score = if lower.starts_with(&prefix) {
+                0
+            } else if lower.contains(&prefix) {
+                1
+            } else {
+                return None;
+            };
+            Some((score, label, kind))
+        })
+        .collect();
+    scored.sort_by_key(|(score, _, _)| *score);
+
+    let items = scored
+        .into_iter()
+        .take(MAX_COMPLETIONS)
+        .map(|(_, label, kind)| CompletionItem { insert_text: insert_text(analysis, &label, kind), label, kind })
+        .collect();
+    CompletionResult { items, replace_start: analysis.replace_start, replace_end: analysis.replace_end }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 以不属于 |> 的 | 标记光标位置
+    fn at(query: &str, dialect: QueryDialect) -> CompletionAnalysis {
+        let chars: Vec<char> = query.chars().collect();
+        let cursor = (0..chars.len()).find(|&i| chars[i] == '|' && chars.get(i + 1) != Some(&'>')).unwrap();
+        let text: String = chars.iter().enumerate().filter(|(i, _)| *i != cursor).map(|(_, c)| c).collect();
+        analyze(&text, cursor, dialect)
+    }
+
+    #[test]
+    fn test_influxql_contexts() {
+        let analysis = at("SELECT * FROM cp|", QueryDialect::InfluxQL);
+        assert_eq!((analysis.context, analysis.prefix.as_str(), analysis.replace_start), (CompletionContext::Measurement, "cp", 14));
+
+        let analysis = at("SELECT us| FROM cpu WHERE time > now() - 1h", QueryDialect::InfluxQL);
+        assert_eq!(analysis.context, CompletionContext::Field);
+        assert_eq!(analysis.measurement.as_deref(), Some("cpu"));
+
+        let analysis = at("SELECT mean(|) FROM \"telegraf\".\"autogen\".\"cpu\"", QueryDialect::InfluxQL);
+        assert_eq!(analysis.context, CompletionContext::Field);
+        assert_eq!((analysis.database.as_deref(), analysis.measurement.as_deref()), (Some("telegraf"), Some("cpu")));
+
+        let analysis = at("SELECT * FROM cpu WHERE ho|", QueryDialect::InfluxQL);
+        assert_eq!(analysis.context, CompletionContext::TagKey);
+
+        let analysis = at("SELECT * FROM cpu WHERE host = 'ser|", QueryDialect::InfluxQL);
+        assert_eq!(analysis.context, CompletionContext::TagValue { tag_key: "host".to_string() });
+        assert!(analysis.quoted);
+        assert_eq!(analysis.prefix, "ser");
+
+        let analysis = at("SELECT * FROM cpu WHERE host = 'a' |", QueryDialect::InfluxQL);
+        assert_eq!(analysis.context, CompletionContext::Keyword);
+
+        let analysis = at("SELECT * FROM cpu GROUP BY |", QueryDialect::InfluxQL);
+        assert_eq!(analysis.context, CompletionContext::TagKey);
+
+        let analysis = at("SHOW MEASUREMENTS ON |", QueryDialect::InfluxQL);
+        assert_eq!(analysis.context, CompletionContext::Database);
+
+        let analysis = at("SELECT * FROM telegraf.autogen.|", QueryDialect::InfluxQL);
+        assert_eq!((analysis.context, analysis.database.as_deref()), (CompletionContext::Measurement, Some("telegraf")));
+
+        let analysis = at("SELECT 1; SEL|", QueryDialect::InfluxQL);
+        assert_eq!((analysis.context, analysis.replace_start), (CompletionContext::Keyword, 10));
+    }
+
+    #[test]
+    fn test_iotdb_paths() {
+        let analysis = at("SELECT * FROM root.sg.d|", QueryDialect::IoTDB);
+        assert_eq!((analysis.context, analysis.prefix.as_str()), (CompletionContext::DevicePath, "root.sg.d"));
+        assert_eq!(analysis.replace_start, 14);
+
+        let analysis = at("SELECT s| FROM root.sg.d1", QueryDialect::IoTDB);
+        assert_eq!(analysis.context, CompletionContext::Field);
+        assert_eq!(analysis.measurement.as_deref(), Some("root.sg.d1"));
+
+        let analysis = at("SELECT s1 FROM root.sg.d1 WHERE s2 = '|", QueryDialect::IoTDB);
+        assert_eq!(analysis.context, CompletionContext::None);
+    }
+
+    #[test]
+    fn test_flux_contexts() {
+        let script = "from(bucket: \"tele|\")";
+        let analysis = at(script, QueryDialect::Flux);
+        assert_eq!((analysis.context, analysis.prefix.as_str()), (CompletionContext::Bucket, "tele"));
+
+        let script = "from(bucket: \"b\")\n  |> range(start: -1h)\n  |> filter(fn: (r) => r._measurement == \"cpu\" and r.host == \"|";
+        let analysis = at(script, QueryDialect::Flux);
+        assert_eq!(analysis.context, CompletionContext::TagValue { tag_key: "host".to_string() });
+        assert_eq!((analysis.database.as_deref(), analysis.measurement.as_deref()), (Some("b"), Some("cpu")));
+
+        let analysis = at("from(bucket: \"b\") |> filter(fn: (r) => r.|)", QueryDialect::Flux);
+        assert_eq!(analysis.context, CompletionContext::TagKey);
+
+        let analysis = at("from(bucket: \"b\") |> filter(fn: (r) => r[\"_field\"] == \"us|\")", QueryDialect::Flux);
+        assert_eq!(analysis.context, CompletionContext::Field);
+
+        let analysis = at("from(bucket: \"b\") |> agg|", QueryDialect::Flux);
+        assert_eq!((analysis.context, analysis.prefix.as_str()), (CompletionContext::Function, "agg"));
+    }
+
+    #[test]
+    fn test_complete_ranking_and_quoting() {
+        let analysis = at("SELECT * FROM |", QueryDialect::InfluxQL);
+        let result = complete(
+            &analysis,
+            vec![("cpu".to_string(), CompletionKind::Measurement), ("disk io".to_string(), CompletionKind::Measurement)],
+        );
+        let inserts: Vec<_> = result.items.iter().map(|i| i.insert_text.as_str()).collect();
+        assert_eq!(inserts, vec!["cpu", "\"disk io\""]);
+
+        let analysis = at("SELECT * FROM cpu WHERE host = |", QueryDialect::InfluxQL);
+        let result = complete(&analysis, vec![("it's".to_string(), CompletionKind::TagValue)]);
+        assert_eq!(result.items[0].insert_text, "'it\\'s'");
+
+        let analysis = at("SELECT * FROM m|", QueryDialect::InfluxQL);
+        let candidates = ["disk_mem", "mem", "cpu"].iter().map(|n| (n.to_string(), CompletionKind::Measurement)).collect();
+        let labels: Vec<_> = complete(&analysis, candidates).items.into_iter().map(|i| i.label).collect();
+        assert_eq!(labels, vec!["mem", "disk_mem"]);
+
+        let analysis = at("SELECT * FROM \"Cpu\" WHERE \"Ho|", QueryDialect::Sql);
+        let result = complete(&analysis, vec![("Host".to_string(), CompletionKind::TagKey)]);
+        assert_eq!(result.items[0].insert_text, "Host");
+    }
+}
@@ -10,10 +10,10 @@ use log::{debug, info};
 use reqwest::Client;
 use std::time::Duration;
 
-/// 为连接配置创建HTTP客户端
+/// 为连接配置创建HTTP客户端，只有显式开启时才跳过证书校验
 pub fn build_http_client(config: &ConnectionConfig) -> Result<Client> {
     let timeout = Duration::from_secs(config.timeout as u64);
-    build_http_client_with_timeout(config.proxy_config.as_ref(), timeout, config.ssl)
+    build_client(config.proxy_config.as_ref(), timeout, config.skip_ssl_verification)
 }
 
 /// 使用指定的超时时间创建HTTP客户端
@@ -22,9 +22,13 @@ pub fn build_http_client_with_timeout(
     timeout: Duration,
     ssl: bool,
 ) -> Result<Client> {
+    build_client(proxy_config, timeout, !ssl) // 开发环境可能使用自签名证书
+}
+
+fn build_client(proxy_config: Option<&ProxyConfig>, timeout: Duration, accept_invalid_certs: bool) -> Result<Client> {
     let mut builder = Client::builder()
         .timeout(timeout)
-        .danger_accept_invalid_certs(!ssl); // 开发环境可能使用自签名证书
+        .danger_accept_invalid_certs(accept_invalid_certs);
 
     // 如果有代理配置，添加代理设置
     if let Some(proxy_config) = proxy_config {
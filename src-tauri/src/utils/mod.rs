@@ -4,5 +4,10 @@ pub mod validation;
 pub mod logger;
 pub mod persistence;
 pub mod http_client;
+pub mod query_params;
+pub mod query_parser;
+pub mod query_formatter;
+pub mod completion;
+pub mod schema_search;
 
 // Remove wildcard imports to reduce warnings
@@ -0,0 +1,610 @@
+/**
+ * 查询格式化
+ *
+ * 基于查询解析器的词法分析按方言美化查询：关键字大小写、每个子句一行、子查询与 Flux 管道缩进、
+ * WHERE/HAVING 条件对齐，注释原样保留。格式化结果再次格式化保持不变
+ */
+
+use crate::commands::settings::EditorSettings;
+use crate::utils::query_parser::lexer::{tokenize_with_comments, Token, TokenKind};
+use crate::utils::query_parser::{detect_dialect, flux, QueryDialect};
+
+/// 关键字大小写
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeywordCase {
+    Upper,
+    Lower,
+    Preserve,
+}
+
+impl KeywordCase {
+    pub fn from_setting(value: &str) -> Self {
+        match value.to_lowercase().as_str() {
+            "lower" => KeywordCase::Lower,
+            "preserve" => KeywordCase::Preserve,
+            _ => KeywordCase::Upper,
+        }
+    }
+
+    fn apply(self, word: &str) -> String {
+        match self {
+            KeywordCase::Upper => word.to_uppercase(),
+            KeywordCase::Lower => word.to_lowercase(),
+            KeywordCase::Preserve => word.to_string(),
+        }
+    }
+}
+
+/// 格式化选项
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    pub indent: String,
+    pub keyword_case: KeywordCase,
+    pub align_conditions: bool, // WHERE/HAVING 中的 AND/OR 与第一个条件对齐
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            indent: "  ".to_string(),
+            keyword_case: KeywordCase::Upper,
+            align_conditions: true,
+        }
+    }
+}
+
+impl FormatOptions {
+    pub fn from_editor_settings(settings: &EditorSettings) -> Self {
+        Self {
+            indent: " ".repeat(settings.tab_size.clamp(1, 8) as usize),
+            keyword_case: KeywordCase::from_setting(&settings.keyword_case),
+            align_conditions: settings.align_where_conditions,
+        }
+    }
+}
+
+// InfluxQL 关键字作为标识符时必须加双引号，因此未加引号时转换大小写不会改变语义
+const INFLUXQL_KEYWORDS: &[&str] = &[
+    "ALL", "ALTER", "ANALYZE", "AND", "ANY", "AS", "ASC", "BEGIN", "BETWEEN", "BY", "CARDINALITY", "CREATE",
+    "CONTINUOUS", "DATABASE", "DATABASES", "DEFAULT", "DELETE", "DESC", "DESTINATIONS", "DIAGNOSTICS", "DISTINCT",
+    "DROP", "DURATION", "END", "EVERY", "EXACT", "EXPLAIN", "FALSE", "FIELD", "FOR", "FROM", "GRANT", "GRANTS",
+    "GROUP", "GROUPS", "IN", "INF", "INSERT", "INTO", "KEY", "KEYS", "KILL", "LIMIT", "MEASUREMENT", "MEASUREMENTS",
+    "NAME", "OFFSET", "ON", "OR", "ORDER", "PASSWORD", "POLICY", "POLICIES", "PRIVILEGES", "QUERIES", "QUERY",
+    "READ", "REPLICATION", "RESAMPLE", "RETENTION", "REVOKE", "SELECT", "SERIES", "SET", "SHARD", "SHARDS", "SHOW",
+    "SLIMIT", "SOFFSET", "STATS", "SUBSCRIPTION", "SUBSCRIPTIONS", "TAG", "TO", "TRUE", "USER", "USERS", "VALUES",
+    "WHERE", "WITH", "WRITE",
+];
+
+// SQL（InfluxDB 3.x）中未加引号的标识符不区分大小写
+const SQL_KEYWORDS: &[&str] = &[
+    "SELECT", "FROM", "WHERE", "GROUP", "BY", "HAVING", "ORDER", "LIMIT", "OFFSET", "AS", "AND", "OR", "NOT", "IN",
+    "IS", "NULL", "TRUE", "FALSE", "LIKE", "ILIKE", "BETWEEN", "CASE", "WHEN", "THEN", "ELSE", "END", "JOIN",
+    "INNER", "LEFT", "RIGHT", "FULL", "OUTER", "CROSS", "NATURAL", "ON", "USING", "UNION", "ALL", "EXCEPT",
+    "INTERSECT", "DISTINCT", "WITH", "RECURSIVE", "ASC", "DESC", "NULLS", "FIRST", "LAST", "INTERVAL", "CAST",
+    "TRY_CAST", "EXISTS", "OVER", "PARTITION", "ROWS", "PRECEDING", "FOLLOWING", "UNBOUNDED", "CURRENT", "ROW",
+    "EXPLAIN", "ANALYZE", "VERBOSE", "SHOW", "TABLES", "COLUMNS", "CREATE", "DROP", "TABLE", "VIEW", "INSERT",
+    "INTO", "VALUES", "DELETE", "UPDATE", "SET", "DESCRIBE", "FILTER",
+];
+
+// IoTDB 关键字不区分大小写，路径节点（带点号的部分）不做转换
+const IOTDB_KEYWORDS: &[&str] = &[
+    "SELECT", "FROM", "WHERE", "GROUP", "BY", "HAVING", "ORDER", "LIMIT", "OFFSET", "SLIMIT", "SOFFSET", "AS",
+    "AND", "OR", "NOT", "IN", "IS", "NULL", "TRUE", "FALSE", "LIKE", "REGEXP", "BETWEEN", "FILL", "ALIGN", "DEVICE",
+    "DISABLE", "LEVEL", "INTO", "ASC", "DESC", "SHOW", "CREATE", "DROP", "DELETE", "INSERT", "VALUES", "TIMESERIES",
+    "DEVICES", "DATABASE", "DATABASES", "STORAGE", "EXPLAIN", "ANALYZE", "SET", "TTL", "FLUSH", "WITH", "TAGS",
+    "ATTRIBUTES", "CHILD", "PATHS", "NODES",
+];
+
+// 紧跟左括号时视为函数调用、左括号前不加空格的关键字
+const CALL_KEYWORDS: &[&str] = &["FILL", "TZ", "CAST", "TRY_CAST", "LEFT", "RIGHT"];
+
+const JOIN_WORDS: &[&str] = &["NATURAL", "INNER", "LEFT", "RIGHT", "FULL", "CROSS", "OUTER", "JOIN"];
+
+/// 方言的关键字列表（Flux 没有对应的 SQL 关键字）
+pub fn dialect_keywords(dialect: QueryDialect) -> &'static [&'static str] {
+    match dialect {
+        QueryDialect::InfluxQL => INFLUXQL_KEYWORDS,
+        QueryDialect::IoTDB => IOTDB_KEYWORDS,
+        _ => SQL_KEYWORDS,
+    }
+}
+
+/// 按方言格式化查询；未指定方言时自动识别
+pub fn format_query(query: &str, dialect: Option<QueryDialect>, options: &FormatOptions) -> String {
+    match dialect.unwrap_or_else(|| detect_dialect(query)) {
+        QueryDialect::Flux => format_flux(query, options),
+        dialect => format_sql(query, dialect, options),
+    }
+}
+
+fn format_sql(query: &str, dialect: QueryDialect, options: &FormatOptions) -> String {
+    let (tokens, _) = tokenize_with_comments(query, dialect);
+
+    let mut statements = Vec::new();
+    for (segment, terminated) in split_statements(&tokens) {
+        let (Some(first), Some(last)) = (segment.first(), segment.last()) else {
+            continue;
+        };
+        let is_insert = segment
+            .iter()
+            .find(|t| t.kind != TokenKind::Comment)
+            .is_some_and(|t| t.is_keyword("INSERT"));
+
+        // InfluxQL 的 INSERT 内容是行协议，保持原样
+        let mut text = if dialect == QueryDialect::InfluxQL && is_insert {
+            query[first.span.start..last.span.end].to_string()
+        } else {
+            SqlFormatter::new(query, dialect, options).format(segment)
+        };
+        if terminated {
+            text.push(';');
+        }
+        statements.push(text);
+    }
+    statements.join("\n")
+}
+
+/// 按分号拆分语句，返回每条语句的词法单元及是否以分号结尾
+fn split_statements(tokens: &[Token]) -> Vec<(&[Token], bool)> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        if token.is_punct(";") {
+            segments.push((&tokens[start..i], true));
+            start = i + 1;
+        }
+    }
+    segments.push((&tokens[start..], false));
+    segments
+}
+
+/// 子句层级：语句本身、子查询或连续查询的 BEGIN ... END
+#[derive(Debug, Clone)]
+struct Level {
+    indent: usize,
+    depth: usize, // 该层子句所在的括号深度
+    in_select: bool,
+    clause: String,
+    block: bool,
+}
+
+struct SqlFormatter<'a> {
+    input: &'a str,
+    dialect: QueryDialect,
+    options: &'a FormatOptions,
+    lines: Vec<String>,
+    line: String,
+    levels: Vec<Level>,
+    parens: Vec<bool>, // true 表示子查询的括号
+    prev: Option<&'a Token>,
+    prev_word: Option<String>,
+    prev_keyword: bool,
+    prev_unary: bool,
+    pending_newline: Option<usize>,
+    between: bool,
+    case_depth: usize,
+}
+
+impl<'a> SqlFormatter<'a> {
+    fn new(input: &'a str, dialect: QueryDialect, options: &'a FormatOptions) -> Self {
+        Self {
+            input,
+            dialect,
+            options,
+            lines: Vec::new(),
+            line: String::new(),
+            levels: vec![Level { indent: 0, depth: 0, in_select: false, clause: String::new(), block: false }],
+            parens: Vec::new(),
+            prev: None,
+            prev_word: None,
+            prev_keyword: false,
+            prev_unary: false,
+            pending_newline: None,
+            between: false,
+            case_depth: 0,
+        }
+    }
+
+    fn keywords(&self) -> &'static [&'static str] {
+        dialect_keywords(self.dialect)
+    }
+
+    fn indent(&self) -> usize {
+        self.levels.last().map_or(0, |level| level.indent)
+    }
+
+    fn level(&self) -> Level {
+        self.levels.last().cloned().unwrap_or(Level { indent: 0, depth: 0, in_select: false, clause: String::new(), block: false })
+    }
+
+    fn is_fresh(&self) -> bool {
+        self.line.trim().is_empty()
+    }
+
+    fn newline(&mut self, indent: usize) {
+        if !self.is_fresh() {
+            self.lines.push(self.line.trim_end().to_string());
+        }
+        self.line = self.options.indent.repeat(indent);
+        self.pending_newline = None;
+    }
+
+    fn write(&mut self, text: &str, space: bool) {
+        if let Some(indent) = self.pending_newline {
+            self.newline(indent);
+        }
+        if space && !self.is_fresh() {
+            self.line.push(' ');
+        }
+        self.line.push_str(text);
+    }
+
+    fn format(mut self, tokens: &'a [Token]) -> String {
+        for (i, token) in tokens.iter().enumerate() {
+            if token.kind == TokenKind::Comment {
+                self.write_comment(tokens, i);
+                continue;
+            }
+
+            let word = word_at(tokens, i);
+            let next = tokens[i + 1..].iter().position(|t| t.kind != TokenKind::Comment).map(|p| i + 1 + p);
+            let next_word = next.and_then(|n| word_at(tokens, n));
+            let next_is_paren = next.is_some_and(|n| tokens[n].is_punct("("));
+
+            if let Some(word) = &word {
+                self.layout_keyword(word, tokens, i, next_word.as_deref(), next_is_paren);
+            }
+
+            if token.is_punct(")") && self.parens.pop() == Some(true) {
+                self.levels.pop();
+                let indent = self.indent();
+                self.newline(indent);
+            }
+
+            let is_keyword = word.as_ref().is_some_and(|w| self.keywords().contains(&w.as_str()));
+            let raw = &self.input[token.span.clone()];
+            let text = if is_keyword { self.options.keyword_case.apply(raw) } else { raw.to_string() };
+            let space = self.needs_space(token);
+            self.write(&text, space);
+
+            if token.is_punct("(") {
+                let subquery = matches!(next_word.as_deref(), Some("SELECT" | "WITH"));
+                self.parens.push(subquery);
+                if subquery {
+                    let indent = self.indent() + 1;
+                    self.push_level(indent, false);
+                }
+            }
+            if word.as_deref() == Some("BEGIN") && self.dialect == QueryDialect::InfluxQL {
+                let indent = self.indent() + 1;
+                self.push_level(indent, true);
+            }
+
+            let operator_context = self.prev.is_none_or(|p| {
+                p.kind == TokenKind::Operator || p.is_punct("(") || p.is_punct(",") || p.is_punct("[")
+            }) || self.prev_keyword;
+            self.prev_unary = (token.is_operator("-") || token.is_operator("+")) && operator_context;
+            self.prev_keyword = is_keyword && !word.as_ref().is_some_and(|w| CALL_KEYWORDS.contains(&w.as_str()));
+            self.prev_word = word;
+            self.prev = Some(token);
+        }
+
+        if !self.is_fresh() {
+            self.lines.push(self.line.trim_end().to_string());
+        }
+        self.lines.join("\n")
+    }
+
+    fn push_level(&mut self, indent: usize, block: bool) {
+        self.levels.push(Level { indent, depth: self.parens.len(), in_select: false, clause: String::new(), block });
+    }
+
+    /// 子句关键字另起一行，WHERE/HAVING 中顶层的 AND/OR 换行对齐
+    fn layout_keyword(&mut self, word: &str, tokens: &[Token], i: usize, next_word: Option<&str>, next_is_paren: bool) {
+        let level = self.level();
+        let at_clause_level = self.parens.len() == level.depth;
+
+        if word == "CASE" {
+            self.case_depth += 1;
+            return;
+        }
+        if word == "END" {
+            if self.case_depth > 0 {
+                self.case_depth -= 1;
+            } else if level.block && self.levels.len() > 1 {
+                self.levels.pop();
+                let indent = self.indent();
+                self.newline(indent);
+            }
+            return;
+        }
+        if word == "BETWEEN" {
+            self.between = true;
+            return;
+        }
+        if word == "AND" && self.between {
+            self.between = false;
+            return;
+        }
+        if !at_clause_level {
+            return;
+        }
+
+        let is_clause = match word {
+            "SELECT" => {
+                if let Some(level) = self.levels.last_mut() {
+                    level.in_select = true;
+                }
+                let after_explain = matches!(self.prev_word.as_deref(), Some("EXPLAIN" | "ANALYZE" | "VERBOSE"));
+                if !after_explain {
+                    self.newline(level.indent);
+                }
+                self.set_clause(word);
+                return;
+            }
+            "FROM" | "WHERE" | "HAVING" | "LIMIT" | "OFFSET" | "SLIMIT" | "SOFFSET" | "INTO" | "UNION" | "EXCEPT"
+            | "INTERSECT" => true,
+            "GROUP" | "ORDER" | "ALIGN" => next_word == Some("BY"),
+            "DISABLE" => next_word == Some("ALIGN"),
+            "FILL" | "TZ" => next_is_paren,
+            _ if JOIN_WORDS.contains(&word) => {
+                let continues_join = self.prev_word.as_deref().is_some_and(|w| JOIN_WORDS.contains(&w));
+                !continues_join && leads_to_join(&tokens[i..])
+            }
+            "AND" | "OR" if matches!(level.clause.as_str(), "WHERE" | "HAVING") => {
+                if self.options.align_conditions {
+                    // 使条件与子句关键字之后的第一个条件对齐
+                    let width = (level.clause.len() + 1).saturating_sub(word.len() + 1);
+                    self.newline(level.indent);
+                    self.line.push_str(&" ".repeat(width));
+                } else {
+                    self.newline(level.indent + 1);
+                }
+                return;
+            }
+            _ => false,
+        };
+
+        if is_clause && level.in_select {
+            self.newline(level.indent);
+            self.set_clause(if JOIN_WORDS.contains(&word) { "JOIN" } else { word });
+        }
+    }
+
+    fn set_clause(&mut self, clause: &str) {
+        if let Some(level) = self.levels.last_mut() {
+            level.clause = clause.to_string();
+        }
+    }
+
+    fn write_comment(&mut self, tokens: &[Token], i: usize) {
+        let token = &tokens[i];
+        let own_line = i > 0 && self.input[tokens[i - 1].span.end..token.span.start].contains('\n');
+        let indent = self.indent();
+        if own_line {
+            self.newline(indent);
+        }
+        let starts_line = self.is_fresh();
+        self.write(&self.input[token.span.clone()], true);
+
+        let followed_by_newline = tokens
+            .get(i + 1)
+            .is_some_and(|next| self.input[token.span.end..next.span.start].contains('\n'));
+        if token.text.starts_with("--") || followed_by_newline {
+            self.pending_newline = Some(if starts_line { indent } else { indent + 1 });
+        }
+    }
+
+    fn needs_space(&self, token: &Token) -> bool {
+        let Some(prev) = self.prev else {
+            return false;
+        };
+        if self.prev_unary {
+            return false;
+        }
+        if token.kind == TokenKind::Punct && matches!(token.text.as_str(), "," | ")" | "]" | "." | ";") {
+            return false;
+        }
+        if token.is_operator("::") || prev.is_operator("::") || prev.is_operator(":") {
+            return false;
+        }
+        if prev.kind == TokenKind::Punct && matches!(prev.text.as_str(), "(" | "[" | ".") {
+            return false;
+        }
+        if token.is_punct("(") || token.is_punct("[") {
+            let is_call = matches!(prev.kind, TokenKind::Ident | TokenKind::QuotedIdent) && !self.prev_keyword;
+            return !is_call && !prev.is_punct(")");
+        }
+        true
+    }
+}
+
+/// 未加引号、且不是限定名一部分的单词（大写）
+fn word_at(tokens: &[Token], i: usize) -> Option<String> {
+    let token = &tokens[i];
+    let dotted = (i > 0 && tokens[i - 1].is_punct(".")) || tokens.get(i + 1).is_some_and(|t| t.is_punct("."));
+    (token.kind == TokenKind::Ident && !dotted).then(|| token.text.to_uppercase())
+}
+
+/// 以当前单词开始的连接关键字序列是否以 JOIN 结束，用于区分 LEFT JOIN 与 left() 函数
+fn leads_to_join(tokens: &[Token]) -> bool {
+    tokens
+        .iter()
+        .filter(|t| t.kind != TokenKind::Comment)
+        .take_while(|t| JOIN_WORDS.iter().any(|w| t.is_keyword(w)))
+        .any(|t| t.is_keyword("JOIN"))
+}
+
+fn format_flux(query: &str, options: &FormatOptions) -> String {
+    let (tokens, _) = flux::tokenize_with_comments(query);
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    let mut depth = 0usize;
+    let mut chain = false; // 当前语句处于管道链中
+    let mut pending_newline = false;
+    let mut prev: Option<&Token> = None;
+    let mut prev_unary = false;
+
+    let start_line = |lines: &mut Vec<String>, line: &mut String, indent: usize, blank: bool| {
+        if !line.trim().is_empty() {
+            lines.push(line.trim_end().to_string());
+        }
+        if blank && lines.last().is_some_and(|l| !l.is_empty()) {
+            lines.push(String::new());
+        }
+        *line = options.indent.repeat(indent);
+    };
+
+    for (i, token) in tokens.iter().enumerate() {
+        let breaks = if i == 0 { 0 } else { query[tokens[i - 1].span.end..token.span.start].matches('\n').count() };
+        let is_closing = token.kind == TokenKind::Punct && matches!(token.text.as_str(), ")" | "]" | "}");
+        if is_closing {
+            depth = depth.saturating_sub(1);
+        }
+
+        if token.is_operator("|>") {
+            if depth == 0 {
+                chain = true;
+            }
+            let nested = usize::from(chain && depth > 0);
+            start_line(&mut lines, &mut line, depth + 1 + nested, false);
+        } else if breaks > 0 || pending_newline {
+            if depth == 0 && !is_closing && token.kind != TokenKind::Comment {
+                chain = false;
+            }
+            start_line(&mut lines, &mut line, depth + usize::from(chain), breaks > 1);
+        }
+        pending_newline = false;
+
+        let space = !line.trim().is_empty() && !prev_unary && prev.is_some_and(|p| flux_needs_space(p, token));
+        if space {
+            line.push(' ');
+        }
+        line.push_str(&token.text);
+
+        if token.kind == TokenKind::Punct && matches!(token.text.as_str(), "(" | "[" | "{") {
+            depth += 1;
+        }
+        if token.kind == TokenKind::Comment {
+            pending_newline = true;
+        } else {
+            let operator_context = prev.is_none_or(|p| {
+                p.kind == TokenKind::Operator
+                    || (p.kind == TokenKind::Punct && matches!(p.text.as_str(), "(" | "[" | "{" | "," | ":"))
+                    || p.is_keyword("return")
+            });
+            prev_unary = (token.is_operator("-") || token.is_operator("+")) && operator_context;
+            prev = Some(token);
+        }
+    }
+
+    if !line.trim().is_empty() {
+        lines.push(line.trim_end().to_string());
+    }
+    lines.join("\n")
+}
+
+fn flux_needs_space(prev: &Token, token: &Token) -> bool {
+    if token.kind == TokenKind::Punct && matches!(token.text.as_str(), "," | ")" | "]" | "}" | "." | ":" | ";") {
+        return false;
+    }
+    if prev.kind == TokenKind::Punct && matches!(prev.text.as_str(), "(" | "[" | "{" | ".") {
+        return false;
+    }
+    if token.is_punct("(") || token.is_punct("[") {
+        let is_call = matches!(prev.kind, TokenKind::Ident | TokenKind::String)
+            || (prev.kind == TokenKind::Punct && matches!(prev.text.as_str(), ")" | "]"));
+        return !is_call;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(query: &str, dialect: QueryDialect) -> String {
+        let options = FormatOptions::default();
+        let formatted = format_query(query, Some(dialect), &options);
+        assert_eq!(format_query(&formatted, Some(dialect), &options), formatted, "格式化结果应保持不变");
+        formatted
+    }
+
+    #[test]
+    fn test_influxql_layout() {
+        let formatted = format(
+            "select mean(\"usage\") as avg from cpu where host =~ /web/ and time > now() - 1h or \"region\" = 'eu' group by time(5m), host fill(none) limit 10",
+            QueryDialect::InfluxQL,
+        );
+        assert_eq!(
+            formatted,
+            "SELECT mean(\"usage\") AS avg\nFROM cpu\nWHERE host =~ /web/\n  AND time > now() - 1h\n   OR \"region\" = 'eu'\nGROUP BY time(5m), host\nfill(none)\nLIMIT 10"
+        );
+
+        let formatted = format("select * from cpu where v between 1 and 2 and host = 'a'", QueryDialect::InfluxQL);
+        assert_eq!(formatted, "SELECT *\nFROM cpu\nWHERE v BETWEEN 1 AND 2\n  AND host = 'a'");
+    }
+
+    #[test]
+    fn test_subquery_and_comments() {
+        let formatted = format(
+            "-- 每小时最大值\nSELECT max(m) FROM (SELECT mean(v) AS m FROM cpu /* 原始数据 */ WHERE time > now() - 1d GROUP BY time(1h)) WHERE m > 0.5;\nshow databases",
+            QueryDialect::InfluxQL,
+        );
+        assert_eq!(
+            formatted,
+            "-- 每小时最大值\nSELECT max(m)\nFROM (\n  SELECT mean(v) AS m\n  FROM cpu /* 原始数据 */\n  WHERE time > now() - 1d\n  GROUP BY time(1h)\n)\nWHERE m > 0.5;\nSHOW DATABASES"
+        );
+    }
+
+    #[test]
+    fn test_sql_and_options() {
+        let options = FormatOptions { indent: "    ".to_string(), keyword_case: KeywordCase::Lower, align_conditions: false };
+        let formatted = format_query(
+            "SELECT h.name, count(*) FROM cpu c LEFT JOIN hosts h ON c.host = h.name WHERE c.usage BETWEEN 0 AND 100 AND c.time >= now() - INTERVAL '1 hour' GROUP BY h.name ORDER BY 2 DESC",
+            Some(QueryDialect::Sql),
+            &options,
+        );
+        assert_eq!(
+            formatted,
+            "select h.name, count(*)\nfrom cpu c\nleft join hosts h on c.host = h.name\nwhere c.usage between 0 and 100\n    and c.time >= now() - interval '1 hour'\ngroup by h.name\norder by 2 desc"
+        );
+    }
+
+    #[test]
+    fn test_iotdb_paths_and_insert() {
+        let formatted = format(
+            "select count(s1) from root.sg.*.** where time >= 2024-01-01T00:00:00 group by ([2024-01-01T00:00:00, 2024-01-02T00:00:00), 1h) align by device",
+            QueryDialect::IoTDB,
+        );
+        assert_eq!(
+            formatted,
+            "SELECT count(s1)\nFROM root.sg.*.**\nWHERE time >= 2024-01-01T00:00:00\nGROUP BY ([2024-01-01T00:00:00, 2024-01-02T00:00:00), 1h)\nALIGN BY DEVICE"
+        );
+
+        let insert = "INSERT cpu,host=a value=\"it's\"   1";
+        assert_eq!(format(insert, QueryDialect::InfluxQL), insert);
+    }
+
+    #[test]
+    fn test_detected_dialect_keeps_influxql_regex() {
+        let query = "SELECT * FROM /cpu|>x/ WHERE host =~ /a|b/";
+        assert_eq!(
+            format_query(query, None, &FormatOptions::default()),
+            "SELECT *\nFROM /cpu|>x/\nWHERE host =~ /a|b/"
+        );
+    }
+
+    #[test]
+    fn test_flux_pipeline() {
+        let formatted = format(
+            "from(bucket:\"b\") |> range(start:-1h)   |> filter(fn:(r)=>r._measurement==\"cpu\" and r.host=~/web/) // 过滤\n|> yield()\n\n\n\nx = 1",
+            QueryDialect::Flux,
+        );
+        assert_eq!(
+            formatted,
+            "from(bucket: \"b\")\n  |> range(start: -1h)\n  |> filter(fn: (r) => r._measurement == \"cpu\" and r.host =~ /web/) // 过滤\n  |> yield()\n\nx = 1"
+        );
+    }
+}
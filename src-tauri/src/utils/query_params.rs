@@ -0,0 +1,714 @@
+/**
+ * 参数化查询工具
+ *
+ * 识别查询中的 $name 占位符并按目标方言处理参数：
+ * InfluxQL、Flux 和 InfluxDB 3.x 由服务端绑定参数，其余场景按方言转义后内联
+ */
+
+use crate::models::{QueryParamValue, QueryParams};
+use anyhow::Result;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{json, Map, Value};
+use std::ops::Range;
+
+/// 查询方言，决定字面量的写法和转义方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamDialect {
+    InfluxQL,
+    Sql,
+    Flux,
+    IoTDB,
+}
+
+/// 查询中的占位符
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placeholder {
+    pub name: String,
+    pub span: Range<usize>, // 包含 $ 的字节范围
+}
+
+fn is_ident_start(b: u8) -> bool {
+    b.is_ascii_alphabetic() || b == b'_'
+}
+
+fn is_ident_char(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+/// 查找 $name 占位符，跳过字符串、带引号的标识符和注释
+pub fn find_placeholders(query: &str) -> Vec<Placeholder> {
+    let bytes = query.as_bytes();
+    let len = bytes.len();
+    let mut placeholders = Vec::new();
+    let mut i = 0;
+
+    while i < len {
+        match bytes[i] {
+            quote @ (b'\'' | b'"' | b'`') => {
+                i += 1;
+                while i < len {
+                    if bytes[i] == b'\\' {
+                        i += 2;
+                        continue;
+                    }
+                    if bytes[i] == quote {
+                        // 连续两个引号表示转义
+                        if bytes.get(i + 1) == Some(&quote) {
+                            i += 2;
+                            continue;
+                        }
+                        break;
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                while i < len && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < len && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i < len && !(bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/')) {
+                    i += 1;
+                }
+                i += 2;
+            }
+            b'$' if i + 1 < len && is_ident_start(bytes[i + 1]) => {
+                let start = i;
+                i += 2;
+                while i < len && is_ident_char(bytes[i]) {
+                    i += 1;
+                }
+                placeholders.push(Placeholder {
+                    name: query[start + 1..i].to_string(),
+                    span: start..i,
+                });
+            }
+            _ => i += 1,
+        }
+    }
+
+    placeholders
+}
+
+/// 按首次出现顺序返回引用的参数名（去重）
+pub fn placeholder_names(query: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for placeholder in find_placeholders(query) {
+        if !names.contains(&placeholder.name) {
+            names.push(placeholder.name);
+        }
+    }
+    names
+}
+
+/// 解析 RFC3339 时间
+pub fn parse_time(value: &str) -> Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value.trim())
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|e| anyhow::anyhow!("无效的时间 '{}': {}", value, e))
+}
+
+/// 解析时长（如 1h30m、500ms），返回纳秒数
+pub fn parse_duration_nanos(value: &str) -> Result<i64> {
+    let invalid = || anyhow::anyhow!("无效的时长 '{}'", value);
+    let mut rest = value.trim();
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut total: i64 = 0;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        if digits == 0 {
+            return Err(invalid());
+        }
+        let amount: i64 = rest[..digits].parse().map_err(|_| invalid())?;
+        rest = &rest[digits..];
+
+        let unit_len = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let multiplier: i64 = match &rest[..unit_len] {
+            "ns" => 1,
+            "us" | "µs" => 1_000,
+            "ms" => 1_000_000,
+            "s" => 1_000_000_000,
+            "m" => 60_000_000_000,
+            "h" => 3_600_000_000_000,
+            "d" => 86_400_000_000_000,
+            "w" => 604_800_000_000_000,
+            _ => return Err(invalid()),
+        };
+        rest = &rest[unit_len..];
+
+        total = amount
+            .checked_mul(multiplier)
+            .and_then(|nanos| total.checked_add(nanos))
+            .ok_or_else(invalid)?;
+    }
+
+    Ok(total)
+}
+
+fn format_time(value: &str) -> Result<String> {
+    Ok(parse_time(value)?.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+}
+
+fn format_float(value: f64) -> Result<String> {
+    if !value.is_finite() {
+        return Err(anyhow::anyhow!("无效的浮点数: {}", value));
+    }
+    let text = value.to_string();
+    Ok(if text.contains('.') { text } else { format!("{}.0", text) })
+}
+
+/// SQL 时间间隔，使用能整除的最大单位
+fn sql_interval(nanos: i64) -> String {
+    let (amount, unit) = if nanos % 1_000_000_000 == 0 {
+        (nanos / 1_000_000_000, "seconds")
+    } else if nanos % 1_000_000 == 0 {
+        (nanos / 1_000_000, "milliseconds")
+    } else if nanos % 1_000 == 0 {
+        (nanos / 1_000, "microseconds")
+    } else {
+        (nanos, "nanoseconds")
+    };
+    format!("INTERVAL '{} {}'", amount, unit)
+}
+
+/// 将参数值转换为目标方言的字面量
+pub fn to_literal(value: &QueryParamValue, dialect: ParamDialect) -> Result<String> {
+    let literal = match value {
+        QueryParamValue::String(text) => match dialect {
+            ParamDialect::InfluxQL => format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'")),
+            ParamDialect::Sql | ParamDialect::IoTDB => format!("'{}'", text.replace('\'', "''")),
+            ParamDialect::Flux => format!(
+                "\"{}\"",
+                text.replace('\\', "\\\\").replace('"', "\\\"").replace("${", "\\${")
+            ),
+        },
+        QueryParamValue::Integer(number) => number.to_string(),
+        QueryParamValue::Float(number) => format_float(*number)?,
+        QueryParamValue::Boolean(flag) => flag.to_string(),
+        QueryParamValue::Time(time) => match dialect {
+            ParamDialect::InfluxQL | ParamDialect::Sql => format!("'{}'", format_time(time)?),
+            ParamDialect::Flux => format_time(time)?,
+            // IoTDB 默认时间精度为毫秒
+            ParamDialect::IoTDB => parse_time(time)?.timestamp_millis().to_string(),
+        },
+        QueryParamValue::Duration(duration) => {
+            let nanos = parse_duration_nanos(duration)?;
+            match dialect {
+                ParamDialect::Sql => sql_interval(nanos),
+                _ => duration.trim().to_string(),
+            }
+        }
+    };
+    Ok(literal)
+}
+
+/// 检查引用的参数都已提供且取值合法，返回按首次出现顺序排列的参数名
+pub fn validate_params(query: &str, params: &QueryParams) -> Result<Vec<String>> {
+    let names = placeholder_names(query);
+    for name in &names {
+        let value = params
+            .get(name)
+            .ok_or_else(|| anyhow::anyhow!("缺少查询参数: ${}", name))?;
+        let checked = match value {
+            QueryParamValue::Time(time) => parse_time(time).map(|_| ()),
+            QueryParamValue::Duration(duration) => parse_duration_nanos(duration).map(|_| ()),
+            QueryParamValue::Float(number) => format_float(*number).map(|_| ()),
+            _ => Ok(()),
+        };
+        checked.map_err(|e| anyhow::anyhow!("参数 ${} 无效: {}", name, e))?;
+    }
+    Ok(names)
+}
+
+/// 替换满足条件的占位符，replacement 返回 None 时保留原样
+fn rewrite<F>(query: &str, params: &QueryParams, mut replacement: F) -> Result<String>
+where
+    F: FnMut(&str, &QueryParamValue) -> Result<Option<String>>,
+{
+    validate_params(query, params)?;
+
+    let mut output = String::with_capacity(query.len());
+    let mut last = 0;
+    for placeholder in find_placeholders(query) {
+        let value = &params[&placeholder.name];
+        if let Some(text) = replacement(&placeholder.name, value)? {
+            output.push_str(&query[last..placeholder.span.start]);
+            output.push_str(&text);
+            last = placeholder.span.end;
+        }
+    }
+    output.push_str(&query[last..]);
+    Ok(output)
+}
+
+/// 将全部占位符替换为转义后的字面量（用于不支持服务端参数的客户端）
+pub fn bind_inline(query: &str, params: &QueryParams, dialect: ParamDialect) -> Result<String> {
+    rewrite(query, params, |_, value| to_literal(value, dialect).map(Some))
+}
+
+fn json_value(value: &QueryParamValue) -> Result<Value> {
+    Ok(match value {
+        QueryParamValue::String(text) => json!(text),
+        QueryParamValue::Integer(number) => json!(number),
+        QueryParamValue::Float(number) => json!(number),
+        QueryParamValue::Boolean(flag) => json!(flag),
+        QueryParamValue::Time(time) => json!(format_time(time)?),
+        QueryParamValue::Duration(duration) => json!(duration.trim()),
+    })
+}
+
+/// InfluxDB 1.x /query 接口的 params 表单值，InfluxQL 原生支持 $name 绑定
+pub fn to_influxql_params(query: &str, params: &QueryParams) -> Result<String> {
+    let mut object = Map::new();
+    for name in validate_params(query, params)? {
+        let value = match &params[&name] {
+            // 时长需要显式声明类型，否则会被当作字符串
+            QueryParamValue::Duration(duration) => json!({ "duration": duration.trim() }),
+            value => json_value(value)?,
+        };
+        object.insert(name, value);
+    }
+    Ok(Value::Object(object).to_string())
+}
+
+/// InfluxDB 2.x Flux 参数：$name 改写为 params.name，时间和时长通过转换函数还原类型
+pub fn to_flux_params(query: &str, params: &QueryParams) -> Result<(String, Value)> {
+    let text = rewrite(query, params, |name, value| {
+        Ok(Some(match value {
+            QueryParamValue::Time(_) => format!("time(v: params.{})", name),
+            QueryParamValue::Duration(_) => format!("duration(v: params.{})", name),
+            _ => format!("params.{}", name),
+        }))
+    })?;
+
+    let mut object = Map::new();
+    for name in placeholder_names(query) {
+        object.insert(name.clone(), json_value(&params[&name])?);
+    }
+    Ok((text, Value::Object(object)))
+}
+
+/// InfluxDB 3.x HTTP 查询的 params 对象；时长不是参数类型，以字面量内联
+pub fn to_v3_params(query: &str, params: &QueryParams, dialect: ParamDialect) -> Result<(String, Value)> {
+    let text = rewrite(query, params, |_, value| match value {
+        QueryParamValue::Duration(_) => to_literal(value, dialect).map(Some),
+        _ => Ok(None),
+    })?;
+
+    let mut object = Map::new();
+    for name in placeholder_names(&text) {
+        object.insert(name.clone(), json_value(&params[&name])?);
+    }
+    Ok((text, Value::Object(object)))
+}
+
+/// FlightSQL 预编译语句使用位置参数：$name 按首次出现顺序改写为 $1、$2…，时长以字面量内联
+pub fn to_positional_params(
+    query: &str,
+    params: &QueryParams,
+    dialect: ParamDialect,
+) -> Result<(String, Vec<(String, QueryParamValue)>)> {
+    let mut ordered: Vec<(String, QueryParamValue)> = Vec::new();
+    let text = rewrite(query, params, |name, value| {
+        if let QueryParamValue::Duration(_) = value {
+            return to_literal(value, dialect).map(Some);
+        }
+        let position = match ordered.iter().position(|(existing, _)| existing == name) {
+            Some(index) => index + 1,
+            None => {
+                ordered.push((name.to_string(), value.clone()));
+                ordered.len()
+            }
+        };
+        Ok(Some(format!("${}", position)))
+    })?;
+    Ok((text, ordered))
+}
+
+/// 将标识符加上双引号（InfluxQL 和 SQL 通用），内部的双引号按 SQL 规则重复一次
+pub fn quote_identifier(name: &str, dialect: ParamDialect) -> String {
+    match dialect {
+        ParamDialect::InfluxQL => format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\"")),
+        _ => format!("\"{}\"", name.replace('"', "\"\"")),
+    }
+}
+
+/// IoTDB 路径节点：普通名称原样返回，其他名称用反引号包裹，内部的反引号重复一次
+pub fn quote_iotdb_node(name: &str) -> String {
+    if is_plain_iotdb_node(name) {
+        name.to_string()
+    } else {
+        format!("`{}`", name.replace('`', "``"))
+    }
+}
+
+/// 不需要反引号的节点名：字母、数字、下划线和中文等，且不是纯数字
+fn is_plain_iotdb_node(name: &str) -> bool {
+    !name.is_empty()
+        && !name.chars().all(|c| c.is_ascii_digit())
+        && name.chars().all(|c| c == '_' || c.is_alphanumeric())
+}
+
+/// 按 . 拆分 IoTDB 路径，反引号内的 . 不拆分
+pub fn split_iotdb_path(path: &str) -> Result<Vec<&str>> {
+    let mut nodes = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    let mut chars = path.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '`' if quoted && chars.peek().is_some_and(|(_, next)| *next == '`') => {
+                chars.next();
+            }
+            '`' => quoted = !quoted,
+            '.' if !quoted => {
+                nodes.push(&path[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if quoted {
+        return Err(anyhow::anyhow!("无效的IoTDB路径 '{}': 反引号未闭合", path));
+    }
+    nodes.push(&path[start..]);
+    Ok(nodes)
+}
+
+/// 校验 IoTDB 路径：以 root 开头，各节点为普通名称或反引号名称，allow_wildcard 时允许 * 和 ** 节点
+pub fn validate_iotdb_path(path: &str, allow_wildcard: bool) -> Result<()> {
+    let invalid = |reason: &str| anyhow::anyhow!("无效的IoTDB路径 '{}': {}", path, reason);
+    let nodes = split_iotdb_path(path)?;
+
+    if !nodes[0].eq_ignore_ascii_case("root") {
+        return Err(invalid("必须以 root 开头"));
+    }
+    for node in &nodes[1..] {
+        let valid = if *node == "*" || *node == "**" {
+            allow_wildcard
+        } else if let Some(inner) = node.strip_prefix('`').and_then(|rest| rest.strip_suffix('`')) {
+            !inner.is_empty() && !inner.replace("``", "").contains('`')
+        } else {
+            is_plain_iotdb_node(node)
+        };
+        if !valid {
+            return Err(invalid(&format!("节点 '{}' 不合法", node)));
+        }
+    }
+    Ok(())
+}
+
+/// 拼接并校验 IoTDB 设备路径，单个节点名按需加反引号
+pub fn iotdb_device_path(database: &str, measurement: &str) -> Result<String> {
+    let device_path = if measurement.is_empty() {
+        database.to_string()
+    } else if database.is_empty() || measurement.starts_with(database) {
+        measurement.to_string()
+    } else if measurement.contains(['.', '`']) {
+        format!("{}.{}", database, measurement)
+    } else {
+        format!("{}.{}", database, quote_iotdb_node(measurement))
+    };
+
+    validate_iotdb_path(&device_path, false)?;
+    Ok(device_path)
+}
+
+/// 聚合查询允许使用的函数
+const AGGREGATE_FUNCTIONS: &[&str] = &[
+    "COUNT", "SUM", "MEAN", "MEDIAN", "MODE", "SPREAD", "STDDEV", "MIN", "MAX", "FIRST", "LAST", "DISTINCT",
+];
+
+/// 去掉前端传入的双引号，避免重复加引号
+pub fn unquote_identifier(name: &str) -> String {
+    match name.strip_prefix('"').and_then(|rest| rest.strip_suffix('"')) {
+        Some(inner) if !inner.is_empty() => inner.replace("\\\"", "\"").replace("\\\\", "\\"),
+        _ => name.to_string(),
+    }
+}
+
+/// InfluxQL 聚合字段：FUNC(field) 的函数名必须在白名单内，字段名重新加引号
+pub fn aggregate_field(field: &str) -> Result<String> {
+    let field = field.trim();
+    let Some((name, rest)) = field.split_once('(') else {
+        return Ok(if field == "*" { "*".to_string() } else { quote_identifier(field, ParamDialect::InfluxQL) });
+    };
+    let argument = rest
+        .strip_suffix(')')
+        .ok_or_else(|| anyhow::anyhow!("无效的聚合字段: {}", field))?
+        .trim();
+
+    let function = name.trim().to_uppercase();
+    if !AGGREGATE_FUNCTIONS.contains(&function.as_str()) {
+        return Err(anyhow::anyhow!("不支持的聚合函数: {}", name.trim()));
+    }
+
+    let argument = if argument == "*" {
+        "*".to_string()
+    } else {
+        quote_identifier(&unquote_identifier(argument), ParamDialect::InfluxQL)
+    };
+    Ok(format!("{}({})", function, argument))
+}
+
+/// 时间范围边界：RFC3339 时间作为参数传递，now() 相对时间校验后直接写入查询
+pub fn time_bound(name: &str, value: &str, params: &mut QueryParams) -> Result<String> {
+    let value = value.trim();
+    if parse_time(value).is_ok() {
+        params.insert(name.to_string(), QueryParamValue::Time(value.to_string()));
+        return Ok(format!("${}", name));
+    }
+    relative_time(value).ok_or_else(|| anyhow::anyhow!("无效的时间: {}", value))
+}
+
+/// 规范化 now()、now() - 1h 形式的相对时间
+fn relative_time(value: &str) -> Option<String> {
+    value.get(..5).filter(|head| head.eq_ignore_ascii_case("now()"))?;
+    let rest = value[5..].trim();
+    if rest.is_empty() {
+        return Some("now()".to_string());
+    }
+
+    let (sign, duration) = if let Some(duration) = rest.strip_prefix('-') {
+        ('-', duration.trim())
+    } else {
+        ('+', rest.strip_prefix('+')?.trim())
+    };
+    parse_duration_nanos(duration).ok()?;
+    Some(format!("now() {} {}", sign, duration))
+}
+
+/// 相对时间转换为时长，如 15 minutes → 15m
+pub fn relative_duration(amount: u32, unit: &str) -> Option<String> {
+    let unit = match unit {
+        "s" | "second" | "seconds" => "s",
+        "m" | "minute" | "minutes" => "m",
+        "h" | "hour" | "hours" => "h",
+        "d" | "day" | "days" => "d",
+        "w" | "week" | "weeks" => "w",
+        _ => return None,
+    };
+    Some(format!("{}{}", amount, unit))
+}
+
+/// JSON 值转换为查询参数：interval 为 true 时作为时长，其余按 JSON 类型推断
+pub fn param_from_json(value: &Value, interval: bool) -> Result<QueryParamValue> {
+    if interval {
+        let duration = value.as_str().ok_or_else(|| anyhow::anyhow!("时长必须为字符串"))?;
+        parse_duration_nanos(duration)?;
+        return Ok(QueryParamValue::Duration(duration.to_string()));
+    }
+
+    match value {
+        Value::Bool(flag) => Ok(QueryParamValue::Boolean(*flag)),
+        Value::Number(number) => match number.as_i64() {
+            Some(integer) => Ok(QueryParamValue::Integer(integer)),
+            None => Ok(QueryParamValue::Float(number.as_f64().unwrap_or_default())),
+        },
+        Value::String(text) => Ok(QueryParamValue::String(text.clone())),
+        _ => Err(anyhow::anyhow!("值不能作为查询参数: {}", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(values: Vec<(&str, QueryParamValue)>) -> QueryParams {
+        values.into_iter().map(|(name, value)| (name.to_string(), value)).collect()
+    }
+
+    #[test]
+    fn test_find_placeholders_skips_literals_and_comments() {
+        let query = "SELECT * FROM \"$table\" WHERE host = $host AND note = 'costs $5 or $x' -- $comment\n AND region = $region /* $block */ AND h2 = $host";
+        assert_eq!(placeholder_names(query), vec!["host", "region"]);
+        assert_eq!(find_placeholders(query).len(), 3);
+        assert!(placeholder_names("SELECT * FROM cpu WHERE host =~ /^web$/").is_empty());
+        assert!(placeholder_names("SELECT '$1', $1 FROM t").is_empty());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration_nanos("1h30m").unwrap(), 5_400_000_000_000);
+        assert_eq!(parse_duration_nanos("500ms").unwrap(), 500_000_000);
+        assert!(parse_duration_nanos("1h; DROP").is_err());
+        assert!(parse_duration_nanos("h").is_err());
+        assert!(parse_duration_nanos("").is_err());
+    }
+
+    #[test]
+    fn test_bind_inline_escapes_per_dialect() {
+        let values = params(vec![
+            ("host", QueryParamValue::String("a' OR '1'='1".to_string())),
+            ("start", QueryParamValue::Time("2024-01-01T08:00:00+08:00".to_string())),
+            ("window", QueryParamValue::Duration("5m".to_string())),
+            ("limit", QueryParamValue::Integer(10)),
+        ]);
+        let query = "SELECT * FROM cpu WHERE host = $host AND time >= $start AND time > now() - $window LIMIT $limit";
+
+        assert_eq!(
+            bind_inline(query, &values, ParamDialect::InfluxQL).unwrap(),
+            "SELECT * FROM cpu WHERE host = 'a\\' OR \\'1\\'=\\'1' AND time >= '2024-01-01T00:00:00Z' AND time > now() - 5m LIMIT 10"
+        );
+        assert_eq!(
+            bind_inline(query, &values, ParamDialect::Sql).unwrap(),
+            "SELECT * FROM cpu WHERE host = 'a'' OR ''1''=''1' AND time >= '2024-01-01T00:00:00Z' AND time > now() - INTERVAL '300 seconds' LIMIT 10"
+        );
+        assert_eq!(
+            bind_inline("SELECT s1 FROM root.sg.d1 WHERE time >= $start", &values, ParamDialect::IoTDB).unwrap(),
+            "SELECT s1 FROM root.sg.d1 WHERE time >= 1704067200000"
+        );
+        assert_eq!(
+            to_literal(&QueryParamValue::String("x\"${y}".to_string()), ParamDialect::Flux).unwrap(),
+            "\"x\\\"\\${y}\""
+        );
+        assert_eq!(to_literal(&QueryParamValue::Float(2.0), ParamDialect::Sql).unwrap(), "2.0");
+    }
+
+    #[test]
+    fn test_validate_params() {
+        let query = "SELECT * FROM cpu WHERE host = $host";
+        let error = bind_inline(query, &QueryParams::new(), ParamDialect::Sql).unwrap_err();
+        assert!(error.to_string().contains("$host"));
+
+        let invalid = params(vec![("host", QueryParamValue::Time("yesterday".to_string()))]);
+        assert!(validate_params(query, &invalid).is_err());
+
+        // 未引用的参数会被忽略
+        let extra = params(vec![
+            ("host", QueryParamValue::String("a".to_string())),
+            ("unused", QueryParamValue::Boolean(true)),
+        ]);
+        assert_eq!(validate_params(query, &extra).unwrap(), vec!["host"]);
+    }
+
+    #[test]
+    fn test_server_side_params() {
+        let values = params(vec![
+            ("host", QueryParamValue::String("web-1".to_string())),
+            ("start", QueryParamValue::Time("2024-01-01T00:00:00Z".to_string())),
+            ("every", QueryParamValue::Duration("1m".to_string())),
+        ]);
+
+        let influxql = to_influxql_params("SELECT mean(v) FROM cpu WHERE host = $host GROUP BY time($every)", &values).unwrap();
+        let influxql: Value = serde_json::from_str(&influxql).unwrap();
+        assert_eq!(influxql, json!({ "host": "web-1", "every": { "duration": "1m" } }));
+
+        let (flux, flux_params) = to_flux_params(
+            "from(bucket: \"b\") |> range(start: $start) |> filter(fn: (r) => r.host == $host) |> aggregateWindow(every: $every, fn: mean)",
+            &values,
+        )
+        .unwrap();
+        assert_eq!(
+            flux,
+            "from(bucket: \"b\") |> range(start: time(v: params.start)) |> filter(fn: (r) => r.host == params.host) |> aggregateWindow(every: duration(v: params.every), fn: mean)"
+        );
+        assert_eq!(flux_params, json!({ "start": "2024-01-01T00:00:00Z", "host": "web-1", "every": "1m" }));
+
+        let (sql, v3_params) = to_v3_params("SELECT * FROM cpu WHERE host = $host AND time > now() - $every", &values, ParamDialect::Sql).unwrap();
+        assert_eq!(sql, "SELECT * FROM cpu WHERE host = $host AND time > now() - INTERVAL '60 seconds'");
+        assert_eq!(v3_params, json!({ "host": "web-1" }));
+
+        let (positional, ordered) = to_positional_params(
+            "SELECT * FROM cpu WHERE time >= $start AND host = $host OR backup = $host",
+            &values,
+            ParamDialect::Sql,
+        )
+        .unwrap();
+        assert_eq!(positional, "SELECT * FROM cpu WHERE time >= $1 AND host = $2 OR backup = $2");
+        assert_eq!(ordered.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>(), vec!["start", "host"]);
+    }
+
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(quote_identifier("cpu\"; DROP", ParamDialect::Sql), "\"cpu\"\"; DROP\"");
+        assert_eq!(quote_identifier("cpu\"x", ParamDialect::InfluxQL), "\"cpu\\\"x\"");
+    }
+
+    #[test]
+    fn test_iotdb_paths() {
+        assert_eq!(quote_iotdb_node("temperature"), "temperature");
+        assert_eq!(quote_iotdb_node("温度_1"), "温度_1");
+        assert_eq!(quote_iotdb_node("123"), "`123`");
+        assert_eq!(quote_iotdb_node("a.b"), "`a.b`");
+        assert_eq!(quote_iotdb_node("x`; DROP"), "`x``; DROP`");
+
+        assert_eq!(split_iotdb_path("root.sg.`a.b`.s1").unwrap(), vec!["root", "sg", "`a.b`", "s1"]);
+        assert!(split_iotdb_path("root.`sg").is_err());
+
+        assert!(validate_iotdb_path("root.sg.d1", false).is_ok());
+        assert!(validate_iotdb_path("root.sg.`d-1`.`x``y`", false).is_ok());
+        assert!(validate_iotdb_path("root.sg.**", true).is_ok());
+        assert!(validate_iotdb_path("root.sg.**", false).is_err());
+        assert!(validate_iotdb_path("root.sg.d1 LIMIT 1; DELETE TIMESERIES root.**", true).is_err());
+        assert!(validate_iotdb_path("root.sg..d1", false).is_err());
+        assert!(validate_iotdb_path("root.sg.d*", true).is_err());
+        assert!(validate_iotdb_path("rooted.sg", false).is_err());
+        assert!(validate_iotdb_path("root.`a`b`", false).is_err());
+    }
+
+    #[test]
+    fn test_iotdb_device_path() {
+        assert_eq!(iotdb_device_path("root.sg", "d1").unwrap(), "root.sg.d1");
+        assert_eq!(iotdb_device_path("root.sg", "d-1").unwrap(), "root.sg.`d-1`");
+        assert_eq!(iotdb_device_path("root.sg", "温度 1").unwrap(), "root.sg.`温度 1`");
+        // 含点号或反引号的名称视为已加引号的路径，不合法时拒绝而不是拼进查询
+        assert_eq!(iotdb_device_path("root.sg", "`a.b`").unwrap(), "root.sg.`a.b`");
+        assert!(iotdb_device_path("root.sg", "x`; DROP").is_err());
+        assert_eq!(iotdb_device_path("root.sg", "root.sg.d1").unwrap(), "root.sg.d1");
+        assert_eq!(iotdb_device_path("root.sg", "").unwrap(), "root.sg");
+        assert!(iotdb_device_path("root.sg", "d1.** ; DELETE").is_err());
+    }
+
+    #[test]
+    fn test_aggregate_field() {
+        assert_eq!(aggregate_field("mean(usage)").unwrap(), "MEAN(\"usage\")");
+        assert_eq!(aggregate_field(" count( * ) ").unwrap(), "COUNT(*)");
+        assert_eq!(aggregate_field("max(\"a\\\"b\")").unwrap(), "MAX(\"a\\\"b\")");
+        assert_eq!(aggregate_field("usage").unwrap(), "\"usage\"");
+        assert!(aggregate_field("sleep(usage)").is_err());
+        assert!(aggregate_field("mean(usage").is_err());
+    }
+
+    #[test]
+    fn test_time_bound() {
+        let mut bound = QueryParams::new();
+        assert_eq!(time_bound("start", "2024-01-01T00:00:00Z", &mut bound).unwrap(), "$start");
+        assert_eq!(bound["start"], QueryParamValue::Time("2024-01-01T00:00:00Z".to_string()));
+
+        assert_eq!(time_bound("end", "now()", &mut bound).unwrap(), "now()");
+        assert_eq!(time_bound("end", "NOW() -1h", &mut bound).unwrap(), "now() - 1h");
+        assert_eq!(time_bound("end", "now() + 30m", &mut bound).unwrap(), "now() + 30m");
+        assert!(time_bound("end", "now() - 1h; DROP", &mut bound).is_err());
+        assert!(time_bound("end", "yesterday", &mut bound).is_err());
+        assert_eq!(bound.len(), 1);
+    }
+
+    #[test]
+    fn test_dashboard_params() {
+        assert_eq!(relative_duration(15, "minutes").as_deref(), Some("15m"));
+        assert_eq!(relative_duration(1, "w").as_deref(), Some("1w"));
+        assert_eq!(relative_duration(1, "month"), None);
+
+        assert_eq!(param_from_json(&json!("5m"), true).unwrap(), QueryParamValue::Duration("5m".to_string()));
+        assert!(param_from_json(&json!("five"), true).is_err());
+        assert!(param_from_json(&json!(5), true).is_err());
+        assert_eq!(param_from_json(&json!(true), false).unwrap(), QueryParamValue::Boolean(true));
+        assert_eq!(param_from_json(&json!(3), false).unwrap(), QueryParamValue::Integer(3));
+        assert_eq!(param_from_json(&json!(0.5), false).unwrap(), QueryParamValue::Float(0.5));
+        assert_eq!(param_from_json(&json!("web"), false).unwrap(), QueryParamValue::String("web".to_string()));
+        assert!(param_from_json(&json!(["a"]), false).is_err());
+    }
+}
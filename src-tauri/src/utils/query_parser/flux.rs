@@ -0,0 +1,310 @@
+/**
+ * Flux 词法分析
+ *
+ * Flux 是函数式脚本语言，这里不构建完整语法树，只做带位置的词法分析，
+ * 检查括号匹配、字符串闭合和管道结构，并收集函数调用用于语句分类
+ */
+
+use super::lexer::{Token, TokenKind};
+use super::{FluxPipeline, FunctionCall, ParseError, ParsedQuery, QueryDialect, Statement, StatementBody, StatementKind};
+
+const DURATION_UNITS: &[&str] = &["ns", "us", "µs", "ms", "mo", "s", "m", "h", "d", "w", "y"];
+
+struct FluxLexer<'a> {
+    input: &'a str,
+    chars: Vec<(usize, char)>,
+    pos: usize,
+    keep_comments: bool,
+    tokens: Vec<Token>,
+    errors: Vec<ParseError>,
+}
+
+impl<'a> FluxLexer<'a> {
+    fn peek(&self, ahead: usize) -> Option<char> {
+        self.chars.get(self.pos + ahead).map(|(_, c)| *c)
+    }
+
+    fn offset(&self) -> usize {
+        self.chars.get(self.pos).map(|(i, _)| *i).unwrap_or(self.input.len())
+    }
+
+    fn push(&mut self, kind: TokenKind, start: usize) {
+        let end = self.offset();
+        let text = self.input[start..end].to_string();
+        self.tokens.push(Token { kind, text, span: start..end });
+    }
+
+    fn skip_while<F: Fn(char) -> bool>(&mut self, predicate: F) {
+        while self.peek(0).is_some_and(&predicate) {
+            self.pos += 1;
+        }
+    }
+
+    /// 前一个词法单元之后的 / 是否应视为正则表达式
+    fn regex_allowed(&self) -> bool {
+        match self.tokens.iter().rev().find(|t| t.kind != TokenKind::Comment) {
+            None => true,
+            Some(token) => match token.kind {
+                TokenKind::Operator => true,
+                TokenKind::Punct => matches!(token.text.as_str(), "(" | "," | "[" | "{" | ":"),
+                _ => false,
+            },
+        }
+    }
+
+    fn run(mut self) -> (Vec<Token>, Vec<ParseError>) {
+        while let Some(c) = self.peek(0) {
+            let start = self.offset();
+            match c {
+                c if c.is_whitespace() => self.pos += 1,
+                '/' if self.peek(1) == Some('/') => {
+                    self.skip_while(|c| c != '\n');
+                    if self.keep_comments {
+                        self.push(TokenKind::Comment, start);
+                    }
+                }
+                '/' if self.regex_allowed() => self.lex_regex(start),
+                '"' => self.lex_string(start),
+                c if c.is_ascii_digit() => self.lex_number(start),
+                c if c.is_alphabetic() || c == '_' => {
+                    self.skip_while(|c| c.is_alphanumeric() || c == '_');
+                    self.push(TokenKind::Ident, start);
+                }
+                '(' | ')' | '[' | ']' | '{' | '}' | ',' | '.' | ':' | ';' => {
+                    self.pos += 1;
+                    self.push(TokenKind::Punct, start);
+                }
+                _ => {
+                    let two: String = [Some(c), self.peek(1)].iter().flatten().collect();
+                    if matches!(two.as_str(), "|>" | "=>" | "<-" | "==" | "!=" | "<=" | ">=" | "=~" | "!~") {
+                        self.pos += 2;
+                        self.push(TokenKind::Operator, start);
+                    } else if "=<>+-*/%^?".contains(c) {
+                        self.pos += 1;
+                        self.push(TokenKind::Operator, start);
+                    } else {
+                        self.pos += 1;
+                        self.errors.push(ParseError::at(self.input, start, &format!("无法识别的字符 '{}'", c)));
+                    }
+                }
+            }
+        }
+        (self.tokens, self.errors)
+    }
+
+    fn lex_string(&mut self, start: usize) {
+        self.pos += 1;
+        loop {
+            match self.peek(0) {
+                None => {
+                    self.errors.push(ParseError::at(self.input, start, "字符串未闭合"));
+                    break;
+                }
+                Some('\\') => self.pos += 2,
+                Some('"') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(_) => self.pos += 1,
+            }
+        }
+        self.push(TokenKind::String, start);
+    }
+
+    fn lex_regex(&mut self, start: usize) {
+        self.pos += 1;
+        loop {
+            match self.peek(0) {
+                None | Some('\n') => {
+                    self.errors.push(ParseError::at(self.input, start, "正则表达式未闭合"));
+                    break;
+                }
+                Some('\\') => self.pos += 2,
+                Some('/') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(_) => self.pos += 1,
+            }
+        }
+        self.push(TokenKind::Regex, start);
+    }
+
+    fn lex_number(&mut self, start: usize) {
+        self.skip_while(|c| c.is_ascii_digit());
+
+        // 2024-01-01T00:00:00Z 形式的时间字面量
+        if self.offset() - start == 4 && self.peek(0) == Some('-') && self.peek(1).is_some_and(|c| c.is_ascii_digit()) {
+            self.skip_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | ':' | '.' | '+'));
+            self.push(TokenKind::Time, start);
+            return;
+        }
+
+        if self.peek(0) == Some('.') && self.peek(1).is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+            self.skip_while(|c| c.is_ascii_digit());
+            self.push(TokenKind::Number, start);
+            return;
+        }
+
+        let mut is_duration = false;
+        while let Some(unit) = self.duration_unit() {
+            is_duration = true;
+            self.pos += unit.chars().count();
+            if !self.peek(0).is_some_and(|c| c.is_ascii_digit()) {
+                break;
+            }
+            self.skip_while(|c| c.is_ascii_digit());
+        }
+        self.push(if is_duration { TokenKind::Duration } else { TokenKind::Number }, start);
+    }
+
+    fn duration_unit(&self) -> Option<&'static str> {
+        let rest = &self.input[self.offset()..];
+        DURATION_UNITS.iter().copied().find(|unit| {
+            rest.starts_with(unit)
+                && !rest[unit.len()..]
+                    .chars()
+                    .next()
+                    .is_some_and(|c| c.is_alphabetic() || c == '_')
+        })
+    }
+}
+
+/// 将 Flux 脚本拆分为词法单元，跳过注释
+pub fn tokenize(input: &str) -> (Vec<Token>, Vec<ParseError>) {
+    run_lexer(input, false)
+}
+
+/// 与 [`tokenize`] 相同，但注释作为 [`TokenKind::Comment`] 保留在结果中
+pub fn tokenize_with_comments(input: &str) -> (Vec<Token>, Vec<ParseError>) {
+    run_lexer(input, true)
+}
+
+fn run_lexer(input: &str, keep_comments: bool) -> (Vec<Token>, Vec<ParseError>) {
+    FluxLexer {
+        input,
+        chars: input.char_indices().collect(),
+        pos: 0,
+        keep_comments,
+        tokens: Vec::new(),
+        errors: Vec::new(),
+    }
+    .run()
+}
+
+fn closing(open: &str) -> &'static str {
+    match open {
+        "(" => ")",
+        "[" => "]",
+        _ => "}",
+    }
+}
+
+/// 解析 Flux 脚本，整段脚本视为一条查询语句
+pub fn parse(input: &str) -> ParsedQuery {
+    let (tokens, mut errors) = tokenize(input);
+
+    // 括号匹配
+    let mut open: Vec<&Token> = Vec::new();
+    for token in tokens.iter().filter(|t| t.kind == TokenKind::Punct) {
+        match token.text.as_str() {
+            "(" | "[" | "{" => open.push(token),
+            ")" | "]" | "}" => match open.pop() {
+                Some(o) if closing(&o.text) == token.text => {}
+                Some(o) => errors.push(ParseError::at(
+                    input,
+                    token.span.start,
+                    &format!("括号不匹配，期望 '{}'", closing(&o.text)),
+                )),
+                None => errors.push(ParseError::at(input, token.span.start, "多余的右括号")),
+            },
+            _ => {}
+        }
+    }
+    for token in open {
+        errors.push(ParseError::at(input, token.span.start, "括号未闭合"));
+    }
+
+    // 函数调用：标识符（可带包名）紧跟左括号
+    let mut calls = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if token.kind != TokenKind::Ident || !tokens.get(i + 1).is_some_and(|t| t.is_punct("(")) {
+            continue;
+        }
+        let qualified = i >= 2 && tokens[i - 1].is_punct(".") && tokens[i - 2].kind == TokenKind::Ident;
+        let (name, start) = if qualified {
+            (format!("{}.{}", tokens[i - 2].text, token.text), tokens[i - 2].span.start)
+        } else {
+            (token.text.clone(), token.span.start)
+        };
+        calls.push(FunctionCall { name, args: Vec::new(), span: start..token.span.end });
+    }
+
+    // 管道运算符之后必须是函数调用
+    for (i, token) in tokens.iter().enumerate() {
+        if !token.is_operator("|>") {
+            continue;
+        }
+        let next = &tokens[i + 1..];
+        let is_call = match next {
+            [name, paren, ..] if name.kind == TokenKind::Ident && paren.is_punct("(") => true,
+            [pkg, dot, name, paren, ..] => {
+                pkg.kind == TokenKind::Ident && dot.is_punct(".") && name.kind == TokenKind::Ident && paren.is_punct("(")
+            }
+            _ => false,
+        };
+        if !is_call {
+            let offset = next.first().map_or(input.len(), |t| t.span.start);
+            errors.push(ParseError::at(input, offset, "管道运算符 |> 之后应为函数调用"));
+        }
+    }
+
+    errors.sort_by_key(|e| e.offset);
+    let statements = match (tokens.first(), tokens.last()) {
+        (Some(first), Some(last)) => vec![Statement {
+            kind: StatementKind::Select,
+            object: None,
+            body: StatementBody::Flux(FluxPipeline { calls }),
+            span: first.span.start..last.span.end,
+        }],
+        _ => Vec::new(),
+    };
+    ParsedQuery { dialect: QueryDialect::Flux, statements, errors }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flux_pipeline() {
+        let parsed = parse(
+            "// cpu\nfrom(bucket: \"b\")\n  |> range(start: -1h)\n  |> filter(fn: (r) => r.host =~ /web/)\n  |> aggregateWindow(every: 1m, fn: mean)",
+        );
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        let statement = &parsed.statements[0];
+        let names: Vec<_> = statement.functions().iter().map(|c| c.name.clone()).collect();
+        assert_eq!(names, vec!["from", "range", "filter", "aggregateWindow"]);
+        assert_eq!(statement.statement_type(), "SELECT_AGGREGATE");
+        assert!(!statement.writes_data());
+    }
+
+    #[test]
+    fn test_flux_errors() {
+        let parsed = parse("from(bucket: \"b\")\n  |> range(start: -1h\n  |> 42");
+        assert_eq!(parsed.errors.len(), 2);
+        assert_eq!((parsed.errors[0].line, parsed.errors[0].column), (2, 11));
+        assert_eq!(parsed.errors[1].message, "管道运算符 |> 之后应为函数调用");
+
+        let parsed = parse("from(bucket: \"b) |> to(bucket: \"x\")");
+        assert!(parsed.errors.iter().any(|e| e.message == "字符串未闭合"));
+    }
+
+    #[test]
+    fn test_flux_writes() {
+        let parsed = parse("from(bucket: \"a\") |> range(start: 2024-01-01T00:00:00Z) |> to(bucket: \"b\")");
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        assert!(parsed.statements[0].writes_data());
+    }
+}
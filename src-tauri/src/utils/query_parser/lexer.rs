@@ -0,0 +1,391 @@
+/**
+ * InfluxQL / IoTDB SQL / SQL 词法分析
+ *
+ * 词法错误（未闭合的字符串、注释等）不会中断分析，而是记录错误后继续，
+ * 以便后续的语句划分和危险操作检测仍然能覆盖整个输入
+ */
+
+use super::{ParseError, QueryDialect};
+use std::ops::Range;
+
+/// 词法单元类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Ident,       // 标识符或关键字
+    QuotedIdent, // "name" 或 `name`
+    String,      // 'text'（IoTDB 中的 "text" 也是字符串）
+    Number,
+    Duration, // 5m、1h30m
+    Time,     // 时间字面量，如 2024-01-01T00:00:00Z（Flux 与 IoTDB）
+    Regex,    // /pattern/（仅 InfluxQL）
+    Param,    // $name
+    Operator, // = != <> < <= > >= =~ !~ + - * / % || :: **
+    Punct,    // ( ) [ ] , . ;
+    Comment,  // 仅在保留注释时产生
+}
+
+/// 词法单元
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String, // 字符串和带引号的标识符为去掉引号后的内容
+    pub span: Range<usize>,
+}
+
+impl Token {
+    /// 是否为指定关键字（忽略大小写）
+    pub fn is_keyword(&self, keyword: &str) -> bool {
+        self.kind == TokenKind::Ident && self.text.eq_ignore_ascii_case(keyword)
+    }
+
+    pub fn is_punct(&self, punct: &str) -> bool {
+        self.kind == TokenKind::Punct && self.text == punct
+    }
+
+    pub fn is_operator(&self, operator: &str) -> bool {
+        self.kind == TokenKind::Operator && self.text == operator
+    }
+}
+
+// 出现在这些关键字之后的 / 是正则表达式的开始
+const REGEX_PRECEDING_KEYWORDS: &[&str] = &["FROM", "BY", "AND", "OR", "WHERE", "KEY", "NOT"];
+
+const DURATION_UNITS: &[&str] = &["ns", "us", "µs", "ms", "mo", "s", "m", "h", "d", "w", "y", "u"];
+
+struct Lexer<'a> {
+    input: &'a str,
+    chars: Vec<(usize, char)>,
+    pos: usize,
+    dialect: QueryDialect,
+    keep_comments: bool,
+    tokens: Vec<Token>,
+    errors: Vec<ParseError>,
+}
+
+impl<'a> Lexer<'a> {
+    fn peek(&self, ahead: usize) -> Option<char> {
+        self.chars.get(self.pos + ahead).map(|(_, c)| *c)
+    }
+
+    fn offset(&self) -> usize {
+        self.chars.get(self.pos).map(|(i, _)| *i).unwrap_or(self.input.len())
+    }
+
+    fn push(&mut self, kind: TokenKind, text: String, start: usize) {
+        let end = self.offset();
+        self.tokens.push(Token { kind, text, span: start..end });
+    }
+
+    fn error(&mut self, message: &str, offset: usize) {
+        self.errors.push(ParseError::at(self.input, offset, message));
+    }
+
+    /// 前一个词法单元之后的 / 是否应视为正则表达式
+    fn regex_allowed(&self) -> bool {
+        if self.dialect != QueryDialect::InfluxQL {
+            return false;
+        }
+        match self.tokens.iter().rev().find(|t| t.kind != TokenKind::Comment) {
+            None => true,
+            Some(token) => match token.kind {
+                TokenKind::Operator => true,
+                TokenKind::Punct => token.text == "(" || token.text == ",",
+                TokenKind::Ident => REGEX_PRECEDING_KEYWORDS.iter().any(|k| token.is_keyword(k)),
+                _ => false,
+            },
+        }
+    }
+
+    fn run(mut self) -> (Vec<Token>, Vec<ParseError>) {
+        while let Some(c) = self.peek(0) {
+            let start = self.offset();
+            match c {
+                c if c.is_whitespace() => self.pos += 1,
+                '-' if self.peek(1) == Some('-') => self.skip_line(start),
+                '/' if self.peek(1) == Some('*') => self.skip_block_comment(start),
+                '/' if self.regex_allowed() => self.lex_regex(start),
+                '\'' => self.lex_quoted('\'', TokenKind::String, start),
+                '"' if self.dialect == QueryDialect::IoTDB => self.lex_quoted('"', TokenKind::String, start),
+                '"' => self.lex_quoted('"', TokenKind::QuotedIdent, start),
+                '`' => self.lex_quoted('`', TokenKind::QuotedIdent, start),
+                '$' if self.peek(1).is_some_and(|n| n.is_alphanumeric() || n == '_') => {
+                    self.pos += 1;
+                    let name = self.take_while(|c| c.is_alphanumeric() || c == '_');
+                    self.push(TokenKind::Param, name, start);
+                }
+                c if c.is_ascii_digit() || (c == '.' && self.peek(1).is_some_and(|n| n.is_ascii_digit())) => {
+                    self.lex_number(start)
+                }
+                c if c.is_alphabetic() || c == '_' => {
+                    let word = self.take_while(|c| c.is_alphanumeric() || c == '_');
+                    self.push(TokenKind::Ident, word, start);
+                }
+                '(' | ')' | '[' | ']' | ',' | '.' | ';' => {
+                    self.pos += 1;
+                    self.push(TokenKind::Punct, c.to_string(), start);
+                }
+                _ => self.lex_operator(c, start),
+            }
+        }
+        (self.tokens, self.errors)
+    }
+
+    fn take_while<F: Fn(char) -> bool>(&mut self, predicate: F) -> String {
+        let mut text = String::new();
+        while let Some(c) = self.peek(0) {
+            if !predicate(c) {
+                break;
+            }
+            text.push(c);
+            self.pos += 1;
+        }
+        text
+    }
+
+    fn skip_line(&mut self, start: usize) {
+        while let Some(c) = self.peek(0) {
+            if c == '\n' {
+                break;
+            }
+            self.pos += 1;
+        }
+        self.push_comment(start);
+    }
+
+    fn push_comment(&mut self, start: usize) {
+        if self.keep_comments {
+            let text = self.input[start..self.offset()].to_string();
+            self.push(TokenKind::Comment, text, start);
+        }
+    }
+
+    fn skip_block_comment(&mut self, start: usize) {
+        self.pos += 2;
+        loop {
+            match self.peek(0) {
+                None => {
+                    self.error("注释未闭合", start);
+                    break;
+                }
+                Some('*') if self.peek(1) == Some('/') => {
+                    self.pos += 2;
+                    break;
+                }
+                _ => self.pos += 1,
+            }
+        }
+        self.push_comment(start);
+    }
+
+    fn lex_quoted(&mut self, quote: char, kind: TokenKind, start: usize) {
+        self.pos += 1;
+        let mut text = String::new();
+        loop {
+            match self.peek(0) {
+                None => {
+                    let message = if kind == TokenKind::String { "字符串未闭合" } else { "标识符引号未闭合" };
+                    self.error(message, start);
+                    break;
+                }
+                Some('\\') => {
+                    if let Some(next) = self.peek(1) {
+                        text.push(next);
+                    }
+                    self.pos += 2;
+                }
+                Some(c) if c == quote => {
+                    // 连续两个引号表示转义
+                    if self.peek(1) == Some(quote) {
+                        text.push(quote);
+                        self.pos += 2;
+                    } else {
+                        self.pos += 1;
+                        break;
+                    }
+                }
+                Some(c) => {
+                    text.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+        self.push(kind, text, start);
+    }
+
+    fn lex_regex(&mut self, start: usize) {
+        self.pos += 1;
+        let mut text = String::new();
+        loop {
+            match self.peek(0) {
+                None | Some('\n') => {
+                    self.error("正则表达式未闭合", start);
+                    break;
+                }
+                Some('\\') if self.peek(1) == Some('/') => {
+                    text.push('/');
+                    self.pos += 2;
+                }
+                Some('/') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(c) => {
+                    text.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+        self.push(TokenKind::Regex, text, start);
+    }
+
+    fn lex_number(&mut self, start: usize) {
+        let mut text = self.take_while(|c| c.is_ascii_digit());
+        if self.peek(0) == Some('.') && self.peek(1).is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+            text.push('.');
+            text.push_str(&self.take_while(|c| c.is_ascii_digit()));
+        }
+        if matches!(self.peek(0), Some('e' | 'E'))
+            && (self.peek(1).is_some_and(|c| c.is_ascii_digit())
+                || (matches!(self.peek(1), Some('+' | '-')) && self.peek(2).is_some_and(|c| c.is_ascii_digit())))
+        {
+            text.push(self.peek(0).unwrap_or('e'));
+            self.pos += 1;
+            if let Some(sign @ ('+' | '-')) = self.peek(0) {
+                text.push(sign);
+                self.pos += 1;
+            }
+            text.push_str(&self.take_while(|c| c.is_ascii_digit()));
+        }
+
+        // IoTDB 允许不加引号的时间字面量，如 2024-01-01T00:00:00
+        let is_date = text.len() == 4
+            && matches!(self.peek(0), Some('-' | '/'))
+            && self.peek(1).is_some_and(|c| c.is_ascii_digit());
+        if self.dialect == QueryDialect::IoTDB && is_date {
+            text.push_str(&self.take_while(|c| c.is_ascii_digit() || matches!(c, '-' | '/' | ':' | '.' | '+' | 'T' | 'Z')));
+            self.push(TokenKind::Time, text, start);
+            return;
+        }
+
+        // InfluxQL 与 IoTDB 支持 5m、1h30m 形式的时长字面量
+        if self.dialect != QueryDialect::Sql && !text.contains('.') && self.duration_unit().is_some() {
+            while let Some(unit) = self.duration_unit() {
+                text.push_str(unit);
+                self.pos += unit.chars().count();
+                let digits = self.take_while(|c| c.is_ascii_digit());
+                if digits.is_empty() {
+                    break;
+                }
+                text.push_str(&digits);
+            }
+            self.push(TokenKind::Duration, text, start);
+            return;
+        }
+
+        self.push(TokenKind::Number, text, start);
+    }
+
+    /// 当前位置的时长单位（单位之后不能紧跟字母）
+    fn duration_unit(&self) -> Option<&'static str> {
+        let rest = &self.input[self.offset()..];
+        DURATION_UNITS.iter().copied().find(|unit| {
+            rest.starts_with(unit)
+                && !rest[unit.len()..]
+                    .chars()
+                    .next()
+                    .is_some_and(|c| c.is_alphabetic() || c == '_')
+        })
+    }
+
+    fn lex_operator(&mut self, c: char, start: usize) {
+        let two: String = [Some(c), self.peek(1)].iter().flatten().collect();
+        let is_two = matches!(two.as_str(), "!=" | "<>" | "<=" | ">=" | "=~" | "!~" | "||" | "::" | "==")
+            || (two == "**" && self.dialect == QueryDialect::IoTDB);
+        if is_two {
+            self.pos += 2;
+            self.push(TokenKind::Operator, two, start);
+            return;
+        }
+
+        self.pos += 1;
+        if matches!(c, '=' | '<' | '>' | '+' | '-' | '*' | '/' | '%' | '!' | '&' | '|' | '^' | ':') {
+            self.push(TokenKind::Operator, c.to_string(), start);
+        } else {
+            self.error(&format!("无法识别的字符 '{}'", c), start);
+        }
+    }
+}
+
+/// 将查询拆分为词法单元，跳过注释；词法错误随结果一起返回
+pub fn tokenize(input: &str, dialect: QueryDialect) -> (Vec<Token>, Vec<ParseError>) {
+    run_lexer(input, dialect, false)
+}
+
+/// 与 [`tokenize`] 相同，但注释作为 [`TokenKind::Comment`] 保留在结果中
+pub fn tokenize_with_comments(input: &str, dialect: QueryDialect) -> (Vec<Token>, Vec<ParseError>) {
+    run_lexer(input, dialect, true)
+}
+
+fn run_lexer(input: &str, dialect: QueryDialect, keep_comments: bool) -> (Vec<Token>, Vec<ParseError>) {
+    Lexer {
+        input,
+        chars: input.char_indices().collect(),
+        pos: 0,
+        dialect,
+        keep_comments,
+        tokens: Vec::new(),
+        errors: Vec::new(),
+    }
+    .run()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(input: &str, dialect: QueryDialect) -> Vec<(TokenKind, String)> {
+        tokenize(input, dialect).0.into_iter().map(|t| (t.kind, t.text)).collect()
+    }
+
+    #[test]
+    fn test_comments_and_literals() {
+        let tokens = kinds(
+            "-- comment\nSELECT \"my field\", 'it''s' /* x */ FROM cpu WHERE host =~ /^web\\/1$/ AND time > now() - 1h30m",
+            QueryDialect::InfluxQL,
+        );
+        assert_eq!(tokens[0], (TokenKind::Ident, "SELECT".to_string()));
+        assert_eq!(tokens[1], (TokenKind::QuotedIdent, "my field".to_string()));
+        assert_eq!(tokens[3], (TokenKind::String, "it's".to_string()));
+        assert!(tokens.contains(&(TokenKind::Regex, "^web/1$".to_string())));
+        assert_eq!(tokens.last().unwrap(), &(TokenKind::Duration, "1h30m".to_string()));
+    }
+
+    #[test]
+    fn test_division_is_not_regex() {
+        let tokens = kinds("SELECT a / 2 FROM m", QueryDialect::InfluxQL);
+        assert_eq!(tokens[2], (TokenKind::Operator, "/".to_string()));
+        let tokens = kinds("SELECT 1m, 2e3, 1.5 FROM t", QueryDialect::Sql);
+        assert_eq!(tokens[1], (TokenKind::Number, "1".to_string()));
+        assert_eq!(tokens[2], (TokenKind::Ident, "m".to_string()));
+        assert_eq!(tokens[4], (TokenKind::Number, "2e3".to_string()));
+    }
+
+    #[test]
+    fn test_iotdb_paths() {
+        let tokens = kinds("SELECT * FROM root.sg.** WHERE s1 = \"a\"", QueryDialect::IoTDB);
+        assert!(tokens.contains(&(TokenKind::Operator, "**".to_string())));
+        assert_eq!(tokens.last().unwrap(), &(TokenKind::String, "a".to_string()));
+    }
+
+    #[test]
+    fn test_errors_with_positions() {
+        let (tokens, errors) = tokenize("SELECT 'abc\nFROM x", QueryDialect::InfluxQL);
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(errors[0].line, 1);
+        assert_eq!(errors[0].column, 8);
+
+        let (_, errors) = tokenize("SELECT 1 /* open", QueryDialect::Sql);
+        assert_eq!(errors[0].message, "注释未闭合");
+    }
+}
@@ -0,0 +1,431 @@
+/**
+ * 查询解析器
+ *
+ * 为 InfluxQL、IoTDB SQL 和 SQL（InfluxDB 3.x）提供递归下降解析，为 Flux 提供词法分析，
+ * 生成的语法树用于语句类型识别、危险操作检测以及带位置的错误提示
+ */
+
+pub mod flux;
+pub mod lexer;
+pub mod sql;
+
+use lexer::{tokenize, TokenKind};
+use serde::Serialize;
+use std::fmt;
+use std::ops::Range;
+
+/// 查询方言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum QueryDialect {
+    InfluxQL,
+    Sql,
+    IoTDB,
+    Flux,
+}
+
+impl QueryDialect {
+    /// 由前端的查询语言名称得到方言
+    pub fn from_language(language: &str) -> Option<Self> {
+        match language.to_lowercase().as_str() {
+            "influxql" => Some(QueryDialect::InfluxQL),
+            "sql" => Some(QueryDialect::Sql),
+            "iotdb" | "iotdb-sql" => Some(QueryDialect::IoTDB),
+            "flux" => Some(QueryDialect::Flux),
+            _ => None,
+        }
+    }
+}
+
+/// 解析错误，行列号从 1 开始
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParseError {
+    pub message: String,
+    pub offset: usize,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl ParseError {
+    /// 在输入的指定字节偏移处创建错误
+    pub fn at(input: &str, offset: usize, message: &str) -> Self {
+        let (line, column) = line_column(input, offset);
+        Self {
+            message: message.to_string(),
+            offset,
+            line,
+            column,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "第 {} 行第 {} 列: {}", self.line, self.column, self.message)
+    }
+}
+
+/// 字节偏移对应的行列号（列按字符计数）
+pub fn line_column(input: &str, offset: usize) -> (u32, u32) {
+    let (mut line, mut column) = (1, 1);
+    for (_, c) in input.char_indices().take_while(|(i, _)| *i < offset) {
+        if c == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+    (line, column)
+}
+
+/// 语句类型
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatementKind {
+    Select,
+    Insert,
+    Delete,
+    Update,
+    Create,
+    Drop,
+    Alter,
+    Show,
+    Explain,
+    Describe,
+    Grant,
+    Revoke,
+    Kill,
+    Truncate,
+    Set,
+    Other(String),
+}
+
+impl StatementKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatementKind::Select => "SELECT",
+            StatementKind::Insert => "INSERT",
+            StatementKind::Delete => "DELETE",
+            StatementKind::Update => "UPDATE",
+            StatementKind::Create => "CREATE",
+            StatementKind::Drop => "DROP",
+            StatementKind::Alter => "ALTER",
+            StatementKind::Show => "SHOW",
+            StatementKind::Explain => "EXPLAIN",
+            StatementKind::Describe => "DESCRIBE",
+            StatementKind::Grant => "GRANT",
+            StatementKind::Revoke => "REVOKE",
+            StatementKind::Kill => "KILL",
+            StatementKind::Truncate => "TRUNCATE",
+            StatementKind::Set => "SET",
+            StatementKind::Other(_) => "UNKNOWN",
+        }
+    }
+}
+
+/// 函数调用
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionCall {
+    pub name: String,
+    pub args: Vec<Expr>,
+    pub span: Range<usize>,
+}
+
+/// 表达式
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Identifier(String), // 可能带限定名，如 db.rp.m、root.sg.d1.s1
+    Literal(String),    // 数字、字符串、时长、布尔值和 NULL
+    Regex(String),
+    Param(String),
+    Wildcard,
+    Call(FunctionCall),
+    Unary { op: String, expr: Box<Expr> },
+    Binary { op: String, left: Box<Expr>, right: Box<Expr> },
+    List(Vec<Expr>),
+    Subquery(Box<SelectStatement>),
+}
+
+impl Expr {
+    fn visit<'a>(&'a self, f: &mut dyn FnMut(&'a Expr)) {
+        f(self);
+        match self {
+            Expr::Call(call) => call.args.iter().for_each(|arg| arg.visit(f)),
+            Expr::Unary { expr, .. } => expr.visit(f),
+            Expr::Binary { left, right, .. } => {
+                left.visit(f);
+                right.visit(f);
+            }
+            Expr::List(items) => items.iter().for_each(|item| item.visit(f)),
+            Expr::Subquery(select) => select.visit_exprs(f),
+            _ => {}
+        }
+    }
+}
+
+/// FROM 子句中的数据源
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    Name(String),
+    Regex(String),
+    Subquery(Box<SelectStatement>),
+}
+
+/// SELECT 语句
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SelectStatement {
+    pub ctes: Vec<(String, SelectStatement)>,
+    pub fields: Vec<Expr>,
+    pub into: Option<String>,
+    pub sources: Vec<Source>,
+    pub condition: Option<Expr>,
+    pub group_by: Vec<Expr>,
+    pub having: Option<Expr>,
+    pub order_by: Vec<Expr>,
+    pub limit: Option<u64>,
+    pub union: Option<Box<SelectStatement>>,
+}
+
+impl SelectStatement {
+    fn visit_exprs<'a>(&'a self, f: &mut dyn FnMut(&'a Expr)) {
+        for (_, cte) in &self.ctes {
+            cte.visit_exprs(f);
+        }
+        for source in &self.sources {
+            if let Source::Subquery(select) = source {
+                select.visit_exprs(f);
+            }
+        }
+        let clauses = self.fields.iter().chain(&self.condition).chain(&self.group_by).chain(&self.having).chain(&self.order_by);
+        for expr in clauses {
+            expr.visit(f);
+        }
+        if let Some(union) = &self.union {
+            union.visit_exprs(f);
+        }
+    }
+
+    /// 条件中是否包含 time 列的比较
+    pub fn has_time_condition(&self) -> bool {
+        let mut found = false;
+        if let Some(condition) = &self.condition {
+            condition.visit(&mut |expr| {
+                if let Expr::Binary { left, right, .. } = expr {
+                    let is_time = |e: &Expr| matches!(e, Expr::Identifier(name) if name.eq_ignore_ascii_case("time"));
+                    found |= is_time(left) || is_time(right);
+                }
+            });
+        }
+        found
+    }
+}
+
+/// Flux 管道信息
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FluxPipeline {
+    pub calls: Vec<FunctionCall>,
+}
+
+/// 语句内容
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatementBody {
+    None,
+    Select(Box<SelectStatement>),
+    Flux(FluxPipeline),
+}
+
+/// 单条语句
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statement {
+    pub kind: StatementKind,
+    pub object: Option<String>, // 操作对象类型，如 DATABASE、MEASUREMENT、RETENTION POLICY
+    pub body: StatementBody,
+    pub span: Range<usize>,
+}
+
+// 聚合函数（InfluxQL、SQL、IoTDB）
+const AGGREGATE_FUNCTIONS: &[&str] = &[
+    "count", "sum", "mean", "avg", "max", "min", "stddev", "variance", "median", "mode", "spread", "integral",
+    "distinct", "first", "last", "percentile", "sample", "top", "bottom", "first_value", "last_value",
+    "max_value", "min_value", "max_time", "min_time", "extreme", "time_weighted_avg",
+];
+
+// Flux 聚合函数
+const FLUX_AGGREGATE_FUNCTIONS: &[&str] = &[
+    "aggregateWindow", "mean", "sum", "count", "max", "min", "median", "mode", "spread", "stddev", "integral",
+    "quantile", "first", "last", "distinct", "reduce",
+];
+
+impl Statement {
+    /// 语句中的全部函数调用（包括子查询）
+    pub fn functions(&self) -> Vec<&FunctionCall> {
+        let mut calls = Vec::new();
+        match &self.body {
+            StatementBody::Select(select) => select.visit_exprs(&mut |expr| {
+                if let Expr::Call(call) = expr {
+                    calls.push(call);
+                }
+            }),
+            StatementBody::Flux(pipeline) => calls.extend(pipeline.calls.iter()),
+            StatementBody::None => {}
+        }
+        calls
+    }
+
+    pub fn select(&self) -> Option<&SelectStatement> {
+        match &self.body {
+            StatementBody::Select(select) => Some(select.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// 是否为聚合查询
+    pub fn is_aggregate(&self) -> bool {
+        let list = match self.body {
+            StatementBody::Flux(_) => FLUX_AGGREGATE_FUNCTIONS,
+            _ => AGGREGATE_FUNCTIONS,
+        };
+        self.functions()
+            .iter()
+            .any(|call| list.iter().any(|name| call.name.eq_ignore_ascii_case(name)))
+    }
+
+    /// 是否为分组查询
+    pub fn has_group_by(&self) -> bool {
+        match &self.body {
+            StatementBody::Select(select) => !select.group_by.is_empty(),
+            StatementBody::Flux(pipeline) => pipeline.calls.iter().any(|call| call.name == "group" || call.name == "window"),
+            StatementBody::None => false,
+        }
+    }
+
+    /// 是否会写入数据（SELECT ... INTO 或 Flux 的 to()）
+    pub fn writes_data(&self) -> bool {
+        match &self.body {
+            StatementBody::Select(select) => select.into.is_some(),
+            StatementBody::Flux(pipeline) => pipeline
+                .calls
+                .iter()
+                .any(|call| matches!(call.name.as_str(), "to" | "experimental.to" | "influxdb.wideTo")),
+            StatementBody::None => false,
+        }
+    }
+
+    /// 兼容原有接口的语句类型字符串，SELECT 进一步区分聚合与分组查询
+    pub fn statement_type(&self) -> String {
+        if self.kind == StatementKind::Select {
+            if self.is_aggregate() {
+                return "SELECT_AGGREGATE".to_string();
+            }
+            if self.has_group_by() {
+                return "SELECT_GROUP".to_string();
+            }
+        }
+        self.kind.as_str().to_string()
+    }
+}
+
+/// 解析结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedQuery {
+    pub dialect: QueryDialect,
+    pub statements: Vec<Statement>,
+    pub errors: Vec<ParseError>,
+}
+
+/// 根据查询内容推断方言
+///
+/// 只根据开头判断 Flux（import、option、from(...)、buckets() 这类函数调用或变量赋值），
+/// 不能因为查询中间出现 |> 就整体按 Flux 处理，否则 `DELETE FROM /cpu|>/` 会绕过权限检查
+pub fn detect_dialect(input: &str) -> QueryDialect {
+    let (tokens, _) = tokenize(strip_flux_comments(input), QueryDialect::Sql);
+
+    let first = tokens.first();
+    let second = tokens.get(1);
+    let is_call_or_assignment = first.is_some_and(|t| {
+        t.kind == TokenKind::Ident && !["SELECT", "WITH", "SHOW", "DELETE", "DROP", "INSERT"].iter().any(|k| t.is_keyword(k))
+    }) && second.is_some_and(|t| t.is_punct("(") || t.is_operator("="));
+    let is_flux = first.is_some_and(|t| t.is_keyword("import") || t.is_keyword("option")) || is_call_or_assignment;
+    if is_flux {
+        return QueryDialect::Flux;
+    }
+
+    let is_iotdb = tokens
+        .iter()
+        .enumerate()
+        .any(|(i, t)| t.kind == TokenKind::Ident && t.text.eq_ignore_ascii_case("root") && tokens.get(i + 1).is_some_and(|n| n.is_punct(".")));
+    if is_iotdb {
+        return QueryDialect::IoTDB;
+    }
+
+    let is_sql = tokens.first().is_some_and(|t| t.is_keyword("WITH"))
+        || tokens.iter().any(|t| t.is_keyword("JOIN") || t.is_keyword("UNION") || t.is_keyword("information_schema"));
+    if is_sql {
+        return QueryDialect::Sql;
+    }
+
+    QueryDialect::InfluxQL
+}
+
+/// 去掉开头的 Flux 行注释（//），SQL 词法不认识这种注释
+fn strip_flux_comments(input: &str) -> &str {
+    let mut rest = input.trim_start();
+    while rest.starts_with("//") {
+        rest = rest.find('\n').map_or("", |i| rest[i + 1..].trim_start());
+    }
+    rest
+}
+
+/// 自动识别方言并解析
+pub fn parse(input: &str) -> ParsedQuery {
+    parse_with_dialect(input, detect_dialect(input))
+}
+
+/// 按指定方言解析
+pub fn parse_with_dialect(input: &str, dialect: QueryDialect) -> ParsedQuery {
+    match dialect {
+        QueryDialect::Flux => flux::parse(input),
+        _ => sql::parse(input, dialect),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_dialect() {
+        assert_eq!(detect_dialect("from(bucket: \"b\") |> range(start: -1h)"), QueryDialect::Flux);
+        assert_eq!(detect_dialect("import \"strings\"\nfrom(bucket: \"b\")"), QueryDialect::Flux);
+        assert_eq!(detect_dialect("SELECT s1 FROM root.sg.d1"), QueryDialect::IoTDB);
+        assert_eq!(detect_dialect("WITH t AS (SELECT 1) SELECT * FROM t"), QueryDialect::Sql);
+        assert_eq!(detect_dialect("SELECT mean(v) FROM cpu WHERE a = '|>'"), QueryDialect::InfluxQL);
+        assert_eq!(detect_dialect("// latest\ndata = from(bucket: \"b\")\ndata |> last()"), QueryDialect::Flux);
+        assert_eq!(detect_dialect("buckets()"), QueryDialect::Flux);
+    }
+
+    #[test]
+    fn test_pipe_does_not_switch_to_flux() {
+        for query in ["DELETE FROM /cpu|>/", "DROP DATABASE mydb // |>"] {
+            assert_eq!(detect_dialect(query), QueryDialect::InfluxQL, "{}", query);
+        }
+        assert_eq!(parse("DELETE FROM /cpu|>/").statements[0].kind, StatementKind::Delete);
+        let parsed = parse("DROP DATABASE mydb // |>");
+        assert_eq!(parsed.statements[0].kind, StatementKind::Drop);
+        assert_eq!(parsed.statements[0].object.as_deref(), Some("DATABASE"));
+    }
+
+    #[test]
+    fn test_statement_type() {
+        let parsed = parse("-- latest\nSELECT mean(usage) FROM cpu GROUP BY time(1m)");
+        assert_eq!(parsed.statements[0].statement_type(), "SELECT_AGGREGATE");
+        assert_eq!(parse("SELECT usage FROM cpu GROUP BY host").statements[0].statement_type(), "SELECT_GROUP");
+        assert_eq!(parse("/* x */ show databases").statements[0].statement_type(), "SHOW");
+        assert_eq!(parse("FROBNICATE").statements[0].statement_type(), "UNKNOWN");
+    }
+
+    #[test]
+    fn test_line_column() {
+        assert_eq!(line_column("ab\ncd", 4), (2, 2));
+        assert_eq!(line_column("中文 x", 7), (1, 4));
+    }
+}
@@ -0,0 +1,985 @@
+/**
+ * InfluxQL / IoTDB SQL / SQL 递归下降解析
+ *
+ * 三种方言共用一套语法：SELECT 语句完整解析为语法树，其余语句识别语句头和操作对象，
+ * 语句体只做括号匹配检查。单条语句出错后跳到下一个分号继续解析
+ */
+
+use super::lexer::{tokenize, Token, TokenKind};
+use super::{
+    Expr, FunctionCall, ParseError, ParsedQuery, QueryDialect, SelectStatement, Source, Statement, StatementBody,
+    StatementKind,
+};
+use std::ops::Range;
+
+// 不能作为标识符或省略 AS 的别名使用的关键字
+const RESERVED: &[&str] = &[
+    "SELECT", "FROM", "WHERE", "GROUP", "ORDER", "LIMIT", "OFFSET", "SLIMIT", "SOFFSET", "INTO", "AS", "FILL",
+    "HAVING", "AND", "OR", "NOT", "ON", "JOIN", "INNER", "LEFT", "RIGHT", "FULL", "CROSS", "OUTER", "NATURAL",
+    "UNION", "EXCEPT", "INTERSECT", "TZ", "ALIGN", "DISABLE", "BY", "WITH", "WHEN", "THEN", "ELSE", "END", "IS",
+    "IN", "LIKE", "ILIKE", "REGEXP", "BETWEEN", "ASC", "DESC", "USING", "CASE",
+];
+
+// 对象类型由两个单词组成时的第二个单词，如 RETENTION POLICY、TAG KEYS、STORAGE GROUP
+const OBJECT_SUFFIXES: &[&str] = &[
+    "KEYS", "VALUES", "POLICY", "POLICIES", "QUERY", "QUERIES", "GROUP", "GROUPS", "CARDINALITY", "SUBSCRIPTION",
+    "SUBSCRIPTIONS", "TABLE", "TABLES", "CACHE", "CACHES", "PATHS", "NODES", "TEMPLATE", "TEMPLATES",
+];
+
+// 非 SELECT 语句的开头关键字，出现在另一条语句中时视为缺少分号的下一条语句
+const STATEMENT_HEADS: &[&str] = &[
+    "INSERT", "DELETE", "UPDATE", "CREATE", "DROP", "ALTER", "SHOW", "EXPLAIN", "GRANT", "REVOKE", "KILL", "TRUNCATE",
+];
+
+const COMPARISON_OPERATORS: &[&str] = &["=", "==", "!=", "<>", "<", "<=", ">", ">=", "=~", "!~"];
+
+type PResult<T> = Result<T, ParseError>;
+
+struct Parser<'a> {
+    input: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+    dialect: QueryDialect,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_at(&self, ahead: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + ahead)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        if token.is_some() {
+            self.pos += 1;
+        }
+        token
+    }
+
+    /// 上一个已消费词法单元的结束位置
+    fn prev_end(&self) -> usize {
+        self.pos.checked_sub(1).and_then(|i| self.tokens.get(i)).map_or(0, |t| t.span.end)
+    }
+
+    fn at_keyword(&self, keyword: &str) -> bool {
+        self.peek().is_some_and(|t| t.is_keyword(keyword))
+    }
+
+    fn at_punct(&self, punct: &str) -> bool {
+        self.peek().is_some_and(|t| t.is_punct(punct))
+    }
+
+    fn at_operator(&self, operator: &str) -> bool {
+        self.peek().is_some_and(|t| t.is_operator(operator))
+    }
+
+    fn at_statement_end(&self) -> bool {
+        self.peek().is_none_or(|t| t.is_punct(";"))
+    }
+
+    /// 是否位于另一条语句的开头；IoTDB 触发器事件（AFTER INSERT、BEFORE DELETE）除外
+    fn at_statement_head(&self) -> bool {
+        let after_trigger_event = self
+            .pos
+            .checked_sub(1)
+            .and_then(|i| self.tokens.get(i))
+            .is_some_and(|t| t.is_keyword("BEFORE") || t.is_keyword("AFTER"));
+        self.peek().is_some_and(is_statement_head) && !after_trigger_event
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let matched = self.at_keyword(keyword);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    /// 仅当整个关键字序列都匹配时才消费
+    fn eat_keywords(&mut self, keywords: &[&str]) -> bool {
+        let matched = keywords
+            .iter()
+            .enumerate()
+            .all(|(i, k)| self.peek_at(i).is_some_and(|t| t.is_keyword(k)));
+        if matched {
+            self.pos += keywords.len();
+        }
+        matched
+    }
+
+    fn eat_punct(&mut self, punct: &str) -> bool {
+        let matched = self.at_punct(punct);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn eat_operator(&mut self, operator: &str) -> bool {
+        let matched = self.at_operator(operator);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> PResult<()> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.unexpected(keyword))
+        }
+    }
+
+    fn expect_punct(&mut self, punct: &str) -> PResult<()> {
+        if self.eat_punct(punct) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("'{}'", punct)))
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> ParseError {
+        match self.peek() {
+            Some(token) => ParseError::at(
+                self.input,
+                token.span.start,
+                &format!("期望 {}，但遇到 '{}'", expected, &self.input[token.span.clone()]),
+            ),
+            None => ParseError::at(self.input, self.input.len(), &format!("期望 {}，但查询已结束", expected)),
+        }
+    }
+
+    /// 跳到当前语句末尾（分号或输入结束）
+    fn skip_statement(&mut self) {
+        while !self.at_statement_end() {
+            self.pos += 1;
+        }
+    }
+
+    /// 出错后跳到分号或下一条语句的开头，避免缺少分号的 DELETE/DROP 被跳过
+    fn recover(&mut self) {
+        while !self.at_statement_end() && !self.at_statement_head() {
+            self.pos += 1;
+        }
+    }
+
+    /// 消费语句剩余部分并检查括号是否匹配，遇到另一条语句的开头时停止
+    fn consume_rest(&mut self) -> PResult<()> {
+        let mut open: Vec<Range<usize>> = Vec::new();
+        while let Some(token) = self.peek() {
+            if token.is_punct(";") || (open.is_empty() && self.at_statement_head()) {
+                break;
+            }
+            if token.is_punct("(") || token.is_punct("[") {
+                open.push(token.span.clone());
+            } else if (token.is_punct(")") || token.is_punct("]")) && open.pop().is_none() {
+                return Err(ParseError::at(self.input, token.span.start, "多余的右括号"));
+            }
+            self.pos += 1;
+        }
+        match open.last() {
+            Some(span) => Err(ParseError::at(self.input, span.start, "括号未闭合")),
+            None => Ok(()),
+        }
+    }
+
+    fn parse_statements(&mut self, errors: &mut Vec<ParseError>) -> Vec<Statement> {
+        let mut statements = Vec::new();
+        loop {
+            while self.eat_punct(";") {}
+            let Some(first) = self.peek() else { break };
+            let start = first.span.start;
+            let start_pos = self.pos;
+
+            let statement = match self.parse_statement() {
+                Ok(statement) => {
+                    if !self.at_statement_end() {
+                        errors.push(self.unexpected("';' 或语句结束"));
+                        self.recover();
+                    }
+                    statement
+                }
+                Err(error) => {
+                    errors.push(error);
+                    self.pos = start_pos;
+                    let (kind, object) = self.parse_head();
+                    if self.pos == start_pos {
+                        self.pos += 1;
+                    }
+                    self.recover();
+                    Statement { kind, object, body: StatementBody::None, span: 0..0 }
+                }
+            };
+            statements.push(Statement { span: start..self.prev_end(), ..statement });
+        }
+        statements
+    }
+
+    /// 识别语句头，返回语句类型和操作对象；SELECT/WITH 不消费
+    fn parse_head(&mut self) -> (StatementKind, Option<String>) {
+        let Some(token) = self.peek() else {
+            return (StatementKind::Other(String::new()), None);
+        };
+        let head = token.text.to_uppercase();
+        if token.kind != TokenKind::Ident {
+            let kind = if token.is_punct("(") { StatementKind::Select } else { StatementKind::Other(head) };
+            return (kind, None);
+        }
+
+        let kind = match head.as_str() {
+            "SELECT" | "WITH" => return (StatementKind::Select, None),
+            "INSERT" => StatementKind::Insert,
+            "DELETE" => StatementKind::Delete,
+            "UPDATE" => StatementKind::Update,
+            "CREATE" => StatementKind::Create,
+            "DROP" => StatementKind::Drop,
+            "ALTER" => StatementKind::Alter,
+            "SHOW" | "LIST" => StatementKind::Show,
+            "EXPLAIN" => StatementKind::Explain,
+            "DESCRIBE" | "DESC" => StatementKind::Describe,
+            "GRANT" => StatementKind::Grant,
+            "REVOKE" => StatementKind::Revoke,
+            "KILL" => StatementKind::Kill,
+            "TRUNCATE" => StatementKind::Truncate,
+            // InfluxQL 的 SET PASSWORD，IoTDB 的 SET TTL / SET SCHEMA TEMPLATE 等
+            "SET" => StatementKind::Set,
+            _ => StatementKind::Other(head),
+        };
+        self.pos += 1;
+
+        let object = match kind {
+            StatementKind::Create | StatementKind::Drop | StatementKind::Alter | StatementKind::Show => {
+                self.eat_keywords(&["OR", "REPLACE"]);
+                self.parse_object()
+            }
+            StatementKind::Delete if !self.at_keyword("FROM") => self.parse_object(),
+            StatementKind::Truncate => {
+                self.eat_keyword("TABLE");
+                Some("TABLE".to_string())
+            }
+            _ => None,
+        };
+        (kind, object)
+    }
+
+    /// 读取对象类型，如 DATABASE、RETENTION POLICY
+    fn parse_object(&mut self) -> Option<String> {
+        let first = self.peek().filter(|t| t.kind == TokenKind::Ident)?.text.to_uppercase();
+        self.pos += 1;
+        let second = self
+            .peek()
+            .filter(|t| OBJECT_SUFFIXES.iter().any(|k| t.is_keyword(k)))
+            .map(|t| t.text.to_uppercase());
+        match second {
+            Some(second) => {
+                self.pos += 1;
+                Some(format!("{} {}", first, second))
+            }
+            None => Some(first),
+        }
+    }
+
+    fn parse_statement(&mut self) -> PResult<Statement> {
+        let start = self.peek().map_or(0, |t| t.span.start);
+        let (kind, object) = self.parse_head();
+        let body = match kind {
+            StatementKind::Select => StatementBody::Select(Box::new(self.parse_select()?)),
+            StatementKind::Explain => {
+                self.eat_keyword("ANALYZE");
+                self.eat_keyword("VERBOSE");
+                // EXPLAIN 其他语句时按内部语句分类，使 EXPLAIN DELETE 等同样受权限检查
+                if self.peek().is_some_and(is_statement_head) {
+                    return self.parse_statement();
+                }
+                StatementBody::Select(Box::new(self.parse_select()?))
+            }
+            StatementKind::Delete if object.is_none() => {
+                self.expect_keyword("FROM")?;
+                let sources = self.parse_sources()?;
+                let condition = if self.eat_keyword("WHERE") { Some(self.parse_expr()?) } else { None };
+                StatementBody::Select(Box::new(SelectStatement { sources, condition, ..Default::default() }))
+            }
+            // 行协议写入，内容不按查询语法解析
+            StatementKind::Insert if self.dialect == QueryDialect::InfluxQL => {
+                self.skip_statement();
+                StatementBody::None
+            }
+            StatementKind::Create if object.as_deref() == Some("CONTINUOUS QUERY") => {
+                while !self.at_statement_end() && !self.at_keyword("BEGIN") && !self.at_statement_head() {
+                    self.pos += 1;
+                }
+                self.expect_keyword("BEGIN")?;
+                let select = self.parse_select()?;
+                self.expect_keyword("END")?;
+                StatementBody::Select(Box::new(select))
+            }
+            StatementKind::Other(ref head) if self.dialect == QueryDialect::InfluxQL => {
+                return Err(ParseError::at(self.input, start, &format!("无法识别的语句 '{}'", head)));
+            }
+            _ => {
+                self.consume_rest()?;
+                StatementBody::None
+            }
+        };
+        Ok(Statement { kind, object, body, span: 0..0 })
+    }
+
+    fn parse_select(&mut self) -> PResult<SelectStatement> {
+        let mut ctes = Vec::new();
+        if self.eat_keyword("WITH") {
+            self.eat_keyword("RECURSIVE");
+            loop {
+                let name = self.parse_identifier()?;
+                if self.at_punct("(") {
+                    self.parse_paren_list()?;
+                }
+                self.expect_keyword("AS")?;
+                self.expect_punct("(")?;
+                let select = self.parse_select()?;
+                self.expect_punct(")")?;
+                ctes.push((name, select));
+                if !self.eat_punct(",") {
+                    break;
+                }
+            }
+        }
+
+        let mut select = if self.eat_punct("(") {
+            let inner = self.parse_select()?;
+            self.expect_punct(")")?;
+            inner
+        } else {
+            self.expect_keyword("SELECT")?;
+            if !self.eat_keyword("DISTINCT") {
+                self.eat_keyword("ALL");
+            }
+            let fields = self.parse_fields()?;
+            let into = if self.eat_keyword("INTO") { Some(self.parse_name()?) } else { None };
+            let sources = if self.eat_keyword("FROM") { self.parse_sources()? } else { Vec::new() };
+            SelectStatement { fields, into, sources, ..Default::default() }
+        };
+        select.ctes = ctes;
+        self.parse_clauses(&mut select)?;
+        Ok(select)
+    }
+
+    /// FROM 之后的子句，IoTDB 与 InfluxQL 的子句顺序不同，这里按出现顺序接受
+    fn parse_clauses(&mut self, select: &mut SelectStatement) -> PResult<()> {
+        loop {
+            if self.eat_keyword("WHERE") {
+                select.condition = Some(self.parse_expr()?);
+            } else if self.eat_keywords(&["GROUP", "BY"]) {
+                select.group_by = self.parse_group_by()?;
+            } else if self.eat_keyword("HAVING") {
+                select.having = Some(self.parse_expr()?);
+            } else if self.eat_keywords(&["ORDER", "BY"]) {
+                loop {
+                    select.order_by.push(self.parse_expr()?);
+                    if !self.eat_keyword("ASC") {
+                        self.eat_keyword("DESC");
+                    }
+                    if self.eat_keyword("NULLS") && !self.eat_keyword("FIRST") {
+                        self.expect_keyword("LAST")?;
+                    }
+                    if !self.eat_punct(",") {
+                        break;
+                    }
+                }
+            } else if self.eat_keyword("LIMIT") {
+                if !self.eat_keyword("ALL") {
+                    select.limit = Some(self.parse_integer("LIMIT")?);
+                }
+            } else if self.eat_keyword("OFFSET") {
+                self.parse_integer("OFFSET")?;
+            } else if self.eat_keyword("SLIMIT") {
+                self.parse_integer("SLIMIT")?;
+            } else if self.eat_keyword("SOFFSET") {
+                self.parse_integer("SOFFSET")?;
+            } else if self.at_keyword("FILL") || self.at_keyword("TZ") {
+                self.pos += 1;
+                self.parse_paren_list()?;
+            } else if self.eat_keywords(&["ALIGN", "BY"]) {
+                if !self.eat_keyword("DEVICE") {
+                    self.expect_keyword("TIME")?;
+                }
+            } else if self.eat_keywords(&["DISABLE", "ALIGN"]) {
+                // IoTDB 不对齐输出，无需记录
+            } else if ["UNION", "EXCEPT", "INTERSECT"].iter().any(|k| self.at_keyword(k)) {
+                self.pos += 1;
+                if !self.eat_keyword("ALL") {
+                    self.eat_keyword("DISTINCT");
+                }
+                select.union = Some(Box::new(self.parse_select()?));
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn parse_integer(&mut self, clause: &str) -> PResult<u64> {
+        match self.peek() {
+            Some(token) if token.kind == TokenKind::Number => {
+                let value = token.text.parse::<u64>().map_err(|_| {
+                    ParseError::at(self.input, token.span.start, &format!("{} 需要非负整数", clause))
+                })?;
+                self.pos += 1;
+                Ok(value)
+            }
+            _ => Err(self.unexpected(&format!("{} 的整数值", clause))),
+        }
+    }
+
+    /// 括号内的逗号分隔表达式列表
+    fn parse_paren_list(&mut self) -> PResult<Vec<Expr>> {
+        self.expect_punct("(")?;
+        let mut items = Vec::new();
+        if !self.eat_punct(")") {
+            loop {
+                items.push(self.parse_expr()?);
+                if !self.eat_punct(",") {
+                    break;
+                }
+            }
+            self.expect_punct(")")?;
+        }
+        Ok(items)
+    }
+
+    fn parse_fields(&mut self) -> PResult<Vec<Expr>> {
+        let mut fields = Vec::new();
+        loop {
+            fields.push(self.parse_expr()?);
+            self.parse_alias()?;
+            if !self.eat_punct(",") {
+                return Ok(fields);
+            }
+        }
+    }
+
+    /// 字段或数据源的别名；InfluxQL 必须使用 AS
+    fn parse_alias(&mut self) -> PResult<()> {
+        if self.eat_keyword("AS") {
+            match self.peek().map(|t| t.kind) {
+                Some(TokenKind::Ident | TokenKind::QuotedIdent | TokenKind::String) => {
+                    self.pos += 1;
+                    Ok(())
+                }
+                _ => Err(self.unexpected("别名")),
+            }
+        } else {
+            let bare_alias = self.dialect != QueryDialect::InfluxQL
+                && self.peek().is_some_and(|t| {
+                    (t.kind == TokenKind::Ident && !is_reserved(t) && !is_statement_head(t)) || t.kind == TokenKind::QuotedIdent
+                });
+            if bare_alias {
+                self.pos += 1;
+            }
+            Ok(())
+        }
+    }
+
+    fn parse_sources(&mut self) -> PResult<Vec<Source>> {
+        let mut sources = Vec::new();
+        loop {
+            sources.push(self.parse_source()?);
+            while self.parse_join_keyword() {
+                sources.push(self.parse_source()?);
+                if self.eat_keyword("ON") {
+                    self.parse_expr()?;
+                } else if self.eat_keyword("USING") {
+                    self.parse_paren_list()?;
+                }
+            }
+            if !self.eat_punct(",") {
+                return Ok(sources);
+            }
+        }
+    }
+
+    fn parse_join_keyword(&mut self) -> bool {
+        let start = self.pos;
+        self.eat_keyword("NATURAL");
+        for keyword in ["INNER", "LEFT", "RIGHT", "FULL", "CROSS"] {
+            if self.eat_keyword(keyword) {
+                break;
+            }
+        }
+        self.eat_keyword("OUTER");
+        if self.eat_keyword("JOIN") {
+            true
+        } else {
+            self.pos = start;
+            false
+        }
+    }
+
+    fn parse_source(&mut self) -> PResult<Source> {
+        let source = if self.eat_punct("(") {
+            let select = self.parse_select()?;
+            self.expect_punct(")")?;
+            Source::Subquery(Box::new(select))
+        } else if let Some(token) = self.peek().filter(|t| t.kind == TokenKind::Regex) {
+            let regex = token.text.clone();
+            self.pos += 1;
+            Source::Regex(regex)
+        } else {
+            Source::Name(self.parse_name()?)
+        };
+        self.parse_alias()?;
+        Ok(source)
+    }
+
+    fn parse_identifier(&mut self) -> PResult<String> {
+        match self.peek() {
+            Some(t) if t.kind == TokenKind::QuotedIdent || (t.kind == TokenKind::Ident && !is_reserved(t)) => {
+                let name = t.text.clone();
+                self.pos += 1;
+                Ok(name)
+            }
+            _ => Err(self.unexpected("标识符")),
+        }
+    }
+
+    /// 带限定名的名称，如 db.rp.m、db..m、root.sg.*.s1
+    fn parse_name(&mut self) -> PResult<String> {
+        let mut name = if self.at_punct(".") { String::new() } else { self.parse_identifier()? };
+        while self.eat_punct(".") {
+            name.push('.');
+            match self.peek() {
+                Some(t) if t.is_punct(".") => {}
+                // SELECT ... INTO db.rp.:MEASUREMENT 反向引用
+                Some(t) if t.is_operator(":") && self.peek_at(1).is_some_and(|n| n.kind == TokenKind::Ident) => {
+                    name.push(':');
+                    name.push_str(&self.tokens[self.pos + 1].text);
+                    self.pos += 2;
+                }
+                Some(t) if matches!(t.kind, TokenKind::Ident | TokenKind::QuotedIdent | TokenKind::Number | TokenKind::Duration)
+                    || t.is_operator("*")
+                    || t.is_operator("**") =>
+                {
+                    name.push_str(&t.text);
+                    self.pos += 1;
+                }
+                _ => return Err(self.unexpected("名称")),
+            }
+        }
+        Ok(name)
+    }
+
+    fn parse_group_by(&mut self) -> PResult<Vec<Expr>> {
+        let mut items = Vec::new();
+        loop {
+            // IoTDB 时间窗口：GROUP BY ([start, end), interval[, step])
+            if self.at_punct("(") && self.peek_at(1).is_some_and(|t| t.is_punct("[")) {
+                self.pos += 2;
+                let mut window = vec![self.parse_expr()?];
+                self.expect_punct(",")?;
+                window.push(self.parse_expr()?);
+                if !self.eat_punct(")") {
+                    self.expect_punct("]")?;
+                }
+                while self.eat_punct(",") {
+                    window.push(self.parse_expr()?);
+                }
+                self.expect_punct(")")?;
+                items.push(Expr::List(window));
+            } else {
+                items.push(self.parse_expr()?);
+            }
+            if !self.eat_punct(",") {
+                return Ok(items);
+            }
+        }
+    }
+
+    fn parse_expr(&mut self) -> PResult<Expr> {
+        let mut left = self.parse_and()?;
+        while self.eat_keyword("OR") {
+            let right = self.parse_and()?;
+            left = binary("OR", left, right);
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> PResult<Expr> {
+        let mut left = self.parse_not()?;
+        while self.eat_keyword("AND") {
+            let right = self.parse_not()?;
+            left = binary("AND", left, right);
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> PResult<Expr> {
+        if self.eat_keyword("NOT") {
+            let expr = self.parse_not()?;
+            return Ok(Expr::Unary { op: "NOT".to_string(), expr: Box::new(expr) });
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> PResult<Expr> {
+        let left = self.parse_additive()?;
+
+        if let Some(op) = self.peek().filter(|t| t.kind == TokenKind::Operator && COMPARISON_OPERATORS.contains(&t.text.as_str())) {
+            let op = op.text.clone();
+            self.pos += 1;
+            let right = self.parse_additive()?;
+            return Ok(binary(&op, left, right));
+        }
+
+        if self.eat_keyword("IS") {
+            let negated = self.eat_keyword("NOT");
+            let right = self.parse_primary()?;
+            return Ok(binary(if negated { "IS NOT" } else { "IS" }, left, right));
+        }
+
+        let negated = self.at_keyword("NOT")
+            && self.peek_at(1).is_some_and(|t| ["IN", "LIKE", "ILIKE", "REGEXP", "BETWEEN"].iter().any(|k| t.is_keyword(k)));
+        if negated {
+            self.pos += 1;
+        }
+        let op = |name: &str| if negated { format!("NOT {}", name) } else { name.to_string() };
+
+        if self.eat_keyword("IN") {
+            let right = if self.at_punct("(") && self.peek_at(1).is_some_and(|t| t.is_keyword("SELECT")) {
+                self.pos += 1;
+                let select = self.parse_select()?;
+                self.expect_punct(")")?;
+                Expr::Subquery(Box::new(select))
+            } else {
+                Expr::List(self.parse_paren_list()?)
+            };
+            return Ok(binary(&op("IN"), left, right));
+        }
+        for keyword in ["LIKE", "ILIKE", "REGEXP"] {
+            if self.eat_keyword(keyword) {
+                let right = self.parse_additive()?;
+                return Ok(binary(&op(keyword), left, right));
+            }
+        }
+        if self.eat_keyword("BETWEEN") {
+            let low = self.parse_additive()?;
+            self.expect_keyword("AND")?;
+            let high = self.parse_additive()?;
+            return Ok(binary(&op("BETWEEN"), left, Expr::List(vec![low, high])));
+        }
+        if negated {
+            return Err(self.unexpected("IN、LIKE 或 BETWEEN"));
+        }
+        Ok(left)
+    }
+
+    fn parse_additive(&mut self) -> PResult<Expr> {
+        let mut left = self.parse_multiplicative()?;
+        while let Some(op) = ["+", "-", "||"].into_iter().find(|op| self.at_operator(op)) {
+            self.pos += 1;
+            let right = self.parse_multiplicative()?;
+            left = binary(op, left, right);
+        }
+        Ok(left)
+    }
+
+    fn parse_multiplicative(&mut self) -> PResult<Expr> {
+        let mut left = self.parse_unary()?;
+        while let Some(op) = ["*", "/", "%", "&", "|", "^"].into_iter().find(|op| self.at_operator(op)) {
+            self.pos += 1;
+            let right = self.parse_unary()?;
+            left = binary(op, left, right);
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> PResult<Expr> {
+        for op in ["-", "+"] {
+            if self.eat_operator(op) {
+                let expr = self.parse_unary()?;
+                return Ok(Expr::Unary { op: op.to_string(), expr: Box::new(expr) });
+            }
+        }
+        let expr = self.parse_primary()?;
+        // InfluxQL 的 field::tag 与 SQL 的类型转换
+        while self.eat_operator("::") {
+            self.parse_identifier()?;
+        }
+        if self.eat_punct("[") {
+            self.parse_expr()?;
+            self.expect_punct("]")?;
+        }
+        Ok(expr)
+    }
+
+    fn parse_primary(&mut self) -> PResult<Expr> {
+        let Some(token) = self.peek().cloned() else {
+            return Err(self.unexpected("表达式"));
+        };
+        match token.kind {
+            TokenKind::Number | TokenKind::String | TokenKind::Duration | TokenKind::Time => {
+                self.pos += 1;
+                Ok(Expr::Literal(token.text))
+            }
+            TokenKind::Regex => {
+                self.pos += 1;
+                Ok(Expr::Regex(token.text))
+            }
+            TokenKind::Param => {
+                self.pos += 1;
+                Ok(Expr::Param(token.text))
+            }
+            TokenKind::Operator if token.text == "*" || token.text == "**" => {
+                self.pos += 1;
+                Ok(Expr::Wildcard)
+            }
+            TokenKind::Punct if token.text == "(" => {
+                self.pos += 1;
+                if self.at_keyword("SELECT") || self.at_keyword("WITH") {
+                    let select = self.parse_select()?;
+                    self.expect_punct(")")?;
+                    return Ok(Expr::Subquery(Box::new(select)));
+                }
+                let mut items = vec![self.parse_expr()?];
+                while self.eat_punct(",") {
+                    items.push(self.parse_expr()?);
+                }
+                self.expect_punct(")")?;
+                Ok(if items.len() == 1 { items.remove(0) } else { Expr::List(items) })
+            }
+            TokenKind::Ident if token.is_keyword("CASE") => self.parse_case(),
+            TokenKind::Ident if ["TRUE", "FALSE", "NULL"].iter().any(|k| token.is_keyword(k)) => {
+                self.pos += 1;
+                Ok(Expr::Literal(token.text.to_uppercase()))
+            }
+            TokenKind::Ident if token.is_keyword("INTERVAL") && self.peek_at(1).is_some_and(|t| t.kind == TokenKind::String) => {
+                self.pos += 2;
+                Ok(Expr::Literal(self.tokens[self.pos - 1].text.clone()))
+            }
+            TokenKind::Ident if token.is_keyword("EXISTS") => {
+                self.pos += 1;
+                self.expect_punct("(")?;
+                let select = self.parse_select()?;
+                self.expect_punct(")")?;
+                Ok(Expr::Unary { op: "EXISTS".to_string(), expr: Box::new(Expr::Subquery(Box::new(select))) })
+            }
+            TokenKind::Ident | TokenKind::QuotedIdent => {
+                if token.kind == TokenKind::Ident && is_reserved(&token) {
+                    return Err(self.unexpected("表达式"));
+                }
+                let name = self.parse_name()?;
+                if self.at_punct("(") {
+                    self.parse_call(name, token.span.start)
+                } else {
+                    Ok(Expr::Identifier(name))
+                }
+            }
+            _ => Err(self.unexpected("表达式")),
+        }
+    }
+
+    fn parse_call(&mut self, name: String, start: usize) -> PResult<Expr> {
+        self.expect_punct("(")?;
+        let mut args = Vec::new();
+        if !self.at_punct(")") {
+            self.eat_keyword("DISTINCT");
+            loop {
+                args.push(self.parse_expr()?);
+                // CAST(x AS type)
+                if self.eat_keyword("AS") {
+                    self.parse_identifier()?;
+                }
+                if !self.eat_punct(",") {
+                    break;
+                }
+            }
+        }
+        self.expect_punct(")")?;
+        let span = start..self.prev_end();
+        if self.eat_keyword("OVER") {
+            self.expect_punct("(")?;
+            let mut depth = 1;
+            while depth > 0 {
+                match self.advance() {
+                    Some(t) if t.is_punct("(") => depth += 1,
+                    Some(t) if t.is_punct(")") => depth -= 1,
+                    Some(_) => {}
+                    None => return Err(self.unexpected("')'")),
+                }
+            }
+        }
+        Ok(Expr::Call(FunctionCall { name, args, span }))
+    }
+
+    fn parse_case(&mut self) -> PResult<Expr> {
+        self.expect_keyword("CASE")?;
+        let mut parts = Vec::new();
+        if !self.at_keyword("WHEN") {
+            parts.push(self.parse_expr()?);
+        }
+        while self.eat_keyword("WHEN") {
+            parts.push(self.parse_expr()?);
+            self.expect_keyword("THEN")?;
+            parts.push(self.parse_expr()?);
+        }
+        if self.eat_keyword("ELSE") {
+            parts.push(self.parse_expr()?);
+        }
+        self.expect_keyword("END")?;
+        Ok(Expr::List(parts))
+    }
+}
+
+fn is_reserved(token: &Token) -> bool {
+    token.kind == TokenKind::Ident && RESERVED.iter().any(|k| token.is_keyword(k))
+}
+
+fn is_statement_head(token: &Token) -> bool {
+    token.kind == TokenKind::Ident && STATEMENT_HEADS.iter().any(|k| token.is_keyword(k))
+}
+
+fn binary(op: &str, left: Expr, right: Expr) -> Expr {
+    Expr::Binary { op: op.to_string(), left: Box::new(left), right: Box::new(right) }
+}
+
+/// 解析 InfluxQL、IoTDB SQL 或 SQL 查询
+pub fn parse(input: &str, dialect: QueryDialect) -> ParsedQuery {
+    let (tokens, mut errors) = tokenize(input, dialect);
+    let mut parser = Parser { input, tokens, pos: 0, dialect };
+    let statements = parser.parse_statements(&mut errors);
+
+    // InfluxQL 的 INSERT 内容是行协议，其中的引号不参与查询词法
+    if dialect == QueryDialect::InfluxQL {
+        errors.retain(|e| {
+            !statements
+                .iter()
+                .any(|s| s.kind == StatementKind::Insert && s.span.contains(&e.offset))
+        });
+    }
+    errors.sort_by_key(|e| e.offset);
+    ParsedQuery { dialect, statements, errors }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(parsed: &ParsedQuery) -> Vec<&'static str> {
+        parsed.statements.iter().map(|s| s.kind.as_str()).collect()
+    }
+
+    #[test]
+    fn test_influxql_select() {
+        let parsed = parse(
+            "SELECT mean(\"usage\") AS avg, max(usage) * 2 INTO \"db\".\"rp\".:MEASUREMENT FROM \"db\"..cpu, /^mem/ \
+             WHERE host =~ /web/ AND time > now() - 1h GROUP BY time(5m), * fill(none) LIMIT 10 tz('Asia/Shanghai')",
+            QueryDialect::InfluxQL,
+        );
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        let select = parsed.statements[0].select().unwrap();
+        assert_eq!(select.into.as_deref(), Some("db.rp.:MEASUREMENT"));
+        assert_eq!(select.sources, vec![Source::Name("db..cpu".to_string()), Source::Regex("^mem".to_string())]);
+        assert_eq!(select.limit, Some(10));
+        assert!(select.has_time_condition());
+        assert!(parsed.statements[0].writes_data());
+        let names: Vec<_> = parsed.statements[0].functions().iter().map(|c| c.name.clone()).collect();
+        assert_eq!(names, vec!["mean", "max", "now", "time"]);
+    }
+
+    #[test]
+    fn test_statement_kinds_and_objects() {
+        let parsed = parse(
+            "SHOW TAG KEYS FROM cpu; DROP MEASUREMENT cpu; CREATE RETENTION POLICY one_day ON db DURATION 1d REPLICATION 1 DEFAULT;\
+             DELETE FROM cpu WHERE time < '2024-01-01'; INSERT cpu,host=a value=\"it's\" 1",
+            QueryDialect::InfluxQL,
+        );
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        assert_eq!(kinds(&parsed), vec!["SHOW", "DROP", "CREATE", "DELETE", "INSERT"]);
+        let objects: Vec<_> = parsed.statements.iter().map(|s| s.object.clone()).collect();
+        assert_eq!(objects[..3], [Some("TAG KEYS".into()), Some("MEASUREMENT".into()), Some("RETENTION POLICY".into())]);
+
+        let parsed = parse("SET PASSWORD FOR \"u\" = 'p'; GRANT READ ON db TO \"u\"; KILL QUERY 36", QueryDialect::InfluxQL);
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        assert_eq!(kinds(&parsed), vec!["SET", "GRANT", "KILL"]);
+    }
+
+    #[test]
+    fn test_sql_select() {
+        let parsed = parse(
+            "WITH recent AS (SELECT * FROM cpu WHERE time >= now() - INTERVAL '1 hour')\n\
+             SELECT r.host, date_bin(INTERVAL '1 minute', r.time) AS minute, avg(CAST(usage AS DOUBLE)) avg_usage \
+             FROM recent r LEFT JOIN hosts h ON r.host = h.name \
+             WHERE h.region IN ('eu', 'us') AND usage BETWEEN 0 AND 100 AND tag IS NOT NULL \
+             GROUP BY 1, 2 ORDER BY minute DESC LIMIT 100",
+            QueryDialect::Sql,
+        );
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        assert_eq!(parsed.statements[0].statement_type(), "SELECT_AGGREGATE");
+        assert_eq!(parsed.statements[0].select().unwrap().ctes[0].0, "recent");
+    }
+
+    #[test]
+    fn test_iotdb_select() {
+        let parsed = parse(
+            "SELECT count(s1), last_value(s2) FROM root.sg.*.** WHERE time >= 2024-01-01T00:00:00 \
+             GROUP BY ([2024-01-01T00:00:00, 2024-01-02T00:00:00), 1h), LEVEL = 1 FILL(previous) ALIGN BY DEVICE;\
+             DELETE STORAGE GROUP root.sg",
+            QueryDialect::IoTDB,
+        );
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        assert_eq!(parsed.statements[0].select().unwrap().sources, vec![Source::Name("root.sg.*.**".to_string())]);
+        assert_eq!(parsed.statements[1].kind, StatementKind::Delete);
+        assert_eq!(parsed.statements[1].object.as_deref(), Some("STORAGE GROUP"));
+    }
+
+    #[test]
+    fn test_error_positions_and_recovery() {
+        let parsed = parse("SELECT mean(usage FROM cpu;\nDROP DATABASE db", QueryDialect::InfluxQL);
+        assert_eq!(parsed.errors.len(), 1);
+        assert_eq!((parsed.errors[0].line, parsed.errors[0].column), (1, 19));
+        assert_eq!(kinds(&parsed), vec!["SELECT", "DROP"]);
+        assert_eq!(parsed.statements[1].object.as_deref(), Some("DATABASE"));
+
+        let parsed = parse("SELECT * FROM cpu DROP MEASUREMENT cpu", QueryDialect::InfluxQL);
+        assert_eq!(parsed.errors[0].column, 19);
+        assert_eq!(kinds(&parsed), vec!["SELECT", "DROP"]);
+
+        let parsed = parse("SELECT * FROM cpu WHERE", QueryDialect::InfluxQL);
+        assert_eq!(parsed.errors[0].message, "期望 表达式，但查询已结束");
+    }
+
+    #[test]
+    fn test_missing_semicolon_hides_nothing() {
+        let parsed = parse("SHOW DATABASES\nDROP DATABASE x", QueryDialect::InfluxQL);
+        assert_eq!(parsed.errors.len(), 1);
+        assert_eq!((parsed.errors[0].line, parsed.errors[0].column), (2, 1));
+        assert_eq!(kinds(&parsed), vec!["SHOW", "DROP"]);
+        assert_eq!(parsed.statements[1].object.as_deref(), Some("DATABASE"));
+
+        let parsed = parse("SELECT * FROM root.sg.d1 DELETE FROM root.sg.d1.s1", QueryDialect::IoTDB);
+        assert_eq!(kinds(&parsed), vec!["SELECT", "DELETE"]);
+
+        let parsed = parse("CREATE TRIGGER t AFTER INSERT ON root.sg.** AS 'org.example.Trigger'", QueryDialect::IoTDB);
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        assert_eq!(kinds(&parsed), vec!["CREATE"]);
+    }
+
+    #[test]
+    fn test_explain_classified_by_inner_statement() {
+        let parsed = parse("EXPLAIN DELETE FROM cpu", QueryDialect::InfluxQL);
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        assert_eq!(kinds(&parsed), vec!["DELETE"]);
+
+        let parsed = parse("EXPLAIN ANALYZE DROP MEASUREMENT cpu", QueryDialect::InfluxQL);
+        assert_eq!(kinds(&parsed), vec!["DROP"]);
+        assert_eq!(kinds(&parse("EXPLAIN ANALYZE SELECT * FROM cpu", QueryDialect::InfluxQL)), vec!["EXPLAIN"]);
+    }
+
+    #[test]
+    fn test_comments_hide_nothing() {
+        let parsed = parse("SELECT * FROM cpu -- ; DROP DATABASE db\n/* ; */ ; DELETE FROM cpu", QueryDialect::InfluxQL);
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        assert_eq!(kinds(&parsed), vec!["SELECT", "DELETE"]);
+    }
+}
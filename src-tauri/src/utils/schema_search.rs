@@ -0,0 +1,234 @@
+/**
+ * 结构信息搜索
+ *
+ * 在各连接的结构信息缓存中按名称查找测量、字段、标签键、存储桶、设备和时间序列，
+ * 支持前缀、模糊（子序列）和正则三种匹配方式
+ */
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+
+const DEFAULT_LIMIT: usize = 100;
+
+/// 匹配方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SearchMode {
+    Prefix,
+    #[default]
+    Fuzzy,
+    Regex,
+}
+
+/// 结构对象类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SchemaKind {
+    Database,
+    Bucket,
+    Measurement,
+    Field,
+    TagKey,
+    Device,
+    Timeseries,
+}
+
+/// 索引条目
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaSearchEntry {
+    pub connection_id: String,
+    pub kind: SchemaKind,
+    pub name: String,
+    /// 从连接开始的树路径（不含名称本身）
+    pub path: Vec<String>,
+    /// 对应的树节点 ID，只有从树节点缓存中得到的条目才有
+    pub node_id: Option<String>,
+}
+
+/// 搜索结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaSearchHit {
+    #[serde(flatten)]
+    pub entry: SchemaSearchEntry,
+    pub score: u32,
+}
+
+/// 名称匹配器
+#[derive(Debug)]
+pub enum Matcher {
+    Prefix(String),
+    Fuzzy(Vec<char>),
+    Regex(Regex),
+}
+
+impl Matcher {
+    /// 创建匹配器，前缀和模糊匹配忽略大小写
+    pub fn new(query: &str, mode: SearchMode) -> Result<Self, String> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Err("搜索内容不能为空".to_string());
+        }
+
+        Ok(match mode {
+            SearchMode::Prefix => Matcher::Prefix(query.to_lowercase()),
+            SearchMode::Fuzzy => Matcher::Fuzzy(query.to_lowercase().chars().collect()),
+            SearchMode::Regex => Matcher::Regex(
+                RegexBuilder::new(query)
+                    .case_insensitive(true)
+                    .size_limit(1 << 20)
+                    .build()
+                    .map_err(|e| format!("无效的正则表达式: {}", e))?,
+            ),
+        })
+    }
+
+    /// 计算匹配得分，不匹配时返回 None，得分越高越靠前
+    pub fn score(&self, name: &str) -> Option<u32> {
+        match self {
+            Matcher::Prefix(prefix) => {
+                let lower = name.to_lowercase();
+                if lower == *prefix {
+                    Some(1000)
+                } else if lower.starts_with(prefix.as_str()) {
+                    Some(800 - length_penalty(&lower, prefix.len()))
+                } else {
+                    None
+                }
+            }
+            Matcher::Fuzzy(pattern) => fuzzy_score(&name.to_lowercase(), pattern),
+            Matcher::Regex(regex) => {
+                let found = regex.find(name)?;
+                if found.start() == 0 && found.end() == name.len() {
+                    Some(1000)
+                } else {
+                    Some(600 - (found.start() as u32).min(200))
+                }
+            }
+        }
+    }
+}
+
+fn length_penalty(name: &str, matched: usize) -> u32 {
+    (name.len().saturating_sub(matched) as u32).min(100)
+}
+
+/// 完全相同 > 前缀 > 子串 > 子序列，子序列按首字符位置和间隔扣分
+fn fuzzy_score(name: &str, pattern: &[char]) -> Option<u32> {
+    let pattern_str: String = pattern.iter().collect();
+    if name == pattern_str {
+        return Some(1000);
+    }
+    if name.starts_with(&pattern_str) {
+        return Some(800 - length_penalty(name, pattern_str.len()));
+    }
+    if let Some(position) = name.find(&pattern_str) {
+        return Some(600 - (position as u32).min(100) - length_penalty(name, pattern_str.len()) / 2);
+    }
+
+    let mut first = None;
+    let mut gaps = 0u32;
+    let mut last = None;
+    let mut pattern_iter = pattern.iter().peekable();
+    for (i, c) in name.chars().enumerate() {
+        let Some(&&expected) = pattern_iter.peek() else {
+            break;
+        };
+        if c == expected {
+            if let Some(last) = last {
+                gaps += (i - last - 1) as u32;
+            }
+            first.get_or_insert(i);
+            last = Some(i);
+            pattern_iter.next();
+        }
+    }
+    if pattern_iter.peek().is_some() {
+        return None;
+    }
+    Some(400u32.saturating_sub(gaps * 10 + (first.unwrap_or(0) as u32) * 5).max(1))
+}
+
+/// 按得分排序并截断，同名时路径短的靠前
+pub fn rank(hits: &mut Vec<SchemaSearchHit>, limit: Option<usize>) {
+    hits.sort_by(|a, b| {
+        (Reverse(a.score), a.entry.path.len(), &a.entry.name).cmp(&(Reverse(b.score), b.entry.path.len(), &b.entry.name))
+    });
+    hits.truncate(limit.unwrap_or(DEFAULT_LIMIT));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(connection_id: &str, kind: SchemaKind, path: &[&str], name: &str) -> SchemaSearchEntry {
+        SchemaSearchEntry {
+            connection_id: connection_id.to_string(),
+            kind,
+            name: name.to_string(),
+            path: path.iter().map(|s| s.to_string()).collect(),
+            node_id: None,
+        }
+    }
+
+    fn entries() -> Vec<SchemaSearchEntry> {
+        vec![
+            entry("a", SchemaKind::Measurement, &["prod", "telegraf"], "cpu"),
+            entry("a", SchemaKind::Field, &["prod", "telegraf", "cpu"], "usage_user"),
+            entry("b", SchemaKind::Measurement, &["edge", "sensors"], "cpu_temp"),
+            entry("c", SchemaKind::Timeseries, &["iotdb", "root.plant", "root.plant.line1"], "CPU_TEMP"),
+        ]
+    }
+
+    fn search(entries: &[SchemaSearchEntry], matcher: &Matcher, limit: Option<usize>) -> Vec<SchemaSearchHit> {
+        let mut hits = entries
+            .iter()
+            .filter_map(|entry| matcher.score(&entry.name).map(|score| SchemaSearchHit { entry: entry.clone(), score }))
+            .collect();
+        rank(&mut hits, limit);
+        hits
+    }
+
+    fn names(hits: &[SchemaSearchHit]) -> Vec<&str> {
+        hits.iter().map(|hit| hit.entry.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_prefix_search() {
+        let matcher = Matcher::new("CPU", SearchMode::Prefix).unwrap();
+        let hits = search(&entries(), &matcher, None);
+        assert_eq!(names(&hits), vec!["cpu", "cpu_temp", "CPU_TEMP"]);
+        assert_eq!(hits[1].entry.connection_id, "b");
+        assert_eq!(hits[2].entry.path, vec!["iotdb", "root.plant", "root.plant.line1"]);
+    }
+
+    #[test]
+    fn test_fuzzy_search() {
+        let matcher = Matcher::new("cpu_tmp", SearchMode::Fuzzy).unwrap();
+        let hits = search(&entries(), &matcher, None);
+        assert_eq!(names(&hits), vec!["cpu_temp", "CPU_TEMP"]);
+
+        let matcher = Matcher::new("temp", SearchMode::Fuzzy).unwrap();
+        let hits = search(&entries(), &matcher, Some(1));
+        assert_eq!(names(&hits), vec!["cpu_temp"]);
+
+        assert!(Matcher::new("  ", SearchMode::Fuzzy).is_err());
+    }
+
+    #[test]
+    fn test_regex_search() {
+        let matcher = Matcher::new(r"^cpu_.*temp$", SearchMode::Regex).unwrap();
+        let hits = search(&entries(), &matcher, None);
+        assert_eq!(names(&hits), vec!["cpu_temp", "CPU_TEMP"]);
+        assert_eq!(hits[1].score, 1000);
+
+        let matcher = Matcher::new(r"user$", SearchMode::Regex).unwrap();
+        let hits = search(&entries(), &matcher, None);
+        assert_eq!(names(&hits), vec!["usage_user"]);
+        assert_eq!(hits[0].score, 594);
+
+        assert!(Matcher::new("cpu(", SearchMode::Regex).is_err());
+    }
+}
@@ -1,9 +1,38 @@
 use crate::models::ConnectionConfig;
+use crate::utils::query_parser::{self, QueryDialect, Statement, StatementKind};
 use anyhow::{anyhow, Result};
 use std::net::IpAddr;
 use std::str::FromStr;
 use log::debug;
 
+/// 查询检查发现的问题
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryIssue {
+    pub message: String,
+    pub line: u32,
+    pub column: u32,
+    pub kind: QueryIssueKind,
+}
+
+/// 查询问题类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryIssueKind {
+    Syntax,
+    Semantic,
+    Permission,
+}
+
+impl QueryIssue {
+    fn new(message: &str, (line, column): (u32, u32), kind: QueryIssueKind) -> Self {
+        Self {
+            message: message.to_string(),
+            line,
+            column,
+            kind,
+        }
+    }
+}
+
 /// 验证工具
 pub struct ValidationUtils;
 
@@ -118,187 +147,186 @@ impl ValidationUtils {
     }
 
     /// 带设置的查询验证
+    ///
+    /// 语法错误只在 [`Self::inspect_query`] 中报告，不阻止执行，最终以数据库的解析结果为准
     pub fn validate_query_with_settings(query: &str, controller_settings: Option<&crate::commands::settings::ControllerSettings>) -> Result<()> {
         debug!("验证查询语句");
 
+        if let Some(issue) = Self::inspect_query(query, controller_settings)
+            .into_iter()
+            .find(|issue| issue.kind != QueryIssueKind::Syntax)
+        {
+            return Err(anyhow!("{}", issue.message));
+        }
+
+        debug!("查询语句验证通过");
+        Ok(())
+    }
+
+    /// 解析查询并检查语法错误、权限限制和不支持的函数，问题带有所在的行列号
+    pub fn inspect_query(query: &str, controller_settings: Option<&crate::commands::settings::ControllerSettings>) -> Vec<QueryIssue> {
         let trimmed_query = query.trim();
 
         if trimmed_query.is_empty() {
-            return Err(anyhow!("查询语句不能为空"));
+            return vec![QueryIssue::new("查询语句不能为空", (1, 1), QueryIssueKind::Semantic)];
         }
 
         // 检查查询长度
         if trimmed_query.len() > 10000 {
-            return Err(anyhow!("查询语句长度不能超过 10000 个字符"));
+            return vec![QueryIssue::new("查询语句长度不能超过 10000 个字符", (1, 1), QueryIssueKind::Semantic)];
         }
 
-        let upper_query = trimmed_query.to_uppercase();
+        let parsed = query_parser::parse(query);
+        let mut issues: Vec<QueryIssue> = parsed
+            .errors
+            .iter()
+            .map(|e| QueryIssue::new(&e.message, (e.line, e.column), QueryIssueKind::Syntax))
+            .collect();
 
-        // 检查控制器设置限制
-        if let Some(settings) = controller_settings {
-            // 检查DELETE语句权限
-            if !settings.allow_delete_statements && upper_query.starts_with("DELETE") {
-                return Err(anyhow!("DELETE语句已被禁用\n\n原因：为了保护数据安全，DELETE操作默认被禁用。\n\n解决方法：\n1. 打开工具栏「安全设置」或应用设置（右上角齿轮图标）\n2. 进入「查询设置」标签\n3. 在「语句权限控制」区域启用「允许DELETE语句」\n4. 保存设置后重新执行查询"));
-            }
+        for (index, statement) in parsed.statements.iter().enumerate() {
+            let position = query_parser::line_column(query, statement.span.start);
 
-            // 检查DROP语句权限
-            if !settings.allow_drop_statements && upper_query.starts_with("DROP") {
-                return Err(anyhow!("DROP语句已被禁用\n\n原因：为了保护数据安全，DROP操作默认被禁用。\n\n解决方法：\n1. 打开工具栏「安全设置」或应用设置（右上角齿轮图标）\n2. 进入「查询设置」标签\n3. 在「语句权限控制」区域启用「允许DROP语句」\n4. 保存设置后重新执行查询"));
+            // 检查控制器设置限制
+            if let Some(settings) = controller_settings {
+                if let Some(message) = Self::permission_message(statement, settings) {
+                    issues.push(QueryIssue::new(&message, position, QueryIssueKind::Permission));
+                }
             }
 
-            // 检查危险操作权限（额外的安全层，用于特别危险的操作）
-            // 注意：allow_dangerous_operations 是一个额外的安全开关
-            // 只有同时满足以下条件才能执行特别危险的操作：
-            // 1. 对应的语句类型权限已开启（allow_delete_statements 或 allow_drop_statements）
-            // 2. 危险操作权限已开启（allow_dangerous_operations）
-            if !settings.allow_dangerous_operations {
-                // 检查特别危险的 DROP 操作
-                if upper_query.contains("DROP DATABASE") && settings.allow_drop_statements {
-                    return Err(anyhow!("DROP DATABASE 是特别危险的操作，已被禁用\n\n原因：此操作将永久删除整个数据库及其所有数据。\n\n解决方法：\n1. 打开工具栏「安全设置」或应用设置\n2. 启用「允许危险操作」选项\n3. 保存设置后重新执行查询\n\n⚠️ 警告：启用此选项后，请务必谨慎操作！"));
-                }
+            // InfluxDB 不支持 TRUNCATE；批量语句中后续的 DELETE/DROP 可能来自 SQL 注入
+            let dangerous = match statement.kind {
+                StatementKind::Truncate => Some("TRUNCATE".to_string()),
+                StatementKind::Delete | StatementKind::Drop if index > 0 => Some(format!("; {}", statement.kind.as_str())),
+                _ => None,
+            };
+            if let Some(pattern) = dangerous {
+                issues.push(QueryIssue::new(&format!("查询包含潜在危险的操作: {}", pattern), position, QueryIssueKind::Semantic));
+            }
 
-                if upper_query.contains("DROP MEASUREMENT") && settings.allow_drop_statements {
-                    return Err(anyhow!("DROP MEASUREMENT 是特别危险的操作，已被禁用\n\n原因：此操作将永久删除整个测量（表）及其所有数据。\n\n解决方法：\n1. 打开工具栏「安全设置」或应用设置\n2. 启用「允许危险操作」选项\n3. 保存设置后重新执行查询\n\n⚠️ 警告：启用此选项后，请务必谨慎操作！"));
+            // 验证 InfluxQL 函数使用（仅对 SELECT 语句）
+            let is_select = matches!(statement.kind, StatementKind::Select | StatementKind::Explain);
+            if parsed.dialect == QueryDialect::InfluxQL && is_select {
+                for call in statement.functions() {
+                    if let Some(message) = Self::unsupported_function_message(&call.name) {
+                        let position = query_parser::line_column(query, call.span.start);
+                        issues.push(QueryIssue::new(&message, position, QueryIssueKind::Semantic));
+                    }
                 }
             }
         }
 
-        // 基本的 SQL 注入检查
-        let dangerous_patterns = [
-            "TRUNCATE",  // InfluxDB不支持TRUNCATE
-            "; DROP",    // 防止SQL注入
-            "; DELETE",  // 防止SQL注入
-        ];
-
-        for pattern in &dangerous_patterns {
-            if upper_query.contains(pattern) {
-                return Err(anyhow!("查询包含潜在危险的操作: {}", pattern));
+        // Flux 脚本同时按 InfluxQL 分类检查权限，防止误判为 Flux 的 DELETE/DROP 绕过限制
+        if parsed.dialect == QueryDialect::Flux {
+            if let Some(settings) = controller_settings {
+                for statement in query_parser::parse_with_dialect(query, QueryDialect::InfluxQL).statements {
+                    if let Some(message) = Self::permission_message(&statement, settings) {
+                        let position = query_parser::line_column(query, statement.span.start);
+                        issues.push(QueryIssue::new(&message, position, QueryIssueKind::Permission));
+                    }
+                }
             }
         }
 
-        // 验证 InfluxQL 函数使用（仅对 SELECT 语句）
-        if upper_query.starts_with("SELECT") {
-            Self::validate_influxql_functions(trimmed_query)?;
-        }
+        issues
+    }
 
-        debug!("查询语句验证通过");
-        Ok(())
+    /// 检查语句是否被控制器设置禁止
+    fn permission_message(statement: &Statement, settings: &crate::commands::settings::ControllerSettings) -> Option<String> {
+        // 检查DELETE语句权限
+        if !settings.allow_delete_statements && statement.kind == StatementKind::Delete {
+            return Some("DELETE语句已被禁用\n\n原因：为了保护数据安全，DELETE操作默认被禁用。\n\n解决方法：\n1. 打开工具栏「安全设置」或应用设置（右上角齿轮图标）\n2. 进入「查询设置」标签\n3. 在「语句权限控制」区域启用「允许DELETE语句」\n4. 保存设置后重新执行查询".to_string());
+        }
+
+        // 检查DROP语句权限
+        if !settings.allow_drop_statements && statement.kind == StatementKind::Drop {
+            return Some("DROP语句已被禁用\n\n原因：为了保护数据安全，DROP操作默认被禁用。\n\n解决方法：\n1. 打开工具栏「安全设置」或应用设置（右上角齿轮图标）\n2. 进入「查询设置」标签\n3. 在「语句权限控制」区域启用「允许DROP语句」\n4. 保存设置后重新执行查询".to_string());
+        }
+
+        // 检查危险操作权限（额外的安全层，用于特别危险的操作）
+        // 注意：allow_dangerous_operations 是一个额外的安全开关
+        // 只有同时满足以下条件才能执行特别危险的操作：
+        // 1. 对应的语句类型权限已开启（allow_delete_statements 或 allow_drop_statements）
+        // 2. 危险操作权限已开启（allow_dangerous_operations）
+        if settings.allow_dangerous_operations {
+            return None;
+        }
+        let (operation, reason) = match (&statement.kind, statement.object.as_deref()) {
+            (StatementKind::Drop, Some("DATABASE")) => ("DROP DATABASE", "此操作将永久删除整个数据库及其所有数据。"),
+            (StatementKind::Drop, Some("MEASUREMENT")) => ("DROP MEASUREMENT", "此操作将永久删除整个测量（表）及其所有数据。"),
+            // IoTDB 删除数据库
+            (StatementKind::Delete, Some("DATABASE")) => ("DELETE DATABASE", "此操作将永久删除整个数据库及其所有数据。"),
+            (StatementKind::Delete, Some("STORAGE GROUP")) => ("DELETE STORAGE GROUP", "此操作将永久删除整个存储组及其所有数据。"),
+            _ => return None,
+        };
+        Some(format!("{} 是特别危险的操作，已被禁用\n\n原因：{}\n\n解决方法：\n1. 打开工具栏「安全设置」或应用设置\n2. 启用「允许危险操作」选项\n3. 保存设置后重新执行查询\n\n⚠️ 警告：启用此选项后，请务必谨慎操作！", operation, reason))
+    }
+
+    /// 所有语句的类型
+    fn statement_kinds(query: &str) -> Vec<StatementKind> {
+        query_parser::parse(query).statements.into_iter().map(|s| s.kind).collect()
     }
 
     /// 检查是否为INSERT语句
     pub fn is_insert_statement(query: &str) -> bool {
-        let trimmed_query = query.trim().to_uppercase();
-        trimmed_query.starts_with("INSERT")
+        Self::statement_kinds(query).first() == Some(&StatementKind::Insert)
     }
 
     /// 检查是否为DDL语句（数据定义语言）
     pub fn is_ddl_statement(query: &str) -> bool {
-        let trimmed_query = query.trim().to_uppercase();
-        trimmed_query.starts_with("CREATE") ||
-        trimmed_query.starts_with("DROP") ||
-        trimmed_query.starts_with("ALTER")
+        Self::statement_kinds(query)
+            .iter()
+            .any(|kind| matches!(kind, StatementKind::Create | StatementKind::Drop | StatementKind::Alter))
     }
 
     /// 检查是否为DML语句（数据操作语言）
     pub fn is_dml_statement(query: &str) -> bool {
-        let trimmed_query = query.trim().to_uppercase();
-        trimmed_query.starts_with("INSERT") ||
-        trimmed_query.starts_with("DELETE") ||
-        trimmed_query.starts_with("UPDATE")
+        Self::statement_kinds(query)
+            .iter()
+            .any(|kind| matches!(kind, StatementKind::Insert | StatementKind::Delete | StatementKind::Update))
     }
 
     /// 检查是否为查询语句
     pub fn is_query_statement(query: &str) -> bool {
-        let trimmed_query = query.trim().to_uppercase();
-        trimmed_query.starts_with("SELECT") ||
-        trimmed_query.starts_with("SHOW") ||
-        trimmed_query.starts_with("EXPLAIN")
+        let kinds = Self::statement_kinds(query);
+        !kinds.is_empty()
+            && kinds
+                .iter()
+                .all(|kind| matches!(kind, StatementKind::Select | StatementKind::Show | StatementKind::Explain))
     }
 
-    /// 获取SQL语句类型
-    pub fn get_statement_type(query: &str) -> String {
-        let trimmed_query = query.trim().to_uppercase();
+    /// 检查语句是否可能改变数据库结构（只读查询之外的语句，以及 SELECT ... INTO 这类写入数据的查询）
+    pub fn changes_schema(query: &str) -> bool {
+        query_parser::parse(query).statements.iter().any(|statement| {
+            statement.writes_data()
+                || !matches!(
+                    statement.kind,
+                    StatementKind::Select | StatementKind::Show | StatementKind::Explain | StatementKind::Describe | StatementKind::Kill
+                )
+        })
+    }
 
-        if trimmed_query.starts_with("SELECT") {
-            // 检测聚合查询
-            if Self::is_aggregate_query(&trimmed_query) {
-                return "SELECT_AGGREGATE".to_string();
-            }
-            // 检测分组查询
-            if Self::is_group_by_query(&trimmed_query) {
-                return "SELECT_GROUP".to_string();
-            }
-            "SELECT".to_string()
-        } else if trimmed_query.starts_with("INSERT") {
-            "INSERT".to_string()
-        } else if trimmed_query.starts_with("DELETE") {
-            "DELETE".to_string()
-        } else if trimmed_query.starts_with("UPDATE") {
-            "UPDATE".to_string()
-        } else if trimmed_query.starts_with("CREATE") {
-            "CREATE".to_string()
-        } else if trimmed_query.starts_with("DROP") {
-            "DROP".to_string()
-        } else if trimmed_query.starts_with("ALTER") {
-            "ALTER".to_string()
-        } else if trimmed_query.starts_with("SHOW") {
-            "SHOW".to_string()
-        } else if trimmed_query.starts_with("EXPLAIN") {
-            "EXPLAIN".to_string()
-        } else if trimmed_query.starts_with("GRANT") {
-            "GRANT".to_string()
-        } else if trimmed_query.starts_with("REVOKE") {
-            "REVOKE".to_string()
-        } else {
-            "UNKNOWN".to_string()
-        }
-    }
-
-    /// 检测是否为聚合查询
-    fn is_aggregate_query(query: &str) -> bool {
-        // InfluxQL 聚合函数
-        let influxql_functions = [
-            "COUNT(", "SUM(", "MEAN(", "MAX(", "MIN(", "STDDEV(", "VARIANCE(",
-            "MEDIAN(", "MODE(", "SPREAD(", "INTEGRAL(", "DISTINCT(",
-            "FIRST(", "LAST(", "PERCENTILE(", "SAMPLE(", "TOP(", "BOTTOM("
-        ];
-        // 标准 SQL 聚合函数（用于检测）
-        let sql_functions = ["AVG("];
-
-        influxql_functions.iter().any(|func| query.contains(func)) ||
-        sql_functions.iter().any(|func| query.contains(func))
-    }
-
-    /// 检测是否为分组查询
-    fn is_group_by_query(query: &str) -> bool {
-        query.contains("GROUP BY")
-    }
-
-    /// 验证 InfluxQL 函数使用
-    /// 检测不支持的 SQL 函数并提供建议
-    pub fn validate_influxql_functions(query: &str) -> Result<()> {
-        let upper_query = query.to_uppercase();
-
-        // 不支持的 SQL 函数及其 InfluxQL 替代方案
-        let unsupported_functions = [
-            ("AVG(", "MEAN(", "AVG() 函数在 InfluxQL 中不支持，请使用 MEAN() 代替"),
-            ("AVERAGE(", "MEAN(", "AVERAGE() 函数在 InfluxQL 中不支持，请使用 MEAN() 代替"),
-            ("STDEV(", "STDDEV(", "STDEV() 函数在 InfluxQL 中不支持，请使用 STDDEV() 代替"),
-            ("VAR(", "VARIANCE(", "VAR() 函数在 InfluxQL 中不支持，请使用 VARIANCE() 代替"),
-        ];
-
-        for (unsupported, replacement, message) in &unsupported_functions {
-            if upper_query.contains(unsupported) {
-                return Err(anyhow!(
-                    "{}。\n建议修改：将 {} 替换为 {}",
-                    message,
-                    unsupported.trim_end_matches('('),
-                    replacement.trim_end_matches('(')
-                ));
-            }
-        }
+    /// 获取SQL语句类型（多条语句时取第一条），SELECT 进一步区分聚合与分组查询
+    pub fn get_statement_type(query: &str) -> String {
+        query_parser::parse(query)
+            .statements
+            .first()
+            .map(|statement| statement.statement_type())
+            .unwrap_or_else(|| "UNKNOWN".to_string())
+    }
 
-        Ok(())
+    /// InfluxQL 不支持的 SQL 函数及其替代方案
+    fn unsupported_function_message(name: &str) -> Option<String> {
+        let (unsupported, replacement) = match name.to_uppercase().as_str() {
+            "AVG" => ("AVG", "MEAN"),
+            "AVERAGE" => ("AVERAGE", "MEAN"),
+            "STDEV" => ("STDEV", "STDDEV"),
+            "VAR" => ("VAR", "VARIANCE"),
+            _ => return None,
+        };
+        Some(format!(
+            "{}() 函数在 InfluxQL 中不支持，请使用 {}() 代替。\n建议修改：将 {} 替换为 {}",
+            unsupported, replacement, unsupported, replacement
+        ))
     }
 
     /// 将INSERT语句转换为Line Protocol格式
@@ -559,4 +587,107 @@ mod tests {
         assert!(ValidationUtils::validate_database_name("my-db").is_err());
         assert!(ValidationUtils::validate_database_name("_internal").is_err());
     }
+
+    fn controller(allow_delete: bool, allow_drop: bool) -> crate::commands::settings::ControllerSettings {
+        crate::commands::settings::ControllerSettings {
+            allow_delete_statements: allow_delete,
+            allow_drop_statements: allow_drop,
+            allow_dangerous_operations: false,
+            require_confirmation_for_delete: true,
+            require_confirmation_for_drop: true,
+        }
+    }
+
+    #[test]
+    fn test_statement_type_ignores_comments() {
+        assert_eq!(ValidationUtils::get_statement_type("-- 清理\nDROP MEASUREMENT cpu"), "DROP");
+        assert_eq!(ValidationUtils::get_statement_type("/* avg( */ SELECT usage FROM cpu"), "SELECT");
+        assert_eq!(ValidationUtils::get_statement_type("SELECT \"count(\" FROM cpu GROUP BY host"), "SELECT_GROUP");
+        assert_eq!(ValidationUtils::get_statement_type("WITH t AS (SELECT avg(v) FROM m) SELECT * FROM t"), "SELECT_AGGREGATE");
+        assert!(ValidationUtils::is_ddl_statement("SHOW DATABASES; DROP DATABASE db"));
+        assert!(!ValidationUtils::is_query_statement("SHOW DATABASES; DROP DATABASE db"));
+    }
+
+    #[test]
+    fn test_influxql_set_password_is_recognised() {
+        let query = "SET PASSWORD FOR \"u\" = 'p'";
+        assert!(ValidationUtils::inspect_query(query, None).is_empty());
+        assert!(ValidationUtils::validate_query_with_settings(query, Some(&controller(false, false))).is_ok());
+        assert_eq!(ValidationUtils::get_statement_type(query), "SET");
+    }
+
+    #[test]
+    fn test_changes_schema() {
+        assert!(!ValidationUtils::changes_schema("SELECT usage FROM cpu; SHOW MEASUREMENTS"));
+        assert!(!ValidationUtils::changes_schema("SHOW TIMESERIES root.sg.**"));
+        assert!(ValidationUtils::changes_schema("SHOW DATABASES; DROP DATABASE db"));
+        assert!(ValidationUtils::changes_schema("INSERT cpu,host=a usage=1"));
+        assert!(ValidationUtils::changes_schema("SELECT mean(usage) INTO cpu_1h FROM cpu GROUP BY time(1h)"));
+        assert!(ValidationUtils::changes_schema("SELECT s1 INTO root.sg.d2(t1) FROM root.sg.d1"));
+        assert!(ValidationUtils::changes_schema("CREATE TIMESERIES root.sg.d1.s1 WITH DATATYPE=FLOAT"));
+    }
+
+    #[test]
+    fn test_dangerous_operations_after_comments_and_batches() {
+        let settings = controller(false, false);
+        let hidden = "/* 例行查询 */\n-- DROP 只是注释\nDELETE FROM cpu WHERE time < now() - 30d";
+        assert!(ValidationUtils::validate_query_with_settings(hidden, Some(&settings)).is_err());
+        assert!(ValidationUtils::validate_query_with_settings("SELECT * FROM cpu -- DROP DATABASE db", Some(&settings)).is_ok());
+        assert!(ValidationUtils::validate_query_with_settings("SELECT * FROM \"DROP DATABASE\"", Some(&settings)).is_ok());
+
+        // 批量语句中的 DELETE 不再依赖分号后的空格
+        let batch = "SELECT * FROM cpu;\n\tDELETE FROM cpu";
+        let issues = ValidationUtils::inspect_query(batch, Some(&controller(true, true)));
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].line, issues[0].column), (2, 2));
+        assert_eq!(issues[0].message, "查询包含潜在危险的操作: ; DELETE");
+
+        let drop = ValidationUtils::validate_query_with_settings("drop   database mydb", Some(&controller(false, true)));
+        assert!(drop.unwrap_err().to_string().starts_with("DROP DATABASE 是特别危险的操作"));
+        assert!(ValidationUtils::validate_query_with_settings("DELETE STORAGE GROUP root.sg", Some(&controller(true, false))).is_err());
+    }
+
+    #[test]
+    fn test_permission_bypass_regressions() {
+        let settings = controller(false, false);
+        for query in [
+            "DELETE FROM /cpu|>/",
+            "DROP DATABASE mydb // |>",
+            "SHOW DATABASES\nDROP DATABASE x",
+            "EXPLAIN DELETE FROM cpu",
+        ] {
+            let issues = ValidationUtils::inspect_query(query, Some(&settings));
+            assert!(issues.iter().any(|issue| issue.kind == QueryIssueKind::Permission), "{}: {:?}", query, issues);
+            assert!(ValidationUtils::validate_query_with_settings(query, Some(&settings)).is_err(), "{}", query);
+        }
+        assert!(ValidationUtils::validate_query_with_settings(
+            "from(bucket: \"b\") |> range(start: -1h) |> filter(fn: (r) => r._measurement == \"cpu\")",
+            Some(&settings)
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_query_issues_have_positions() {
+        let issues = ValidationUtils::inspect_query("SELECT mean(usage),\n  avg(usage) FROM cpu", None);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, QueryIssueKind::Semantic);
+        assert_eq!((issues[0].line, issues[0].column), (2, 3));
+        assert!(issues[0].message.contains("请使用 MEAN() 代替"));
+
+        let issues = ValidationUtils::inspect_query("SELECT usage FROM cpu WHERE", None);
+        assert_eq!(issues[0].kind, QueryIssueKind::Syntax);
+        assert_eq!((issues[0].line, issues[0].column), (1, 28));
+        // 语法错误交给数据库判断，不阻止执行
+        assert!(ValidationUtils::validate_query("SELECT usage FROM cpu WHERE").is_ok());
+        assert!(ValidationUtils::validate_query("   ").is_err());
+    }
+
+    #[test]
+    fn test_select_into_and_truncate() {
+        let parsed = query_parser::parse("SELECT mean(usage) INTO cpu_1h FROM cpu GROUP BY time(1h)");
+        assert!(parsed.statements[0].writes_data());
+        assert!(ValidationUtils::validate_query("TRUNCATE TABLE cpu").is_err());
+        assert!(ValidationUtils::validate_query("SELECT * FROM \"truncate\"").is_ok());
+    }
 }
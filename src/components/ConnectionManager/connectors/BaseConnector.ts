@@ -82,6 +82,14 @@ export abstract class BaseConnector<T extends BaseConnectionConfig = BaseConnect
             label: t('use_ssl'),
             type: 'switch',
             defaultValue: false
+          },
+          {
+            name: 'skipSslVerification',
+            label: t('skip_ssl_verification'),
+            type: 'switch',
+            defaultValue: false,
+            description: t('skip_ssl_verification_description'),
+            visible: (formData: any) => !!formData.ssl
           }
         ]
       },
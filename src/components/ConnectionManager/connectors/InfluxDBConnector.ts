@@ -254,6 +254,7 @@ export class InfluxDBConnector extends BaseConnector<InfluxDBConfig> {
       password: formData.password || '',
       database: formData.database || '',
       ssl: formData.ssl || false,
+      skipSslVerification: (formData.ssl && formData.skipSslVerification) || false,
       timeout: formData.timeout || 30,
       connectionTimeout: formData.connectionTimeout || 30,
       queryTimeout: formData.queryTimeout || 300,
@@ -298,6 +299,7 @@ export class InfluxDBConnector extends BaseConnector<InfluxDBConfig> {
       password: config.password,
       database: config.database,
       ssl: config.ssl,
+      skipSslVerification: config.skipSslVerification,
       timeout: config.timeout,
       connectionTimeout: config.connectionTimeout,
       queryTimeout: config.queryTimeout,
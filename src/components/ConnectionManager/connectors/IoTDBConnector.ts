@@ -253,6 +253,7 @@ export class IoTDBConnector extends BaseConnector<IoTDBConfig> {
       password: formData.password || 'root',
       database: formData.database || 'root',
       ssl: formData.ssl || false,
+      skipSslVerification: (formData.ssl && formData.skipSslVerification) || false,
       timeout: formData.timeout || 30,
       connectionTimeout: formData.connectionTimeout || 30,
       queryTimeout: formData.queryTimeout || 300,
@@ -290,6 +291,7 @@ export class IoTDBConnector extends BaseConnector<IoTDBConfig> {
       password: config.password,
       database: config.database,
       ssl: config.ssl,
+      skipSslVerification: config.skipSslVerification,
       timeout: config.timeout,
       connectionTimeout: config.connectionTimeout,
       queryTimeout: config.queryTimeout,
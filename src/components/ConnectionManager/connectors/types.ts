@@ -13,6 +13,7 @@ export interface BaseConnectionConfig {
   username?: string;
   password?: string;
   ssl?: boolean;
+  skipSslVerification?: boolean;
   timeout?: number;
   connectionTimeout?: number;
   queryTimeout?: number;
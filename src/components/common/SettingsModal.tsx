@@ -61,7 +61,7 @@ import { useNoticeStore } from '@/store/notice';
 import { UpdateSettings } from '@/components/updater/UpdateSettings';
 import { openExternalLink } from '@/utils/externalLinks';
 import { dataExplorerRefresh } from '@/utils/refreshEvents';
-import type { AppConfig } from '@/types';
+import type { AppConfig, KeywordCase } from '@/types';
 import { getAppVersion } from '@/utils/version';
 import { useTranslation, useSettingsTranslation, useCommonTranslation } from '@/hooks/useTranslation';
 import { useLanguageSwitcher } from '@/hooks/useLanguageSwitcher';
@@ -89,6 +89,40 @@ const SettingsModal: React.FC<SettingsModalProps> = ({ visible, onClose, initial
   const { t: tCommon } = useCommonTranslation();
   const { switchLanguage } = useLanguageSwitcher();
 
+  // 查询格式化选项（保存在后端的编辑器设置中）
+  const [formatOptions, setFormatOptions] = useState<{
+    keyword_case: KeywordCase;
+    align_where_conditions: boolean;
+  }>({ keyword_case: 'upper', align_where_conditions: true });
+
+  useEffect(() => {
+    if (!visible) return;
+    safeTauriInvoke<{ editor: { keyword_case?: KeywordCase; align_where_conditions?: boolean } }>(
+      'get_app_settings'
+    )
+      .then(settings => {
+        setFormatOptions({
+          keyword_case: settings.editor.keyword_case ?? 'upper',
+          align_where_conditions: settings.editor.align_where_conditions ?? true,
+        });
+      })
+      .catch(error => logger.warn('加载格式化设置失败:', error));
+  }, [visible]);
+
+  // 即时保存格式化选项，保留其余编辑器设置
+  const saveFormatOption = async (patch: Partial<typeof formatOptions>) => {
+    setFormatOptions(prev => ({ ...prev, ...patch }));
+    try {
+      const settings = await safeTauriInvoke<{ editor: Record<string, unknown> }>('get_app_settings');
+      await safeTauriInvoke('update_editor_settings', {
+        editorSettings: { ...settings.editor, ...patch },
+      });
+    } catch (error) {
+      logger.error('保存格式化设置失败:', error);
+      showMessage.error(`${tCommon('error')}: ${error}`);
+    }
+  };
+
   // 菜单面板宽度状态（使用百分比）
   const [menuPanelSize, setMenuPanelSize] = useState<number>(() => {
     const saved = localStorage.getItem('settings-menu-panel-size');
@@ -124,6 +158,7 @@ const SettingsModal: React.FC<SettingsModalProps> = ({ visible, onClose, initial
             word_wrap: true,
             line_numbers: true,
             minimap: true,
+            ...formatOptions,
           },
           query: {
             timeout: 30000,
@@ -339,6 +374,46 @@ const SettingsModal: React.FC<SettingsModalProps> = ({ visible, onClose, initial
                 />
               </div>
 
+              {/* 查询格式化设置 */}
+              <div className='space-y-4'>
+                <div>
+                  <Label className='text-base font-medium'>{tSettings('query_formatting')}</Label>
+                  <p className='text-sm text-muted-foreground'>
+                    {tSettings('query_formatting_description')}
+                  </p>
+                </div>
+                <div className='grid grid-cols-2 gap-4 items-start'>
+                  <div className='space-y-2'>
+                    <Label htmlFor='keyword-case'>{tSettings('keyword_case')}</Label>
+                    <Select
+                      value={formatOptions.keyword_case}
+                      onValueChange={value => saveFormatOption({ keyword_case: value as KeywordCase })}
+                    >
+                      <SelectTrigger id='keyword-case'>
+                        <SelectValue />
+                      </SelectTrigger>
+                      <SelectContent>
+                        <SelectItem value='upper'>{tSettings('keyword_case_upper')}</SelectItem>
+                        <SelectItem value='lower'>{tSettings('keyword_case_lower')}</SelectItem>
+                        <SelectItem value='preserve'>{tSettings('keyword_case_preserve')}</SelectItem>
+                      </SelectContent>
+                    </Select>
+                  </div>
+                </div>
+                <div className='flex items-center justify-between'>
+                  <div className='space-y-0.5'>
+                    <Label className='text-sm'>{tSettings('align_where_conditions')}</Label>
+                    <p className='text-xs text-muted-foreground'>
+                      {tSettings('align_where_conditions_description')}
+                    </p>
+                  </div>
+                  <Switch
+                    checked={formatOptions.align_where_conditions}
+                    onCheckedChange={checked => saveFormatOption({ align_where_conditions: checked })}
+                  />
+                </div>
+              </div>
+
             </div>
           </div>
 
@@ -36,8 +36,8 @@ import {
 } from 'lucide-react';
 import { favoritesUtils } from '@/store/favorites';
 import { useTreeStatusStore } from '@/stores/treeStatusStore';
-import type { ConnectionConfig } from '@/types';
-import { safeTauriInvoke } from '@/utils/tauri';
+import type { ConnectionConfig, SchemaChangeEvent } from '@/types';
+import { safeTauriInvoke, safeTauriListen } from '@/utils/tauri';
 import { showMessage } from '@/utils/message';
 import { writeToClipboard } from '@/utils/clipboard';
 
@@ -1840,6 +1840,41 @@ const DatabaseExplorer: React.FC<DatabaseExplorerProps> = ({
     }
   }, [refreshTrigger, buildCompleteTreeData, clearDatabasesCache]);
 
+  // 监听后端结构变化事件（后台刷新发现新增/删除的节点），合并短时间内的多次变化后重建树
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    let disposed = false;
+    let timer: ReturnType<typeof setTimeout> | undefined;
+    const changedConnections = new Set<string>();
+
+    safeTauriListen<SchemaChangeEvent>('schema-changed', event => {
+      logger.debug('收到结构变化事件:', event.payload);
+      changedConnections.add(event.payload.connectionId);
+      if (timer) clearTimeout(timer);
+      timer = setTimeout(() => {
+        changedConnections.forEach(connectionId => clearDatabasesCache(connectionId));
+        changedConnections.clear();
+        buildCompleteTreeData(false); // 后台变化时不显示全局 loading
+      }, 500);
+    })
+      .then(fn => {
+        if (disposed) {
+          fn();
+        } else {
+          unlisten = fn;
+        }
+      })
+      .catch(error => {
+        logger.error('监听结构变化事件失败:', error);
+      });
+
+    return () => {
+      disposed = true;
+      if (timer) clearTimeout(timer);
+      unlisten?.();
+    };
+  }, [buildCompleteTreeData, clearDatabasesCache]);
+
   // 使用 ref 跟踪上一次的 openedDatabasesList，避免内容相同但引用不同时触发更新
   const prevOpenedDatabasesListRef = useRef<string[]>([]);
 
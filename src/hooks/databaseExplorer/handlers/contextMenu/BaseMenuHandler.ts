@@ -361,11 +361,14 @@ export abstract class BaseMenuHandler {
    */
   protected async handleRefresh(
     action: ContextMenuAction,
-    connectionId?: string
+    connectionId?: string,
+    database?: string
   ): Promise<void> {
     try {
       if (connectionId) {
         this.deps.clearDatabasesCache(connectionId);
+        // 同时清除后端结构缓存，否则树仍会显示缓存中的旧节点
+        await this.invokeTauri('invalidate_schema_cache', { connectionId, database });
       }
       await this.refreshTree(true);
       this.showSuccess(action);
@@ -22,7 +22,7 @@ export class BucketMenuHandler extends BaseMenuHandler {
         break;
 
       case 'refresh_bucket':
-        await this.handleRefresh(action, connectionId);
+        await this.handleRefresh(action, connectionId, bucket);
         break;
 
       case 'bucket_info':
@@ -21,7 +21,7 @@ export class DatabaseMenuHandler extends BaseMenuHandler {
         break;
 
       case 'refresh_database':
-        await this.handleRefresh(action, connectionId);
+        await this.handleRefresh(action, connectionId, database);
         break;
 
       case 'show_tables':
@@ -25,7 +25,7 @@ export class TableMenuHandler extends BaseMenuHandler {
         break;
 
       case 'refresh_table':
-        await this.handleRefresh(action, connectionId);
+        await this.handleRefresh(action, connectionId, database);
         break;
 
       case 'generate_select_query':
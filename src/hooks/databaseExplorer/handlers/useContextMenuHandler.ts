@@ -190,6 +190,7 @@ export const useContextMenuHandler = (props: UseContextMenuHandlerProps) => {
                     if (nodeType === 'connection') {
                         try {
                             clearDatabasesCache(connectionId);
+                            await safeTauriInvoke('invalidate_schema_cache', { connectionId });
                             buildCompleteTreeData(true);
                             showMessage.success(tExplorer('connectionRefreshed', { name: node.name }));
                         } catch (error) {
@@ -347,6 +348,7 @@ export const useContextMenuHandler = (props: UseContextMenuHandlerProps) => {
                     if (nodeType.includes('database')) {
                         try {
                             logger.debug(`刷新数据库结构: ${database}`);
+                            await safeTauriInvoke('invalidate_schema_cache', { connectionId, database });
                             await buildCompleteTreeData(true);
                             showMessage.success(tExplorer('databaseRefreshed', { database }));
                         } catch (error) {
@@ -5,6 +5,7 @@ import { useSmartSql } from './useSmartSql';
 import { toast } from 'sonner';
 import { safeTauriInvoke } from '@/utils/tauri';
 import logger from '@/utils/logger';
+import type { NamedQueryParams } from '@/types';
 import { useContextMenuTranslation, useConnectionsTranslation, useCommonTranslation } from './useTranslation';
 
 export interface ContextMenuState {
@@ -15,7 +16,7 @@ export interface ContextMenuState {
 }
 
 export interface ContextMenuOptions {
-  onSqlGenerated?: (sql: string, description: string) => void;
+  onSqlGenerated?: (sql: string, description: string, params?: NamedQueryParams) => void;
   onActionExecuted?: (action: string, result?: any) => void;
   onError?: (error: string) => void;
 }
@@ -247,11 +248,12 @@ export function useContextMenu(options: ContextMenuOptions = {}) {
     
     // 回调或导航到查询页面
     if (onSqlGenerated) {
-      onSqlGenerated(result.sql, result.description);
+      onSqlGenerated(result.sql, result.description, result.params);
     } else {
       navigate('/query', {
         state: {
           query: result.sql,
+          params: result.params,
           database: params.database,
           description: result.description,
         },
@@ -101,12 +101,12 @@ export const useQuery = () => {
      * 格式化查询
      */
     const formatQuery = useCallback(
-        async (query: string): Promise<string> => {
+        async (query: string, language: string): Promise<string> => {
             setLocalLoading(true);
             setLocalError(null);
 
             try {
-                const result = await storeFormatQuery(query);
+                const result = await storeFormatQuery(query, language);
                 return result;
             } catch (err) {
                 const errorMessage = err instanceof Error ? err.message : String(err);
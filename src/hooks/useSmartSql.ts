@@ -3,6 +3,7 @@ import { smartSqlService, type SqlGenerationRequest, type SqlGenerationResult }
 import { useConnectionStore } from '@/store/connection';
 import { toast } from 'sonner';
 import { writeToClipboard } from '@/utils/clipboard';
+import type { NamedQueryParams } from '@/types';
 
 export interface UseSmartSqlOptions {
   database?: string;
@@ -33,12 +34,9 @@ export function useSmartSql(options: UseSmartSqlOptions = {}) {
         throw new Error(validationError);
       }
 
-      // 生成 SQL
+      // 生成 SQL（后端已处理引号和参数，不再二次格式化）
       const result = await smartSqlService.generateSmartSql(request);
       
-      // 格式化 SQL
-      result.sql = smartSqlService.formatSql(result.sql);
-      
       setLastGeneratedSql(result);
       onSqlGenerated?.(result);
       
@@ -208,7 +206,8 @@ export function useSmartSql(options: UseSmartSqlOptions = {}) {
    */
   const executeGeneratedSql = useCallback(async (
     sql: string,
-    description?: string
+    description?: string,
+    params?: NamedQueryParams
   ): Promise<void> => {
     try {
       if (!activeConnectionId) {
@@ -222,7 +221,7 @@ export function useSmartSql(options: UseSmartSqlOptions = {}) {
 
       // 可以通过事件或回调将 SQL 传递给查询编辑器
       window.dispatchEvent(new CustomEvent('sql-generated', {
-        detail: { sql, description }
+        detail: { sql, description, params }
       }));
 
     } catch (err) {
@@ -5,6 +5,9 @@ import type {
   ConnectionTestResult,
   QueryRequest,
   QueryResult,
+  CompletionResult,
+  SchemaSearchHit,
+  SchemaSearchMode,
   QueryValidationResult,
   DatabaseInfo,
   MeasurementInfo,
@@ -181,11 +184,30 @@ export class QueryAPI {
     });
   }
 
+  /**
+   * 获取光标位置的自动补全候选
+   */
+  static async getCompletions(
+    connectionId: string,
+    query: string,
+    cursor: number,
+    database?: string,
+    language?: string
+  ): Promise<CompletionResult> {
+    return safeTauriInvoke<CompletionResult>('get_completions', {
+      connectionId,
+      database,
+      query,
+      cursor,
+      language,
+    });
+  }
+
   /**
    * 格式化查询
    */
-  static async formatQuery(query: string): Promise<string> {
-    return safeTauriInvoke<string>('format_query', { query });
+  static async formatQuery(query: string, language: string): Promise<string> {
+    return safeTauriInvoke<string>('format_query', { query, language });
   }
 
   /**
@@ -193,11 +215,15 @@ export class QueryAPI {
    */
   static async explainQuery(
     connection_id: string,
-    query: string
-  ): Promise<string> {
-    return safeTauriInvoke<string>('explain_query', {
+    query: string,
+    database?: string,
+    analyze = false
+  ): Promise<QueryResult> {
+    return safeTauriInvoke<QueryResult>('explain_query', {
       connectionId: connection_id,
       query,
+      database,
+      analyze,
     });
   }
 }
@@ -313,6 +339,21 @@ export class DatabaseAPI {
       limit,
     });
   }
+
+  /**
+   * 在所有连接已缓存的结构信息中搜索
+   */
+  static async searchSchema(
+    query: string,
+    mode?: SchemaSearchMode,
+    limit?: number
+  ): Promise<SchemaSearchHit[]> {
+    return safeTauriInvoke<SchemaSearchHit[]>('search_schema', {
+      query,
+      mode,
+      limit,
+    });
+  }
 }
 
 /**
@@ -1,5 +1,6 @@
 import { invoke } from '@tauri-apps/api/core';
 import logger from '@/utils/logger';
+import type { NamedQueryParams, QueryRequest, QueryResult } from '@/types';
 
 export interface SqlGenerationRequest {
   sql_type: string;
@@ -22,6 +23,7 @@ export interface SqlGenerationRequest {
 export interface SqlGenerationResult {
   sql: string;
   description: string;
+  params?: NamedQueryParams; // 执行时随查询一起提交
 }
 
 export interface ContextMenuResponse {
@@ -62,6 +64,29 @@ export class SmartSqlService {
     }
   }
 
+  /**
+   * 执行生成的 SQL，连同生成时的参数一起提交
+   */
+  async executeGeneratedSql(
+    connectionId: string,
+    database: string | undefined,
+    result: SqlGenerationResult
+  ): Promise<QueryResult> {
+    const request: QueryRequest = {
+      connectionId,
+      database,
+      query: result.sql,
+      params: result.params,
+    };
+
+    try {
+      return await invoke<QueryResult>('execute_query', { request });
+    } catch (error) {
+      logger.error('Failed to execute generated SQL:', error);
+      throw new Error(`执行生成的 SQL 失败: ${error}`);
+    }
+  }
+
   /**
    * 获取数据库上下文菜单
    */
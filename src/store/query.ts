@@ -63,7 +63,7 @@ interface QueryState {
   // 查询执行
   executeQuery: (request: QueryRequest) => Promise<QueryResult>;
   validateQuery: (query: string) => Promise<QueryValidationResult>;
-  formatQuery: (query: string) => Promise<string>;
+  formatQuery: (query: string, language: string) => Promise<string>;
   getQuerySuggestions: (
     connectionId: string,
     database?: string,
@@ -242,9 +242,9 @@ export const useQueryStore = create<QueryState>()(
         }
       },
 
-      formatQuery: async query => {
+      formatQuery: async (query, language) => {
         try {
-          const formatted = await QueryAPI.formatQuery(query);
+          const formatted = await QueryAPI.formatQuery(query, language);
 
           // 如果有活跃选项卡，更新其查询内容
           if (get().activeTabId) {
@@ -22,6 +22,7 @@ export type InfluxDBVersion = '1.x' | '2.x' | '3.x';
 
 // IoTDB 版本
 export type IoTDBVersion = '0.13.x' | '0.14.x' | '1.0.x' | '1.1.x' | '1.2.x';
+export type IoTDBTransport = 'thrift' | 'rest_v2' | 'auto';
 
 // 通用数据库版本类型
 export type DatabaseVersion = InfluxDBVersion | IoTDBVersion;
@@ -57,6 +58,12 @@ export interface IoTDBConfig {
   enableRedirection?: boolean;
   maxRetryCount?: number;
   retryIntervalMs?: number;
+  // 传输方式：thrift（默认）、rest_v2，或 auto（优先 Thrift，不可达时回退到 REST v2）
+  transport?: IoTDBTransport;
+  // REST v2 服务端口，默认 18080
+  restPort?: number;
+  // 查询结果中的时间戳格式化为会话时区的字符串，默认为毫秒时间戳
+  formatTimestamps?: boolean;
 }
 
 // 数据库驱动特定配置联合类型
@@ -137,6 +144,7 @@ export interface ConnectionConfig extends Omit<BaseDatabaseConnectionConfig, 'dr
   username?: string;
   password?: string;
   ssl?: boolean;
+  skipSslVerification?: boolean;
   timeout?: number;
   connectionTimeout?: number;
   queryTimeout?: number;
@@ -253,10 +261,23 @@ export interface QueryRequest {
   language?: QueryLanguage; // 查询语言类型
   format?: 'json' | 'csv' | 'table';
   parameters?: Record<string, any>; // 查询参数
+  params?: NamedQueryParams; // 查询中以 $name 引用的参数
   timeout?: number; // 查询超时
   maxRows?: number; // 最大返回行数
 }
 
+// 查询参数值（带类型）
+export type QueryParamValue =
+  | { type: 'string'; value: string }
+  | { type: 'integer'; value: number }
+  | { type: 'float'; value: number }
+  | { type: 'boolean'; value: boolean }
+  | { type: 'time'; value: string } // RFC3339 时间，如 2024-01-01T00:00:00Z
+  | { type: 'duration'; value: string }; // 时长，如 1h、30m
+
+// 命名查询参数：参数名（不含 $）→ 参数值
+export type NamedQueryParams = Record<string, QueryParamValue>;
+
 // 执行消息类型
 export type MessageType = 'success' | 'warning' | 'error' | 'info';
 
@@ -295,6 +316,63 @@ export interface ExecutionPlan {
   estimatedRows?: number;
 }
 
+// 自动补全候选类型
+export type CompletionKind =
+  | 'keyword'
+  | 'function'
+  | 'database'
+  | 'bucket'
+  | 'measurement'
+  | 'field'
+  | 'tagKey'
+  | 'tagValue'
+  | 'device'
+  | 'timeseries';
+
+// 自动补全候选
+export interface CompletionItem {
+  label: string;
+  kind: CompletionKind;
+  insertText: string;
+}
+
+// 自动补全结果，替换范围为字符偏移
+export interface CompletionResult {
+  items: CompletionItem[];
+  replaceStart: number;
+  replaceEnd: number;
+}
+
+// 结构信息搜索
+export type SchemaSearchMode = 'prefix' | 'fuzzy' | 'regex';
+
+export type SchemaKind =
+  | 'database'
+  | 'bucket'
+  | 'measurement'
+  | 'field'
+  | 'tagKey'
+  | 'device'
+  | 'timeseries';
+
+export interface SchemaSearchHit {
+  connectionId: string;
+  kind: SchemaKind;
+  name: string;
+  path: string[];
+  nodeId?: string;
+  score: number;
+}
+
+// 结构信息变化事件（schema-changed）
+export interface SchemaChangeEvent {
+  connectionId: string;
+  database?: string;
+  item: { kind: string } & Record<string, unknown>;
+  added: string[];
+  removed: string[];
+}
+
 // 聚合信息
 export interface AggregationInfo {
   count?: number;
@@ -1084,6 +1162,9 @@ export interface TimeRange {
 // Automation types (already defined above)
 
 // 应用设置相关类型
+// 格式化查询时的关键字大小写
+export type KeywordCase = 'upper' | 'lower' | 'preserve';
+
 export interface AppSettings {
   general: {
     theme: string;
@@ -1097,6 +1178,8 @@ export interface AppSettings {
     wordWrap: boolean;
     lineNumbers: boolean;
     minimap: boolean;
+    keywordCase?: KeywordCase;
+    alignWhereConditions?: boolean;
   };
   query: {
     timeout: number;